@@ -0,0 +1,85 @@
+use std::{
+    env,
+    io::{Error, ErrorKind},
+    sync::Arc,
+    time::Duration,
+};
+
+use shaku::{HasComponent, HasProvider};
+use source_control_application::{
+    commands::rebuild_organization_projection::{
+        OrganizationProjectionRebuild, RebuildOrganizationProjectionCommand,
+        RebuildOrganizationProjectionCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::info;
+
+/// Runs instead of the server when given as arguments, the process exits when it is done.
+pub enum CliCommand {
+    /// `rebuild-projection organizations`
+    RebuildProjection { projection: String },
+}
+
+pub fn get_command() -> Result<Option<CliCommand>, Error> {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        None => Ok(None),
+        Some("rebuild-projection") => Ok(Some(CliCommand::RebuildProjection {
+            projection: args.next().unwrap_or_default(),
+        })),
+        Some(command) => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Unknown command {}, expected rebuild-projection", command),
+        )),
+    }
+}
+
+pub async fn run(command: CliCommand, module: &Arc<ApplicationModule>) -> Result<(), Error> {
+    match command {
+        CliCommand::RebuildProjection { projection } if projection == "organizations" => {
+            rebuild_organization_projection(module).await
+        }
+        CliCommand::RebuildProjection { projection } => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Unknown projection {}, only organizations can be rebuilt",
+                projection
+            ),
+        )),
+    }
+}
+
+async fn rebuild_organization_projection(module: &Arc<ApplicationModule>) -> Result<(), Error> {
+    let command_handler: Box<dyn RebuildOrganizationProjectionCommandHandler> =
+        module.provide().unwrap();
+    let rebuild: Arc<dyn OrganizationProjectionRebuild> = module.resolve();
+
+    let reporter = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let progress = rebuild.progress();
+            info!(
+                state = format!("{:?}", progress.state),
+                events_replayed = progress.events_replayed,
+                "Rebuilding the organization projection"
+            );
+        }
+    });
+    let result = command_handler
+        .handle(RebuildOrganizationProjectionCommand {})
+        .await;
+    reporter.abort();
+
+    match result {
+        Ok(progress) => {
+            info!(
+                events_replayed = progress.events_replayed,
+                "Rebuilt the organization projection"
+            );
+            Ok(())
+        }
+        Err(err) => Err(Error::other(err.to_string())),
+    }
+}
@@ -1,7 +1,11 @@
-use std::{env, sync::Arc};
+use std::{env, sync::Arc, time::Duration};
 
 use config::Config;
 use serde::{Deserialize, Serialize};
+use source_control_application::{
+    commands::conflict_retry::ConflictRetrySettings, module::EventStorage,
+    projection_host::WorkerRestartSettings,
+};
 
 const OTEL_EXPORTER_OTLP_ENDPOINT_ENV_NAME: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
 const OTEL_SERVICE_NAME_ENV_NAME: &str = "OTEL_SERVICE_NAME";
@@ -39,26 +43,96 @@ pub struct SourceControlConfig {
     pub eventstore: EventStoreConfig,
     pub postgres: PostgresConfig,
     pub telemetry: TelemetryConfig,
+    pub id_generator: IdGeneratorConfig,
+    pub commands: CommandsConfig,
+    pub idempotency: IdempotencyConfig,
+    pub shutdown: ShutdownConfig,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct EventStoreConfig {
     pub connection_string: String,
+    pub backend: EventStoreBackend,
+    /// How long postgres subscriptions wait before looking for new events again
+    pub poll_interval_milliseconds: u64,
     pub projections: ProjectionsConfig,
+    pub snapshots: SnapshotsConfig,
+    pub worker_restart: WorkerRestartConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum EventStoreBackend {
+    #[default]
+    EventStoreDb,
+    /// Stores only organizations in the `events` table, the other aggregates stay in EventStoreDB
+    PostgresOrganizations,
+}
+
+impl From<EventStoreBackend> for EventStorage {
+    fn from(value: EventStoreBackend) -> Self {
+        match value {
+            EventStoreBackend::EventStoreDb => EventStorage::EventStore,
+            EventStoreBackend::PostgresOrganizations => EventStorage::PostgresOrganizations,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionsConfig {
     pub organizations_postgres: ProjectionConfig,
+    pub repositories_postgres: ProjectionConfig,
+    pub pull_requests_postgres: ProjectionConfig,
+    pub commit_graphs_postgres: ProjectionConfig,
+    pub branches_postgres: ProjectionConfig,
+    pub developers_postgres: ProjectionConfig,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionConfig {
+    /// Also names the checkpoint of catch-up projections
     pub persistent_subscription_name: String,
     pub workers: u32,
+    #[serde(default)]
+    pub runtime: ProjectionRuntime,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectionRuntime {
+    #[default]
+    PersistentSubscription,
+    /// Subscribes to `$all` from a checkpoint in postgres, committed together with the read model
+    /// so every event is applied exactly once. Only supported by organizations, one worker is
+    /// started no matter how many are configured. Required to rebuild the projection
+    CatchUp,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerRestartConfig {
+    /// Delay before restarting a failed projection worker, doubled for every next restart
+    pub base_delay_milliseconds: u64,
+    pub max_delay_milliseconds: u64,
+}
+
+impl From<&WorkerRestartConfig> for WorkerRestartSettings {
+    fn from(value: &WorkerRestartConfig) -> Self {
+        WorkerRestartSettings {
+            base_delay: Duration::from_millis(value.base_delay_milliseconds),
+            max_delay: Duration::from_millis(value.max_delay_milliseconds),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotsConfig {
+    /// Number of events between snapshots of an aggregate, 0 disables snapshots
+    pub frequency: u64,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -77,3 +151,56 @@ pub struct TelemetryConfig {
     pub directives: Vec<String>,
     pub level_directive: String,
 }
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IdGeneratorConfig {
+    /// Has to be unique for every instance writing at the same time, at most 1023
+    pub worker_id: u16,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandsConfig {
+    pub conflict_retry: ConflictRetryConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictRetryConfig {
+    /// Attempts of a command including the first one, 1 returns the first conflict to the client
+    pub max_attempts: u32,
+    /// Upper bound of the random delay before the first retry, doubled for every next retry
+    pub base_delay_milliseconds: u64,
+    pub max_delay_milliseconds: u64,
+}
+
+impl From<&ConflictRetryConfig> for ConflictRetrySettings {
+    fn from(value: &ConflictRetryConfig) -> Self {
+        ConflictRetrySettings {
+            max_attempts: value.max_attempts,
+            base_delay: Duration::from_millis(value.base_delay_milliseconds),
+            max_delay: Duration::from_millis(value.max_delay_milliseconds),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IdempotencyConfig {
+    /// How long the response of a request with an `Idempotency-Key` is replayed to retries
+    pub ttl_seconds: u64,
+    /// How long a request holds its `Idempotency-Key` while it is handled, retries take over the
+    /// key of a request that didn't finish after it
+    pub lease_seconds: u64,
+    /// How often keys of which the response expired are removed
+    pub cleanup_interval_seconds: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShutdownConfig {
+    /// How long in-flight requests and projection workers get to complete after SIGTERM, telemetry
+    /// is flushed after
+    pub drain_timeout_seconds: u64,
+}
@@ -1,24 +1,72 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use actix_tracing_util::MeterFactory;
 use actix_web::{web::Data, App, HttpServer};
-use config::get_config;
-use metrics::{request_metrics, subscriber_metrics};
+use config::{get_config, EventStoreBackend, ProjectionRuntime};
+use metrics::{conflict_retry_metrics, projection_worker_gauge, request_metrics};
 use myopenapi::WithOpenApi;
-use shaku::HasProvider;
+use shaku::HasComponent;
 use source_control_application::module::{get_module, ApplicationModule};
-use source_control_domain::aggregates::organization::OrganizationEvent;
-use source_control_event_store_interface::subscribers::organization_subscriber::OrganizationSubscriber;
-use source_control_postgres_persistence_adapter::projectors::Projector;
+use source_control_domain::aggregates::{
+    branch::BranchEvent, commit_graph::CommitGraphEvent, developer::DeveloperEvent,
+    organization::OrganizationEvent, pull_request::PullRequestEvent, repository::RepositoryEvent,
+};
+use source_control_event_store_interface::subscribers::{
+    branch_subscriber::BRANCH_STREAM_PREFIX, commit_graph_subscriber::COMMIT_GRAPH_STREAM_PREFIX,
+    developer_subscriber::DEVELOPER_STREAM_PREFIX,
+    organization_subscriber::ORGANIZATION_STREAM_PREFIX,
+    pull_request_subscriber::PULL_REQUEST_STREAM_PREFIX,
+    repository_subscriber::REPOSITORY_STREAM_PREFIX,
+};
+use source_control_rest_interface::endpoints::admin::projection::{
+    get_rebuild::get_organization_projection_rebuild, get_workers::get_projection_workers,
+    rebuild::rebuild_organization_projection,
+};
+use source_control_rest_interface::endpoints::admin::subscription::{
+    get_all::get_persistent_subscriptions, get_parked::get_parked_events,
+    replay::replay_parked_events,
+};
+use source_control_rest_interface::endpoints::developer::{
+    create::create_developer, get::get_developer, link_identity::link_developer_identity,
+    merge::merge_developers, resolve::resolve_developer_identity,
+    unlink_identity::unlink_developer_identity, unmerge::unmerge_developer,
+};
+use source_control_rest_interface::endpoints::organization::platform_account::repository::{
+    archive::archive_repository, get::get_repository, get_all::get_repositories,
+    move_repository::move_repository, register::register_repository, rename::rename_repository,
+};
 use source_control_rest_interface::endpoints::organization::{
-    create::create_organization, get_log::get_organization_log,
+    create::create_organization, delete::delete_organization, get_log::get_organization_log,
     platform_account::add::add_platform_account,
 };
 use source_control_rest_interface::endpoints::organization::{
     get::get_organization, get_all::get_organizations,
-    platform_account::remove::remove_platform_account,
+    platform_account::remove::remove_platform_account, update::update_organization,
 };
-use startup::{eventstore::setup_eventstore, postgres::setup_postgres};
+use source_control_rest_interface::endpoints::repository::branch::{
+    create::create_branch, delete::delete_branch, get::get_branch, get_pushes::get_branch_pushes,
+    push::push_branch,
+};
+use source_control_rest_interface::endpoints::repository::commit::{
+    get_between::get_commits_between, get_merge_bases::get_merge_bases, is_ancestor::is_ancestor,
+    record::record_commits,
+};
+use source_control_rest_interface::endpoints::repository::pull_request::{
+    close::close_pull_request, dismiss_review::dismiss_review, edit::edit_pull_request,
+    get::get_pull_request, get_all::get_pull_requests, merge::merge_pull_request,
+    open::open_pull_request, submit_review::submit_review,
+};
+use source_control_rest_interface::idempotency::IdempotencyFactory;
+use startup::{
+    eventstore::setup_eventstore,
+    idempotency::start_idempotency_key_cleanup,
+    postgres::setup_postgres,
+    projections::{
+        start_catch_up_subscriber, start_postgres_subscriber, start_subscribers, ProjectionWorkers,
+    },
+    shutdown::{cancel_on_signal, finish_shutdown, stop_server_on_shutdown},
+};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use tracing::{info, instrument};
 use tracing_actix_web::TracingLogger;
 use tracing_core::LevelFilter;
@@ -26,6 +74,7 @@ use tracing_subscriber::EnvFilter;
 use tracing_util::{setup_tracing, shutdown_tracing};
 use utoipa_actix_web::AppExt;
 
+mod cli;
 mod config;
 mod metrics;
 mod startup;
@@ -34,6 +83,7 @@ mod startup;
 #[instrument]
 async fn main() -> std::result::Result<(), std::io::Error> {
     let config = get_config();
+    let cli_command = cli::get_command()?;
 
     let mut env_filter = EnvFilter::from_default_env();
 
@@ -71,39 +121,124 @@ async fn main() -> std::result::Result<(), std::io::Error> {
     let eventstore_client_arc = setup_eventstore(&config.eventstore.connection_string);
     let postgres_client_arc = setup_postgres(&config.postgres).await;
 
+    // Only the catch-up runtime keeps its checkpoint next to the read model, the other runtimes
+    // would apply the events a rebuild replayed again
+    let organization_projection = &config.eventstore.projections.organizations_postgres;
+    let rebuildable_projection_name =
+        match (config.eventstore.backend, organization_projection.runtime) {
+            (EventStoreBackend::EventStoreDb, ProjectionRuntime::CatchUp) => {
+                Some(organization_projection.persistent_subscription_name.clone())
+            }
+            _ => None,
+        };
     let module = Arc::new(get_module(
         postgres_client_arc.clone(),
         eventstore_client_arc.clone(),
+        config.id_generator.worker_id,
+        config.eventstore.snapshots.frequency,
+        config.eventstore.backend.into(),
+        (&config.commands.conflict_retry).into(),
+        conflict_retry_metrics(),
+        rebuildable_projection_name,
     ));
 
-    for i in 0..config.eventstore.projections.organizations_postgres.workers {
-        let projector: Box<dyn Projector<OrganizationEvent>> = module.provide().unwrap();
-        let subscriber = OrganizationSubscriber {
-            client: eventstore_client_arc.clone(),
-            projector,
-            worker_id: i,
-            subscription_name: config
-                .eventstore
-                .projections
-                .organizations_postgres
-                .persistent_subscription_name
-                .clone(),
-            metrics: subscriber_metrics(),
-        };
+    if let Some(command) = cli_command {
+        let result = cli::run(command, &module).await;
+        shutdown_tracing();
+        return result;
+    }
 
-        info!("Starting subscriber");
-        subscriber
-            .prepare_subscription()
-            .await
-            .expect("Could not prepare subscription");
-        tokio::spawn(async move { subscriber.subscribe().await });
+    let shutdown = CancellationToken::new();
+    cancel_on_signal(shutdown.clone());
+    let drain_timeout = Duration::from_secs(config.shutdown.drain_timeout_seconds);
+    let projection_workers = ProjectionWorkers {
+        restart: (&config.eventstore.worker_restart).into(),
+        shutdown: shutdown.clone(),
+        tasks: TaskTracker::new(),
+    };
+    let _projection_worker_gauge = projection_worker_gauge(module.resolve());
+    match (config.eventstore.backend, organization_projection.runtime) {
+        (EventStoreBackend::EventStoreDb, ProjectionRuntime::PersistentSubscription) => {
+            start_subscribers::<OrganizationEvent>(
+                &module,
+                &eventstore_client_arc,
+                organization_projection,
+                ORGANIZATION_STREAM_PREFIX,
+                &projection_workers,
+            )
+        }
+        (EventStoreBackend::EventStoreDb, ProjectionRuntime::CatchUp) => {
+            start_catch_up_subscriber::<OrganizationEvent>(
+                &module,
+                &eventstore_client_arc,
+                organization_projection,
+                ORGANIZATION_STREAM_PREFIX,
+                &projection_workers,
+            )
+        }
+        (EventStoreBackend::PostgresOrganizations, _) => {
+            start_postgres_subscriber::<OrganizationEvent>(
+                &module,
+                organization_projection,
+                ORGANIZATION_STREAM_PREFIX,
+                Duration::from_millis(config.eventstore.poll_interval_milliseconds),
+                &projection_workers,
+            )
+        }
     }
+    start_subscribers::<RepositoryEvent>(
+        &module,
+        &eventstore_client_arc,
+        &config.eventstore.projections.repositories_postgres,
+        REPOSITORY_STREAM_PREFIX,
+        &projection_workers,
+    );
+    start_subscribers::<PullRequestEvent>(
+        &module,
+        &eventstore_client_arc,
+        &config.eventstore.projections.pull_requests_postgres,
+        PULL_REQUEST_STREAM_PREFIX,
+        &projection_workers,
+    );
+    start_subscribers::<CommitGraphEvent>(
+        &module,
+        &eventstore_client_arc,
+        &config.eventstore.projections.commit_graphs_postgres,
+        COMMIT_GRAPH_STREAM_PREFIX,
+        &projection_workers,
+    );
+    start_subscribers::<BranchEvent>(
+        &module,
+        &eventstore_client_arc,
+        &config.eventstore.projections.branches_postgres,
+        BRANCH_STREAM_PREFIX,
+        &projection_workers,
+    );
+    start_subscribers::<DeveloperEvent>(
+        &module,
+        &eventstore_client_arc,
+        &config.eventstore.projections.developers_postgres,
+        DEVELOPER_STREAM_PREFIX,
+        &projection_workers,
+    );
+    start_idempotency_key_cleanup(
+        &module,
+        Duration::from_secs(config.idempotency.cleanup_interval_seconds),
+        &shutdown,
+        &projection_workers.tasks,
+    );
     let metrics = request_metrics();
+    let idempotency_ttl = Duration::from_secs(config.idempotency.ttl_seconds);
+    let idempotency_lease = Duration::from_secs(config.idempotency.lease_seconds);
 
     info!("Starting server");
-    let val = HttpServer::new(move || {
+    let server = HttpServer::new(move || {
         let app_data: Data<ApplicationModule> = module.clone().into();
         App::new()
+            .wrap(IdempotencyFactory {
+                ttl: idempotency_ttl,
+                lease: idempotency_lease,
+            })
             .wrap(TracingLogger::default())
             .wrap(MeterFactory {
                 metrics: metrics.clone(),
@@ -114,15 +249,63 @@ async fn main() -> std::result::Result<(), std::io::Error> {
             .service(create_organization)
             .service(get_organizations)
             .service(get_organization_log)
+            .service(update_organization)
+            .service(delete_organization)
             .service(add_platform_account)
             .service(remove_platform_account)
+            .service(register_repository)
+            .service(get_repositories)
+            .service(get_repository)
+            .service(rename_repository)
+            .service(archive_repository)
+            .service(move_repository)
+            .service(open_pull_request)
+            .service(get_pull_requests)
+            .service(get_pull_request)
+            .service(edit_pull_request)
+            .service(submit_review)
+            .service(dismiss_review)
+            .service(merge_pull_request)
+            .service(close_pull_request)
+            .service(record_commits)
+            .service(is_ancestor)
+            .service(get_merge_bases)
+            .service(get_commits_between)
+            .service(create_branch)
+            .service(get_branch)
+            .service(delete_branch)
+            .service(push_branch)
+            .service(get_branch_pushes)
+            .service(create_developer)
+            .service(get_developer)
+            .service(link_developer_identity)
+            .service(unlink_developer_identity)
+            .service(merge_developers)
+            .service(unmerge_developer)
+            .service(resolve_developer_identity)
+            .service(rebuild_organization_projection)
+            .service(get_organization_projection_rebuild)
+            .service(get_persistent_subscriptions)
+            .service(get_parked_events)
+            .service(replay_parked_events)
+            .service(get_projection_workers)
             .with_openapi()
     })
+    .disable_signals()
+    .shutdown_timeout(drain_timeout.as_secs())
     .bind(("0.0.0.0", 8080))?
-    .run()
-    .await;
+    .run();
+    let server_stopped = stop_server_on_shutdown(server.handle(), shutdown.clone(), drain_timeout);
 
-    shutdown_tracing();
+    let val = server.await;
+
+    finish_shutdown(
+        &shutdown,
+        server_stopped,
+        &projection_workers.tasks,
+        shutdown_tracing,
+    )
+    .await;
 
     val
 }
@@ -1,7 +1,13 @@
 use actix_tracing_util::RequestMetrics;
-use opentelemetry::global;
+use std::sync::Arc;
+
+use opentelemetry::{global, metrics::ObservableGauge, KeyValue};
 use opentelemetry_semantic_conventions::metric::HTTP_SERVER_REQUEST_DURATION;
-use source_control_event_store_interface::subscribers::organization_subscriber::SubscriberMetrics;
+use source_control_application::{
+    commands::conflict_retry::ConflictRetryMetrics,
+    projection_host::{ProjectionHost, ProjectionHostMetrics, WorkerState},
+};
+use source_control_event_store_interface::subscribers::aggregate_subscriber::SubscriberMetrics;
 
 pub fn request_metrics() -> RequestMetrics {
     let meter = global::meter("com.rafaeltab.actix");
@@ -82,3 +88,43 @@ pub fn subscriber_metrics() -> SubscriberMetrics {
             .build(),
     }
 }
+
+pub fn conflict_retry_metrics() -> ConflictRetryMetrics {
+    ConflictRetryMetrics::new(&global::meter("com.rafaeltab.application"))
+}
+
+pub fn projection_host_metrics() -> ProjectionHostMetrics {
+    ProjectionHostMetrics::new(&global::meter("com.rafaeltab.eventstore"))
+}
+
+/// Number of projection workers in every state, states without workers report 0 so their series
+/// don't keep the last count. Workers are listed ordered by projection.
+pub fn projection_worker_gauge(host: Arc<dyn ProjectionHost>) -> ObservableGauge<u64> {
+    global::meter("com.rafaeltab.eventstore")
+        .u64_observable_gauge("eventstore.projection.workers")
+        .with_description("Amount of projection workers by state")
+        .with_unit("worker")
+        .with_callback(move |observer| {
+            let workers = host.workers();
+            let mut projections: Vec<&str> =
+                workers.iter().map(|w| w.projection.as_str()).collect();
+            projections.dedup();
+
+            for projection in projections {
+                for state in WorkerState::NAMES {
+                    let count = workers
+                        .iter()
+                        .filter(|w| w.projection == projection && w.state.name() == state)
+                        .count();
+                    observer.observe(
+                        count as u64,
+                        &[
+                            KeyValue::new("projection", projection.to_string()),
+                            KeyValue::new("state", state),
+                        ],
+                    );
+                }
+            }
+        })
+        .build()
+}
@@ -0,0 +1,36 @@
+use std::{sync::Arc, time::Duration};
+
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_postgres_persistence_adapter::repositories::idempotency_key_repository::IdempotencyKeyRepository;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::{error, info};
+
+/// Removes idempotency keys of which the response or lease expired every `interval`, expired keys
+/// are already ignored when reserving so this only keeps the table small. Stops on `shutdown`
+/// after the removal it is running, `tasks` tracks it so the shutdown waits for that.
+pub fn start_idempotency_key_cleanup(
+    module: &Arc<ApplicationModule>,
+    interval: Duration,
+    shutdown: &CancellationToken,
+    tasks: &TaskTracker,
+) {
+    let repository: Box<dyn IdempotencyKeyRepository> = module.provide().unwrap();
+    let shutdown = shutdown.clone();
+
+    info!("Starting idempotency key cleanup");
+    tasks.spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match repository.delete_expired().await {
+                Ok(deleted) => info!(deleted, "Removed expired idempotency keys"),
+                Err(err) => error!("Could not remove expired idempotency keys {:?}", err),
+            }
+        }
+        info!("Stopped idempotency key cleanup");
+    });
+}
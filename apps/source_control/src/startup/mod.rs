@@ -1,2 +1,5 @@
 pub mod eventstore;
+pub mod idempotency;
 pub mod postgres;
+pub mod projections;
+pub mod shutdown;
@@ -7,7 +7,9 @@ use tracing::{error, info, instrument};
 use crate::config::PostgresConfig;
 
 #[instrument]
-pub async fn setup_postgres(config: &PostgresConfig) -> Arc<Pool<PostgresConnectionManager<NoTls>>> {
+pub async fn setup_postgres(
+    config: &PostgresConfig,
+) -> Arc<Pool<PostgresConnectionManager<NoTls>>> {
     info!("Connecting to postgres");
     let postgres_client = connect_postgres(config).await;
 
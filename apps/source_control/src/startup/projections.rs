@@ -0,0 +1,158 @@
+use std::{sync::Arc, time::Duration};
+
+use event_store_util::FromJson;
+use eventstore::Client;
+use shaku::{HasComponent, HasProvider};
+use source_control_application::{
+    module::ApplicationModule,
+    projection_host::{supervise, ProjectionHost, WorkerRestartSettings},
+};
+use source_control_event_store_interface::subscribers::{
+    aggregate_subscriber::AggregateSubscriber, catch_up_subscriber::CatchUpSubscriber,
+    postgres_subscriber::PostgresAggregateSubscriber,
+};
+use source_control_postgres_persistence_adapter::{
+    projectors::{checkpoint::CheckpointedProjector, Projector},
+    provider::PostgresProvider,
+};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::info;
+
+use crate::{
+    config::ProjectionConfig,
+    metrics::{projection_host_metrics, subscriber_metrics},
+};
+
+/// Shared by every projection worker, so they stop on the same signal and can be waited for.
+#[derive(Clone)]
+pub struct ProjectionWorkers {
+    pub restart: WorkerRestartSettings,
+    pub shutdown: CancellationToken,
+    pub tasks: TaskTracker,
+}
+
+/// Every worker is supervised by the [`ProjectionHost`], which starts it again when it fails.
+pub fn start_subscribers<TEvent>(
+    module: &Arc<ApplicationModule>,
+    client: &Arc<Client>,
+    config: &ProjectionConfig,
+    stream_prefix: &'static str,
+    workers: &ProjectionWorkers,
+) where
+    ApplicationModule: HasProvider<dyn Projector<TEvent>>,
+    TEvent: FromJson + Send + 'static,
+{
+    for i in 0..config.workers {
+        let projector: Box<dyn Projector<TEvent>> = module.provide().unwrap();
+        let subscriber = AggregateSubscriber::<TEvent>::new(
+            client.clone(),
+            projector,
+            config.persistent_subscription_name.clone(),
+            stream_prefix,
+            i,
+            subscriber_metrics(),
+        );
+
+        let host: Arc<dyn ProjectionHost> = module.resolve();
+        let workers = workers.clone();
+
+        info!("Starting subscriber");
+        workers.tasks.clone().spawn(async move {
+            supervise(
+                &*host,
+                &workers.restart,
+                &projection_host_metrics(),
+                &subscriber.subscription_name,
+                i,
+                &workers.shutdown,
+                || async {
+                    subscriber.prepare_subscription().await?;
+                    subscriber.subscribe(&workers.shutdown).await
+                },
+            )
+            .await
+        });
+    }
+}
+
+/// Events in postgres are read by polling from a single position, so one worker is started no
+/// matter how many are configured.
+pub fn start_postgres_subscriber<TEvent>(
+    module: &Arc<ApplicationModule>,
+    config: &ProjectionConfig,
+    stream_prefix: &'static str,
+    poll_interval: Duration,
+    workers: &ProjectionWorkers,
+) where
+    ApplicationModule: HasProvider<dyn Projector<TEvent>>,
+    TEvent: FromJson + Send + 'static,
+{
+    let projector: Box<dyn Projector<TEvent>> = module.provide().unwrap();
+    let client: Arc<dyn PostgresProvider> = module.resolve();
+    let subscriber = PostgresAggregateSubscriber::<TEvent>::new(
+        client,
+        projector,
+        config.persistent_subscription_name.clone(),
+        stream_prefix,
+        poll_interval,
+        subscriber_metrics(),
+    );
+
+    let host: Arc<dyn ProjectionHost> = module.resolve();
+    let workers = workers.clone();
+
+    info!("Starting postgres subscriber");
+    workers.tasks.clone().spawn(async move {
+        supervise(
+            &*host,
+            &workers.restart,
+            &projection_host_metrics(),
+            &subscriber.subscription_name,
+            0,
+            &workers.shutdown,
+            || subscriber.subscribe(&workers.shutdown),
+        )
+        .await
+    });
+}
+
+/// Catch-up subscriptions apply events in order under the lock of their checkpoint, so one worker
+/// is started no matter how many are configured.
+pub fn start_catch_up_subscriber<TEvent>(
+    module: &Arc<ApplicationModule>,
+    client: &Arc<Client>,
+    config: &ProjectionConfig,
+    stream_prefix: &'static str,
+    workers: &ProjectionWorkers,
+) where
+    ApplicationModule: HasProvider<dyn CheckpointedProjector<TEvent>>,
+    TEvent: FromJson + Send + 'static,
+{
+    let projector: Box<dyn CheckpointedProjector<TEvent>> = module.provide().unwrap();
+    let postgres: Arc<dyn PostgresProvider> = module.resolve();
+    let subscriber = CatchUpSubscriber::<TEvent>::new(
+        client.clone(),
+        postgres,
+        projector,
+        config.persistent_subscription_name.clone(),
+        stream_prefix,
+        subscriber_metrics(),
+    );
+
+    let host: Arc<dyn ProjectionHost> = module.resolve();
+    let workers = workers.clone();
+
+    info!("Starting catch-up subscriber");
+    workers.tasks.clone().spawn(async move {
+        supervise(
+            &*host,
+            &workers.restart,
+            &projection_host_metrics(),
+            &subscriber.projection_name,
+            0,
+            &workers.shutdown,
+            || subscriber.subscribe(&workers.shutdown),
+        )
+        .await
+    });
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use actix_web::dev::ServerHandle;
+use tokio::{signal, task::JoinHandle, time::Instant};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::{info, warn};
+
+/// Cancels `shutdown` on SIGTERM or Ctrl+C, the server and every subscriber stop on it.
+pub fn cancel_on_signal(shutdown: CancellationToken) {
+    tokio::spawn(async move {
+        let ctrl_c = signal::ctrl_c();
+        let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Could not listen for SIGTERM");
+
+        tokio::select! {
+            _ = ctrl_c => info!("Received Ctrl+C, shutting down"),
+            _ = terminate.recv() => info!("Received SIGTERM, shutting down"),
+        }
+        shutdown.cancel();
+    });
+}
+
+/// Stops the server once `shutdown` is cancelled, in-flight requests get until the drain timeout
+/// to complete. Returns when the drain timeout ends.
+pub fn stop_server_on_shutdown(
+    server: ServerHandle,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
+) -> JoinHandle<Instant> {
+    tokio::spawn(async move {
+        shutdown.cancelled().await;
+        let deadline = Instant::now() + drain_timeout;
+        server.stop(true).await;
+        deadline
+    })
+}
+
+/// Runs once the server stopped. Stops the projection workers, waits for them until the drain
+/// timeout ends, and only then flushes telemetry, so the spans of the last events are exported.
+pub async fn finish_shutdown(
+    shutdown: &CancellationToken,
+    server_stopped: JoinHandle<Instant>,
+    tasks: &TaskTracker,
+    flush_telemetry: impl FnOnce(),
+) {
+    // The server may also stop by itself, the workers are stopped either way
+    shutdown.cancel();
+    let deadline = server_stopped.await.expect("Stopping the server panicked");
+    drain_projection_workers(tasks, deadline).await;
+    flush_telemetry();
+}
+
+/// Waits until every worker finished or nacked the event it was handling, or `deadline` passes.
+async fn drain_projection_workers(tasks: &TaskTracker, deadline: Instant) {
+    tasks.close();
+    info!(
+        workers = tasks.len(),
+        "Waiting for projection workers to stop"
+    );
+    match tokio::time::timeout_at(deadline, tasks.wait()).await {
+        Ok(_) => info!("Projection workers stopped"),
+        Err(_) => warn!(
+            workers = tasks.len(),
+            "Projection workers did not stop before the drain timeout"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use tokio::time::Instant;
+    use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+    use super::finish_shutdown;
+
+    #[tokio::test]
+    async fn should_flush_telemetry_after_workers_finished_their_event() {
+        let shutdown = CancellationToken::new();
+        let tasks = TaskTracker::new();
+        let committed = Arc::new(AtomicBool::new(false));
+        let worker_shutdown = shutdown.clone();
+        let worker_committed = committed.clone();
+        tasks.spawn(async move {
+            // Still handling an event when the shutdown starts
+            worker_shutdown.cancelled().await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            worker_committed.store(true, Ordering::SeqCst);
+        });
+        let server_stopped = tokio::spawn(async { Instant::now() + Duration::from_secs(5) });
+
+        let mut flushed_after_commit = None;
+        finish_shutdown(&shutdown, server_stopped, &tasks, || {
+            flushed_after_commit = Some(committed.load(Ordering::SeqCst));
+        })
+        .await;
+
+        assert!(shutdown.is_cancelled());
+        assert_eq!(flushed_after_commit, Some(true));
+    }
+}
@@ -45,7 +45,7 @@ impl Request for DeletePlatformAccount {
         &self,
         client: Client,
         request_handler: Box<dyn RequestHandler>,
-    ) -> BoxFuture<()> {
+    ) -> BoxFuture<'_, ()> {
         Box::pin(self.request(client, request_handler))
     }
 }
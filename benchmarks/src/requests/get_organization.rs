@@ -51,7 +51,7 @@ impl Request for GetOrganization {
         &self,
         client: Client,
         request_handler: Box<dyn RequestHandler>,
-    ) -> BoxFuture<()> {
+    ) -> BoxFuture<'_, ()> {
         Box::pin(self.request(client, request_handler))
     }
 }
@@ -32,7 +32,7 @@ impl Request for GetOrganizationLog {
         &self,
         client: Client,
         request_handler: Box<dyn RequestHandler>,
-    ) -> BoxFuture<()> {
+    ) -> BoxFuture<'_, ()> {
         Box::pin(self.request(client, request_handler))
     }
 }
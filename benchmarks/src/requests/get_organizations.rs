@@ -55,7 +55,7 @@ impl Request for GetOrganizations {
         &self,
         client: Client,
         request_handler: Box<dyn RequestHandler>,
-    ) -> BoxFuture<()> {
+    ) -> BoxFuture<'_, ()> {
         Box::pin(self.request(client, request_handler))
     }
 }
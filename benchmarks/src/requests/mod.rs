@@ -14,7 +14,7 @@ pub trait Request {
         &self,
         client: Client,
         response_handler: Box<dyn RequestHandler>,
-    ) -> BoxFuture<()>;
+    ) -> BoxFuture<'_, ()>;
 }
 
 pub trait RequestHandler: Send + Sync {
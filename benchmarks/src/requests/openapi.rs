@@ -27,7 +27,7 @@ impl Request for OpenApi {
         &self,
         client: Client,
         request_handler: Box<dyn RequestHandler>,
-    )  -> BoxFuture<()> {
+    )  -> BoxFuture<'_, ()> {
         Box::pin(self.request(client, request_handler))
     }
 }
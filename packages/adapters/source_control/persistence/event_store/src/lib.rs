@@ -1,2 +1,3 @@
 pub mod repositories;
 pub mod provider;
+pub mod snapshots;
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        base::SnapshotFrequency,
+        branch::{BranchAggregate, BranchEvent},
+        metadata::CommandMetadata,
+    },
+    entities::{
+        branch::{Branch, BranchId},
+        commit::CommitSha,
+        repository::RepositoryId,
+    },
+    repositories::branch_repository::{
+        BranchRepository, CreateBranchError, GetBranchError, SaveBranchError,
+    },
+};
+use tracing::instrument;
+
+use crate::provider::EventStoreProvider;
+
+use super::event_sourced_repository::{
+    AppendError, EventSourcedRepository, LoadError, StreamCategory,
+};
+
+impl StreamCategory for BranchEvent {
+    const CATEGORY: &'static str = "Branch";
+}
+
+#[derive(Provider)]
+#[shaku(interface = BranchRepository)]
+pub struct BranchRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl BranchRepository for BranchRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get(&self, branch_id: BranchId) -> Result<BranchAggregate, GetBranchError> {
+        self.events()
+            .load(branch_id)
+            .await
+            .map_err(|err| match err {
+                LoadError::NotFound => GetBranchError::NotFound { branch_id },
+                LoadError::Corrupt { reason } => GetBranchError::Corrupt { reason },
+                LoadError::Connection => GetBranchError::Connection,
+                LoadError::Unexpected => GetBranchError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self, branch))]
+    async fn save(&self, branch: BranchAggregate) -> Result<(), SaveBranchError> {
+        self.events()
+            .append(&branch, branch.root.id, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => SaveBranchError::Conflict,
+                AppendError::Connection => SaveBranchError::Connection,
+                AppendError::Unexpected => SaveBranchError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn create(
+        &self,
+        repository_id: RepositoryId,
+        name: String,
+        head: CommitSha,
+        based_on: Option<BranchId>,
+    ) -> Result<Branch, CreateBranchError> {
+        let id = BranchId::from_name(repository_id, &name);
+
+        let event = BranchEvent::CreateBranch {
+            branch_id: id,
+            repository_id,
+            name: name.clone(),
+            head,
+            based_on,
+        };
+
+        self.events()
+            .create(id, &event, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => CreateBranchError::Conflict,
+                AppendError::Connection => CreateBranchError::Connection,
+                AppendError::Unexpected => CreateBranchError::Unexpected,
+            })?;
+
+        Ok(Branch {
+            id,
+            repository_id,
+            name,
+            head,
+            based_on,
+            deleted: false,
+            pushes: vec![],
+        })
+    }
+}
+
+impl BranchRepositoryImpl {
+    fn events(&self) -> EventSourcedRepository<BranchEvent, Branch> {
+        EventSourcedRepository::new(self.client.get_client(), SnapshotFrequency::default())
+    }
+}
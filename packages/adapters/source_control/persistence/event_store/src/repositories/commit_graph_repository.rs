@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_store_util::snapshots::commit_graph::EventStoreCommitGraphSnapshot;
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        commit_graph::{CommitGraphAggregate, CommitGraphEvent},
+        metadata::CommandMetadata,
+    },
+    entities::{commit_graph::CommitGraph, repository::RepositoryId},
+    repositories::commit_graph_repository::{
+        CommitGraphRepository, GetCommitGraphError, SaveCommitGraphError,
+    },
+};
+use tracing::instrument;
+
+use crate::{provider::EventStoreProvider, snapshots::SnapshotSettings};
+
+use super::event_sourced_repository::{
+    AppendError, EventSourcedRepository, LoadError, StreamCategory,
+};
+
+impl StreamCategory for CommitGraphEvent {
+    const CATEGORY: &'static str = "CommitGraph";
+}
+
+/// Every recorded push appends to the stream of the repository, and the snapshot holds every
+/// commit. Once a snapshot would be larger than EventStore accepts it is no longer written, and
+/// loading the graph replays the stream from the last snapshot that was.
+#[derive(Provider)]
+#[shaku(interface = CommitGraphRepository)]
+pub struct CommitGraphRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn EventStoreProvider>,
+    #[shaku(inject)]
+    pub snapshot_settings: Arc<dyn SnapshotSettings>,
+}
+
+#[async_trait]
+impl CommitGraphRepository for CommitGraphRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        repository_id: RepositoryId,
+    ) -> Result<CommitGraphAggregate, GetCommitGraphError> {
+        // Every repository has a commit graph, it is stored once the first commit is pushed
+        match self
+            .events()
+            .load_from_snapshot::<EventStoreCommitGraphSnapshot>(repository_id)
+            .await
+        {
+            Ok(aggregate) => Ok(aggregate),
+            Err(LoadError::NotFound) => Ok(CommitGraphAggregate::new(repository_id)),
+            Err(LoadError::Corrupt { reason }) => Err(GetCommitGraphError::Corrupt { reason }),
+            Err(LoadError::Connection) => Err(GetCommitGraphError::Connection),
+            Err(LoadError::Unexpected) => Err(GetCommitGraphError::Unexpected),
+        }
+    }
+
+    #[instrument(skip(self, commit_graph))]
+    async fn save(
+        &self,
+        commit_graph: CommitGraphAggregate,
+        metadata: CommandMetadata,
+    ) -> Result<(), SaveCommitGraphError> {
+        if commit_graph.draft_events.is_empty() {
+            return Ok(());
+        }
+
+        let events = self.events();
+        let repository_id = commit_graph.root.repository_id;
+        let result = if commit_graph.is_new() {
+            events.start(&commit_graph, repository_id, &metadata).await
+        } else {
+            events
+                .append_and_snapshot::<EventStoreCommitGraphSnapshot>(
+                    &commit_graph,
+                    repository_id,
+                    &metadata,
+                )
+                .await
+        };
+
+        result.map_err(|err| match err {
+            AppendError::Conflict => SaveCommitGraphError::Conflict,
+            AppendError::Connection => SaveCommitGraphError::Connection,
+            AppendError::Unexpected => SaveCommitGraphError::Unexpected,
+        })
+    }
+}
+
+impl CommitGraphRepositoryImpl {
+    fn events(&self) -> EventSourcedRepository<CommitGraphEvent, CommitGraph> {
+        EventSourcedRepository::new(self.client.get_client(), self.snapshot_settings.frequency())
+    }
+}
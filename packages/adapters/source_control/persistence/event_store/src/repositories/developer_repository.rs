@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        base::SnapshotFrequency,
+        developer::{DeveloperAggregate, DeveloperEvent},
+        metadata::CommandMetadata,
+    },
+    entities::developer::{Developer, DeveloperId},
+    repositories::developer_repository::{
+        CreateDeveloperError, DeveloperRepository, GetDeveloperError, SaveDeveloperError,
+    },
+};
+use tracing::instrument;
+
+use crate::provider::EventStoreProvider;
+
+use super::event_sourced_repository::{
+    AppendError, EventSourcedRepository, LoadError, StreamCategory,
+};
+
+impl StreamCategory for DeveloperEvent {
+    const CATEGORY: &'static str = "Developer";
+}
+
+#[derive(Provider)]
+#[shaku(interface = DeveloperRepository)]
+pub struct DeveloperRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl DeveloperRepository for DeveloperRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        developer_id: DeveloperId,
+    ) -> Result<DeveloperAggregate, GetDeveloperError> {
+        self.events()
+            .load(developer_id)
+            .await
+            .map_err(|err| match err {
+                LoadError::NotFound => GetDeveloperError::NotFound { developer_id },
+                LoadError::Corrupt { reason } => GetDeveloperError::Corrupt { reason },
+                LoadError::Connection => GetDeveloperError::Connection,
+                LoadError::Unexpected => GetDeveloperError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self, developer))]
+    async fn save(&self, developer: DeveloperAggregate) -> Result<(), SaveDeveloperError> {
+        self.events()
+            .append(&developer, developer.root.id, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => SaveDeveloperError::Conflict,
+                AppendError::Connection => SaveDeveloperError::Connection,
+                AppendError::Unexpected => SaveDeveloperError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn create(
+        &self,
+        id: DeveloperId,
+        username: String,
+    ) -> Result<Developer, CreateDeveloperError> {
+        let event = DeveloperEvent::CreateDeveloper {
+            developer_id: id,
+            username: username.clone(),
+        };
+
+        self.events()
+            .create(id, &event, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => CreateDeveloperError::Conflict,
+                AppendError::Connection => CreateDeveloperError::Connection,
+                AppendError::Unexpected => CreateDeveloperError::Unexpected,
+            })?;
+
+        Ok(Developer {
+            id,
+            username,
+            identities: vec![],
+            merged_into: None,
+            merged_from: vec![],
+        })
+    }
+}
+
+impl DeveloperRepositoryImpl {
+    fn events(&self) -> EventSourcedRepository<DeveloperEvent, Developer> {
+        EventSourcedRepository::new(self.client.get_client(), SnapshotFrequency::default())
+    }
+}
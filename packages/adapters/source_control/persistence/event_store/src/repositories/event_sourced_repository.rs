@@ -0,0 +1,381 @@
+use std::{fmt::Display, marker::PhantomData, sync::Arc};
+
+use event_store_util::{
+    from_recorded_event,
+    metadata::{event_metadata, metadata_from_recorded_event},
+    FromJson, ToJson,
+};
+use eventstore::{AppendToStreamOptions, Client, EventData, ReadStreamOptions, StreamPosition};
+use source_control_domain::aggregates::{
+    base::{Aggregate, DomainEvent, Snapshot, SnapshotFrequency},
+    metadata::{CommandMetadata, LoggedEvent},
+};
+use thiserror::Error;
+use tracing::{error, span, Instrument, Level};
+
+use crate::snapshots::{read_snapshot, write_snapshot};
+
+use super::DomainEventJson;
+
+/// Names the streams the events of an aggregate are stored in, implemented by its event.
+pub trait StreamCategory {
+    /// `Organization` stores its events in `Porti.SourceControl/Aggregates/Organization/<id>`, the
+    /// id formatted with its `Display`.
+    const CATEGORY: &'static str;
+
+    fn get_stream_name(id: impl Display) -> String {
+        format!("Porti.SourceControl/Aggregates/{}/{}", Self::CATEGORY, id)
+    }
+
+    /// Kept outside of the aggregate prefix, so subscribers never see snapshots.
+    fn get_snapshot_stream_name(id: impl Display) -> String {
+        format!("Porti.SourceControl/Snapshots/{}/{}", Self::CATEGORY, id)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    #[error("The stream does not exist")]
+    NotFound,
+    #[error("An event in the stream could not be decoded, {reason}")]
+    Corrupt { reason: String },
+    #[error("Could not reach the event store")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AppendError {
+    #[error("The stream is not at the expected revision")]
+    Conflict,
+    #[error("Could not reach the event store")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+/// Reads and writes the stream of an aggregate, the concrete repositories map its errors to the
+/// errors of their domain.
+pub struct EventSourcedRepository<TEvent, TRoot> {
+    client: Arc<Client>,
+    snapshot_frequency: SnapshotFrequency,
+    aggregate: PhantomData<fn() -> (TEvent, TRoot)>,
+}
+
+impl<TEvent, TRoot> EventSourcedRepository<TEvent, TRoot>
+where
+    TEvent: DomainEvent<TRoot> + StreamCategory,
+    TRoot: Default,
+{
+    pub fn new(client: Arc<Client>, snapshot_frequency: SnapshotFrequency) -> Self {
+        Self {
+            client,
+            snapshot_frequency,
+            aggregate: PhantomData,
+        }
+    }
+
+    /// Replays every event of the stream.
+    pub async fn load(&self, id: impl Display) -> Result<Aggregate<TEvent, TRoot>, LoadError> {
+        let (events, latest_revision) = self
+            .read_events(&TEvent::get_stream_name(id), StreamPosition::Start)
+            .await?;
+
+        match latest_revision {
+            Some(latest_revision) => Ok(Aggregate::from_events(events, latest_revision)),
+            None => Err(LoadError::NotFound),
+        }
+    }
+
+    pub async fn load_log(
+        &self,
+        id: impl Display,
+    ) -> Result<Box<[LoggedEvent<TEvent>]>, LoadError> {
+        let read_span = span!(Level::INFO, "event_store_read_stream");
+        let mut event_stream = self
+            .client
+            .read_stream(TEvent::get_stream_name(id), &ReadStreamOptions::default())
+            .instrument(read_span)
+            .await
+            .map_err(map_read_error)?;
+
+        let mut events = Vec::new();
+        while let Some(event) = event_stream.next().await.map_err(map_read_error)? {
+            let original_event = event.get_original_event();
+            events.push(LoggedEvent {
+                event: decode(original_event)?,
+                metadata: metadata_from_recorded_event(original_event),
+            });
+        }
+
+        if events.is_empty() {
+            return Err(LoadError::NotFound);
+        }
+
+        Ok(events.into())
+    }
+
+    /// Appends the draft events, expecting the stream to still be at the revision it was loaded
+    /// at.
+    pub async fn append(
+        &self,
+        aggregate: &Aggregate<TEvent, TRoot>,
+        id: impl Display,
+        metadata: &CommandMetadata,
+    ) -> Result<(), AppendError> {
+        self.append_events(
+            TEvent::get_stream_name(id),
+            eventstore::ExpectedRevision::Exact(aggregate.latest_revision),
+            &aggregate.draft_events,
+            metadata,
+        )
+        .await
+    }
+
+    /// Appends the draft events of an aggregate that was never saved, conflicts when the stream
+    /// already exists.
+    pub async fn start(
+        &self,
+        aggregate: &Aggregate<TEvent, TRoot>,
+        id: impl Display,
+        metadata: &CommandMetadata,
+    ) -> Result<(), AppendError> {
+        self.append_events(
+            TEvent::get_stream_name(id),
+            eventstore::ExpectedRevision::NoStream,
+            &aggregate.draft_events,
+            metadata,
+        )
+        .await
+    }
+
+    /// Starts the stream with `event`, conflicts when the stream already exists.
+    pub async fn create(
+        &self,
+        id: impl Display,
+        event: &TEvent,
+        metadata: &CommandMetadata,
+    ) -> Result<(), AppendError> {
+        self.append_events(
+            TEvent::get_stream_name(id),
+            eventstore::ExpectedRevision::NoStream,
+            std::slice::from_ref(event),
+            metadata,
+        )
+        .await
+    }
+
+    /// The events after `position` and the revision of the last one, `None` when there are none.
+    async fn read_events(
+        &self,
+        stream: &str,
+        position: StreamPosition<u64>,
+    ) -> Result<(Vec<TEvent>, Option<u64>), LoadError> {
+        let read_span = span!(Level::INFO, "event_store_read_stream");
+        let mut event_stream = self
+            .client
+            .read_stream(stream, &ReadStreamOptions::default().position(position))
+            .instrument(read_span)
+            .await
+            .map_err(map_read_error)?;
+
+        let mut events = Vec::new();
+        let mut latest_revision = None;
+        while let Some(event) = event_stream.next().await.map_err(map_read_error)? {
+            let original_event = event.get_original_event();
+            latest_revision = Some(original_event.revision);
+            events.push(decode(original_event)?);
+        }
+
+        Ok((events, latest_revision))
+    }
+
+    async fn append_events(
+        &self,
+        stream: String,
+        expected_revision: eventstore::ExpectedRevision,
+        events: &[TEvent],
+        metadata: &CommandMetadata,
+    ) -> Result<(), AppendError> {
+        let metadata = event_metadata(metadata);
+        let events = events
+            .iter()
+            .map(|event| event.to_event_data_with_metadata(&metadata))
+            .collect::<Option<Vec<EventData>>>()
+            .ok_or(AppendError::Unexpected)?;
+
+        let write_span = span!(Level::INFO, "event_store_append_stream");
+        self.client
+            .append_to_stream(
+                stream,
+                &AppendToStreamOptions::default().expected_revision(expected_revision),
+                events,
+            )
+            .instrument(write_span)
+            .await
+            .map(|_| ())
+            .map_err(|err| match err {
+                eventstore::Error::WrongExpectedVersion { .. } => AppendError::Conflict,
+                eventstore::Error::ConnectionClosed => AppendError::Connection,
+                _ => {
+                    error!("Error occurred while appending events: {}", err);
+                    AppendError::Unexpected
+                }
+            })
+    }
+}
+
+/// Aggregates whose roots are snapshotted every `snapshot_frequency` events, `TSnapshot` being
+/// the json of the root.
+impl<TEvent, TRoot> EventSourcedRepository<TEvent, TRoot>
+where
+    TEvent: DomainEvent<TRoot> + StreamCategory,
+    TRoot: Snapshot,
+{
+    /// Replays only the events after the latest snapshot, or the whole stream without one.
+    pub async fn load_from_snapshot<TSnapshot>(
+        &self,
+        id: impl Display + Copy,
+    ) -> Result<Aggregate<TEvent, TRoot>, LoadError>
+    where
+        TSnapshot: FromJson + Into<TRoot>,
+    {
+        let snapshot = read_snapshot::<TSnapshot>(
+            &self.client,
+            &TEvent::get_snapshot_stream_name(id),
+            &Self::get_snapshot_type(),
+        )
+        .await
+        .map_err(map_read_error)?;
+
+        let position = match &snapshot {
+            Some((_, revision)) => StreamPosition::Position(revision + 1),
+            None => StreamPosition::Start,
+        };
+        let (events, latest_revision) = self
+            .read_events(&TEvent::get_stream_name(id), position)
+            .await?;
+
+        match (snapshot, latest_revision) {
+            (Some((root, revision)), _) => Ok(Aggregate::from_snapshot(
+                root.into(),
+                events,
+                latest_revision.unwrap_or(revision),
+            )),
+            (None, Some(latest_revision)) => Ok(Aggregate::from_events(events, latest_revision)),
+            (None, None) => Err(LoadError::NotFound),
+        }
+    }
+
+    /// Appends the draft events, then snapshots the root when they pass a multiple of the
+    /// snapshot frequency.
+    pub async fn append_and_snapshot<TSnapshot>(
+        &self,
+        aggregate: &Aggregate<TEvent, TRoot>,
+        id: impl Display + Copy,
+        metadata: &CommandMetadata,
+    ) -> Result<(), AppendError>
+    where
+        TSnapshot: for<'a> ToJson<&'a TRoot>,
+    {
+        self.append(aggregate, id, metadata).await?;
+
+        if aggregate.should_snapshot(self.snapshot_frequency) {
+            write_snapshot(
+                &self.client,
+                &TEvent::get_snapshot_stream_name(id),
+                &Self::get_snapshot_type(),
+                TSnapshot::to_json(&aggregate.root),
+                aggregate.revision_after_save(),
+            )
+            .await;
+        }
+
+        Ok(())
+    }
+
+    fn get_snapshot_type() -> String {
+        format!(
+            "Porti.SourceControl/Snapshots/{}/{}",
+            TEvent::CATEGORY,
+            TRoot::SNAPSHOT_VERSION
+        )
+    }
+}
+
+fn decode<TEvent: FromJson>(event: &eventstore::RecordedEvent) -> Result<TEvent, LoadError> {
+    from_recorded_event::<TEvent>(event).map_err(|err| {
+        error!("{}", err);
+        LoadError::Corrupt {
+            reason: err.to_string(),
+        }
+    })
+}
+
+fn map_read_error(err: eventstore::Error) -> LoadError {
+    match err {
+        eventstore::Error::ConnectionClosed => LoadError::Connection,
+        eventstore::Error::Grpc { .. } => LoadError::Connection,
+        eventstore::Error::GrpcConnectionError(..) => LoadError::Connection,
+        eventstore::Error::AccessDenied => LoadError::Connection,
+        eventstore::Error::DeadlineExceeded => LoadError::Connection,
+        eventstore::Error::ResourceNotFound => LoadError::NotFound,
+        _ => LoadError::Unexpected,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use event_store_util::snapshots::{
+        commit_graph::COMMIT_GRAPH_SNAPSHOT_TYPE, organization::ORGANIZATION_SNAPSHOT_TYPE,
+    };
+    use source_control_domain::{
+        aggregates::{
+            base::Snapshot, commit_graph::CommitGraphEvent, organization::OrganizationEvent,
+        },
+        entities::{
+            commit_graph::CommitGraph,
+            organization::{Organization, OrganizationId},
+            repository::RepositoryId,
+        },
+    };
+
+    use super::{EventSourcedRepository, StreamCategory};
+
+    #[test]
+    fn should_keep_stream_names_of_organizations() {
+        assert_eq!(
+            OrganizationEvent::get_stream_name(OrganizationId(1)),
+            "Porti.SourceControl/Aggregates/Organization/OrganizationId(1)"
+        );
+        assert_eq!(
+            OrganizationEvent::get_snapshot_stream_name(OrganizationId(1)),
+            "Porti.SourceControl/Snapshots/Organization/OrganizationId(1)"
+        );
+        assert_eq!(
+            EventSourcedRepository::<OrganizationEvent, Organization>::get_snapshot_type(),
+            format!(
+                "{}/{}",
+                ORGANIZATION_SNAPSHOT_TYPE,
+                Organization::SNAPSHOT_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn should_keep_stream_names_of_commit_graphs() {
+        assert_eq!(
+            CommitGraphEvent::get_stream_name(RepositoryId(1)),
+            "Porti.SourceControl/Aggregates/CommitGraph/RepositoryId(1)"
+        );
+        assert_eq!(
+            EventSourcedRepository::<CommitGraphEvent, CommitGraph>::get_snapshot_type(),
+            format!(
+                "{}/{}",
+                COMMIT_GRAPH_SNAPSHOT_TYPE,
+                CommitGraph::SNAPSHOT_VERSION
+            )
+        );
+    }
+}
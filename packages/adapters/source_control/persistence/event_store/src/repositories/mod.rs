@@ -1 +1,44 @@
+use event_codec::{EventCodec, EventField};
+use eventstore::EventData;
+use source_control_domain::aggregates::metadata::EventMetadata;
+use tracing::error;
+
+pub mod branch_repository;
+pub mod commit_graph_repository;
+pub mod developer_repository;
+pub mod event_sourced_repository;
 pub mod organization_repository;
+pub mod pull_request_repository;
+pub mod repository_repository;
+
+trait DomainEventJson
+where
+    Self: Sized,
+{
+    fn to_event_data(&self) -> Option<EventData>;
+
+    /// Stores `metadata` next to the json of the event.
+    fn to_event_data_with_metadata(&self, metadata: &EventMetadata) -> Option<EventData>;
+}
+
+impl<T: EventCodec> DomainEventJson for T {
+    fn to_event_data(&self) -> Option<EventData> {
+        match EventData::json(self.get_versioned_event_type(), self.encode()) {
+            Ok(data) => Some(data),
+            Err(err) => {
+                error!("{}", err);
+                None
+            }
+        }
+    }
+
+    fn to_event_data_with_metadata(&self, metadata: &EventMetadata) -> Option<EventData> {
+        match self.to_event_data()?.metadata_as_json(metadata.encode()) {
+            Ok(data) => Some(data),
+            Err(err) => {
+                error!("{}", err);
+                None
+            }
+        }
+    }
+}
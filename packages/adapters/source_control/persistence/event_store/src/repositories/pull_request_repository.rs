@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        base::SnapshotFrequency,
+        metadata::CommandMetadata,
+        pull_request::{PullRequestAggregate, PullRequestEvent},
+    },
+    entities::{
+        developer::DeveloperId,
+        pull_request::{PullRequest, PullRequestId, PullRequestState},
+        repository::RepositoryId,
+    },
+    repositories::pull_request_repository::{
+        CreatePullRequestError, GetPullRequestError, PullRequestRepository, SavePullRequestError,
+    },
+};
+use tracing::instrument;
+
+use crate::provider::EventStoreProvider;
+
+use super::event_sourced_repository::{
+    AppendError, EventSourcedRepository, LoadError, StreamCategory,
+};
+
+impl StreamCategory for PullRequestEvent {
+    const CATEGORY: &'static str = "PullRequest";
+}
+
+#[derive(Provider)]
+#[shaku(interface = PullRequestRepository)]
+pub struct PullRequestRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl PullRequestRepository for PullRequestRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        pull_request_id: PullRequestId,
+    ) -> Result<PullRequestAggregate, GetPullRequestError> {
+        self.events()
+            .load(pull_request_id)
+            .await
+            .map_err(|err| match err {
+                LoadError::NotFound => GetPullRequestError::NotFound { pull_request_id },
+                LoadError::Corrupt { reason } => GetPullRequestError::Corrupt { reason },
+                LoadError::Connection => GetPullRequestError::Connection,
+                LoadError::Unexpected => GetPullRequestError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self, pull_request))]
+    async fn save(&self, pull_request: PullRequestAggregate) -> Result<(), SavePullRequestError> {
+        self.events()
+            .append(
+                &pull_request,
+                pull_request.root.id,
+                &CommandMetadata::default(),
+            )
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => SavePullRequestError::Conflict,
+                AppendError::Connection => SavePullRequestError::Connection,
+                AppendError::Unexpected => SavePullRequestError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn create(
+        &self,
+        id: PullRequestId,
+        repository_id: RepositoryId,
+        number: u64,
+        author_id: DeveloperId,
+        title: String,
+        description: String,
+    ) -> Result<PullRequest, CreatePullRequestError> {
+        let event = PullRequestEvent::OpenPullRequest {
+            pull_request_id: id,
+            repository_id,
+            number,
+            author_id,
+            title: title.clone(),
+            description: description.clone(),
+        };
+
+        self.events()
+            .create(id, &event, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => CreatePullRequestError::Conflict,
+                AppendError::Connection => CreatePullRequestError::Connection,
+                AppendError::Unexpected => CreatePullRequestError::Unexpected,
+            })?;
+
+        Ok(PullRequest {
+            id,
+            repository: repository_id,
+            number,
+            author: author_id,
+            title,
+            description,
+            state: PullRequestState::Open,
+            reviews: vec![],
+        })
+    }
+}
+
+impl PullRequestRepositoryImpl {
+    fn events(&self) -> EventSourcedRepository<PullRequestEvent, PullRequest> {
+        EventSourcedRepository::new(self.client.get_client(), SnapshotFrequency::default())
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        base::SnapshotFrequency,
+        metadata::CommandMetadata,
+        repository::{RepositoryAggregate, RepositoryEvent},
+    },
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    repositories::repository_repository::{
+        CreateRepositoryError, GetRepositoryError, RepositoryRepository, SaveRepositoryError,
+    },
+};
+use tracing::instrument;
+
+use crate::provider::EventStoreProvider;
+
+use super::event_sourced_repository::{
+    AppendError, EventSourcedRepository, LoadError, StreamCategory,
+};
+
+impl StreamCategory for RepositoryEvent {
+    const CATEGORY: &'static str = "Repository";
+}
+
+#[derive(Provider)]
+#[shaku(interface = RepositoryRepository)]
+pub struct RepositoryRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl RepositoryRepository for RepositoryRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        repository_id: RepositoryId,
+    ) -> Result<RepositoryAggregate, GetRepositoryError> {
+        self.events()
+            .load(repository_id)
+            .await
+            .map_err(|err| match err {
+                LoadError::NotFound => GetRepositoryError::NotFound { repository_id },
+                LoadError::Corrupt { reason } => GetRepositoryError::Corrupt { reason },
+                LoadError::Connection => GetRepositoryError::Connection,
+                LoadError::Unexpected => GetRepositoryError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self, repository))]
+    async fn save(&self, repository: RepositoryAggregate) -> Result<(), SaveRepositoryError> {
+        self.events()
+            .append(&repository, repository.root.id, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => SaveRepositoryError::Conflict,
+                AppendError::Connection => SaveRepositoryError::Connection,
+                AppendError::Unexpected => SaveRepositoryError::Unexpected,
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn create(
+        &self,
+        id: RepositoryId,
+        organization_id: OrganizationId,
+        platform_account_id: PlatformAccountId,
+        name: String,
+    ) -> Result<Repository, CreateRepositoryError> {
+        let event = RepositoryEvent::RegisterRepository {
+            repository_id: id,
+            organization_id,
+            platform_account_id,
+            name: name.clone(),
+        };
+
+        self.events()
+            .create(id, &event, &CommandMetadata::default())
+            .await
+            .map_err(|err| match err {
+                AppendError::Conflict => CreateRepositoryError::Conflict,
+                AppendError::Connection => CreateRepositoryError::Connection,
+                AppendError::Unexpected => CreateRepositoryError::Unexpected,
+            })?;
+
+        Ok(Repository {
+            id,
+            organization_id,
+            platform_account: platform_account_id,
+            name,
+            archived: false,
+        })
+    }
+}
+
+impl RepositoryRepositoryImpl {
+    fn events(&self) -> EventSourcedRepository<RepositoryEvent, Repository> {
+        EventSourcedRepository::new(self.client.get_client(), SnapshotFrequency::default())
+    }
+}
@@ -0,0 +1,158 @@
+use event_store_util::{
+    snapshots::{snapshot_revision, snapshot_root, to_snapshot_json},
+    FromJson,
+};
+use eventstore::{
+    AppendToStreamOptions, Client, EventData, ReadStreamOptions, StreamMetadata, StreamPosition,
+};
+use shaku::{Component, Interface};
+use source_control_domain::aggregates::base::SnapshotFrequency;
+use tracing::{error, span, warn, Instrument, Level};
+
+/// Only the latest snapshot is ever read, older ones are truncated by the event store.
+const SNAPSHOTS_KEPT: u64 = 1;
+/// The default max append size of EventStore, larger snapshots would be rejected by it.
+const MAX_SNAPSHOT_BYTES: usize = 1024 * 1024;
+
+pub trait SnapshotSettings: Interface {
+    fn frequency(&self) -> SnapshotFrequency;
+}
+
+#[derive(Component)]
+#[shaku(interface = SnapshotSettings)]
+pub struct SnapshotSettingsImpl {
+    frequency: u64,
+}
+
+impl SnapshotSettings for SnapshotSettingsImpl {
+    fn frequency(&self) -> SnapshotFrequency {
+        SnapshotFrequency(self.frequency)
+    }
+}
+
+/// Reads the latest snapshot and the revision it was taken at. Snapshots of a different type or
+/// version, or that can't be decoded, are treated as missing.
+pub(crate) async fn read_snapshot<T: FromJson>(
+    client: &Client,
+    stream: &str,
+    event_type: &str,
+) -> Result<Option<(T, u64)>, eventstore::Error> {
+    let read_span = span!(Level::INFO, "event_store_read_snapshot");
+    let mut snapshot_stream = match client
+        .read_stream(
+            stream,
+            &ReadStreamOptions::default()
+                .backwards()
+                .position(StreamPosition::End)
+                .max_count(1),
+        )
+        .instrument(read_span)
+        .await
+    {
+        Ok(snapshot_stream) => snapshot_stream,
+        Err(eventstore::Error::ResourceNotFound) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let event = match snapshot_stream.next().await {
+        Ok(Some(event)) => event,
+        Ok(None) | Err(eventstore::Error::ResourceNotFound) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let recorded = event.get_original_event();
+    if recorded.event_type != event_type {
+        return Ok(None);
+    }
+
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(&recorded.data) else {
+        warn!(stream, "Ignoring snapshot that is not valid json");
+        return Ok(None);
+    };
+    let Some(revision) = snapshot_revision(&value) else {
+        warn!(stream, "Ignoring snapshot without a revision");
+        return Ok(None);
+    };
+
+    match T::from_json(snapshot_root(&value).clone(), event_type) {
+        Ok(snapshot) => Ok(Some((snapshot, revision))),
+        Err(failure) => {
+            warn!(
+                stream,
+                "Ignoring snapshot that could not be decoded, {}", failure
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Snapshots are an optimization, failing to write one is logged and otherwise ignored. Roots
+/// that grow past `MAX_SNAPSHOT_BYTES` are not snapshotted, they are loaded by replaying their
+/// stream.
+pub(crate) async fn write_snapshot(
+    client: &Client,
+    stream: &str,
+    event_type: &str,
+    root: serde_json::Value,
+    revision: u64,
+) {
+    let snapshot = to_snapshot_json(root, revision);
+    if let Some(size) = exceeds_max_size(&snapshot) {
+        warn!(
+            stream,
+            size, "Not saving snapshot, it is larger than the event store accepts"
+        );
+        return;
+    }
+
+    let event_data = match EventData::json(event_type, snapshot) {
+        Ok(data) => data,
+        Err(err) => {
+            error!("Error occurred while serializing snapshot: {}", err);
+            return;
+        }
+    };
+
+    let write_span = span!(Level::INFO, "event_store_append_snapshot");
+    let write_result = client
+        .append_to_stream(stream, &AppendToStreamOptions::default(), event_data)
+        .instrument(write_span)
+        .await;
+
+    match write_result {
+        // The first snapshot creates the stream, which is when its max count gets set
+        Ok(result) if result.next_expected_version == 0 => {
+            let metadata = StreamMetadata::builder().max_count(SNAPSHOTS_KEPT).build();
+            if let Err(err) = client
+                .set_stream_metadata(stream, &AppendToStreamOptions::default(), metadata)
+                .await
+            {
+                warn!("Error occurred while limiting snapshot stream: {}", err);
+            }
+        }
+        Ok(_) => {}
+        Err(err) => warn!("Error occurred while saving snapshot: {}", err),
+    }
+}
+
+/// The encoded size of the snapshot when it is larger than `MAX_SNAPSHOT_BYTES`.
+fn exceeds_max_size(snapshot: &serde_json::Value) -> Option<usize> {
+    let size = serde_json::to_vec(snapshot).map_or(0, |encoded| encoded.len());
+    (size > MAX_SNAPSHOT_BYTES).then_some(size)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{exceeds_max_size, MAX_SNAPSHOT_BYTES};
+
+    #[test]
+    fn should_only_skip_snapshots_larger_than_the_event_store_accepts() {
+        let small = json!({ "root": "a".repeat(MAX_SNAPSHOT_BYTES / 2) });
+        let large = json!({ "root": "a".repeat(MAX_SNAPSHOT_BYTES) });
+
+        assert_eq!(exceeds_max_size(&small), None);
+        assert!(exceeds_max_size(&large).is_some_and(|size| size > MAX_SNAPSHOT_BYTES));
+    }
+}
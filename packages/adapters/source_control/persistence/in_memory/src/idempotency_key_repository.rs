@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use source_control_postgres_persistence_adapter::repositories::idempotency_key_repository::{
+    IdempotencyKey, IdempotencyKeyError, IdempotencyKeyRepository, IdempotencyKeyReservation,
+    StoredResponse,
+};
+
+struct StoredKey {
+    request_hash: String,
+    lease_id: String,
+    response: Option<StoredResponse>,
+    expires_at: Instant,
+}
+
+/// Shares the keys between the repositories it is cloned into.
+#[derive(Clone, Default)]
+pub struct InMemoryIdempotencyKeyRepositoryImpl {
+    keys: Arc<Mutex<HashMap<IdempotencyKey, StoredKey>>>,
+    leases: Arc<AtomicU64>,
+}
+
+#[async_trait]
+impl IdempotencyKeyRepository for InMemoryIdempotencyKeyRepositoryImpl {
+    async fn reserve(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        lease: Duration,
+    ) -> Result<IdempotencyKeyReservation, IdempotencyKeyError> {
+        let mut keys = self.keys.lock().unwrap();
+        let now = Instant::now();
+
+        match keys.get(key) {
+            Some(existing) if existing.expires_at >= now => Ok(match &existing.response {
+                Some(response) => IdempotencyKeyReservation::Completed {
+                    request_hash: existing.request_hash.clone(),
+                    response: response.clone(),
+                },
+                None => IdempotencyKeyReservation::InProgress {
+                    request_hash: existing.request_hash.clone(),
+                },
+            }),
+            _ => {
+                let lease_id = self.leases.fetch_add(1, Ordering::SeqCst).to_string();
+                keys.insert(
+                    key.clone(),
+                    StoredKey {
+                        request_hash: request_hash.to_string(),
+                        lease_id: lease_id.clone(),
+                        response: None,
+                        expires_at: now + lease,
+                    },
+                );
+                Ok(IdempotencyKeyReservation::Reserved { lease_id })
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyKeyError> {
+        if let Some(existing) = self.keys.lock().unwrap().get_mut(key) {
+            if existing.lease_id == lease_id && existing.response.is_none() {
+                existing.response = Some(response.clone());
+                existing.expires_at = Instant::now() + ttl;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+    ) -> Result<(), IdempotencyKeyError> {
+        let mut keys = self.keys.lock().unwrap();
+        if keys
+            .get(key)
+            .is_some_and(|existing| existing.lease_id == lease_id && existing.response.is_none())
+        {
+            keys.remove(key);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self) -> Result<u64, IdempotencyKeyError> {
+        let mut keys = self.keys.lock().unwrap();
+        let before = keys.len();
+        let now = Instant::now();
+        keys.retain(|_, existing| existing.expires_at >= now);
+
+        Ok((before - keys.len()) as u64)
+    }
+}
@@ -0,0 +1,6 @@
+pub mod idempotency_key_repository;
+pub mod organization_name_repository;
+pub mod organization_read_model;
+pub mod organization_repository;
+pub mod organizations;
+pub mod unconnected;
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use source_control_domain::{
+    entities::organization::OrganizationId,
+    repositories::organization_name_repository::{
+        organization_name_key, OrganizationNameRepository, ReleaseOrganizationNameError,
+        ReserveOrganizationNameError,
+    },
+};
+
+use crate::organizations::InMemoryOrganizations;
+
+pub struct InMemoryOrganizationNameRepositoryImpl {
+    pub organizations: Arc<InMemoryOrganizations>,
+}
+
+#[async_trait]
+impl OrganizationNameRepository for InMemoryOrganizationNameRepositoryImpl {
+    async fn reserve(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReserveOrganizationNameError> {
+        let mut names = self.organizations.names.lock().unwrap();
+        let owner = *names
+            .entry(organization_name_key(name))
+            .or_insert(organization_id.0);
+
+        if owner == organization_id.0 {
+            Ok(())
+        } else {
+            Err(ReserveOrganizationNameError::Taken {
+                organization_id: OrganizationId(owner),
+            })
+        }
+    }
+
+    async fn release(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReleaseOrganizationNameError> {
+        let mut names = self.organizations.names.lock().unwrap();
+        let key = organization_name_key(name);
+        if names.get(&key) == Some(&organization_id.0) {
+            names.remove(&key);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use source_control_domain::{
+    aggregates::organization::OrganizationEvent, entities::organization::OrganizationId,
+};
+use source_control_postgres_persistence_adapter::{
+    projectors::{Projector, ProjectorError},
+    queries::get_organizations::{
+        GetOrganizationsQuery, GetOrganizationsQueryError, GetOrganizationsQueryHandler,
+        OrganizationResult,
+    },
+};
+use thiserror::Error;
+
+use crate::organizations::InMemoryOrganizations;
+
+/// Same page size as the postgres query.
+const PAGE_SIZE: usize = 100;
+
+/// A row of the `Organization` table together with its platform accounts.
+pub(crate) struct OrganizationRow {
+    name: String,
+    platform_account_ids: HashSet<u64>,
+    archived: bool,
+    deleted: bool,
+}
+
+pub struct InMemoryOrganizationProjector {
+    pub organizations: Arc<InMemoryOrganizations>,
+}
+
+#[derive(Error, Debug)]
+enum InMemoryOrganizationProjectorError {
+    #[error("The key already existed in the read model")]
+    DuplicateKey,
+}
+
+impl ProjectorError for InMemoryOrganizationProjectorError {
+    fn get_retryable(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl Projector<OrganizationEvent> for InMemoryOrganizationProjector {
+    async fn project(&self, event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let mut read_model = self.organizations.read_model.lock().unwrap();
+
+        // Like the postgres updates, events for rows that don't exist change nothing
+        match event {
+            OrganizationEvent::CreateOrganizationEvent {
+                organization_id,
+                name,
+            } => {
+                if read_model.contains_key(&to_key(organization_id)) {
+                    return Err(Box::new(InMemoryOrganizationProjectorError::DuplicateKey));
+                }
+                read_model.insert(
+                    to_key(organization_id),
+                    OrganizationRow {
+                        name,
+                        platform_account_ids: HashSet::new(),
+                        archived: false,
+                        deleted: false,
+                    },
+                );
+            }
+            OrganizationEvent::AddPlatformAccount {
+                organization_id,
+                account,
+            } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    if !row.platform_account_ids.insert(account.id.0) {
+                        return Err(Box::new(InMemoryOrganizationProjectorError::DuplicateKey));
+                    }
+                }
+            }
+            OrganizationEvent::RemovePlatformAccount {
+                organization_id,
+                account_id,
+            } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    row.platform_account_ids.remove(&account_id.0);
+                }
+            }
+            OrganizationEvent::RenameOrganization {
+                organization_id,
+                name,
+            } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    row.name = name;
+                }
+            }
+            OrganizationEvent::ArchiveOrganization { organization_id } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    row.archived = true;
+                }
+            }
+            OrganizationEvent::UnarchiveOrganization { organization_id } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    row.archived = false;
+                }
+            }
+            OrganizationEvent::DeleteOrganization { organization_id } => {
+                if let Some(row) = read_model.get_mut(&to_key(organization_id)) {
+                    row.deleted = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct InMemoryGetOrganizationsQueryHandlerImpl {
+    pub organizations: Arc<InMemoryOrganizations>,
+}
+
+#[async_trait]
+impl GetOrganizationsQueryHandler for InMemoryGetOrganizationsQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetOrganizationsQuery,
+    ) -> Result<Vec<OrganizationResult>, GetOrganizationsQueryError> {
+        let read_model = self.organizations.read_model.lock().unwrap();
+        let existing = read_model.iter().filter(|(_, row)| !row.deleted);
+
+        let mut page: Vec<_> = match (query.before, query.after) {
+            (None, Some(after)) => existing
+                .filter(|(key, _)| **key > to_key(after))
+                .take(PAGE_SIZE)
+                .collect(),
+            (Some(before), None) => existing
+                .filter(|(key, _)| **key < to_key(before))
+                .rev()
+                .take(PAGE_SIZE)
+                .collect(),
+            _ => existing.take(PAGE_SIZE).collect(),
+        };
+        page.sort_by_key(|(key, _)| **key);
+
+        Ok(page
+            .into_iter()
+            .map(|(key, row)| OrganizationResult {
+                id: OrganizationId(u64::from_ne_bytes(key.to_ne_bytes())),
+                name: row.name.clone(),
+                paltform_account_count: row.platform_account_ids.len() as i64,
+                archived: row.archived,
+            })
+            .collect())
+    }
+}
+
+fn to_key(organization_id: OrganizationId) -> i64 {
+    i64::from_ne_bytes(organization_id.0.to_ne_bytes())
+}
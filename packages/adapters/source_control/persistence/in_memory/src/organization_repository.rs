@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_codec::EventCodec;
+use event_store_util::{from_stored_json, metadata::event_metadata};
+use source_control_domain::{
+    aggregates::{
+        metadata::{CommandMetadata, EventMetadata, LoggedEvent},
+        organization::{OrganizationAggregate, OrganizationEvent},
+    },
+    entities::organization::{Organization, OrganizationId},
+    repositories::organization_repository::{
+        CreateOrganizationError, GetOrganizationError, GetOrganizationLogError,
+        OrganizationRepository, SaveOrganizationError,
+    },
+};
+use source_control_postgres_persistence_adapter::projectors::Projector;
+use tracing::error;
+
+use crate::organizations::{InMemoryOrganizations, StoredEvent};
+
+/// Keeps the revisions of a stream like EventStore does, a save that doesn't start from the
+/// latest revision conflicts.
+pub struct InMemoryOrganizationRepositoryImpl {
+    pub organizations: Arc<InMemoryOrganizations>,
+    pub projector: Box<dyn Projector<OrganizationEvent>>,
+}
+
+#[async_trait]
+impl OrganizationRepository for InMemoryOrganizationRepositoryImpl {
+    async fn get_log(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<Box<[LoggedEvent<OrganizationEvent>]>, GetOrganizationLogError> {
+        let streams = self.organizations.streams.lock().unwrap();
+        let stream = streams
+            .get(&organization_id.0)
+            .ok_or(GetOrganizationLogError::NotFound { organization_id })?;
+
+        stream
+            .iter()
+            .enumerate()
+            .map(|(revision, stored)| {
+                decode(organization_id, revision, stored)
+                    .map(|event| LoggedEvent {
+                        event,
+                        metadata: Some(stored.metadata.clone()),
+                    })
+                    .map_err(|reason| GetOrganizationLogError::Corrupt { reason })
+            })
+            .collect()
+    }
+
+    async fn get(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<OrganizationAggregate, GetOrganizationError> {
+        let streams = self.organizations.streams.lock().unwrap();
+        let stream = streams
+            .get(&organization_id.0)
+            .ok_or(GetOrganizationError::NotFound { organization_id })?;
+
+        let events = stream
+            .iter()
+            .enumerate()
+            .map(|(revision, stored)| decode(organization_id, revision, stored))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|reason| GetOrganizationError::Corrupt { reason })?;
+
+        Ok(OrganizationAggregate::from_events(
+            events,
+            stream.len() as u64 - 1,
+        ))
+    }
+
+    async fn save(
+        &self,
+        organization: OrganizationAggregate,
+        metadata: CommandMetadata,
+    ) -> Result<(), SaveOrganizationError> {
+        let metadata = event_metadata(&metadata);
+        {
+            let mut streams = self.organizations.streams.lock().unwrap();
+            let stream = streams
+                .get_mut(&organization.root.id.0)
+                .ok_or(SaveOrganizationError::Conflict)?;
+            if stream.len() as u64 != organization.latest_revision + 1 {
+                return Err(SaveOrganizationError::Conflict);
+            }
+
+            stream.extend(
+                organization
+                    .draft_events
+                    .iter()
+                    .map(|event| to_stored_event(event, &metadata)),
+            );
+        }
+
+        for event in organization.draft_events {
+            self.projector.project(event).await.map_err(|err| {
+                error!("Error occurred while projecting organization: {}", err);
+                SaveOrganizationError::Unexpected
+            })?;
+        }
+
+        Ok(())
+    }
+
+    async fn create(
+        &self,
+        id: OrganizationId,
+        name: String,
+        metadata: CommandMetadata,
+    ) -> Result<Organization, CreateOrganizationError> {
+        let event = OrganizationEvent::CreateOrganizationEvent {
+            organization_id: id,
+            name: name.clone(),
+        };
+
+        {
+            let mut streams = self.organizations.streams.lock().unwrap();
+            if streams.contains_key(&id.0) {
+                return Err(CreateOrganizationError::Conflict);
+            }
+            streams.insert(
+                id.0,
+                vec![to_stored_event(&event, &event_metadata(&metadata))],
+            );
+        }
+
+        self.projector.project(event).await.map_err(|err| {
+            error!("Error occurred while projecting organization: {}", err);
+            CreateOrganizationError::Unexpected
+        })?;
+
+        Ok(Organization {
+            id,
+            name,
+            platform_accounts: vec![],
+            archived: false,
+            deleted: false,
+        })
+    }
+}
+
+fn to_stored_event(event: &OrganizationEvent, metadata: &EventMetadata) -> StoredEvent {
+    StoredEvent {
+        event_type: event.get_versioned_event_type(),
+        data: event.encode(),
+        metadata: metadata.clone(),
+    }
+}
+
+fn decode(
+    organization_id: OrganizationId,
+    revision: usize,
+    stored: &StoredEvent,
+) -> Result<OrganizationEvent, String> {
+    let event_id = format!("{}@{}", organization_id, revision);
+    from_stored_json::<OrganizationEvent>(&event_id, &stored.event_type, stored.data.clone())
+        .map_err(|err| {
+            error!("{}", err);
+            err.to_string()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use source_control_domain::{
+        aggregates::metadata::CommandMetadata, entities::organization::OrganizationId,
+        repositories::organization_repository::SaveOrganizationError,
+    };
+
+    use crate::organizations::InMemoryOrganizations;
+
+    #[tokio::test]
+    async fn should_conflict_when_saving_a_stale_organization() {
+        let repository = Arc::new(InMemoryOrganizations::default()).repository();
+        let id = OrganizationId(1);
+        repository
+            .create(id, "Porti".to_string(), CommandMetadata::default())
+            .await
+            .unwrap();
+
+        let mut first = repository.get(id).await.unwrap();
+        let mut second = repository.get(id).await.unwrap();
+        first.rename("First".to_string()).unwrap();
+        second.rename("Second".to_string()).unwrap();
+        repository
+            .save(first, CommandMetadata::default())
+            .await
+            .unwrap();
+
+        let result = repository.save(second, CommandMetadata::default()).await;
+
+        assert!(matches!(result, Err(SaveOrganizationError::Conflict)));
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use serde_json::Value;
+use source_control_domain::{
+    aggregates::{metadata::EventMetadata, organization::OrganizationEvent},
+    repositories::{
+        organization_name_repository::OrganizationNameRepository,
+        organization_repository::OrganizationRepository,
+    },
+};
+use source_control_postgres_persistence_adapter::{
+    projectors::Projector, queries::get_organizations::GetOrganizationsQueryHandler,
+};
+
+use crate::{
+    organization_name_repository::InMemoryOrganizationNameRepositoryImpl,
+    organization_read_model::{
+        InMemoryGetOrganizationsQueryHandlerImpl, InMemoryOrganizationProjector, OrganizationRow,
+    },
+    organization_repository::InMemoryOrganizationRepositoryImpl,
+};
+
+/// Everything kept about organizations. The providers it hands out share it, so what a command
+/// writes is seen by the queries after it.
+#[derive(Default)]
+pub struct InMemoryOrganizations {
+    pub(crate) streams: Mutex<HashMap<u64, Vec<StoredEvent>>>,
+    pub(crate) names: Mutex<HashMap<String, u64>>,
+    /// Ordered the way postgres orders the ids, as signed numbers.
+    pub(crate) read_model: Mutex<BTreeMap<i64, OrganizationRow>>,
+}
+
+/// Events are kept as json, so they go through the same encoding as the stored ones.
+pub(crate) struct StoredEvent {
+    pub event_type: String,
+    pub data: Value,
+    pub metadata: EventMetadata,
+}
+
+impl InMemoryOrganizations {
+    /// Projects the events it saves before returning, instead of through a subscription.
+    pub fn repository(self: &Arc<Self>) -> Box<dyn OrganizationRepository> {
+        Box::new(InMemoryOrganizationRepositoryImpl {
+            organizations: self.clone(),
+            projector: self.projector(),
+        })
+    }
+
+    pub fn names(self: &Arc<Self>) -> Box<dyn OrganizationNameRepository> {
+        Box::new(InMemoryOrganizationNameRepositoryImpl {
+            organizations: self.clone(),
+        })
+    }
+
+    pub fn projector(self: &Arc<Self>) -> Box<dyn Projector<OrganizationEvent>> {
+        Box::new(InMemoryOrganizationProjector {
+            organizations: self.clone(),
+        })
+    }
+
+    pub fn get_organizations(self: &Arc<Self>) -> Box<dyn GetOrganizationsQueryHandler> {
+        Box::new(InMemoryGetOrganizationsQueryHandlerImpl {
+            organizations: self.clone(),
+        })
+    }
+}
@@ -0,0 +1,391 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bb8_postgres::{bb8::PooledConnection, PostgresConnectionManager};
+use source_control_domain::{
+    aggregates::{
+        branch::{BranchAggregate, BranchEvent},
+        commit_graph::{CommitGraphAggregate, CommitGraphEvent},
+        developer::{DeveloperAggregate, DeveloperEvent},
+        metadata::CommandMetadata,
+        pull_request::{PullRequestAggregate, PullRequestEvent},
+        repository::{RepositoryAggregate, RepositoryEvent},
+    },
+    entities::{
+        branch::{Branch, BranchId},
+        commit::{Commit, CommitSha},
+        developer::{Developer, DeveloperId, DeveloperIdentity},
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        pull_request::{PullRequest, PullRequestId},
+        repository::{Repository, RepositoryId},
+    },
+    repositories::{
+        branch_repository::{BranchRepository, CreateBranchError, GetBranchError, SaveBranchError},
+        commit_graph_repository::{
+            CommitGraphRepository, GetCommitGraphError, SaveCommitGraphError,
+        },
+        developer_identity_repository::{
+            DeveloperIdentityRepository, ReleaseDeveloperIdentityError,
+            ReserveDeveloperIdentityError,
+        },
+        developer_repository::{
+            CreateDeveloperError, DeveloperRepository, GetDeveloperError, SaveDeveloperError,
+        },
+        developer_username_repository::{
+            DeveloperUsernameRepository, ReleaseDeveloperUsernameError,
+            ReserveDeveloperUsernameError,
+        },
+        pull_request_number_repository::{
+            PullRequestNumberRepository, ReleasePullRequestNumberError,
+            ReservePullRequestNumberError,
+        },
+        pull_request_repository::{
+            CreatePullRequestError, GetPullRequestError, PullRequestRepository,
+            SavePullRequestError,
+        },
+        repository_repository::{
+            CreateRepositoryError, GetRepositoryError, RepositoryRepository, SaveRepositoryError,
+        },
+    },
+};
+use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+use source_control_postgres_persistence_adapter::{
+    projectors::{Projector, ProjectorError},
+    provider::PostgresProvider,
+    queries::{
+        get_branch_pushes::{
+            BranchPushResult, GetBranchPushesQuery, GetBranchPushesQueryError,
+            GetBranchPushesQueryHandler,
+        },
+        get_commits_between::{
+            GetCommitsBetweenQuery, GetCommitsBetweenQueryError, GetCommitsBetweenQueryHandler,
+        },
+        get_merge_bases::{GetMergeBasesQuery, GetMergeBasesQueryError, GetMergeBasesQueryHandler},
+        get_pull_requests::{
+            GetPullRequestsQuery, GetPullRequestsQueryError, GetPullRequestsQueryHandler,
+            PullRequestResult,
+        },
+        get_repositories::{
+            GetRepositoriesQuery, GetRepositoriesQueryError, GetRepositoriesQueryHandler,
+            RepositoryResult,
+        },
+        is_ancestor::{IsAncestorQuery, IsAncestorQueryError, IsAncestorQueryHandler},
+        resolve_developer_identity::{
+            ResolveDeveloperIdentityQuery, ResolveDeveloperIdentityQueryError,
+            ResolveDeveloperIdentityQueryHandler,
+        },
+    },
+};
+use thiserror::Error;
+use tokio_postgres::NoTls;
+
+const UNCONNECTED: &str = "Only organizations are kept in memory, other aggregates need a database";
+
+/// Stands in for EventStore in an in-memory module. The module overrides everything that injects
+/// it, so it is never asked for a client.
+pub struct UnconnectedEventStoreProvider;
+
+impl EventStoreProvider for UnconnectedEventStoreProvider {
+    fn get_client(&self) -> Arc<eventstore::Client> {
+        unreachable!("{}", UNCONNECTED)
+    }
+}
+
+/// Stands in for postgres in an in-memory module. The module overrides everything that injects
+/// it, so it is never asked for a client.
+pub struct UnconnectedPostgresProvider;
+
+#[async_trait]
+impl PostgresProvider for UnconnectedPostgresProvider {
+    async fn get_client(&self) -> PooledConnection<'_, PostgresConnectionManager<NoTls>> {
+        unreachable!("{}", UNCONNECTED)
+    }
+}
+
+/// Stands in for the repositories, queries and projectors of the aggregates that aren't kept in
+/// memory, they fail as if the database was unreachable.
+#[derive(Clone, Copy, Default)]
+pub struct Unconnected;
+
+#[derive(Error, Debug)]
+#[error("{}", UNCONNECTED)]
+struct UnconnectedError;
+
+impl ProjectorError for UnconnectedError {
+    fn get_retryable(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl BranchRepository for Unconnected {
+    async fn get(&self, _branch_id: BranchId) -> Result<BranchAggregate, GetBranchError> {
+        Err(GetBranchError::Connection)
+    }
+
+    async fn save(&self, _branch: BranchAggregate) -> Result<(), SaveBranchError> {
+        Err(SaveBranchError::Connection)
+    }
+
+    async fn create(
+        &self,
+        _repository_id: RepositoryId,
+        _name: String,
+        _head: CommitSha,
+        _based_on: Option<BranchId>,
+    ) -> Result<Branch, CreateBranchError> {
+        Err(CreateBranchError::Connection)
+    }
+}
+
+#[async_trait]
+impl CommitGraphRepository for Unconnected {
+    async fn get(
+        &self,
+        _repository_id: RepositoryId,
+    ) -> Result<CommitGraphAggregate, GetCommitGraphError> {
+        Err(GetCommitGraphError::Connection)
+    }
+
+    async fn save(
+        &self,
+        _commit_graph: CommitGraphAggregate,
+        _metadata: CommandMetadata,
+    ) -> Result<(), SaveCommitGraphError> {
+        Err(SaveCommitGraphError::Connection)
+    }
+}
+
+#[async_trait]
+impl DeveloperIdentityRepository for Unconnected {
+    async fn reserve(
+        &self,
+        _identity: &DeveloperIdentity,
+        _developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperIdentityError> {
+        Err(ReserveDeveloperIdentityError::Connection)
+    }
+
+    async fn release(
+        &self,
+        _identity: &DeveloperIdentity,
+        _developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperIdentityError> {
+        Err(ReleaseDeveloperIdentityError::Connection)
+    }
+}
+
+#[async_trait]
+impl DeveloperRepository for Unconnected {
+    async fn get(
+        &self,
+        _developer_id: DeveloperId,
+    ) -> Result<DeveloperAggregate, GetDeveloperError> {
+        Err(GetDeveloperError::Connection)
+    }
+
+    async fn save(&self, _developer: DeveloperAggregate) -> Result<(), SaveDeveloperError> {
+        Err(SaveDeveloperError::Connection)
+    }
+
+    async fn create(
+        &self,
+        _id: DeveloperId,
+        _username: String,
+    ) -> Result<Developer, CreateDeveloperError> {
+        Err(CreateDeveloperError::Connection)
+    }
+}
+
+#[async_trait]
+impl DeveloperUsernameRepository for Unconnected {
+    async fn reserve(
+        &self,
+        _username: &str,
+        _developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperUsernameError> {
+        Err(ReserveDeveloperUsernameError::Connection)
+    }
+
+    async fn release(
+        &self,
+        _username: &str,
+        _developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperUsernameError> {
+        Err(ReleaseDeveloperUsernameError::Connection)
+    }
+}
+
+#[async_trait]
+impl PullRequestNumberRepository for Unconnected {
+    async fn reserve(
+        &self,
+        _repository_id: RepositoryId,
+        _number: u64,
+        _pull_request_id: PullRequestId,
+    ) -> Result<(), ReservePullRequestNumberError> {
+        Err(ReservePullRequestNumberError::Connection)
+    }
+
+    async fn release(
+        &self,
+        _repository_id: RepositoryId,
+        _number: u64,
+        _pull_request_id: PullRequestId,
+    ) -> Result<(), ReleasePullRequestNumberError> {
+        Err(ReleasePullRequestNumberError::Connection)
+    }
+}
+
+#[async_trait]
+impl PullRequestRepository for Unconnected {
+    async fn get(
+        &self,
+        _pull_request_id: PullRequestId,
+    ) -> Result<PullRequestAggregate, GetPullRequestError> {
+        Err(GetPullRequestError::Connection)
+    }
+
+    async fn save(&self, _pull_request: PullRequestAggregate) -> Result<(), SavePullRequestError> {
+        Err(SavePullRequestError::Connection)
+    }
+
+    async fn create(
+        &self,
+        _id: PullRequestId,
+        _repository_id: RepositoryId,
+        _number: u64,
+        _author_id: DeveloperId,
+        _title: String,
+        _description: String,
+    ) -> Result<PullRequest, CreatePullRequestError> {
+        Err(CreatePullRequestError::Connection)
+    }
+}
+
+#[async_trait]
+impl RepositoryRepository for Unconnected {
+    async fn get(
+        &self,
+        _repository_id: RepositoryId,
+    ) -> Result<RepositoryAggregate, GetRepositoryError> {
+        Err(GetRepositoryError::Connection)
+    }
+
+    async fn save(&self, _repository: RepositoryAggregate) -> Result<(), SaveRepositoryError> {
+        Err(SaveRepositoryError::Connection)
+    }
+
+    async fn create(
+        &self,
+        _id: RepositoryId,
+        _organization_id: OrganizationId,
+        _platform_account_id: PlatformAccountId,
+        _name: String,
+    ) -> Result<Repository, CreateRepositoryError> {
+        Err(CreateRepositoryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetBranchPushesQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetBranchPushesQuery,
+    ) -> Result<Vec<BranchPushResult>, GetBranchPushesQueryError> {
+        Err(GetBranchPushesQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetCommitsBetweenQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetCommitsBetweenQuery,
+    ) -> Result<Vec<Commit>, GetCommitsBetweenQueryError> {
+        Err(GetCommitsBetweenQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetMergeBasesQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetMergeBasesQuery,
+    ) -> Result<Vec<CommitSha>, GetMergeBasesQueryError> {
+        Err(GetMergeBasesQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetPullRequestsQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetPullRequestsQuery,
+    ) -> Result<Vec<PullRequestResult>, GetPullRequestsQueryError> {
+        Err(GetPullRequestsQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetRepositoriesQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetRepositoriesQuery,
+    ) -> Result<Vec<RepositoryResult>, GetRepositoriesQueryError> {
+        Err(GetRepositoriesQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl IsAncestorQueryHandler for Unconnected {
+    async fn handle(&self, _query: IsAncestorQuery) -> Result<bool, IsAncestorQueryError> {
+        Err(IsAncestorQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl ResolveDeveloperIdentityQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: ResolveDeveloperIdentityQuery,
+    ) -> Result<Developer, ResolveDeveloperIdentityQueryError> {
+        Err(ResolveDeveloperIdentityQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl Projector<BranchEvent> for Unconnected {
+    async fn project(&self, _event: BranchEvent) -> Result<(), Box<dyn ProjectorError>> {
+        Err(Box::new(UnconnectedError))
+    }
+}
+
+#[async_trait]
+impl Projector<CommitGraphEvent> for Unconnected {
+    async fn project(&self, _event: CommitGraphEvent) -> Result<(), Box<dyn ProjectorError>> {
+        Err(Box::new(UnconnectedError))
+    }
+}
+
+#[async_trait]
+impl Projector<DeveloperEvent> for Unconnected {
+    async fn project(&self, _event: DeveloperEvent) -> Result<(), Box<dyn ProjectorError>> {
+        Err(Box::new(UnconnectedError))
+    }
+}
+
+#[async_trait]
+impl Projector<PullRequestEvent> for Unconnected {
+    async fn project(&self, _event: PullRequestEvent) -> Result<(), Box<dyn ProjectorError>> {
+        Err(Box::new(UnconnectedError))
+    }
+}
+
+#[async_trait]
+impl Projector<RepositoryEvent> for Unconnected {
+    async fn project(&self, _event: RepositoryEvent) -> Result<(), Box<dyn ProjectorError>> {
+        Err(Box::new(UnconnectedError))
+    }
+}
@@ -0,0 +1,13 @@
+CREATE TABLE "Repository" (
+    id bigint primary key,
+    organization_id bigint references "Organization",
+    platform_account_id bigint,
+    name varchar,
+    archived boolean not null default false
+);
+
+CREATE INDEX IF NOT EXISTS "Repository_platform_account_id_IDX"
+    ON public."Repository" USING btree
+    (platform_account_id ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
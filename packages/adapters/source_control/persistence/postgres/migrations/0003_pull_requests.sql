@@ -0,0 +1,23 @@
+CREATE TABLE "PullRequest" (
+    id bigint primary key,
+    repository_id bigint references "Repository",
+    number bigint,
+    author_id bigint,
+    title varchar,
+    description varchar,
+    state varchar not null default 'open'
+);
+
+CREATE INDEX IF NOT EXISTS "PullRequest_repository_id_IDX"
+    ON public."PullRequest" USING btree
+    (repository_id ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
+
+CREATE TABLE "Review" (
+    pull_request_id bigint references "PullRequest",
+    developer_id bigint,
+    status_name varchar,
+    accepting boolean,
+    primary key (pull_request_id, developer_id)
+);
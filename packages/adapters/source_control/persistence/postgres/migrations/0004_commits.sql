@@ -0,0 +1,23 @@
+CREATE TABLE "Commit" (
+    repository_id bigint references "Repository",
+    sha bytea,
+    message varchar,
+    -- Commits are recorded parents first, so ordering on seq is a topological order
+    seq bigserial,
+    primary key (repository_id, sha)
+);
+
+CREATE TABLE "CommitParent" (
+    repository_id bigint,
+    sha bytea,
+    parent_sha bytea,
+    position smallint,
+    primary key (repository_id, sha, position),
+    foreign key (repository_id, sha) references "Commit"
+);
+
+CREATE INDEX IF NOT EXISTS "CommitParent_parent_sha_IDX"
+    ON public."CommitParent" USING btree
+    (repository_id ASC NULLS LAST, parent_sha ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
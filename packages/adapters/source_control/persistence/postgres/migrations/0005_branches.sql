@@ -0,0 +1,25 @@
+CREATE TABLE "Branch" (
+    id bigint primary key,
+    repository_id bigint references "Repository",
+    name varchar,
+    head bytea,
+    based_on bigint,
+    deleted boolean not null default false
+);
+
+CREATE INDEX IF NOT EXISTS "Branch_repository_id_IDX"
+    ON public."Branch" USING btree
+    (repository_id ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
+
+CREATE TABLE "BranchPush" (
+    seq bigserial primary key,
+    branch_id bigint references "Branch",
+    pusher_id bigint,
+    before bytea,
+    after bytea,
+    pushed_at timestamptz,
+    forced boolean,
+    unique (branch_id, pushed_at, after)
+);
@@ -0,0 +1,26 @@
+CREATE TABLE "Developer" (
+    id bigint primary key,
+    username varchar,
+    merged_into bigint
+);
+
+CREATE INDEX IF NOT EXISTS "Developer_merged_into_IDX"
+    ON public."Developer" USING btree
+    (merged_into ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
+
+-- platform is empty for identities that are not tied to a platform, like commit e-mails
+CREATE TABLE "DeveloperIdentity" (
+    kind varchar not null,
+    platform varchar not null,
+    value varchar not null,
+    developer_id bigint references "Developer",
+    primary key (kind, platform, value)
+);
+
+CREATE INDEX IF NOT EXISTS "DeveloperIdentity_developer_id_IDX"
+    ON public."DeveloperIdentity" USING btree
+    (developer_id ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
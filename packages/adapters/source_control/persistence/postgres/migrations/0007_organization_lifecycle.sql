@@ -0,0 +1,3 @@
+ALTER TABLE "Organization"
+    ADD COLUMN archived boolean not null default false,
+    ADD COLUMN deleted boolean not null default false;
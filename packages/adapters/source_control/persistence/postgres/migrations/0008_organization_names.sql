@@ -0,0 +1,16 @@
+-- Written by the commands directly instead of a projector, reservations have to be consistent
+CREATE TABLE "OrganizationName" (
+    name_key varchar primary key,
+    organization_id bigint not null
+);
+
+CREATE INDEX IF NOT EXISTS "OrganizationName_organization_id_IDX"
+    ON public."OrganizationName" USING btree
+    (organization_id ASC NULLS LAST)
+    WITH (deduplicate_items=False)
+    TABLESPACE pg_default;
+
+-- Organizations created before names were reserved keep their names
+INSERT INTO "OrganizationName" (name_key, organization_id)
+SELECT lower(trim(name)), id FROM "Organization" WHERE NOT deleted
+ON CONFLICT DO NOTHING;
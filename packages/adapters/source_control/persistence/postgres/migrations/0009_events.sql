@@ -0,0 +1,27 @@
+-- Only used when events are stored in postgres instead of EventStore
+CREATE TABLE events (
+    global_position bigserial primary key,
+    event_id uuid not null default gen_random_uuid(),
+    stream_id varchar not null,
+    revision bigint not null,
+    event_type varchar not null,
+    data jsonb not null,
+    metadata jsonb,
+    created_at timestamptz not null default now(),
+    unique (stream_id, revision)
+);
+
+-- Last global position every subscription has handled
+CREATE TABLE event_subscription_checkpoints (
+    subscription_name varchar primary key,
+    position bigint not null
+);
+
+-- Events a subscription gave up on, they are skipped until replayed
+CREATE TABLE parked_events (
+    subscription_name varchar not null,
+    global_position bigint not null references events,
+    reason varchar not null,
+    parked_at timestamptz not null default now(),
+    primary key (subscription_name, global_position)
+);
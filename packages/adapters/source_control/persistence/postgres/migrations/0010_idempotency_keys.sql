@@ -0,0 +1,26 @@
+-- Responses of requests sent with an Idempotency-Key, a retry of the request gets the same response
+CREATE TABLE idempotency_keys (
+    -- Clients pick their keys, so keys are scoped to the X-Actor-Id (empty without one) and the
+    -- endpoint of the request
+    actor_id varchar not null,
+    method varchar not null,
+    path varchar not null,
+    idempotency_key varchar not null,
+    -- Hash of the body, the key can't be reused for another request to the endpoint
+    request_hash varchar not null,
+    -- The request that is handling the key, only it stores the response or releases the key
+    lease_id varchar not null,
+    -- Null while the first request is still being handled
+    status_code smallint,
+    headers jsonb,
+    body bytea,
+    -- End of the lease while the request is being handled, a retry takes over the key after it.
+    -- Once the response is stored, when the response expires.
+    expires_at timestamptz not null,
+    PRIMARY KEY (actor_id, method, path, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS "idempotency_keys_expires_at_IDX"
+    ON public.idempotency_keys USING btree
+    (expires_at ASC NULLS LAST)
+    TABLESPACE pg_default;
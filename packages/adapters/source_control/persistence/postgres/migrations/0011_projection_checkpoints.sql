@@ -0,0 +1,9 @@
+-- Position in $all of the last event every catch-up projection applied, written in the same
+-- transaction as the read model so every event is applied exactly once
+CREATE TABLE projection_checkpoints (
+    projection_name varchar primary key,
+    -- Both null until the projection applied its first event
+    commit_position bigint,
+    prepare_position bigint,
+    updated_at timestamptz not null default now()
+);
@@ -0,0 +1,15 @@
+-- Appends to different streams no longer wait for each other, so global positions can commit
+-- out of order. Subscriptions read events in (transaction_id, global_position) order and only
+-- from transactions older than every transaction still running.
+-- The events stored before take the transaction id of this migration.
+ALTER TABLE events
+    ADD COLUMN transaction_id xid8 not null default pg_current_xact_id();
+
+CREATE INDEX events_transaction_id_global_position ON events (transaction_id, global_position);
+
+ALTER TABLE event_subscription_checkpoints
+    ADD COLUMN transaction_id bigint not null default 0;
+
+UPDATE event_subscription_checkpoints
+SET transaction_id = pg_current_xact_id()::text::bigint
+WHERE position > 0;
@@ -0,0 +1,13 @@
+-- Written by the commands directly instead of a projector, reservations have to be consistent
+CREATE TABLE "PullRequestNumber" (
+    repository_id bigint not null,
+    number bigint not null,
+    pull_request_id bigint not null,
+    primary key (repository_id, number)
+);
+
+-- Pull requests opened before numbers were reserved keep their numbers
+INSERT INTO "PullRequestNumber" (repository_id, number, pull_request_id)
+SELECT repository_id, number, id FROM "PullRequest"
+WHERE repository_id IS NOT NULL AND number IS NOT NULL
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,10 @@
+-- Written by the commands directly instead of a projector, reservations have to be consistent
+CREATE TABLE "DeveloperUsername" (
+    username varchar primary key,
+    developer_id bigint not null
+);
+
+-- Developers created before usernames were reserved keep their usernames
+INSERT INTO "DeveloperUsername" (username, developer_id)
+SELECT username, id FROM "Developer" WHERE username IS NOT NULL
+ON CONFLICT DO NOTHING;
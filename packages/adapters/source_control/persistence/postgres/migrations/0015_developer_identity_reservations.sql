@@ -0,0 +1,15 @@
+-- Written by the commands directly instead of a projector, reservations have to be consistent.
+-- Identities are split into columns the same way as in "DeveloperIdentity".
+CREATE TABLE "DeveloperIdentityReservation" (
+    kind varchar not null,
+    platform varchar not null,
+    value varchar not null,
+    developer_id bigint not null,
+    primary key (kind, platform, value)
+);
+
+-- Identities linked before they were reserved stay linked
+INSERT INTO "DeveloperIdentityReservation" (kind, platform, value, developer_id)
+SELECT kind, platform, value, developer_id FROM "DeveloperIdentity"
+WHERE developer_id IS NOT NULL
+ON CONFLICT DO NOTHING;
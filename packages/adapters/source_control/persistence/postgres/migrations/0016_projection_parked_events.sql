@@ -0,0 +1,16 @@
+-- Events a catch-up projection gave up on. The checkpoint moves past them in the transaction that
+-- parks them, so they are kept here with why instead of being lost. `parked_events` references
+-- the postgres `events` table, these events are read from EventStore.
+CREATE TABLE projection_parked_events (
+    projection_name varchar not null,
+    commit_position bigint not null,
+    prepare_position bigint not null,
+    event_id varchar not null,
+    stream_id varchar not null,
+    event_type varchar not null,
+    data bytea not null,
+    reason varchar not null,
+    retry_count integer not null,
+    parked_at timestamptz not null default now(),
+    primary key (projection_name, commit_position, prepare_position)
+);
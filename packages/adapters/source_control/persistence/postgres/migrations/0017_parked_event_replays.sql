@@ -0,0 +1,5 @@
+-- Parked events an operator asked to replay. The postgres subscription hands them to its projector
+-- again before reading new events, and removes them once they were projected. Events that fail
+-- again are parked again with the flag cleared.
+ALTER TABLE parked_events
+    ADD COLUMN replay_requested boolean not null default false;
@@ -1,3 +1,6 @@
 pub mod projectors;
 pub mod queries;
+pub mod repositories;
 pub mod provider;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
@@ -0,0 +1,130 @@
+use shaku::Provider;
+use std::sync::Arc;
+use thiserror::Error;
+
+use async_trait::async_trait;
+use source_control_domain::{aggregates::branch::BranchEvent, entities::branch_push::BranchPush};
+use tracing::{instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{Projector, ProjectorError};
+
+#[derive(Provider)]
+#[shaku(interface = Projector<BranchEvent>)]
+pub struct BranchProjector {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[derive(Error, Debug)]
+enum BranchProjectorError {
+    #[error("Unknown error")]
+    Unexpected(Box<tokio_postgres::Error>),
+}
+
+impl ProjectorError for BranchProjectorError {
+    fn get_retryable(&self) -> bool {
+        match self {
+            BranchProjectorError::Unexpected(_) => true,
+        }
+    }
+}
+
+#[async_trait]
+impl Projector<BranchEvent> for BranchProjector {
+    #[instrument(skip(self), err)]
+    async fn project(&self, event: BranchEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let res = match event {
+            BranchEvent::CreateBranch {
+                branch_id,
+                repository_id,
+                name,
+                head,
+                based_on,
+            } => {
+                let id = i64::from_ne_bytes(branch_id.0.to_ne_bytes());
+                let repository_id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+                let based_on = based_on.map(|x| i64::from_ne_bytes(x.0.to_ne_bytes()));
+
+                // A deleted branch can be created again under the same id
+                let upsert_span = span!(Level::INFO, "upsert_branch");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"Branch\" (id, repository_id, name, head, based_on) VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (id) DO UPDATE SET head = EXCLUDED.head, based_on = EXCLUDED.based_on, deleted = false;",
+                        &[&id, &repository_id, &name, &head.0.as_slice(), &based_on],
+                    )
+                    .instrument(upsert_span)
+                    .await
+                    .map(|_| ())
+            }
+            BranchEvent::PushBranch { branch_id, push }
+            | BranchEvent::ForcePushBranch { branch_id, push } => {
+                self.insert_push(branch_id.0, push).await
+            }
+            BranchEvent::DeleteBranch { branch_id } => {
+                let id = i64::from_ne_bytes(branch_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "delete_branch");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Branch\" SET deleted = true WHERE id = $1;",
+                        &[&id],
+                    )
+                    .instrument(update_span)
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Box::new(BranchProjectorError::Unexpected(Box::new(e)))),
+        }
+    }
+}
+
+impl BranchProjector {
+    async fn insert_push(
+        &self,
+        branch_id: u64,
+        push: BranchPush,
+    ) -> Result<(), tokio_postgres::Error> {
+        let id = i64::from_ne_bytes(branch_id.to_ne_bytes());
+        let pusher_id = i64::from_ne_bytes(push.pusher.0.to_ne_bytes());
+
+        let insert_span = span!(Level::INFO, "insert_branch_push");
+        async {
+            let mut client = self.client.get_client().await;
+            let transaction = client.transaction().await?;
+            transaction
+                .execute(
+                    "UPDATE \"Branch\" SET head = $2 WHERE id = $1;",
+                    &[&id, &push.after.0.as_slice()],
+                )
+                .await?;
+            // Retried pushes hit the unique constraint and are skipped
+            transaction
+                .execute(
+                    "INSERT INTO \"BranchPush\" (branch_id, pusher_id, before, after, pushed_at, forced) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT DO NOTHING;",
+                    &[
+                        &id,
+                        &pusher_id,
+                        &push.before.0.as_slice(),
+                        &push.after.0.as_slice(),
+                        &push.pushed_at,
+                        &push.forced,
+                    ],
+                )
+                .await?;
+            transaction.commit().await
+        }
+        .instrument(insert_span)
+        .await
+    }
+}
@@ -0,0 +1,284 @@
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+use tokio_postgres::{Client, Transaction};
+use tracing::{span, Instrument, Level};
+
+use super::ProjectorError;
+
+/// Position in `$all` of the last event a projection applied. Ordered by commit position first,
+/// like EventStore orders `$all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProjectionCheckpoint {
+    pub commit_position: u64,
+    pub prepare_position: u64,
+}
+
+/// A projector that writes the read model with the transaction it is given, so its writes only
+/// commit together with the checkpoint of the event.
+#[async_trait]
+pub trait CheckpointedProjector<TEvent>: Interface + Send + Sync {
+    async fn project_in(
+        &self,
+        transaction: &Transaction<'_>,
+        event: TEvent,
+    ) -> Result<(), Box<dyn ProjectorError>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointedProjection {
+    Applied,
+    /// The checkpoint was already at or past the event, it was redelivered.
+    AlreadyApplied,
+}
+
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("Could not read or write the checkpoint, {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+    #[error("The projection failed, {0}")]
+    Projector(Box<dyn ProjectorError>),
+}
+
+/// The checkpoint to continue `projection_name` after, `None` before it applied any event.
+pub async fn read_projection_checkpoint(
+    client: &Client,
+    projection_name: &str,
+) -> Result<Option<ProjectionCheckpoint>, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            "SELECT commit_position, prepare_position FROM projection_checkpoints
+WHERE projection_name = $1;",
+            &[&projection_name],
+        )
+        .await?;
+
+    Ok(row.and_then(|row| to_checkpoint(row.get(0), row.get(1))))
+}
+
+/// An event a projection gave up on, with why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParkedProjectionEvent {
+    pub event_id: String,
+    pub stream_id: String,
+    pub event_type: String,
+    pub data: Vec<u8>,
+    pub reason: String,
+    pub retry_count: u32,
+}
+
+/// Applies `event` and moves the checkpoint to `position` in one transaction. The checkpoint row
+/// stays locked until the transaction ends, so another runtime of the same projection waits and
+/// then sees the event as applied.
+pub async fn project_with_checkpoint<TEvent: Send + 'static>(
+    client: &mut Client,
+    projector: &dyn CheckpointedProjector<TEvent>,
+    projection_name: &str,
+    position: ProjectionCheckpoint,
+    event: TEvent,
+) -> Result<CheckpointedProjection, CheckpointError> {
+    let transaction_span = span!(Level::INFO, "project_with_checkpoint");
+    async move {
+        let transaction = client.transaction().await?;
+        if is_applied(
+            lock_checkpoint(&transaction, projection_name).await?,
+            position,
+        ) {
+            return Ok(CheckpointedProjection::AlreadyApplied);
+        }
+
+        projector
+            .project_in(&transaction, event)
+            .await
+            .map_err(CheckpointError::Projector)?;
+
+        move_checkpoint(&transaction, projection_name, position).await?;
+        transaction.commit().await?;
+
+        Ok(CheckpointedProjection::Applied)
+    }
+    .instrument(transaction_span)
+    .await
+}
+
+/// Parks an event the projection can't apply and moves the checkpoint past it in one
+/// transaction, so the event is never skipped without a trace.
+pub async fn park_with_checkpoint(
+    client: &mut Client,
+    projection_name: &str,
+    position: ProjectionCheckpoint,
+    parked: &ParkedProjectionEvent,
+) -> Result<CheckpointedProjection, tokio_postgres::Error> {
+    let transaction_span = span!(Level::INFO, "park_with_checkpoint");
+    async move {
+        let transaction = client.transaction().await?;
+        if is_applied(
+            lock_checkpoint(&transaction, projection_name).await?,
+            position,
+        ) {
+            return Ok(CheckpointedProjection::AlreadyApplied);
+        }
+
+        transaction
+            .execute(
+                "INSERT INTO projection_parked_events (projection_name, commit_position,
+    prepare_position, event_id, stream_id, event_type, data, reason, retry_count)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);",
+                &[
+                    &projection_name,
+                    &(position.commit_position as i64),
+                    &(position.prepare_position as i64),
+                    &parked.event_id,
+                    &parked.stream_id,
+                    &parked.event_type,
+                    &parked.data,
+                    &parked.reason,
+                    &(parked.retry_count as i32),
+                ],
+            )
+            .await?;
+
+        move_checkpoint(&transaction, projection_name, position).await?;
+        transaction.commit().await?;
+
+        Ok(CheckpointedProjection::Applied)
+    }
+    .instrument(transaction_span)
+    .await
+}
+
+/// The checkpoint of `projection_name`, locked until `transaction` ends.
+async fn lock_checkpoint(
+    transaction: &Transaction<'_>,
+    projection_name: &str,
+) -> Result<Option<ProjectionCheckpoint>, tokio_postgres::Error> {
+    transaction
+        .execute(
+            "INSERT INTO projection_checkpoints (projection_name) VALUES ($1)
+ON CONFLICT (projection_name) DO NOTHING;",
+            &[&projection_name],
+        )
+        .await?;
+    let row = transaction
+        .query_one(
+            "SELECT commit_position, prepare_position FROM projection_checkpoints
+WHERE projection_name = $1 FOR UPDATE;",
+            &[&projection_name],
+        )
+        .await?;
+
+    Ok(to_checkpoint(row.get(0), row.get(1)))
+}
+
+async fn move_checkpoint(
+    transaction: &Transaction<'_>,
+    projection_name: &str,
+    position: ProjectionCheckpoint,
+) -> Result<(), tokio_postgres::Error> {
+    transaction
+        .execute(
+            "UPDATE projection_checkpoints
+SET commit_position = $2, prepare_position = $3, updated_at = now()
+WHERE projection_name = $1;",
+            &[
+                &projection_name,
+                &(position.commit_position as i64),
+                &(position.prepare_position as i64),
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn to_checkpoint(
+    commit_position: Option<i64>,
+    prepare_position: Option<i64>,
+) -> Option<ProjectionCheckpoint> {
+    Some(ProjectionCheckpoint {
+        commit_position: commit_position? as u64,
+        prepare_position: prepare_position? as u64,
+    })
+}
+
+fn is_applied(checkpoint: Option<ProjectionCheckpoint>, position: ProjectionCheckpoint) -> bool {
+    checkpoint.is_some_and(|checkpoint| position <= checkpoint)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_support::TestDatabase;
+
+    use super::{
+        is_applied, park_with_checkpoint, read_projection_checkpoint, CheckpointedProjection,
+        ParkedProjectionEvent, ProjectionCheckpoint,
+    };
+
+    #[test]
+    fn should_only_apply_events_after_the_checkpoint() {
+        let checkpoint = ProjectionCheckpoint {
+            commit_position: 20,
+            prepare_position: 20,
+        };
+        let position = |commit_position, prepare_position| ProjectionCheckpoint {
+            commit_position,
+            prepare_position,
+        };
+
+        assert!(!is_applied(None, position(0, 0)));
+        assert!(is_applied(Some(checkpoint), position(10, 30)));
+        assert!(is_applied(Some(checkpoint), position(20, 20)));
+        assert!(!is_applied(Some(checkpoint), position(20, 21)));
+        assert!(!is_applied(Some(checkpoint), position(30, 10)));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_park_the_event_while_moving_the_checkpoint_past_it() {
+        let database = TestDatabase::migrated("projection_checkpoint_test").await;
+        let mut client = database.connect().await;
+        let position = ProjectionCheckpoint {
+            commit_position: 10,
+            prepare_position: 10,
+        };
+        let parked = ParkedProjectionEvent {
+            event_id: "9c7a8e2e-5b1f-4c83-a1f4-1f0a5d3c2b6e".to_string(),
+            stream_id: "Porti.SourceControl/Aggregates/Organization/1".to_string(),
+            event_type: "ArchiveOrganization".to_string(),
+            data: b"{}".to_vec(),
+            reason: "Projection failed, the organization doesn't exist".to_string(),
+            retry_count: 10,
+        };
+
+        let first = park_with_checkpoint(&mut client, "organizations", position, &parked)
+            .await
+            .unwrap();
+        let redelivered = park_with_checkpoint(&mut client, "organizations", position, &parked)
+            .await
+            .unwrap();
+
+        assert_eq!(first, CheckpointedProjection::Applied);
+        assert_eq!(redelivered, CheckpointedProjection::AlreadyApplied);
+        assert_eq!(
+            read_projection_checkpoint(&client, "organizations")
+                .await
+                .unwrap(),
+            Some(position)
+        );
+        let rows = client
+            .query(
+                "SELECT event_id, reason, retry_count FROM projection_parked_events
+WHERE projection_name = 'organizations';",
+                &[],
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<_, String>(0), parked.event_id);
+        assert_eq!(rows[0].get::<_, String>(1), parked.reason);
+        assert_eq!(rows[0].get::<_, i32>(2), 10);
+
+        drop(client);
+        database.remove().await;
+    }
+}
@@ -0,0 +1,80 @@
+use shaku::Provider;
+use std::sync::Arc;
+use thiserror::Error;
+
+use async_trait::async_trait;
+use source_control_domain::aggregates::commit_graph::CommitGraphEvent;
+use tracing::{instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{Projector, ProjectorError};
+
+#[derive(Provider)]
+#[shaku(interface = Projector<CommitGraphEvent>)]
+pub struct CommitGraphProjector {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[derive(Error, Debug)]
+enum CommitGraphProjectorError {
+    #[error("Unknown error")]
+    Unexpected(Box<tokio_postgres::Error>),
+}
+
+impl ProjectorError for CommitGraphProjectorError {
+    fn get_retryable(&self) -> bool {
+        match self {
+            CommitGraphProjectorError::Unexpected(_) => true,
+        }
+    }
+}
+
+#[async_trait]
+impl Projector<CommitGraphEvent> for CommitGraphProjector {
+    #[instrument(skip(self, event), err)]
+    async fn project(&self, event: CommitGraphEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let res = match event {
+            CommitGraphEvent::RecordCommits {
+                repository_id,
+                commits,
+            } => {
+                let repository_id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+
+                let insert_span = span!(Level::INFO, "insert_commits", count = commits.len());
+                // Retried batches are skipped commit by commit, so the insert is idempotent
+                async {
+                    let mut client = self.client.get_client().await;
+                    let transaction = client.transaction().await?;
+                    for commit in &commits {
+                        transaction
+                            .execute(
+                                "INSERT INTO \"Commit\" (repository_id, sha, message) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+                                &[&repository_id, &commit.sha.0.as_slice(), &commit.message],
+                            )
+                            .await?;
+
+                        for (position, parent) in commit.parents.iter().enumerate() {
+                            let position = position as i16;
+                            transaction
+                                .execute(
+                                    "INSERT INTO \"CommitParent\" (repository_id, sha, parent_sha, position) VALUES ($1, $2, $3, $4) ON CONFLICT DO NOTHING;",
+                                    &[&repository_id, &commit.sha.0.as_slice(), &parent.0.as_slice(), &position],
+                                )
+                                .await?;
+                        }
+                    }
+                    transaction.commit().await
+                }
+                .instrument(insert_span)
+                .await
+            }
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Box::new(CommitGraphProjectorError::Unexpected(Box::new(e)))),
+        }
+    }
+}
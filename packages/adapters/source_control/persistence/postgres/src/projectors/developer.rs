@@ -0,0 +1,158 @@
+use shaku::Provider;
+use std::sync::Arc;
+use thiserror::Error;
+
+use async_trait::async_trait;
+use source_control_domain::{
+    aggregates::developer::DeveloperEvent, entities::developer::DeveloperIdentity,
+};
+use tracing::{instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{Projector, ProjectorError};
+
+#[derive(Provider)]
+#[shaku(interface = Projector<DeveloperEvent>)]
+pub struct DeveloperProjector {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[derive(Error, Debug)]
+enum DeveloperProjectorError {
+    #[error("Unknown error")]
+    Unexpected(Box<tokio_postgres::Error>),
+}
+
+impl ProjectorError for DeveloperProjectorError {
+    fn get_retryable(&self) -> bool {
+        match self {
+            DeveloperProjectorError::Unexpected(_) => true,
+        }
+    }
+}
+
+/// Splits an identity into the kind, platform and value columns of "DeveloperIdentity".
+pub fn identity_to_db(identity: &DeveloperIdentity) -> (&'static str, &str, &str) {
+    match identity {
+        DeveloperIdentity::PlatformLogin { platform, login } => ("platform_login", platform, login),
+        DeveloperIdentity::CommitEmail { email } => ("commit_email", "", email),
+    }
+}
+
+pub fn identity_from_db(kind: &str, platform: String, value: String) -> Option<DeveloperIdentity> {
+    match kind {
+        "platform_login" => Some(DeveloperIdentity::PlatformLogin {
+            platform,
+            login: value,
+        }),
+        "commit_email" => Some(DeveloperIdentity::CommitEmail { email: value }),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl Projector<DeveloperEvent> for DeveloperProjector {
+    #[instrument(skip(self), err)]
+    async fn project(&self, event: DeveloperEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let res = match event {
+            DeveloperEvent::CreateDeveloper {
+                developer_id,
+                username,
+            } => {
+                let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+
+                let insert_span = span!(Level::INFO, "insert_developer");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"Developer\" (id, username) VALUES ($1, $2) ON CONFLICT DO NOTHING;",
+                        &[&id, &username],
+                    )
+                    .instrument(insert_span)
+                    .await
+                    .map(|_| ())
+            }
+            DeveloperEvent::LinkIdentity {
+                developer_id,
+                identity,
+            } => {
+                let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+                let (kind, platform, value) = identity_to_db(&identity);
+
+                let upsert_span = span!(Level::INFO, "upsert_developer_identity");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"DeveloperIdentity\" (kind, platform, value, developer_id) VALUES ($1, $2, $3, $4)
+ON CONFLICT (kind, platform, value) DO UPDATE SET developer_id = EXCLUDED.developer_id;",
+                        &[&kind, &platform, &value, &id],
+                    )
+                    .instrument(upsert_span)
+                    .await
+                    .map(|_| ())
+            }
+            DeveloperEvent::UnlinkIdentity {
+                developer_id,
+                identity,
+            } => {
+                let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+                let (kind, platform, value) = identity_to_db(&identity);
+
+                let delete_span = span!(Level::INFO, "delete_developer_identity");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "DELETE FROM \"DeveloperIdentity\" WHERE kind = $1 AND platform = $2 AND value = $3 AND developer_id = $4;",
+                        &[&kind, &platform, &value, &id],
+                    )
+                    .instrument(delete_span)
+                    .await
+                    .map(|_| ())
+            }
+            DeveloperEvent::MergeDeveloper { developer_id, into } => {
+                let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+                let into = i64::from_ne_bytes(into.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "merge_developer");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Developer\" SET merged_into = $2 WHERE id = $1;",
+                        &[&id, &into],
+                    )
+                    .instrument(update_span)
+                    .await
+                    .map(|_| ())
+            }
+            DeveloperEvent::UnmergeDeveloper { developer_id, .. } => {
+                let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "unmerge_developer");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Developer\" SET merged_into = NULL WHERE id = $1;",
+                        &[&id],
+                    )
+                    .instrument(update_span)
+                    .await
+                    .map(|_| ())
+            }
+            // The read model resolves duplicates through `merged_into` already
+            DeveloperEvent::AddMergedDeveloper { .. }
+            | DeveloperEvent::RemoveMergedDeveloper { .. } => Ok(()),
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Box::new(DeveloperProjectorError::Unexpected(Box::new(e)))),
+        }
+    }
+}
@@ -3,7 +3,14 @@ use std::fmt::{Debug, Display};
 use async_trait::async_trait;
 use shaku::Interface;
 
+pub mod branch;
+pub mod checkpoint;
+pub mod commit_graph;
+pub mod developer;
 pub mod organization;
+pub mod pull_request;
+pub mod rebuild;
+pub mod repository;
 
 #[async_trait]
 pub trait Projector<TEvent>: Interface + Send + Sync {
@@ -4,11 +4,12 @@ use thiserror::Error;
 
 use async_trait::async_trait;
 use source_control_domain::aggregates::organization::OrganizationEvent;
+use tokio_postgres::{types::ToSql, GenericClient, Transaction};
 use tracing::{instrument, span, Instrument, Level};
 
 use crate::provider::PostgresProvider;
 
-use super::{Projector, ProjectorError};
+use super::{checkpoint::CheckpointedProjector, Projector, ProjectorError};
 
 #[derive(Provider)]
 #[shaku(interface = Projector<OrganizationEvent>)]
@@ -17,6 +18,12 @@ pub struct OrganizationProjector {
     pub client: Arc<dyn PostgresProvider>,
 }
 
+/// Projects organizations in the transaction of a catch-up projection, see
+/// [`CheckpointedProjector`].
+#[derive(Provider)]
+#[shaku(interface = CheckpointedProjector<OrganizationEvent>)]
+pub struct CheckpointedOrganizationProjector {}
+
 #[derive(Error, Debug)]
 enum OrganizationProjectorError {
     #[error("Unknown error")]
@@ -38,81 +45,159 @@ impl ProjectorError for OrganizationProjectorError {
 impl Projector<OrganizationEvent> for OrganizationProjector {
     #[instrument(skip(self), err)]
     async fn project(&self, event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
-        match event {
-            OrganizationEvent::AddPlatformAccount {
-                organization_id,
-                account,
-            } => {
-                let id = i64::from_ne_bytes(account.id.0.to_ne_bytes());
-                let organization_id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
-
-                let insert_span = span!(Level::INFO, "insert_platform_account");
-                let res = self.client.get_client().await.execute("INSERT INTO \"PlatformAccount\" (id, organization_id, name, platform_name) VALUES ($1, $2, $3, $4);", &[&id, &organization_id, &account.name, &account.platform.name]).instrument(insert_span).await;
-
-                match res {
-                    Ok(_) => Ok(()),
-                    Err(e) => {
-                        if let Some(db_error) = e.as_db_error() {
-                            if db_error.code().code() == "23505" {
-                                return Err(Box::new(OrganizationProjectorError::DuplicateKey));
-                            }
-                        }
+        project_organization_event(&*self.client.get_client().await, event).await
+    }
+}
+
+#[async_trait]
+impl CheckpointedProjector<OrganizationEvent> for CheckpointedOrganizationProjector {
+    #[instrument(skip(self, transaction), err)]
+    async fn project_in(
+        &self,
+        transaction: &Transaction<'_>,
+        event: OrganizationEvent,
+    ) -> Result<(), Box<dyn ProjectorError>> {
+        project_organization_event(transaction, event).await
+    }
+}
+
+async fn project_organization_event<C: GenericClient + Sync>(
+    client: &C,
+    event: OrganizationEvent,
+) -> Result<(), Box<dyn ProjectorError>> {
+    match event {
+        OrganizationEvent::AddPlatformAccount {
+            organization_id,
+            account,
+        } => {
+            let id = i64::from_ne_bytes(account.id.0.to_ne_bytes());
+            let organization_id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
 
-                        return Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
-                            e,
-                        ))));
+            let insert_span = span!(Level::INFO, "insert_platform_account");
+            let res = client.execute("INSERT INTO \"PlatformAccount\" (id, organization_id, name, platform_name) VALUES ($1, $2, $3, $4);", &[&id, &organization_id, &account.name, &account.platform.name]).instrument(insert_span).await;
+
+            match res {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    if let Some(db_error) = e.as_db_error() {
+                        if db_error.code().code() == "23505" {
+                            return Err(Box::new(OrganizationProjectorError::DuplicateKey));
+                        }
                     }
-                }
-            }
-            OrganizationEvent::RemovePlatformAccount { account_id, .. } => {
-                let id = i64::from_ne_bytes(account_id.0.to_ne_bytes());
-
-                let delete_span = span!(Level::INFO, "delete_platform_account");
-                match self
-                    .client
-                    .get_client()
-                    .await
-                    .execute("DELETE FROM \"PlatformAccount\" WHERE id = $1;", &[&id])
-                    .instrument(delete_span)
-                    .await
-                {
-                    Ok(_) => Ok(()),
-                    Err(e) => Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
+
+                    Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
                         e,
-                    )))),
+                    ))))
                 }
             }
-            OrganizationEvent::CreateOrganizationEvent {
-                organization_id,
-                name,
-            } => {
-                let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
-                let insert_span = span!(Level::INFO, "insert_organization");
-                let res = self
-                    .client
-                    .get_client()
-                    .await
-                    .execute(
-                        "INSERT INTO \"Organization\" (id, name)  VALUES ($1, $2);",
-                        &[&id, &name],
-                    )
-                    .instrument(insert_span)
-                    .await;
-
-                match res {
-                    Ok(_) => Ok(()),
-                    Err(e) => {
-                        if let Some(db_error) = e.as_db_error() {
-                            if db_error.code().code() == "23505" {
-                                return Err(Box::new(OrganizationProjectorError::DuplicateKey));
-                            }
+        }
+        OrganizationEvent::RemovePlatformAccount { account_id, .. } => {
+            let id = i64::from_ne_bytes(account_id.0.to_ne_bytes());
+
+            let delete_span = span!(Level::INFO, "delete_platform_account");
+            match client
+                .execute("DELETE FROM \"PlatformAccount\" WHERE id = $1;", &[&id])
+                .instrument(delete_span)
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(e) => Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
+                    e,
+                )))),
+            }
+        }
+        OrganizationEvent::CreateOrganizationEvent {
+            organization_id,
+            name,
+        } => {
+            let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+            let insert_span = span!(Level::INFO, "insert_organization");
+            let res = client
+                .execute(
+                    "INSERT INTO \"Organization\" (id, name)  VALUES ($1, $2);",
+                    &[&id, &name],
+                )
+                .instrument(insert_span)
+                .await;
+
+            match res {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    if let Some(db_error) = e.as_db_error() {
+                        if db_error.code().code() == "23505" {
+                            return Err(Box::new(OrganizationProjectorError::DuplicateKey));
                         }
-                        return Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
-                            e,
-                        ))));
                     }
+                    Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
+                        e,
+                    ))))
                 }
             }
         }
+        OrganizationEvent::RenameOrganization {
+            organization_id,
+            name,
+        } => {
+            let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+            let update_span = span!(Level::INFO, "rename_organization");
+            update_organization(
+                client,
+                "UPDATE \"Organization\" SET name = $2 WHERE id = $1;",
+                &[&id, &name],
+            )
+            .instrument(update_span)
+            .await
+        }
+        OrganizationEvent::ArchiveOrganization { organization_id } => {
+            let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+            let update_span = span!(Level::INFO, "archive_organization");
+            update_organization(
+                client,
+                "UPDATE \"Organization\" SET archived = true WHERE id = $1;",
+                &[&id],
+            )
+            .instrument(update_span)
+            .await
+        }
+        OrganizationEvent::UnarchiveOrganization { organization_id } => {
+            let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+            let update_span = span!(Level::INFO, "unarchive_organization");
+            update_organization(
+                client,
+                "UPDATE \"Organization\" SET archived = false WHERE id = $1;",
+                &[&id],
+            )
+            .instrument(update_span)
+            .await
+        }
+        OrganizationEvent::DeleteOrganization { organization_id } => {
+            let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+            // The row stays as a tombstone, repositories and accounts still reference it
+            let update_span = span!(Level::INFO, "delete_organization");
+            update_organization(
+                client,
+                "UPDATE \"Organization\" SET deleted = true WHERE id = $1;",
+                &[&id],
+            )
+            .instrument(update_span)
+            .await
+        }
+    }
+}
+
+async fn update_organization<C: GenericClient + Sync>(
+    client: &C,
+    statement: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<(), Box<dyn ProjectorError>> {
+    match client.execute(statement, params).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Box::new(OrganizationProjectorError::Unexpected(Box::new(
+            e,
+        )))),
     }
 }
@@ -0,0 +1,175 @@
+use shaku::Provider;
+use std::sync::Arc;
+use thiserror::Error;
+
+use async_trait::async_trait;
+use source_control_domain::{
+    aggregates::pull_request::PullRequestEvent, entities::pull_request::PullRequestState,
+};
+use tracing::{instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{Projector, ProjectorError};
+
+#[derive(Provider)]
+#[shaku(interface = Projector<PullRequestEvent>)]
+pub struct PullRequestProjector {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[derive(Error, Debug)]
+enum PullRequestProjectorError {
+    #[error("Unknown error")]
+    Unexpected(Box<tokio_postgres::Error>),
+    #[error("The key already existed in the database")]
+    DuplicateKey,
+}
+
+impl ProjectorError for PullRequestProjectorError {
+    fn get_retryable(&self) -> bool {
+        match self {
+            PullRequestProjectorError::Unexpected(_) => true,
+            PullRequestProjectorError::DuplicateKey => false,
+        }
+    }
+}
+
+pub fn state_to_db(state: PullRequestState) -> &'static str {
+    match state {
+        PullRequestState::Open => "open",
+        PullRequestState::Merged => "merged",
+        PullRequestState::Closed => "closed",
+    }
+}
+
+#[async_trait]
+impl Projector<PullRequestEvent> for PullRequestProjector {
+    #[instrument(skip(self), err)]
+    async fn project(&self, event: PullRequestEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let res = match event {
+            PullRequestEvent::OpenPullRequest {
+                pull_request_id,
+                repository_id,
+                number,
+                author_id,
+                title,
+                description,
+            } => {
+                let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+                let repository_id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+                let number = i64::from_ne_bytes(number.to_ne_bytes());
+                let author_id = i64::from_ne_bytes(author_id.0.to_ne_bytes());
+
+                let insert_span = span!(Level::INFO, "insert_pull_request");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"PullRequest\" (id, repository_id, number, author_id, title, description) VALUES ($1, $2, $3, $4, $5, $6);",
+                        &[&id, &repository_id, &number, &author_id, &title, &description],
+                    )
+                    .instrument(insert_span)
+                    .await
+            }
+            PullRequestEvent::EditPullRequest {
+                pull_request_id,
+                title,
+                description,
+            } => {
+                let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "edit_pull_request");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"PullRequest\" SET title = $2, description = $3 WHERE id = $1;",
+                        &[&id, &title, &description],
+                    )
+                    .instrument(update_span)
+                    .await
+            }
+            PullRequestEvent::SubmitReview {
+                pull_request_id,
+                review,
+            } => {
+                let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+                let developer_id = i64::from_ne_bytes(review.developer_id.0.to_ne_bytes());
+
+                let upsert_span = span!(Level::INFO, "upsert_review");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"Review\" (pull_request_id, developer_id, status_name, accepting) VALUES ($1, $2, $3, $4)
+ON CONFLICT (pull_request_id, developer_id) DO UPDATE SET status_name = EXCLUDED.status_name, accepting = EXCLUDED.accepting;",
+                        &[&id, &developer_id, &review.status.name, &review.status.accepting],
+                    )
+                    .instrument(upsert_span)
+                    .await
+            }
+            PullRequestEvent::DismissReview {
+                pull_request_id,
+                developer_id,
+            } => {
+                let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+                let developer_id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+
+                let delete_span = span!(Level::INFO, "delete_review");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "DELETE FROM \"Review\" WHERE pull_request_id = $1 AND developer_id = $2;",
+                        &[&id, &developer_id],
+                    )
+                    .instrument(delete_span)
+                    .await
+            }
+            PullRequestEvent::MergePullRequest { pull_request_id } => {
+                self.set_state(pull_request_id.0, PullRequestState::Merged)
+                    .await
+            }
+            PullRequestEvent::ClosePullRequest { pull_request_id } => {
+                self.set_state(pull_request_id.0, PullRequestState::Closed)
+                    .await
+            }
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if let Some(db_error) = e.as_db_error() {
+                    if db_error.code().code() == "23505" {
+                        return Err(Box::new(PullRequestProjectorError::DuplicateKey));
+                    }
+                }
+
+                Err(Box::new(PullRequestProjectorError::Unexpected(Box::new(e))))
+            }
+        }
+    }
+}
+
+impl PullRequestProjector {
+    async fn set_state(
+        &self,
+        pull_request_id: u64,
+        state: PullRequestState,
+    ) -> Result<u64, tokio_postgres::Error> {
+        let id = i64::from_ne_bytes(pull_request_id.to_ne_bytes());
+
+        let update_span = span!(Level::INFO, "update_pull_request_state");
+        self.client
+            .get_client()
+            .await
+            .execute(
+                "UPDATE \"PullRequest\" SET state = $2 WHERE id = $1;",
+                &[&id, &state_to_db(state)],
+            )
+            .instrument(update_span)
+            .await
+    }
+}
@@ -0,0 +1,302 @@
+use tokio_postgres::{Client, Transaction};
+
+use super::checkpoint::ProjectionCheckpoint;
+
+// Rebuilt tables are filled in `organization_rebuild` while the live ones in `public` keep serving
+// reads, the swap moves the live tables to `organization_retired` and the rebuilt ones to
+// `public`. `OrganizationName` is written by the commands instead of projected, so it stays as is.
+
+/// Creates empty copies of `Organization` and `PlatformAccount` to replay the events into,
+/// dropping whatever an earlier rebuild left behind.
+pub async fn create_organization_shadow_tables(
+    client: &Client,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .batch_execute(
+            "DROP SCHEMA IF EXISTS organization_rebuild CASCADE;
+DROP SCHEMA IF EXISTS organization_retired CASCADE;
+CREATE SCHEMA organization_rebuild;
+CREATE TABLE organization_rebuild.\"Organization\" (LIKE public.\"Organization\" INCLUDING ALL);
+CREATE TABLE organization_rebuild.\"PlatformAccount\" (LIKE public.\"PlatformAccount\" INCLUDING ALL);
+ALTER TABLE organization_rebuild.\"PlatformAccount\"
+    ADD FOREIGN KEY (organization_id) REFERENCES organization_rebuild.\"Organization\";",
+        )
+        .await
+}
+
+/// Makes the projector write to the shadow tables until the transaction ends, its statements
+/// don't name a schema.
+pub async fn use_organization_shadow_tables(
+    transaction: &Transaction<'_>,
+) -> Result<(), tokio_postgres::Error> {
+    transaction
+        .batch_execute("SET LOCAL search_path TO organization_rebuild, public;")
+        .await
+}
+
+/// Stops the live projection from writing until the transaction ends, reads go on until the swap.
+/// The checkpoint of `live_projection_name` is locked first, the order catch-up projections lock
+/// in as well.
+pub async fn lock_organization_tables(
+    transaction: &Transaction<'_>,
+    live_projection_name: &str,
+) -> Result<(), tokio_postgres::Error> {
+    transaction
+        .execute(
+            "SELECT 1 FROM projection_checkpoints WHERE projection_name = $1 FOR UPDATE;",
+            &[&live_projection_name],
+        )
+        .await?;
+
+    transaction
+        .batch_execute(
+            "LOCK TABLE public.\"Organization\", public.\"PlatformAccount\" IN EXCLUSIVE MODE;",
+        )
+        .await
+}
+
+/// Puts the shadow tables in place of the live ones. The checkpoint of `live_projection_name`
+/// moves to `position`, so it doesn't apply the events the rebuild applied again.
+pub async fn swap_organization_shadow_tables(
+    transaction: &Transaction<'_>,
+    live_projection_name: &str,
+    position: Option<ProjectionCheckpoint>,
+) -> Result<(), tokio_postgres::Error> {
+    // Foreign keys follow the table instead of its name, so the one of repositories is recreated.
+    // Checking it would block reads of repositories, that happens after the swap
+    transaction
+        .batch_execute(
+            "CREATE SCHEMA organization_retired;
+ALTER TABLE public.\"Repository\" DROP CONSTRAINT IF EXISTS \"Repository_organization_id_fkey\";
+ALTER TABLE public.\"PlatformAccount\" SET SCHEMA organization_retired;
+ALTER TABLE public.\"Organization\" SET SCHEMA organization_retired;
+ALTER TABLE organization_rebuild.\"Organization\" SET SCHEMA public;
+ALTER TABLE organization_rebuild.\"PlatformAccount\" SET SCHEMA public;
+ALTER TABLE public.\"Repository\" ADD CONSTRAINT \"Repository_organization_id_fkey\"
+    FOREIGN KEY (organization_id) REFERENCES public.\"Organization\" NOT VALID;",
+        )
+        .await?;
+
+    if let Some(position) = position {
+        transaction
+            .execute(
+                "UPDATE projection_checkpoints
+SET commit_position = $2, prepare_position = $3, updated_at = now()
+WHERE projection_name = $1
+    AND (commit_position IS NULL OR (commit_position, prepare_position) < ($2, $3));",
+                &[
+                    &live_projection_name,
+                    &(position.commit_position as i64),
+                    &(position.prepare_position as i64),
+                ],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Drops the replaced tables and checks the recreated foreign key of repositories.
+pub async fn drop_retired_organization_tables(
+    client: &Client,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .batch_execute(
+            "DROP SCHEMA organization_retired CASCADE;
+DROP SCHEMA organization_rebuild;
+ALTER TABLE public.\"Repository\" VALIDATE CONSTRAINT \"Repository_organization_id_fkey\";",
+        )
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use source_control_domain::{
+        aggregates::organization::OrganizationEvent,
+        entities::{
+            organization::OrganizationId,
+            platform::Platform,
+            platform_account::{PlatformAccount, PlatformAccountId},
+        },
+    };
+    use tokio_postgres::Client;
+
+    use crate::{
+        projectors::{
+            checkpoint::{CheckpointedProjector, ProjectionCheckpoint},
+            organization::CheckpointedOrganizationProjector,
+        },
+        test_support::TestDatabase,
+    };
+
+    use super::{
+        create_organization_shadow_tables, drop_retired_organization_tables,
+        lock_organization_tables, swap_organization_shadow_tables, use_organization_shadow_tables,
+    };
+
+    const PROJECTION_NAME: &str = "organizations";
+
+    async fn organizations(client: &Client) -> Vec<(i64, String)> {
+        client
+            .query(
+                "SELECT id, name FROM public.\"Organization\" ORDER BY id;",
+                &[],
+            )
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| (row.get("id"), row.get("name")))
+            .collect()
+    }
+
+    async fn checkpoint(client: &Client) -> (Option<i64>, Option<i64>) {
+        let row = client
+            .query_one(
+                "SELECT commit_position, prepare_position FROM projection_checkpoints
+WHERE projection_name = $1;",
+                &[&PROJECTION_NAME],
+            )
+            .await
+            .unwrap();
+        (row.get(0), row.get(1))
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_serve_the_rebuilt_tables_after_the_swap() {
+        let database = TestDatabase::migrated("organization_rebuild_test").await;
+        let mut client = database.connect().await;
+        // The live projection missed the rename and the platform account
+        client
+            .batch_execute(
+                "INSERT INTO \"Organization\" (id, name) VALUES (1, 'Porti');
+INSERT INTO \"Repository\" (id, organization_id, platform_account_id, name) VALUES (3, 1, 2, 'crate');
+INSERT INTO projection_checkpoints (projection_name, commit_position, prepare_position)
+VALUES ('organizations', 10, 10);",
+            )
+            .await
+            .unwrap();
+        let events = vec![
+            OrganizationEvent::CreateOrganizationEvent {
+                organization_id: OrganizationId(1),
+                name: "Porti".to_string(),
+            },
+            OrganizationEvent::RenameOrganization {
+                organization_id: OrganizationId(1),
+                name: "rafaeltab".to_string(),
+            },
+            OrganizationEvent::AddPlatformAccount {
+                organization_id: OrganizationId(1),
+                account: PlatformAccount {
+                    id: PlatformAccountId(2),
+                    name: "rafaeltab".to_string(),
+                    platform: Platform {
+                        name: "github".to_string(),
+                    },
+                },
+            },
+        ];
+
+        create_organization_shadow_tables(&client).await.unwrap();
+        let transaction = client.transaction().await.unwrap();
+        use_organization_shadow_tables(&transaction).await.unwrap();
+        for event in events {
+            CheckpointedOrganizationProjector {}
+                .project_in(&transaction, event)
+                .await
+                .unwrap();
+        }
+        transaction.commit().await.unwrap();
+
+        assert_eq!(organizations(&client).await, vec![(1, "Porti".to_string())]);
+
+        let position = ProjectionCheckpoint {
+            commit_position: 30,
+            prepare_position: 30,
+        };
+        let transaction = client.transaction().await.unwrap();
+        lock_organization_tables(&transaction, PROJECTION_NAME)
+            .await
+            .unwrap();
+        swap_organization_shadow_tables(&transaction, PROJECTION_NAME, Some(position))
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        drop_retired_organization_tables(&client).await.unwrap();
+
+        assert_eq!(
+            organizations(&client).await,
+            vec![(1, "rafaeltab".to_string())]
+        );
+        let accounts: Vec<(i64, i64, String)> = client
+            .query(
+                "SELECT id, organization_id, platform_name FROM public.\"PlatformAccount\";",
+                &[],
+            )
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect();
+        assert_eq!(accounts, vec![(2, 1, "github".to_string())]);
+        assert_eq!(checkpoint(&client).await, (Some(30), Some(30)));
+        let schemas: i64 = client
+            .query_one(
+                "SELECT count(*) FROM pg_namespace
+WHERE nspname IN ('organization_rebuild', 'organization_retired');",
+                &[],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(schemas, 0);
+        // Repositories reference the rebuilt organizations
+        let orphan = client
+            .execute(
+                "INSERT INTO \"Repository\" (id, organization_id) VALUES (4, 5);",
+                &[],
+            )
+            .await;
+        assert!(orphan.is_err());
+
+        drop(client);
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_not_move_the_checkpoint_of_the_live_projection_back() {
+        let database = TestDatabase::migrated("organization_rebuild_test").await;
+        let mut client = database.connect().await;
+        client
+            .batch_execute(
+                "INSERT INTO projection_checkpoints (projection_name, commit_position, prepare_position)
+VALUES ('organizations', 50, 50);",
+            )
+            .await
+            .unwrap();
+
+        create_organization_shadow_tables(&client).await.unwrap();
+        let transaction = client.transaction().await.unwrap();
+        lock_organization_tables(&transaction, PROJECTION_NAME)
+            .await
+            .unwrap();
+        swap_organization_shadow_tables(
+            &transaction,
+            PROJECTION_NAME,
+            Some(ProjectionCheckpoint {
+                commit_position: 30,
+                prepare_position: 30,
+            }),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+        drop_retired_organization_tables(&client).await.unwrap();
+
+        assert_eq!(checkpoint(&client).await, (Some(50), Some(50)));
+
+        drop(client);
+        database.remove().await;
+    }
+}
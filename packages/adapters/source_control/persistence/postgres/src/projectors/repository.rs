@@ -0,0 +1,127 @@
+use shaku::Provider;
+use std::sync::Arc;
+use thiserror::Error;
+
+use async_trait::async_trait;
+use source_control_domain::aggregates::repository::RepositoryEvent;
+use tracing::{instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{Projector, ProjectorError};
+
+#[derive(Provider)]
+#[shaku(interface = Projector<RepositoryEvent>)]
+pub struct RepositoryProjector {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[derive(Error, Debug)]
+enum RepositoryProjectorError {
+    #[error("Unknown error")]
+    Unexpected(Box<tokio_postgres::Error>),
+    #[error("The key already existed in the database")]
+    DuplicateKey,
+}
+
+impl ProjectorError for RepositoryProjectorError {
+    fn get_retryable(&self) -> bool {
+        match self {
+            RepositoryProjectorError::Unexpected(_) => true,
+            RepositoryProjectorError::DuplicateKey => false,
+        }
+    }
+}
+
+#[async_trait]
+impl Projector<RepositoryEvent> for RepositoryProjector {
+    #[instrument(skip(self), err)]
+    async fn project(&self, event: RepositoryEvent) -> Result<(), Box<dyn ProjectorError>> {
+        let res = match event {
+            RepositoryEvent::RegisterRepository {
+                repository_id,
+                organization_id,
+                platform_account_id,
+                name,
+            } => {
+                let id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+                let organization_id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+                let platform_account_id = i64::from_ne_bytes(platform_account_id.0.to_ne_bytes());
+
+                let insert_span = span!(Level::INFO, "insert_repository");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "INSERT INTO \"Repository\" (id, organization_id, platform_account_id, name) VALUES ($1, $2, $3, $4);",
+                        &[&id, &organization_id, &platform_account_id, &name],
+                    )
+                    .instrument(insert_span)
+                    .await
+            }
+            RepositoryEvent::RenameRepository {
+                repository_id,
+                name,
+            } => {
+                let id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "rename_repository");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Repository\" SET name = $2 WHERE id = $1;",
+                        &[&id, &name],
+                    )
+                    .instrument(update_span)
+                    .await
+            }
+            RepositoryEvent::ArchiveRepository { repository_id } => {
+                let id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "archive_repository");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Repository\" SET archived = true WHERE id = $1;",
+                        &[&id],
+                    )
+                    .instrument(update_span)
+                    .await
+            }
+            RepositoryEvent::MoveRepository {
+                repository_id,
+                platform_account_id,
+            } => {
+                let id = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+                let platform_account_id = i64::from_ne_bytes(platform_account_id.0.to_ne_bytes());
+
+                let update_span = span!(Level::INFO, "move_repository");
+                self.client
+                    .get_client()
+                    .await
+                    .execute(
+                        "UPDATE \"Repository\" SET platform_account_id = $2 WHERE id = $1;",
+                        &[&id, &platform_account_id],
+                    )
+                    .instrument(update_span)
+                    .await
+            }
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if let Some(db_error) = e.as_db_error() {
+                    if db_error.code().code() == "23505" {
+                        return Err(Box::new(RepositoryProjectorError::DuplicateKey));
+                    }
+                }
+
+                Err(Box::new(RepositoryProjectorError::Unexpected(Box::new(e))))
+            }
+        }
+    }
+}
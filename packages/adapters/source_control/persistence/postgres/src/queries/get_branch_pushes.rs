@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{
+    branch::BranchId, branch_push::BranchPush, developer::DeveloperId,
+};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{bytes_to_sha, dbid_to_domain_id, domain_id_to_dbid};
+
+const PAGE_SIZE: i64 = 100;
+
+/// Pushes are returned newest first, `before` and `after` are push sequence numbers.
+pub struct GetBranchPushesQuery {
+    pub branch_id: BranchId,
+    pub forced_only: bool,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+pub struct BranchPushResult {
+    pub sequence: u64,
+    pub push: BranchPush,
+}
+
+#[async_trait]
+pub trait GetBranchPushesQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetBranchPushesQuery,
+    ) -> Result<Vec<BranchPushResult>, GetBranchPushesQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetBranchPushesQueryHandler)]
+pub struct GetBranchPushesQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl GetBranchPushesQueryHandler for GetBranchPushesQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetBranchPushesQuery,
+    ) -> Result<Vec<BranchPushResult>, GetBranchPushesQueryError> {
+        let GetBranchPushesQuery {
+            branch_id,
+            forced_only,
+            before,
+            after,
+        } = query;
+        let branch_id = domain_id_to_dbid(branch_id.to_primitive());
+        let client = self.client.get_client().await;
+
+        let branch = client
+            .query_opt("SELECT 1 FROM \"Branch\" WHERE id = $1;", &[&branch_id])
+            .await
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while looking up branch"
+                );
+                GetBranchPushesQueryError::Unexpected
+            })?;
+        if branch.is_none() {
+            return Err(GetBranchPushesQueryError::NotFound);
+        }
+
+        // Newer pushes have higher sequence numbers, `after` pages towards older pushes
+        let (cursor_clause, order, cursor) = match (before, after) {
+            (None, Some(aft)) => ("AND p.seq < $4", "DESC", Some(aft)),
+            (Some(bef), None) => ("AND p.seq > $4", "ASC", Some(bef)),
+            _ => ("AND $4::bigint IS NULL", "DESC", None),
+        };
+        let cursor = cursor.map(domain_id_to_dbid);
+
+        let statement = format!(
+            "SELECT p.seq, p.pusher_id, p.before, p.after, p.pushed_at, p.forced
+FROM \"BranchPush\" p
+WHERE p.branch_id = $2 AND (NOT $3 OR p.forced) {}
+ORDER BY p.seq {}
+LIMIT $1;",
+            cursor_clause, order
+        );
+
+        let span = span!(Level::INFO, "select_branch_push");
+        let result = client
+            .query(&statement, &[&PAGE_SIZE, &branch_id, &forced_only, &cursor])
+            .instrument(span)
+            .await;
+
+        match result {
+            Ok(result) => {
+                info!("Successfully queried branch pushes");
+                let vals: Result<Vec<BranchPushResult>, GetBranchPushesQueryError> =
+                    result.iter().map(map_row_to_branch_push_result).collect();
+
+                vals.map(|mut vec| {
+                    vec.sort_by_key(|x| std::cmp::Reverse(x.sequence));
+                    vec
+                })
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying branch pushes"
+                );
+                Err(GetBranchPushesQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+fn map_row_to_branch_push_result(
+    row: &tokio_postgres::Row,
+) -> Result<BranchPushResult, GetBranchPushesQueryError> {
+    let (raw_sequence, raw_pusher_id, raw_before, raw_after, pushed_at, forced) =
+        extract_values(row).map_err(|err| {
+            error!(
+                error = format!("{:?}", err),
+                "Error while parsing branch pushes query response"
+            );
+            GetBranchPushesQueryError::Unexpected
+        })?;
+
+    Ok(BranchPushResult {
+        sequence: dbid_to_domain_id(raw_sequence),
+        push: BranchPush {
+            pusher: DeveloperId(dbid_to_domain_id(raw_pusher_id)),
+            before: bytes_to_sha(&raw_before).ok_or(GetBranchPushesQueryError::Unexpected)?,
+            after: bytes_to_sha(&raw_after).ok_or(GetBranchPushesQueryError::Unexpected)?,
+            pushed_at,
+            forced,
+        },
+    })
+}
+
+type BranchPushRow = (i64, i64, Vec<u8>, Vec<u8>, DateTime<Utc>, bool);
+
+fn extract_values(row: &tokio_postgres::Row) -> Result<BranchPushRow, tokio_postgres::Error> {
+    let raw_sequence = row.try_get("seq")?;
+    let raw_pusher_id = row.try_get("pusher_id")?;
+    let raw_before = row.try_get("before")?;
+    let raw_after = row.try_get("after")?;
+    let pushed_at = row.try_get("pushed_at")?;
+    let forced = row.try_get("forced")?;
+
+    Ok((
+        raw_sequence,
+        raw_pusher_id,
+        raw_before,
+        raw_after,
+        pushed_at,
+        forced,
+    ))
+}
+
+#[derive(Error, Debug)]
+pub enum GetBranchPushesQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The branch could not be found")]
+    NotFound,
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{
+    commit::{Commit, CommitSha},
+    repository::RepositoryId,
+};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{ancestors_cte, bytes_to_sha, domain_id_to_dbid, find_missing_commit};
+
+/// Selects the commits reachable from `to` but not from `from`, like `git log from..to`.
+pub struct GetCommitsBetweenQuery {
+    pub repository_id: RepositoryId,
+    pub from: CommitSha,
+    pub to: CommitSha,
+}
+
+#[async_trait]
+pub trait GetCommitsBetweenQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetCommitsBetweenQuery,
+    ) -> Result<Vec<Commit>, GetCommitsBetweenQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetCommitsBetweenQueryHandler)]
+pub struct GetCommitsBetweenQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl GetCommitsBetweenQueryHandler for GetCommitsBetweenQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetCommitsBetweenQuery,
+    ) -> Result<Vec<Commit>, GetCommitsBetweenQueryError> {
+        let repository_id = domain_id_to_dbid(query.repository_id.to_primitive());
+        let client = self.client.get_client().await;
+
+        let missing = find_missing_commit(&client, repository_id, &[query.from, query.to])
+            .await
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while looking up commits"
+                );
+                GetCommitsBetweenQueryError::Unexpected
+            })?;
+        if let Some(sha) = missing {
+            return Err(GetCommitsBetweenQueryError::UnknownCommit { sha });
+        }
+
+        let statement = format!(
+            "WITH RECURSIVE {},
+{}
+SELECT c.sha, c.message, array(
+    SELECT p.parent_sha FROM \"CommitParent\" p
+    WHERE p.repository_id = c.repository_id AND p.sha = c.sha
+    ORDER BY p.position
+) AS parents
+FROM \"Commit\" c
+WHERE c.repository_id = $1
+    AND c.sha IN (SELECT sha FROM included EXCEPT SELECT sha FROM excluded)
+ORDER BY c.seq DESC;",
+            ancestors_cte("included", 3),
+            ancestors_cte("excluded", 2)
+        );
+
+        let span = span!(Level::INFO, "select_commits_between");
+        let result = client
+            .query(
+                &statement,
+                &[
+                    &repository_id,
+                    &query.from.0.as_slice(),
+                    &query.to.0.as_slice(),
+                ],
+            )
+            .instrument(span)
+            .await;
+
+        match result {
+            Ok(rows) => {
+                info!("Successfully queried commits between");
+                rows.iter().map(map_row_to_commit).collect()
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying commits between"
+                );
+                Err(GetCommitsBetweenQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+fn map_row_to_commit(row: &tokio_postgres::Row) -> Result<Commit, GetCommitsBetweenQueryError> {
+    let (raw_sha, message, raw_parents) = extract_values(row).map_err(|err| {
+        error!(
+            error = format!("{:?}", err),
+            "Error while parsing commits between query response"
+        );
+        GetCommitsBetweenQueryError::Unexpected
+    })?;
+
+    let sha = bytes_to_sha(&raw_sha).ok_or(GetCommitsBetweenQueryError::Unexpected)?;
+    let parents: Option<Vec<CommitSha>> = raw_parents.iter().map(|x| bytes_to_sha(x)).collect();
+
+    Ok(Commit {
+        sha,
+        message,
+        parents: parents.ok_or(GetCommitsBetweenQueryError::Unexpected)?,
+    })
+}
+
+type CommitRow = (Vec<u8>, String, Vec<Vec<u8>>);
+
+fn extract_values(row: &tokio_postgres::Row) -> Result<CommitRow, tokio_postgres::Error> {
+    let raw_sha = row.try_get("sha")?;
+    let message = row.try_get("message")?;
+    let raw_parents = row.try_get("parents")?;
+
+    Ok((raw_sha, message, raw_parents))
+}
+
+#[derive(Error, Debug)]
+pub enum GetCommitsBetweenQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Commit {sha} is not part of the repository")]
+    UnknownCommit { sha: CommitSha },
+}
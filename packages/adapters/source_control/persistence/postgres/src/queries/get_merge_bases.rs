@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{commit::CommitSha, repository::RepositoryId};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{ancestors_cte, bytes_to_sha, domain_id_to_dbid, find_missing_commit};
+
+pub struct GetMergeBasesQuery {
+    pub repository_id: RepositoryId,
+    pub a: CommitSha,
+    pub b: CommitSha,
+}
+
+#[async_trait]
+pub trait GetMergeBasesQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetMergeBasesQuery,
+    ) -> Result<Vec<CommitSha>, GetMergeBasesQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetMergeBasesQueryHandler)]
+pub struct GetMergeBasesQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl GetMergeBasesQueryHandler for GetMergeBasesQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetMergeBasesQuery,
+    ) -> Result<Vec<CommitSha>, GetMergeBasesQueryError> {
+        let repository_id = domain_id_to_dbid(query.repository_id.to_primitive());
+        let client = self.client.get_client().await;
+
+        let missing = find_missing_commit(&client, repository_id, &[query.a, query.b])
+            .await
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while looking up commits"
+                );
+                GetMergeBasesQueryError::Unexpected
+            })?;
+        if let Some(sha) = missing {
+            return Err(GetMergeBasesQueryError::UnknownCommit { sha });
+        }
+
+        // The best common ancestors are the common ancestors without a child in the common set
+        let statement = format!(
+            "WITH RECURSIVE {},
+{},
+common AS (
+    SELECT sha FROM ancestors_a
+    INTERSECT
+    SELECT sha FROM ancestors_b
+)
+SELECT c.sha
+FROM common c
+WHERE NOT EXISTS (
+    SELECT 1 FROM \"CommitParent\" p
+    JOIN common d ON d.sha = p.sha
+    WHERE p.repository_id = $1 AND p.parent_sha = c.sha
+)
+ORDER BY c.sha;",
+            ancestors_cte("ancestors_a", 2),
+            ancestors_cte("ancestors_b", 3)
+        );
+
+        let span = span!(Level::INFO, "select_merge_bases");
+        let result = client
+            .query(
+                &statement,
+                &[&repository_id, &query.a.0.as_slice(), &query.b.0.as_slice()],
+            )
+            .instrument(span)
+            .await;
+
+        match result {
+            Ok(rows) => {
+                info!("Successfully queried merge bases");
+                rows.iter()
+                    .map(|row| {
+                        row.try_get::<_, Vec<u8>>("sha")
+                            .ok()
+                            .and_then(|bytes| bytes_to_sha(&bytes))
+                            .ok_or_else(|| {
+                                error!("Error while parsing merge bases query response");
+                                GetMergeBasesQueryError::Unexpected
+                            })
+                    })
+                    .collect()
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying merge bases"
+                );
+                Err(GetMergeBasesQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetMergeBasesQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Commit {sha} is not part of the repository")]
+    UnknownCommit { sha: CommitSha },
+}
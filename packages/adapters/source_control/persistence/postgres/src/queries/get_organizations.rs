@@ -8,6 +8,8 @@ use tracing::{error, info, span, Instrument, Level};
 
 use crate::provider::PostgresProvider;
 
+use super::{dbid_to_domain_id, domain_id_to_dbid};
+
 const PAGE_SIZE: i64 = 100;
 
 pub struct GetOrganizationsQuery {
@@ -19,6 +21,7 @@ pub struct OrganizationResult {
     pub id: OrganizationId,
     pub name: String,
     pub paltform_account_count: i64,
+    pub archived: bool,
 }
 
 #[async_trait]
@@ -52,7 +55,7 @@ impl GetOrganizationsQueryHandler for GetOrganizationsQueryHandlerImpl {
                         "select o.*, count(pa.*)
 from \"Organization\" o
          left join \"PlatformAccount\" pa ON o.id = pa.organization_id
-WHERE o.id > $2
+WHERE o.id > $2 AND NOT o.deleted
 GROUP BY o.id
 ORDER BY o.id ASC
 LIMIT $1;",
@@ -67,7 +70,7 @@ LIMIT $1;",
                         "select o.*, count(pa.*)
 from \"Organization\" o
          left join \"PlatformAccount\" pa ON o.id = pa.organization_id
-WHERE o.id < $2
+WHERE o.id < $2 AND NOT o.deleted
 GROUP BY o.id
 ORDER BY o.id DESC
 LIMIT $1;",
@@ -82,6 +85,7 @@ LIMIT $1;",
                         "select o.*, count(pa.*)
 from \"Organization\" o
          left join \"PlatformAccount\" pa ON o.id = pa.organization_id
+WHERE NOT o.deleted
 GROUP BY o.id
 ORDER BY o.id ASC
 LIMIT $1;",
@@ -114,18 +118,10 @@ LIMIT $1;",
     }
 }
 
-fn dbid_to_domain_id(dbid: i64) -> u64 {
-    u64::from_ne_bytes(dbid.to_ne_bytes())
-}
-
-fn domain_id_to_dbid(domainid: u64) -> i64 {
-    i64::from_ne_bytes(domainid.to_ne_bytes())
-}
-
 fn map_row_to_organization_result(
     row: &tokio_postgres::Row,
 ) -> Result<OrganizationResult, GetOrganizationsQueryError> {
-    let (raw_id, name, paltform_account_count) = extract_values(row).map_err(|err| {
+    let (raw_id, name, paltform_account_count, archived) = extract_values(row).map_err(|err| {
         error!(
             error = format!("{:?}", err),
             "Error while parsing organizations query response"
@@ -139,15 +135,19 @@ fn map_row_to_organization_result(
         id,
         name,
         paltform_account_count,
+        archived,
     })
 }
 
-fn extract_values(row: &tokio_postgres::Row) -> Result<(i64, String, i64), tokio_postgres::Error> {
+fn extract_values(
+    row: &tokio_postgres::Row,
+) -> Result<(i64, String, i64, bool), tokio_postgres::Error> {
     let raw_id = row.try_get("id")?;
     let name = row.try_get("name")?;
     let paltform_account_count = row.try_get("count")?;
+    let archived = row.try_get("archived")?;
 
-    Ok((raw_id, name, paltform_account_count))
+    Ok((raw_id, name, paltform_account_count, archived))
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{
+    developer::DeveloperId,
+    pull_request::{PullRequestId, PullRequestState},
+    repository::RepositoryId,
+};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::{projectors::pull_request::state_to_db, provider::PostgresProvider};
+
+use super::{dbid_to_domain_id, domain_id_to_dbid};
+
+const PAGE_SIZE: i64 = 100;
+
+pub struct GetPullRequestsQuery {
+    pub repository_id: RepositoryId,
+    pub state: Option<PullRequestState>,
+    pub before: Option<PullRequestId>,
+    pub after: Option<PullRequestId>,
+}
+
+pub struct PullRequestResult {
+    pub id: PullRequestId,
+    pub repository_id: RepositoryId,
+    pub number: u64,
+    pub author_id: DeveloperId,
+    pub title: String,
+    pub state: PullRequestState,
+    pub review_count: u64,
+    pub approval_count: u64,
+}
+
+#[async_trait]
+pub trait GetPullRequestsQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetPullRequestsQuery,
+    ) -> Result<Vec<PullRequestResult>, GetPullRequestsQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetPullRequestsQueryHandler)]
+pub struct GetPullRequestsQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl GetPullRequestsQueryHandler for GetPullRequestsQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetPullRequestsQuery,
+    ) -> Result<Vec<PullRequestResult>, GetPullRequestsQueryError> {
+        let GetPullRequestsQuery {
+            repository_id,
+            state,
+            before,
+            after,
+        } = query;
+        let repository_id = domain_id_to_dbid(repository_id.to_primitive());
+        let state = state.map(state_to_db);
+        let (cursor_clause, order, cursor) = match (before, after) {
+            (None, Some(aft)) => ("AND p.id > $4", "ASC", Some(aft)),
+            (Some(bef), None) => ("AND p.id < $4", "DESC", Some(bef)),
+            _ => ("AND $4::bigint IS NULL", "ASC", None),
+        };
+        let cursor = cursor.map(|x| domain_id_to_dbid(x.to_primitive()));
+
+        let statement = format!(
+            "select p.id, p.repository_id, p.number, p.author_id, p.title, p.state,
+    count(r.developer_id) as review_count,
+    count(r.developer_id) FILTER (WHERE r.accepting) as approval_count
+from \"PullRequest\" p
+LEFT JOIN \"Review\" r ON r.pull_request_id = p.id
+WHERE p.repository_id = $2 AND ($3::varchar IS NULL OR p.state = $3) {}
+GROUP BY p.id
+ORDER BY p.id {}
+LIMIT $1;",
+            cursor_clause, order
+        );
+
+        let span = span!(Level::INFO, "select_pull_request");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query(&statement, &[&PAGE_SIZE, &repository_id, &state, &cursor])
+            .instrument(span)
+            .await;
+
+        match result {
+            Ok(result) => {
+                info!("Successfully queried pull requests");
+                let vals: Result<Vec<PullRequestResult>, GetPullRequestsQueryError> =
+                    result.iter().map(map_row_to_pull_request_result).collect();
+
+                vals.map(|mut vec| {
+                    vec.sort_by_key(|x| domain_id_to_dbid(x.id.to_primitive()));
+                    vec
+                })
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying pull requests"
+                );
+                Err(GetPullRequestsQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+fn map_row_to_pull_request_result(
+    row: &tokio_postgres::Row,
+) -> Result<PullRequestResult, GetPullRequestsQueryError> {
+    let map_err = |err| {
+        error!(
+            error = format!("{:?}", err),
+            "Error while parsing pull requests query response"
+        );
+        GetPullRequestsQueryError::Unexpected
+    };
+    let (
+        raw_id,
+        raw_repository_id,
+        raw_number,
+        raw_author_id,
+        title,
+        raw_state,
+        review_count,
+        approval_count,
+    ) = extract_values(row).map_err(map_err)?;
+
+    let state = match raw_state.as_str() {
+        "open" => PullRequestState::Open,
+        "merged" => PullRequestState::Merged,
+        "closed" => PullRequestState::Closed,
+        _ => {
+            error!(state = raw_state, "Unknown pull request state");
+            return Err(GetPullRequestsQueryError::Unexpected);
+        }
+    };
+
+    Ok(PullRequestResult {
+        id: PullRequestId(dbid_to_domain_id(raw_id)),
+        repository_id: RepositoryId(dbid_to_domain_id(raw_repository_id)),
+        number: dbid_to_domain_id(raw_number),
+        author_id: DeveloperId(dbid_to_domain_id(raw_author_id)),
+        title,
+        state,
+        review_count: dbid_to_domain_id(review_count),
+        approval_count: dbid_to_domain_id(approval_count),
+    })
+}
+
+type PullRequestRow = (i64, i64, i64, i64, String, String, i64, i64);
+
+fn extract_values(row: &tokio_postgres::Row) -> Result<PullRequestRow, tokio_postgres::Error> {
+    let raw_id = row.try_get("id")?;
+    let raw_repository_id = row.try_get("repository_id")?;
+    let raw_number = row.try_get("number")?;
+    let raw_author_id = row.try_get("author_id")?;
+    let title = row.try_get("title")?;
+    let raw_state = row.try_get("state")?;
+    let review_count = row.try_get("review_count")?;
+    let approval_count = row.try_get("approval_count")?;
+
+    Ok((
+        raw_id,
+        raw_repository_id,
+        raw_number,
+        raw_author_id,
+        title,
+        raw_state,
+        review_count,
+        approval_count,
+    ))
+}
+
+#[derive(Error, Debug)]
+pub enum GetPullRequestsQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{
+    organization::OrganizationId, platform_account::PlatformAccountId, repository::RepositoryId,
+};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{dbid_to_domain_id, domain_id_to_dbid};
+
+const PAGE_SIZE: i64 = 100;
+
+pub struct GetRepositoriesQuery {
+    pub organization_id: OrganizationId,
+    pub platform_account_id: PlatformAccountId,
+    pub before: Option<RepositoryId>,
+    pub after: Option<RepositoryId>,
+}
+
+pub struct RepositoryResult {
+    pub id: RepositoryId,
+    pub organization_id: OrganizationId,
+    pub platform_account_id: PlatformAccountId,
+    pub name: String,
+    pub archived: bool,
+}
+
+#[async_trait]
+pub trait GetRepositoriesQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetRepositoriesQuery,
+    ) -> Result<Vec<RepositoryResult>, GetRepositoriesQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetRepositoriesQueryHandler)]
+pub struct GetRepositoriesQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl GetRepositoriesQueryHandler for GetRepositoriesQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetRepositoriesQuery,
+    ) -> Result<Vec<RepositoryResult>, GetRepositoriesQueryError> {
+        let GetRepositoriesQuery {
+            organization_id,
+            platform_account_id,
+            before,
+            after,
+        } = query;
+        let organization_id = domain_id_to_dbid(organization_id.to_primitive());
+        let platform_account_id = domain_id_to_dbid(platform_account_id.to_primitive());
+
+        let span = span!(Level::INFO, "select_repository");
+        let client = self.client.get_client().await;
+        let result = match (before, after) {
+            (None, Some(aft)) => {
+                client
+                    .query(
+                        "select r.*
+from \"Repository\" r
+WHERE r.organization_id = $2 AND r.platform_account_id = $3 AND r.id > $4
+ORDER BY r.id ASC
+LIMIT $1;",
+                        &[
+                            &PAGE_SIZE,
+                            &organization_id,
+                            &platform_account_id,
+                            &domain_id_to_dbid(aft.to_primitive()),
+                        ],
+                    )
+                    .instrument(span)
+                    .await
+            }
+            (Some(bef), None) => {
+                client
+                    .query(
+                        "select r.*
+from \"Repository\" r
+WHERE r.organization_id = $2 AND r.platform_account_id = $3 AND r.id < $4
+ORDER BY r.id DESC
+LIMIT $1;",
+                        &[
+                            &PAGE_SIZE,
+                            &organization_id,
+                            &platform_account_id,
+                            &domain_id_to_dbid(bef.to_primitive()),
+                        ],
+                    )
+                    .instrument(span)
+                    .await
+            }
+            _ => {
+                client
+                    .query(
+                        "select r.*
+from \"Repository\" r
+WHERE r.organization_id = $2 AND r.platform_account_id = $3
+ORDER BY r.id ASC
+LIMIT $1;",
+                        &[&PAGE_SIZE, &organization_id, &platform_account_id],
+                    )
+                    .instrument(span)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(result) => {
+                info!("Successfully queried repositories");
+                let vals: Result<Vec<RepositoryResult>, GetRepositoriesQueryError> =
+                    result.iter().map(map_row_to_repository_result).collect();
+
+                vals.map(|mut vec| {
+                    vec.sort_by_key(|x| domain_id_to_dbid(x.id.to_primitive()));
+                    vec
+                })
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying repositories"
+                );
+                Err(GetRepositoriesQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+fn map_row_to_repository_result(
+    row: &tokio_postgres::Row,
+) -> Result<RepositoryResult, GetRepositoriesQueryError> {
+    let (raw_id, raw_organization_id, raw_platform_account_id, name, archived) =
+        extract_values(row).map_err(|err| {
+            error!(
+                error = format!("{:?}", err),
+                "Error while parsing repositories query response"
+            );
+            GetRepositoriesQueryError::Unexpected
+        })?;
+
+    Ok(RepositoryResult {
+        id: RepositoryId(dbid_to_domain_id(raw_id)),
+        organization_id: OrganizationId(dbid_to_domain_id(raw_organization_id)),
+        platform_account_id: PlatformAccountId(dbid_to_domain_id(raw_platform_account_id)),
+        name,
+        archived,
+    })
+}
+
+fn extract_values(
+    row: &tokio_postgres::Row,
+) -> Result<(i64, i64, i64, String, bool), tokio_postgres::Error> {
+    let raw_id = row.try_get("id")?;
+    let raw_organization_id = row.try_get("organization_id")?;
+    let raw_platform_account_id = row.try_get("platform_account_id")?;
+    let name = row.try_get("name")?;
+    let archived = row.try_get("archived")?;
+
+    Ok((
+        raw_id,
+        raw_organization_id,
+        raw_platform_account_id,
+        name,
+        archived,
+    ))
+}
+
+#[derive(Error, Debug)]
+pub enum GetRepositoriesQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::{commit::CommitSha, repository::RepositoryId};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+use super::{ancestors_cte, domain_id_to_dbid, find_missing_commit};
+
+pub struct IsAncestorQuery {
+    pub repository_id: RepositoryId,
+    pub ancestor: CommitSha,
+    pub descendant: CommitSha,
+}
+
+#[async_trait]
+pub trait IsAncestorQueryHandler: Interface {
+    async fn handle(&self, query: IsAncestorQuery) -> Result<bool, IsAncestorQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = IsAncestorQueryHandler)]
+pub struct IsAncestorQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl IsAncestorQueryHandler for IsAncestorQueryHandlerImpl {
+    async fn handle(&self, query: IsAncestorQuery) -> Result<bool, IsAncestorQueryError> {
+        let repository_id = domain_id_to_dbid(query.repository_id.to_primitive());
+        let client = self.client.get_client().await;
+
+        let missing =
+            find_missing_commit(&client, repository_id, &[query.ancestor, query.descendant])
+                .await
+                .map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while looking up commits"
+                    );
+                    IsAncestorQueryError::Unexpected
+                })?;
+        if let Some(sha) = missing {
+            return Err(IsAncestorQueryError::UnknownCommit { sha });
+        }
+
+        let statement = format!(
+            "WITH RECURSIVE {}
+SELECT EXISTS (SELECT 1 FROM ancestors WHERE sha = $3) AS is_ancestor;",
+            ancestors_cte("ancestors", 2)
+        );
+
+        let span = span!(Level::INFO, "select_is_ancestor");
+        let result = client
+            .query_one(
+                &statement,
+                &[
+                    &repository_id,
+                    &query.descendant.0.as_slice(),
+                    &query.ancestor.0.as_slice(),
+                ],
+            )
+            .instrument(span)
+            .await;
+
+        match result.and_then(|row| row.try_get::<_, bool>("is_ancestor")) {
+            Ok(is_ancestor) => {
+                info!("Successfully queried commit ancestry");
+                Ok(is_ancestor)
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while querying commit ancestry"
+                );
+                Err(IsAncestorQueryError::Unexpected)
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum IsAncestorQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Commit {sha} is not part of the repository")]
+    UnknownCommit { sha: CommitSha },
+}
@@ -1 +1,59 @@
+use source_control_domain::entities::commit::CommitSha;
+
+pub mod get_branch_pushes;
+pub mod get_commits_between;
+pub mod get_merge_bases;
 pub mod get_organizations;
+pub mod resolve_developer_identity;
+pub mod get_pull_requests;
+pub mod get_repositories;
+pub mod is_ancestor;
+
+fn dbid_to_domain_id(dbid: i64) -> u64 {
+    u64::from_ne_bytes(dbid.to_ne_bytes())
+}
+
+fn domain_id_to_dbid(domainid: u64) -> i64 {
+    i64::from_ne_bytes(domainid.to_ne_bytes())
+}
+
+/// Returns the first of the given commits that has not been projected for the repository.
+async fn find_missing_commit(
+    client: &tokio_postgres::Client,
+    repository_id: i64,
+    shas: &[CommitSha],
+) -> Result<Option<CommitSha>, tokio_postgres::Error> {
+    for sha in shas {
+        let row = client
+            .query_opt(
+                "SELECT 1 FROM \"Commit\" WHERE repository_id = $1 AND sha = $2;",
+                &[&repository_id, &sha.0.as_slice()],
+            )
+            .await?;
+
+        if row.is_none() {
+            return Ok(Some(*sha));
+        }
+    }
+
+    Ok(None)
+}
+
+fn bytes_to_sha(bytes: &[u8]) -> Option<CommitSha> {
+    let bytes: [u8; 20] = bytes.try_into().ok()?;
+    Some(CommitSha(bytes))
+}
+
+/// Recursive CTE selecting the commit bound to `$parameter` and all of its ancestors in the
+/// repository bound to `$1`.
+fn ancestors_cte(name: &str, parameter: u8) -> String {
+    format!(
+        "{name}(sha) AS (
+    SELECT ${parameter}::bytea
+    UNION
+    SELECT p.parent_sha FROM \"CommitParent\" p
+    JOIN {name} a ON p.sha = a.sha
+    WHERE p.repository_id = $1
+)"
+    )
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::entities::developer::{Developer, DeveloperId, DeveloperIdentity};
+use thiserror::Error;
+use tracing::{error, info, span, Instrument, Level};
+
+use crate::{
+    projectors::developer::{identity_from_db, identity_to_db},
+    provider::PostgresProvider,
+};
+
+use super::dbid_to_domain_id;
+
+/// Follows the merges starting at the developer owning the identity, and collects the identities
+/// of every developer merged into the canonical developer.
+const RESOLVE_IDENTITY_QUERY: &str = "WITH RECURSIVE chain(id, merged_into, depth) AS (
+    SELECT d.id, d.merged_into, 0 FROM \"DeveloperIdentity\" i
+    JOIN \"Developer\" d ON d.id = i.developer_id
+    WHERE i.kind = $1 AND i.platform = $2 AND i.value = $3
+    UNION ALL
+    SELECT d.id, d.merged_into, c.depth + 1 FROM \"Developer\" d
+    JOIN chain c ON d.id = c.merged_into
+    WHERE c.depth < 64
+),
+canonical AS (
+    SELECT id FROM chain WHERE merged_into IS NULL
+),
+merged(id) AS (
+    SELECT id FROM canonical
+    UNION
+    SELECT d.id FROM \"Developer\" d
+    JOIN merged m ON d.merged_into = m.id
+)
+SELECT c.id, d.username, i.kind, i.platform, i.value
+FROM canonical c
+JOIN \"Developer\" d ON d.id = c.id
+LEFT JOIN merged m ON true
+LEFT JOIN \"DeveloperIdentity\" i ON i.developer_id = m.id;";
+
+pub struct ResolveDeveloperIdentityQuery {
+    pub identity: DeveloperIdentity,
+}
+
+#[async_trait]
+pub trait ResolveDeveloperIdentityQueryHandler: Interface {
+    /// Returns the canonical developer, with the identities of all developers merged into it.
+    async fn handle(
+        &self,
+        query: ResolveDeveloperIdentityQuery,
+    ) -> Result<Developer, ResolveDeveloperIdentityQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = ResolveDeveloperIdentityQueryHandler)]
+pub struct ResolveDeveloperIdentityQueryHandlerImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl ResolveDeveloperIdentityQueryHandler for ResolveDeveloperIdentityQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: ResolveDeveloperIdentityQuery,
+    ) -> Result<Developer, ResolveDeveloperIdentityQueryError> {
+        let (kind, platform, value) = identity_to_db(&query.identity);
+
+        let span = span!(Level::INFO, "resolve_developer_identity");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query(RESOLVE_IDENTITY_QUERY, &[&kind, &platform, &value])
+            .instrument(span)
+            .await;
+
+        let rows = match result {
+            Ok(rows) => {
+                info!("Successfully resolved developer identity");
+                rows
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while resolving developer identity"
+                );
+                return Err(ResolveDeveloperIdentityQueryError::Unexpected);
+            }
+        };
+
+        let Some(first) = rows.first() else {
+            return Err(ResolveDeveloperIdentityQueryError::NotFound);
+        };
+
+        let (raw_id, username) = extract_developer(first).map_err(map_parse_error)?;
+        let identities: Result<Vec<DeveloperIdentity>, ResolveDeveloperIdentityQueryError> = rows
+            .iter()
+            .filter_map(|row| extract_identity(row).map_err(map_parse_error).transpose())
+            .collect();
+
+        Ok(Developer {
+            id: DeveloperId(dbid_to_domain_id(raw_id)),
+            username,
+            identities: identities?,
+            merged_into: None,
+            merged_from: vec![],
+        })
+    }
+}
+
+fn map_parse_error(err: tokio_postgres::Error) -> ResolveDeveloperIdentityQueryError {
+    error!(
+        error = format!("{:?}", err),
+        "Error while parsing resolve developer identity query response"
+    );
+    ResolveDeveloperIdentityQueryError::Unexpected
+}
+
+fn extract_developer(row: &tokio_postgres::Row) -> Result<(i64, String), tokio_postgres::Error> {
+    let raw_id = row.try_get("id")?;
+    let username = row.try_get("username")?;
+
+    Ok((raw_id, username))
+}
+
+fn extract_identity(
+    row: &tokio_postgres::Row,
+) -> Result<Option<DeveloperIdentity>, tokio_postgres::Error> {
+    let kind: Option<String> = row.try_get("kind")?;
+    let platform: Option<String> = row.try_get("platform")?;
+    let value: Option<String> = row.try_get("value")?;
+
+    Ok(match (kind, platform, value) {
+        (Some(kind), Some(platform), Some(value)) => identity_from_db(&kind, platform, value),
+        _ => None,
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum ResolveDeveloperIdentityQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("No developer has this identity")]
+    NotFound,
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    entities::developer::{DeveloperId, DeveloperIdentity},
+    repositories::developer_identity_repository::{
+        DeveloperIdentityRepository, ReleaseDeveloperIdentityError, ReserveDeveloperIdentityError,
+    },
+};
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::{projectors::developer::identity_to_db, provider::PostgresProvider};
+
+#[derive(Provider)]
+#[shaku(interface = DeveloperIdentityRepository)]
+pub struct DeveloperIdentityRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl DeveloperIdentityRepository for DeveloperIdentityRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn reserve(
+        &self,
+        identity: &DeveloperIdentity,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperIdentityError> {
+        let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+        let (kind, platform, value) = identity_to_db(identity);
+
+        // The select can't see the row inserted by the same statement, so it only returns the
+        // owner when the identity was reserved before
+        let reserve_span = span!(Level::INFO, "reserve_developer_identity");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query_one(
+                "WITH inserted AS (
+    INSERT INTO \"DeveloperIdentityReservation\" (kind, platform, value, developer_id)
+    VALUES ($1, $2, $3, $4)
+    ON CONFLICT (kind, platform, value) DO NOTHING
+    RETURNING developer_id
+)
+SELECT developer_id FROM inserted
+UNION ALL
+SELECT developer_id FROM \"DeveloperIdentityReservation\"
+WHERE kind = $1 AND platform = $2 AND value = $3
+LIMIT 1;",
+                &[&kind, &platform, &value, &id],
+            )
+            .instrument(reserve_span)
+            .await;
+
+        match result {
+            Ok(row) => {
+                let owner: i64 = row.try_get("developer_id").map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while parsing developer identity reservation"
+                    );
+                    ReserveDeveloperIdentityError::Unexpected
+                })?;
+
+                if owner == id {
+                    Ok(())
+                } else {
+                    Err(ReserveDeveloperIdentityError::Taken {
+                        developer_id: DeveloperId(u64::from_ne_bytes(owner.to_ne_bytes())),
+                    })
+                }
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while reserving developer identity"
+                );
+                Err(ReserveDeveloperIdentityError::Unexpected)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn release(
+        &self,
+        identity: &DeveloperIdentity,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperIdentityError> {
+        let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+        let (kind, platform, value) = identity_to_db(identity);
+
+        let release_span = span!(Level::INFO, "release_developer_identity");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM \"DeveloperIdentityReservation\"
+WHERE kind = $1 AND platform = $2 AND value = $3 AND developer_id = $4;",
+                &[&kind, &platform, &value, &id],
+            )
+            .instrument(release_span)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while releasing developer identity"
+                );
+                Err(ReleaseDeveloperIdentityError::Unexpected)
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    entities::developer::DeveloperId,
+    repositories::developer_username_repository::{
+        DeveloperUsernameRepository, ReleaseDeveloperUsernameError, ReserveDeveloperUsernameError,
+    },
+};
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+#[derive(Provider)]
+#[shaku(interface = DeveloperUsernameRepository)]
+pub struct DeveloperUsernameRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl DeveloperUsernameRepository for DeveloperUsernameRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn reserve(
+        &self,
+        username: &str,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperUsernameError> {
+        let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+
+        // The select can't see the row inserted by the same statement, so it only returns the
+        // owner when the username was reserved before
+        let reserve_span = span!(Level::INFO, "reserve_developer_username");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query_one(
+                "WITH inserted AS (
+    INSERT INTO \"DeveloperUsername\" (username, developer_id) VALUES ($1, $2)
+    ON CONFLICT (username) DO NOTHING
+    RETURNING developer_id
+)
+SELECT developer_id FROM inserted
+UNION ALL
+SELECT developer_id FROM \"DeveloperUsername\" WHERE username = $1
+LIMIT 1;",
+                &[&username, &id],
+            )
+            .instrument(reserve_span)
+            .await;
+
+        match result {
+            Ok(row) => {
+                let owner: i64 = row.try_get("developer_id").map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while parsing developer username reservation"
+                    );
+                    ReserveDeveloperUsernameError::Unexpected
+                })?;
+
+                if owner == id {
+                    Ok(())
+                } else {
+                    Err(ReserveDeveloperUsernameError::Taken {
+                        developer_id: DeveloperId(u64::from_ne_bytes(owner.to_ne_bytes())),
+                    })
+                }
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while reserving developer username"
+                );
+                Err(ReserveDeveloperUsernameError::Unexpected)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn release(
+        &self,
+        username: &str,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperUsernameError> {
+        let id = i64::from_ne_bytes(developer_id.0.to_ne_bytes());
+
+        let release_span = span!(Level::INFO, "release_developer_username");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM \"DeveloperUsername\" WHERE username = $1 AND developer_id = $2;",
+                &[&username, &id],
+            )
+            .instrument(release_span)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while releasing developer username"
+                );
+                Err(ReleaseDeveloperUsernameError::Unexpected)
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use shaku::{Interface, Provider};
+use thiserror::Error;
+use tokio_postgres::Row;
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+const RESERVE_ATTEMPTS: usize = 3;
+
+/// Clients pick their keys, so a key is only reused by the same actor for the same endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey {
+    /// `X-Actor-Id` of the request, empty without one.
+    pub actor_id: String,
+    pub method: String,
+    pub path: String,
+    pub key: String,
+}
+
+/// The response stored for a key, replayed to retries of the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyKeyReservation {
+    /// Nobody used the key before, its response expired or the request handling it ran out of
+    /// its lease. The request has to be handled, completing or releasing the key with `lease_id`.
+    Reserved { lease_id: String },
+    /// Another request with the key is still being handled.
+    InProgress { request_hash: String },
+    Completed {
+        request_hash: String,
+        response: StoredResponse,
+    },
+}
+
+#[async_trait]
+pub trait IdempotencyKeyRepository: Interface {
+    /// Reserves `key` for `lease`, or returns what the request that reserved it before did. A
+    /// request that crashed while handling the key holds it until its lease ends.
+    async fn reserve(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        lease: Duration,
+    ) -> Result<IdempotencyKeyReservation, IdempotencyKeyError>;
+
+    /// Stores the response for `ttl`, unless another request took over the key meanwhile.
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyKeyError>;
+
+    /// Releases a key without storing a response, so a retry handles the request again.
+    async fn release(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+    ) -> Result<(), IdempotencyKeyError>;
+
+    /// Removes keys of which the response or lease expired, returns how many were removed.
+    async fn delete_expired(&self) -> Result<u64, IdempotencyKeyError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = IdempotencyKeyRepository)]
+pub struct IdempotencyKeyRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl IdempotencyKeyRepository for IdempotencyKeyRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn reserve(
+        &self,
+        key: &IdempotencyKey,
+        request_hash: &str,
+        lease: Duration,
+    ) -> Result<IdempotencyKeyReservation, IdempotencyKeyError> {
+        // Like reserving organization names, the select only sees the row when it was there
+        // before. Expired rows are taken over as if they didn't exist. When a concurrent request
+        // inserted the row after this statement started, neither sees it, so it is tried again.
+        for _ in 0..RESERVE_ATTEMPTS {
+            let reserve_span = span!(Level::INFO, "reserve_idempotency_key");
+            let row = self
+                .client
+                .get_client()
+                .await
+                .query_opt(
+                    "WITH reserved AS (
+    INSERT INTO idempotency_keys
+        (actor_id, method, path, idempotency_key, request_hash, lease_id, expires_at)
+    VALUES ($1, $2, $3, $4, $5, gen_random_uuid()::varchar, now() + make_interval(secs => $6))
+    ON CONFLICT (actor_id, method, path, idempotency_key) DO UPDATE SET
+        request_hash = EXCLUDED.request_hash,
+        lease_id = EXCLUDED.lease_id,
+        status_code = NULL,
+        headers = NULL,
+        body = NULL,
+        expires_at = EXCLUDED.expires_at
+    WHERE idempotency_keys.expires_at < now()
+    RETURNING lease_id
+)
+SELECT true AS reserved, lease_id, NULL::varchar AS request_hash, NULL::smallint AS status_code,
+    NULL::jsonb AS headers, NULL::bytea AS body
+FROM reserved
+UNION ALL
+SELECT false, NULL::varchar, request_hash, status_code, headers, body FROM idempotency_keys
+WHERE actor_id = $1 AND method = $2 AND path = $3 AND idempotency_key = $4
+LIMIT 1;",
+                    &[
+                        &key.actor_id,
+                        &key.method,
+                        &key.path,
+                        &key.key,
+                        &request_hash,
+                        &lease.as_secs_f64(),
+                    ],
+                )
+                .instrument(reserve_span)
+                .await
+                .map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while reserving idempotency key"
+                    );
+                    IdempotencyKeyError::Unexpected
+                })?;
+
+            if let Some(row) = row {
+                return to_reservation(&row).map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while parsing idempotency key"
+                    );
+                    IdempotencyKeyError::Unexpected
+                });
+            }
+        }
+
+        error!("Idempotency key kept changing while reserving it");
+        Err(IdempotencyKeyError::Unexpected)
+    }
+
+    #[instrument(skip(self, response))]
+    async fn complete(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyKeyError> {
+        let headers = Value::from(
+            response
+                .headers
+                .iter()
+                .map(|(name, value)| Value::from(vec![name.as_str(), value.as_str()]))
+                .collect::<Vec<_>>(),
+        );
+
+        let complete_span = span!(Level::INFO, "complete_idempotency_key");
+        self.client
+            .get_client()
+            .await
+            .execute(
+                "UPDATE idempotency_keys SET status_code = $6, headers = $7, body = $8,
+    expires_at = now() + make_interval(secs => $9)
+WHERE actor_id = $1 AND method = $2 AND path = $3 AND idempotency_key = $4 AND lease_id = $5
+    AND status_code IS NULL;",
+                &[
+                    &key.actor_id,
+                    &key.method,
+                    &key.path,
+                    &key.key,
+                    &lease_id,
+                    &(response.status_code as i16),
+                    &headers,
+                    &response.body,
+                    &ttl.as_secs_f64(),
+                ],
+            )
+            .instrument(complete_span)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while storing idempotent response"
+                );
+                IdempotencyKeyError::Unexpected
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn release(
+        &self,
+        key: &IdempotencyKey,
+        lease_id: &str,
+    ) -> Result<(), IdempotencyKeyError> {
+        let release_span = span!(Level::INFO, "release_idempotency_key");
+        self.client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM idempotency_keys
+WHERE actor_id = $1 AND method = $2 AND path = $3 AND idempotency_key = $4 AND lease_id = $5
+    AND status_code IS NULL;",
+                &[&key.actor_id, &key.method, &key.path, &key.key, &lease_id],
+            )
+            .instrument(release_span)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while releasing idempotency key"
+                );
+                IdempotencyKeyError::Unexpected
+            })
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_expired(&self) -> Result<u64, IdempotencyKeyError> {
+        let delete_span = span!(Level::INFO, "delete_expired_idempotency_keys");
+        self.client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM idempotency_keys WHERE expires_at < now();",
+                &[],
+            )
+            .instrument(delete_span)
+            .await
+            .map_err(|err| {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while deleting expired idempotency keys"
+                );
+                IdempotencyKeyError::Unexpected
+            })
+    }
+}
+
+fn to_reservation(row: &Row) -> Result<IdempotencyKeyReservation, tokio_postgres::Error> {
+    if row.try_get::<_, bool>("reserved")? {
+        return Ok(IdempotencyKeyReservation::Reserved {
+            lease_id: row.try_get("lease_id")?,
+        });
+    }
+
+    let request_hash: String = row.try_get("request_hash")?;
+    let Some(status_code) = row.try_get::<_, Option<i16>>("status_code")? else {
+        return Ok(IdempotencyKeyReservation::InProgress { request_hash });
+    };
+
+    let headers = row
+        .try_get::<_, Option<Value>>("headers")?
+        .and_then(|headers| headers.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|header| match header.as_array()?.as_slice() {
+            [name, value] => Some((name.as_str()?.to_string(), value.as_str()?.to_string())),
+            _ => None,
+        })
+        .collect();
+
+    Ok(IdempotencyKeyReservation::Completed {
+        request_hash,
+        response: StoredResponse {
+            status_code: status_code as u16,
+            headers,
+            body: row
+                .try_get::<_, Option<Vec<u8>>>("body")?
+                .unwrap_or_default(),
+        },
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum IdempotencyKeyError {
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::test_support::TestDatabase;
+
+    use super::{
+        IdempotencyKey, IdempotencyKeyRepository, IdempotencyKeyRepositoryImpl,
+        IdempotencyKeyReservation, StoredResponse,
+    };
+
+    async fn migrated_repository() -> (TestDatabase, IdempotencyKeyRepositoryImpl) {
+        let database = TestDatabase::migrated("idempotency_key_test").await;
+        let repository = IdempotencyKeyRepositoryImpl {
+            client: database.postgres().await,
+        };
+        (database, repository)
+    }
+
+    fn key(actor_id: &str, key: &str) -> IdempotencyKey {
+        IdempotencyKey {
+            actor_id: actor_id.to_string(),
+            method: "POST".to_string(),
+            path: "/organizations".to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_reserve_a_key_for_one_of_concurrent_requests() {
+        let (database, repository) = migrated_repository().await;
+        let lease = Duration::from_secs(60);
+
+        for attempt in 0..20 {
+            let key = key("alice", &attempt.to_string());
+            let (first, second) = tokio::join!(
+                repository.reserve(&key, "hash", lease),
+                repository.reserve(&key, "hash", lease)
+            );
+
+            let mut reservations = [first.unwrap(), second.unwrap()];
+            reservations.sort_by_key(|reservation| {
+                !matches!(reservation, IdempotencyKeyReservation::Reserved { .. })
+            });
+            assert!(matches!(
+                reservations[0],
+                IdempotencyKeyReservation::Reserved { .. }
+            ));
+            assert_eq!(
+                reservations[1],
+                IdempotencyKeyReservation::InProgress {
+                    request_hash: "hash".to_string()
+                }
+            );
+        }
+
+        drop(repository);
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_only_complete_the_key_with_the_current_lease() {
+        let (database, repository) = migrated_repository().await;
+        let key = key("alice", "create-porti");
+        let response = StoredResponse {
+            status_code: 201,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: b"{}".to_vec(),
+        };
+
+        let Ok(IdempotencyKeyReservation::Reserved { lease_id: expired }) =
+            repository.reserve(&key, "hash", Duration::ZERO).await
+        else {
+            panic!("The key should be reserved")
+        };
+        let Ok(IdempotencyKeyReservation::Reserved { lease_id: current }) = repository
+            .reserve(&key, "hash", Duration::from_secs(60))
+            .await
+        else {
+            panic!("The key should be taken over once the lease expired")
+        };
+        repository
+            .complete(&key, &expired, &response, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let after_expired = repository
+            .reserve(&key, "hash", Duration::from_secs(60))
+            .await;
+        repository
+            .complete(&key, &current, &response, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let after_current = repository
+            .reserve(&key, "hash", Duration::from_secs(60))
+            .await;
+        let other_actor = repository
+            .reserve(
+                &self::key("bob", "create-porti"),
+                "hash",
+                Duration::from_secs(60),
+            )
+            .await;
+
+        assert_eq!(
+            after_expired.unwrap(),
+            IdempotencyKeyReservation::InProgress {
+                request_hash: "hash".to_string()
+            }
+        );
+        assert_eq!(
+            after_current.unwrap(),
+            IdempotencyKeyReservation::Completed {
+                request_hash: "hash".to_string(),
+                response
+            }
+        );
+        assert!(matches!(
+            other_actor.unwrap(),
+            IdempotencyKeyReservation::Reserved { .. }
+        ));
+
+        drop(repository);
+        database.remove().await;
+    }
+}
@@ -0,0 +1,5 @@
+pub mod developer_identity_repository;
+pub mod developer_username_repository;
+pub mod idempotency_key_repository;
+pub mod organization_name_repository;
+pub mod pull_request_number_repository;
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    entities::organization::OrganizationId,
+    repositories::organization_name_repository::{
+        organization_name_key, OrganizationNameRepository, ReleaseOrganizationNameError,
+        ReserveOrganizationNameError,
+    },
+};
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+#[derive(Provider)]
+#[shaku(interface = OrganizationNameRepository)]
+pub struct OrganizationNameRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl OrganizationNameRepository for OrganizationNameRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn reserve(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReserveOrganizationNameError> {
+        let key = organization_name_key(name);
+        let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+        // The select can't see the row inserted by the same statement, so it only returns the
+        // owner when the name was reserved before
+        let reserve_span = span!(Level::INFO, "reserve_organization_name");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query_one(
+                "WITH inserted AS (
+    INSERT INTO \"OrganizationName\" (name_key, organization_id) VALUES ($1, $2)
+    ON CONFLICT (name_key) DO NOTHING
+    RETURNING organization_id
+)
+SELECT organization_id FROM inserted
+UNION ALL
+SELECT organization_id FROM \"OrganizationName\" WHERE name_key = $1
+LIMIT 1;",
+                &[&key, &id],
+            )
+            .instrument(reserve_span)
+            .await;
+
+        match result {
+            Ok(row) => {
+                let owner: i64 = row.try_get("organization_id").map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while parsing organization name reservation"
+                    );
+                    ReserveOrganizationNameError::Unexpected
+                })?;
+
+                if owner == id {
+                    Ok(())
+                } else {
+                    Err(ReserveOrganizationNameError::Taken {
+                        organization_id: OrganizationId(u64::from_ne_bytes(owner.to_ne_bytes())),
+                    })
+                }
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while reserving organization name"
+                );
+                Err(ReserveOrganizationNameError::Unexpected)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn release(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReleaseOrganizationNameError> {
+        let key = organization_name_key(name);
+        let id = i64::from_ne_bytes(organization_id.0.to_ne_bytes());
+
+        let release_span = span!(Level::INFO, "release_organization_name");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM \"OrganizationName\" WHERE name_key = $1 AND organization_id = $2;",
+                &[&key, &id],
+            )
+            .instrument(release_span)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while releasing organization name"
+                );
+                Err(ReleaseOrganizationNameError::Unexpected)
+            }
+        }
+    }
+}
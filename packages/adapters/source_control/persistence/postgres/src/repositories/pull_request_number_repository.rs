@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::Provider;
+use source_control_domain::{
+    entities::{pull_request::PullRequestId, repository::RepositoryId},
+    repositories::pull_request_number_repository::{
+        PullRequestNumberRepository, ReleasePullRequestNumberError, ReservePullRequestNumberError,
+    },
+};
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::provider::PostgresProvider;
+
+#[derive(Provider)]
+#[shaku(interface = PullRequestNumberRepository)]
+pub struct PullRequestNumberRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl PullRequestNumberRepository for PullRequestNumberRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn reserve(
+        &self,
+        repository_id: RepositoryId,
+        number: u64,
+        pull_request_id: PullRequestId,
+    ) -> Result<(), ReservePullRequestNumberError> {
+        let repository = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+        let number = number as i64;
+        let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+
+        // The select can't see the row inserted by the same statement, so it only returns the
+        // owner when the number was reserved before
+        let reserve_span = span!(Level::INFO, "reserve_pull_request_number");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .query_one(
+                "WITH inserted AS (
+    INSERT INTO \"PullRequestNumber\" (repository_id, number, pull_request_id) VALUES ($1, $2, $3)
+    ON CONFLICT (repository_id, number) DO NOTHING
+    RETURNING pull_request_id
+)
+SELECT pull_request_id FROM inserted
+UNION ALL
+SELECT pull_request_id FROM \"PullRequestNumber\" WHERE repository_id = $1 AND number = $2
+LIMIT 1;",
+                &[&repository, &number, &id],
+            )
+            .instrument(reserve_span)
+            .await;
+
+        match result {
+            Ok(row) => {
+                let owner: i64 = row.try_get("pull_request_id").map_err(|err| {
+                    error!(
+                        error = format!("{:?}", err),
+                        "Error while parsing pull request number reservation"
+                    );
+                    ReservePullRequestNumberError::Unexpected
+                })?;
+
+                if owner == id {
+                    Ok(())
+                } else {
+                    Err(ReservePullRequestNumberError::Taken {
+                        pull_request_id: PullRequestId(u64::from_ne_bytes(owner.to_ne_bytes())),
+                    })
+                }
+            }
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while reserving pull request number"
+                );
+                Err(ReservePullRequestNumberError::Unexpected)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn release(
+        &self,
+        repository_id: RepositoryId,
+        number: u64,
+        pull_request_id: PullRequestId,
+    ) -> Result<(), ReleasePullRequestNumberError> {
+        let repository = i64::from_ne_bytes(repository_id.0.to_ne_bytes());
+        let number = number as i64;
+        let id = i64::from_ne_bytes(pull_request_id.0.to_ne_bytes());
+
+        let release_span = span!(Level::INFO, "release_pull_request_number");
+        let result = self
+            .client
+            .get_client()
+            .await
+            .execute(
+                "DELETE FROM \"PullRequestNumber\"
+WHERE repository_id = $1 AND number = $2 AND pull_request_id = $3;",
+                &[&repository, &number, &id],
+            )
+            .instrument(release_span)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    error = format!("{:?}", err),
+                    "Error while releasing pull request number"
+                );
+                Err(ReleasePullRequestNumberError::Unexpected)
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Databases for tests that need a postgres server. Set `SOURCE_CONTROL_TEST_POSTGRES` to the
+//! connection string of a server the tests may create databases on.
+use std::{
+    env, fs,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bb8_postgres::{
+    bb8::{Pool, PooledConnection},
+    PostgresConnectionManager,
+};
+use tokio_postgres::{Client, NoTls};
+
+use crate::provider::PostgresProvider;
+
+pub const POSTGRES_ENV_NAME: &str = "SOURCE_CONTROL_TEST_POSTGRES";
+
+pub async fn connect(config: &str) -> Client {
+    let (client, connection) = tokio_postgres::connect(config, NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+/// A database with every migration applied, named after the tests that created it.
+pub struct TestDatabase {
+    server: Client,
+    name: String,
+    config: String,
+}
+
+impl TestDatabase {
+    pub async fn migrated(prefix: &str) -> Self {
+        let server_config = env::var(POSTGRES_ENV_NAME)
+            .unwrap_or_else(|_| panic!("{} is not set", POSTGRES_ENV_NAME));
+        let server = connect(&server_config).await;
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let name = format!("{}_{}", prefix, nanos);
+        server
+            .batch_execute(&format!("CREATE DATABASE {};", name))
+            .await
+            .unwrap();
+
+        let database = Self {
+            server,
+            config: format!("{} dbname={}", server_config, name),
+            name,
+        };
+        let client = database.connect().await;
+        let mut migrations: Vec<_> =
+            fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/migrations"))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+        migrations.sort();
+        for migration in migrations {
+            let sql = fs::read_to_string(migration).unwrap();
+            client.batch_execute(&sql).await.unwrap();
+        }
+
+        database
+    }
+
+    pub async fn connect(&self) -> Client {
+        connect(&self.config).await
+    }
+
+    /// A pool of connections to the database, for the repositories and subscribers under test.
+    pub async fn postgres(&self) -> Arc<TestPostgres> {
+        let pool = Pool::builder()
+            .build(PostgresConnectionManager::new_from_stringlike(&self.config, NoTls).unwrap())
+            .await
+            .unwrap();
+        Arc::new(TestPostgres { pool })
+    }
+
+    /// Drops the database, closing the connections that are still open.
+    pub async fn remove(self) {
+        self.server
+            .batch_execute(&format!("DROP DATABASE {} WITH (FORCE);", self.name))
+            .await
+            .unwrap();
+    }
+}
+
+pub struct TestPostgres {
+    pool: Pool<PostgresConnectionManager<NoTls>>,
+}
+
+#[async_trait]
+impl PostgresProvider for TestPostgres {
+    async fn get_client(&self) -> PooledConnection<'_, PostgresConnectionManager<NoTls>> {
+        self.pool.get().await.unwrap()
+    }
+}
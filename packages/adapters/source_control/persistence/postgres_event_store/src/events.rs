@@ -0,0 +1,394 @@
+use serde_json::Value;
+use thiserror::Error;
+use tokio_postgres::{error::SqlState, Client, Row};
+
+pub struct NewEvent {
+    /// Includes the version suffix, `.../Create/1`.
+    pub event_type: String,
+    pub data: Value,
+    pub metadata: Value,
+}
+
+/// Where an event is in the order subscriptions read them. Appends to different streams run
+/// concurrently, so global positions can commit out of order, but a transaction only becomes
+/// readable once every transaction that started before it finished.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventPosition {
+    pub transaction_id: i64,
+    pub global_position: i64,
+}
+
+#[derive(Debug)]
+pub struct StoredEvent {
+    /// Position of the event across all streams, increases with every append.
+    pub global_position: i64,
+    /// Id of the transaction that appended the event.
+    pub transaction_id: i64,
+    pub event_id: String,
+    pub stream_id: String,
+    pub revision: u64,
+    pub event_type: String,
+    pub data: Value,
+    /// `null` when the event was stored without metadata.
+    pub metadata: Value,
+}
+
+#[derive(Debug)]
+pub struct ParkedStoredEvent {
+    pub event: StoredEvent,
+    /// The nack reason the subscription parked the event with.
+    pub reason: String,
+    /// RFC 3339.
+    pub parked_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedRevision {
+    NoStream,
+    /// Revision of the last event in the stream, the first event has revision 0.
+    Exact(u64),
+}
+
+#[derive(Error, Debug)]
+pub enum AppendError {
+    #[error("The stream is not at the expected revision")]
+    WrongExpectedRevision,
+    #[error(transparent)]
+    Postgres(#[from] tokio_postgres::Error),
+}
+
+pub async fn append_to_stream(
+    client: &mut Client,
+    stream_id: &str,
+    expected_revision: ExpectedRevision,
+    events: &[NewEvent],
+) -> Result<(), AppendError> {
+    let transaction = client.transaction().await?;
+    // Concurrent appends to the stream wait here and then fail the revision check, appends to
+    // other streams don't wait
+    transaction
+        .execute("SELECT pg_advisory_xact_lock(hashtext($1));", &[&stream_id])
+        .await?;
+
+    let current_revision: Option<i64> = transaction
+        .query_one(
+            "SELECT max(revision) FROM events WHERE stream_id = $1;",
+            &[&stream_id],
+        )
+        .await?
+        .get(0);
+
+    let is_expected = match expected_revision {
+        ExpectedRevision::NoStream => current_revision.is_none(),
+        ExpectedRevision::Exact(revision) => current_revision == Some(revision as i64),
+    };
+    if !is_expected {
+        return Err(AppendError::WrongExpectedRevision);
+    }
+
+    let statement = transaction
+        .prepare(
+            "INSERT INTO events (stream_id, revision, event_type, data, metadata)
+VALUES ($1, $2, $3, $4, $5);",
+        )
+        .await?;
+    let first_revision = current_revision.map_or(0, |revision| revision + 1);
+    for (offset, event) in events.iter().enumerate() {
+        let revision = first_revision + offset as i64;
+        let result = transaction
+            .execute(
+                &statement,
+                &[
+                    &stream_id,
+                    &revision,
+                    &event.event_type,
+                    &event.data,
+                    &event.metadata,
+                ],
+            )
+            .await;
+
+        match result {
+            Ok(_) => {}
+            Err(err) if err.code() == Some(&SqlState::UNIQUE_VIOLATION) => {
+                return Err(AppendError::WrongExpectedRevision)
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Every event of the stream ordered by revision, empty when the stream doesn't exist.
+pub async fn read_stream(
+    client: &Client,
+    stream_id: &str,
+) -> Result<Vec<StoredEvent>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT global_position, transaction_id::text::bigint, event_id::text, stream_id, revision,
+event_type, data, metadata
+FROM events WHERE stream_id = $1 ORDER BY revision;",
+            &[&stream_id],
+        )
+        .await?;
+
+    rows.iter().map(to_stored_event).collect()
+}
+
+/// At most `limit` events after `position` of the streams starting with `stream_prefix`. Events
+/// of transactions that might still be followed by an earlier one aren't returned yet.
+pub async fn read_all_after(
+    client: &Client,
+    stream_prefix: &str,
+    position: EventPosition,
+    limit: i64,
+) -> Result<Vec<StoredEvent>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT global_position, transaction_id::text::bigint, event_id::text, stream_id, revision,
+event_type, data, metadata
+FROM events
+WHERE (transaction_id, global_position) > ($1::bigint::text::xid8, $2)
+    AND transaction_id < pg_snapshot_xmin(pg_current_snapshot())
+    AND starts_with(stream_id, $3)
+ORDER BY transaction_id, global_position LIMIT $4;",
+            &[
+                &position.transaction_id,
+                &position.global_position,
+                &stream_prefix,
+                &limit,
+            ],
+        )
+        .await?;
+
+    rows.iter().map(to_stored_event).collect()
+}
+
+/// Position of the last event the subscription handled, the default before it handled any.
+pub async fn read_checkpoint(
+    client: &Client,
+    subscription_name: &str,
+) -> Result<EventPosition, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            "SELECT transaction_id, position FROM event_subscription_checkpoints
+WHERE subscription_name = $1;",
+            &[&subscription_name],
+        )
+        .await?;
+
+    Ok(row.map_or(EventPosition::default(), |row| EventPosition {
+        transaction_id: row.get("transaction_id"),
+        global_position: row.get("position"),
+    }))
+}
+
+pub async fn write_checkpoint(
+    client: &Client,
+    subscription_name: &str,
+    position: EventPosition,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute(
+            "INSERT INTO event_subscription_checkpoints (subscription_name, transaction_id, position)
+VALUES ($1, $2, $3)
+ON CONFLICT (subscription_name)
+DO UPDATE SET transaction_id = EXCLUDED.transaction_id, position = EXCLUDED.position;",
+            &[
+                &subscription_name,
+                &position.transaction_id,
+                &position.global_position,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Takes the session lock of the subscription on this connection, so only one process runs it.
+/// Returns false when another connection holds it. The lock stays with the connection until it is
+/// unlocked or closed, so a pooled connection has to unlock it before it is returned.
+pub async fn try_lock_subscription(
+    client: &Client,
+    subscription_name: &str,
+) -> Result<bool, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "SELECT pg_try_advisory_lock(hashtextextended($1, 0));",
+            &[&subscription_name],
+        )
+        .await?;
+
+    row.try_get(0)
+}
+
+pub async fn unlock_subscription(
+    client: &Client,
+    subscription_name: &str,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute(
+            "SELECT pg_advisory_unlock(hashtextextended($1, 0));",
+            &[&subscription_name],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn park_event(
+    client: &Client,
+    subscription_name: &str,
+    global_position: i64,
+    reason: &str,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute(
+            "INSERT INTO parked_events (subscription_name, global_position, reason) VALUES ($1, $2, $3)
+ON CONFLICT (subscription_name, global_position)
+DO UPDATE SET reason = EXCLUDED.reason, replay_requested = false;",
+            &[&subscription_name, &global_position, &reason],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Whether the subscription ever handled an event, subscriptions that didn't have nothing parked.
+pub async fn has_checkpoint(
+    client: &Client,
+    subscription_name: &str,
+) -> Result<bool, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            "SELECT 1 FROM event_subscription_checkpoints WHERE subscription_name = $1;",
+            &[&subscription_name],
+        )
+        .await?;
+
+    Ok(row.is_some())
+}
+
+/// At most `limit` events the subscription parked, by global position.
+pub async fn read_parked_events(
+    client: &Client,
+    subscription_name: &str,
+    limit: i64,
+) -> Result<Vec<ParkedStoredEvent>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT e.global_position, e.transaction_id::text::bigint, e.event_id::text, e.stream_id,
+e.revision, e.event_type, e.data, e.metadata, p.reason, to_json(p.parked_at) #>> '{}' AS parked_at
+FROM parked_events p JOIN events e USING (global_position)
+WHERE p.subscription_name = $1
+ORDER BY p.global_position LIMIT $2;",
+            &[&subscription_name, &limit],
+        )
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(ParkedStoredEvent {
+                event: to_stored_event(row)?,
+                reason: row.try_get("reason")?,
+                parked_at: row.try_get("parked_at")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn is_parked(
+    client: &Client,
+    subscription_name: &str,
+    global_position: i64,
+) -> Result<bool, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            "SELECT 1 FROM parked_events WHERE subscription_name = $1 AND global_position = $2;",
+            &[&subscription_name, &global_position],
+        )
+        .await?;
+
+    Ok(row.is_some())
+}
+
+/// Marks the parked events up to and including `up_to` for replay, every parked event when
+/// `None`. Returns how many were marked.
+pub async fn request_replay(
+    client: &Client,
+    subscription_name: &str,
+    up_to: Option<i64>,
+) -> Result<u64, tokio_postgres::Error> {
+    client
+        .execute(
+            "UPDATE parked_events SET replay_requested = true
+WHERE subscription_name = $1 AND ($2::bigint IS NULL OR global_position <= $2);",
+            &[&subscription_name, &up_to],
+        )
+        .await
+}
+
+/// At most `limit` parked events marked for replay, by global position.
+pub async fn read_replay_requested(
+    client: &Client,
+    subscription_name: &str,
+    limit: i64,
+) -> Result<Vec<StoredEvent>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT e.global_position, e.transaction_id::text::bigint, e.event_id::text, e.stream_id,
+e.revision, e.event_type, e.data, e.metadata
+FROM parked_events p JOIN events e USING (global_position)
+WHERE p.subscription_name = $1 AND p.replay_requested
+ORDER BY p.global_position LIMIT $2;",
+            &[&subscription_name, &limit],
+        )
+        .await?;
+
+    rows.iter().map(to_stored_event).collect()
+}
+
+/// Removes a replayed event unless it was parked again while replaying it.
+pub async fn remove_replayed_event(
+    client: &Client,
+    subscription_name: &str,
+    global_position: i64,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute(
+            "DELETE FROM parked_events
+WHERE subscription_name = $1 AND global_position = $2 AND replay_requested;",
+            &[&subscription_name, &global_position],
+        )
+        .await?;
+
+    Ok(())
+}
+
+impl StoredEvent {
+    pub fn position(&self) -> EventPosition {
+        EventPosition {
+            transaction_id: self.transaction_id,
+            global_position: self.global_position,
+        }
+    }
+}
+
+fn to_stored_event(row: &Row) -> Result<StoredEvent, tokio_postgres::Error> {
+    let revision: i64 = row.try_get("revision")?;
+    Ok(StoredEvent {
+        global_position: row.try_get("global_position")?,
+        transaction_id: row.try_get("transaction_id")?,
+        event_id: row.try_get("event_id")?,
+        stream_id: row.try_get("stream_id")?,
+        revision: revision as u64,
+        event_type: row.try_get("event_type")?,
+        data: row.try_get("data")?,
+        metadata: row
+            .try_get::<_, Option<Value>>("metadata")?
+            .unwrap_or(Value::Null),
+    })
+}
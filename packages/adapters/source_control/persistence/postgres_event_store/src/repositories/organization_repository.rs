@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_codec::{EventCodec, EventField};
+use event_store_util::from_stored_json;
+use event_store_util::metadata::{event_metadata, metadata_from_json};
+use shaku::Provider;
+use source_control_domain::{
+    aggregates::{
+        metadata::{CommandMetadata, LoggedEvent},
+        organization::{OrganizationAggregate, OrganizationEvent},
+    },
+    entities::organization::{Organization, OrganizationId},
+    repositories::organization_repository::{
+        CreateOrganizationError, GetOrganizationError, GetOrganizationLogError,
+        OrganizationRepository, SaveOrganizationError,
+    },
+};
+use source_control_postgres_persistence_adapter::provider::PostgresProvider;
+use tracing::{error, instrument, span, Instrument, Level};
+
+use crate::events::{
+    append_to_stream, read_stream, AppendError, ExpectedRevision, NewEvent, StoredEvent,
+};
+
+/// Stores organizations in the `events` table. Reading a whole stream is a single query, so
+/// unlike the EventStore repository it doesn't take snapshots.
+#[derive(Provider)]
+#[shaku(interface = OrganizationRepository)]
+pub struct PostgresOrganizationRepositoryImpl {
+    #[shaku(inject)]
+    pub client: Arc<dyn PostgresProvider>,
+}
+
+#[async_trait]
+impl OrganizationRepository for PostgresOrganizationRepositoryImpl {
+    #[instrument(skip(self))]
+    async fn get_log(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<Box<[LoggedEvent<OrganizationEvent>]>, GetOrganizationLogError> {
+        let stream = PostgresOrganizationRepositoryImpl::get_stream_name(organization_id);
+
+        let read_span = span!(Level::INFO, "postgres_read_stream");
+        let stored_events = read_stream(&*self.client.get_client().await, &stream)
+            .instrument(read_span)
+            .await
+            .map_err(|err| {
+                error!("Error occurred while reading organization: {}", err);
+                match err.is_closed() {
+                    true => GetOrganizationLogError::Connection,
+                    false => GetOrganizationLogError::Unexpected,
+                }
+            })?;
+
+        if stored_events.is_empty() {
+            return Err(GetOrganizationLogError::NotFound { organization_id });
+        }
+
+        stored_events
+            .into_iter()
+            .map(|stored| {
+                let metadata = metadata_from_json(&stored.event_id, &stored.metadata);
+                decode(stored)
+                    .map(|event| LoggedEvent { event, metadata })
+                    .map_err(|reason| GetOrganizationLogError::Corrupt { reason })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<OrganizationAggregate, GetOrganizationError> {
+        let stream = PostgresOrganizationRepositoryImpl::get_stream_name(organization_id);
+
+        let read_span = span!(Level::INFO, "postgres_read_stream");
+        let stored_events = read_stream(&*self.client.get_client().await, &stream)
+            .instrument(read_span)
+            .await
+            .map_err(|err| {
+                error!("Error occurred while reading organization: {}", err);
+                match err.is_closed() {
+                    true => GetOrganizationError::Connection,
+                    false => GetOrganizationError::Unexpected,
+                }
+            })?;
+
+        let Some(latest_revision) = stored_events.last().map(|stored| stored.revision) else {
+            return Err(GetOrganizationError::NotFound { organization_id });
+        };
+
+        let events = stored_events
+            .into_iter()
+            .map(decode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|reason| GetOrganizationError::Corrupt { reason })?;
+
+        Ok(OrganizationAggregate::from_events(events, latest_revision))
+    }
+
+    #[instrument(skip(self, organization))]
+    async fn save(
+        &self,
+        organization: OrganizationAggregate,
+        metadata: CommandMetadata,
+    ) -> Result<(), SaveOrganizationError> {
+        let metadata = event_metadata(&metadata).encode();
+        let events: Vec<NewEvent> = organization
+            .draft_events
+            .iter()
+            .map(|event| NewEvent {
+                event_type: event.get_versioned_event_type(),
+                data: event.encode(),
+                metadata: metadata.clone(),
+            })
+            .collect();
+
+        let stream = PostgresOrganizationRepositoryImpl::get_stream_name(organization.root.id);
+
+        let write_span = span!(Level::INFO, "postgres_append_stream");
+        let write_result = append_to_stream(
+            &mut *self.client.get_client().await,
+            &stream,
+            ExpectedRevision::Exact(organization.latest_revision),
+            &events,
+        )
+        .instrument(write_span)
+        .await;
+
+        match write_result {
+            Ok(_) => Ok(()),
+            Err(AppendError::WrongExpectedRevision) => Err(SaveOrganizationError::Conflict),
+            Err(AppendError::Postgres(err)) if err.is_closed() => {
+                Err(SaveOrganizationError::Connection)
+            }
+            Err(err) => {
+                error!("Error occurred while saving organization: {}", err);
+                Err(SaveOrganizationError::Unexpected)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn create(
+        &self,
+        id: OrganizationId,
+        name: String,
+        metadata: CommandMetadata,
+    ) -> Result<Organization, CreateOrganizationError> {
+        let stream = PostgresOrganizationRepositoryImpl::get_stream_name(id);
+
+        let event = OrganizationEvent::CreateOrganizationEvent {
+            organization_id: id,
+            name: name.clone(),
+        };
+        let new_event = NewEvent {
+            event_type: event.get_versioned_event_type(),
+            data: event.encode(),
+            metadata: event_metadata(&metadata).encode(),
+        };
+
+        let write_span = span!(Level::INFO, "postgres_append_stream");
+        let write_result = append_to_stream(
+            &mut *self.client.get_client().await,
+            &stream,
+            ExpectedRevision::NoStream,
+            &[new_event],
+        )
+        .instrument(write_span)
+        .await;
+
+        match write_result {
+            Ok(_) => Ok(Organization {
+                id,
+                name,
+                platform_accounts: vec![],
+                archived: false,
+                deleted: false,
+            }),
+            Err(AppendError::WrongExpectedRevision) => Err(CreateOrganizationError::Conflict),
+            Err(AppendError::Postgres(err)) if err.is_closed() => {
+                Err(CreateOrganizationError::Connection)
+            }
+            Err(err) => {
+                error!("Error occurred while saving organization: {}", err);
+                Err(CreateOrganizationError::Unexpected)
+            }
+        }
+    }
+}
+
+impl PostgresOrganizationRepositoryImpl {
+    /// The same stream names as in EventStore, so subscribers filter on the same prefix.
+    fn get_stream_name(organization_id: OrganizationId) -> String {
+        format!(
+            "Porti.SourceControl/Aggregates/Organization/{}",
+            organization_id
+        )
+    }
+}
+
+fn decode(stored: StoredEvent) -> Result<OrganizationEvent, String> {
+    from_stored_json::<OrganizationEvent>(&stored.event_id, &stored.event_type, stored.data)
+        .map_err(|err| {
+            error!("{}", err);
+            err.to_string()
+        })
+}
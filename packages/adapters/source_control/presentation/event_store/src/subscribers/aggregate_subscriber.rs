@@ -0,0 +1,228 @@
+use std::{sync::Arc, time::SystemTime};
+
+use event_store_util::{
+    from_resolved_event,
+    metadata::{extract_trace_context, metadata_from_recorded_event},
+    parked::NackReason,
+    DecodeError, FromJson,
+};
+use eventstore::{
+    Client, Error, PersistentSubscription, PersistentSubscriptionEvent,
+    PersistentSubscriptionToAllOptions, RecordedEvent, ResolvedEvent,
+    SubscribeToPersistentSubscriptionOptions, SubscriptionFilter,
+};
+use opentelemetry::{
+    metrics::{Counter, Histogram},
+    Context, KeyValue,
+};
+use source_control_postgres_persistence_adapter::projectors::{Projector, ProjectorError};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, span, Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+pub struct AggregateSubscriber<TEvent> {
+    pub client: Arc<Client>,
+    pub projector: Box<dyn Projector<TEvent>>,
+    pub subscription_name: String,
+    pub stream_prefix: &'static str,
+    pub worker_id: u32,
+    pub metrics: SubscriberMetrics,
+}
+
+pub struct SubscriberMetrics {
+    pub event_projection_started: Counter<u64>,
+    pub event_projection_completed: Counter<u64>,
+    pub event_projection_duration_seconds: Histogram<f64>,
+}
+
+impl SubscriberMetrics {
+    pub(crate) fn record_completed(
+        &self,
+        mut attributes: Vec<KeyValue>,
+        start: SystemTime,
+        failure: bool,
+    ) {
+        let duration = start.elapsed();
+        attributes.push(KeyValue::new("eventstore_event.failure", failure));
+        self.event_projection_completed.add(1, &attributes);
+        self.event_projection_duration_seconds.record(
+            duration.map(|t| t.as_secs_f64()).unwrap_or_default(),
+            &attributes,
+        );
+    }
+}
+
+impl<TEvent> AggregateSubscriber<TEvent>
+where
+    TEvent: FromJson + 'static,
+{
+    pub fn new(
+        client: Arc<Client>,
+        projector: Box<dyn Projector<TEvent>>,
+        subscription_name: String,
+        stream_prefix: &'static str,
+        worker_id: u32,
+        metrics: SubscriberMetrics,
+    ) -> Self {
+        Self {
+            client,
+            projector,
+            subscription_name,
+            stream_prefix,
+            worker_id,
+            metrics,
+        }
+    }
+
+    pub async fn prepare_subscription(&self) -> Result<(), Error> {
+        let subscription = self
+            .client
+            .create_persistent_subscription_to_all(
+                self.subscription_name.clone(),
+                &PersistentSubscriptionToAllOptions::default()
+                    .start_from(eventstore::StreamPosition::Start)
+                    .filter(SubscriptionFilter::on_stream_name().add_prefix(self.stream_prefix))
+                    .consumer_strategy_name(eventstore::SystemConsumerStrategy::Pinned),
+            )
+            .await;
+
+        match subscription {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                eventstore::Error::ResourceAlreadyExists => Ok(()),
+                _ => Err(err),
+            },
+        }
+    }
+
+    /// Returns once `shutdown` is cancelled, after the event being handled is acked or nacked.
+    /// Events the client received but didn't hand out yet are redelivered by EventStore.
+    pub async fn subscribe(&self, shutdown: &CancellationToken) -> eventstore::Result<()> {
+        let mut sub = self
+            .client
+            .subscribe_to_persistent_subscription_to_all(
+                self.subscription_name.clone(),
+                &SubscribeToPersistentSubscriptionOptions::default(),
+            )
+            .await?;
+
+        loop {
+            let next = tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                next = sub.next_subscription_event() => next?,
+            };
+
+            // Only events carry a retry count, confirmations are skipped
+            if let PersistentSubscriptionEvent::EventAppeared { event, retry_count } = next {
+                self.handle_next(&mut sub, event, retry_count).await?;
+            }
+        }
+    }
+
+    #[instrument(
+        skip(sub, self),
+        fields(
+            eventstore.event.id = tracing::field::Empty,
+            eventstore.event.type = tracing::field::Empty
+        )
+    )]
+    async fn handle_next(
+        &self,
+        sub: &mut PersistentSubscription,
+        event: ResolvedEvent,
+        retry_count: usize,
+    ) -> eventstore::Result<()> {
+        let original_event = event.get_original_event();
+        if let Some(parent) = parent_context(original_event) {
+            Span::current().set_parent(parent);
+        }
+        let event_id = original_event.id.to_string();
+        let event_type = &original_event.event_type;
+        Span::current().record("eventstore.event.id", event_id);
+        Span::current().record("eventstore.event.type", event_type);
+        let attributes = vec![KeyValue::new("eventstore.event.type", event_type.clone())];
+        self.metrics.event_projection_started.add(1, &attributes);
+        let start = SystemTime::now();
+        info!("Begin processing event");
+
+        let aggregate_event = match from_resolved_event::<TEvent>(&event) {
+            Ok(aggregate_event) => aggregate_event,
+            Err(err) => {
+                self.handle_decode_error(sub, event, retry_count, err).await;
+                self.metrics.record_completed(attributes, start, true);
+                return Ok(());
+            }
+        };
+
+        let res = self.projector.project(aggregate_event).await;
+        if let Err(err) = res {
+            self.handle_error(sub, event, retry_count, err).await;
+            self.metrics.record_completed(attributes, start, true);
+            return Ok(());
+        };
+        sub.ack(event).await?;
+        self.metrics.record_completed(attributes, start, false);
+
+        info!("Acknowledged event");
+        Ok(())
+    }
+
+    /// Retrying can't fix an event that doesn't decode, so it is parked with the reason and the
+    /// subscriber moves on to the next event.
+    #[instrument(skip(sub, self, event), level = "error")]
+    async fn handle_decode_error(
+        &self,
+        sub: &mut PersistentSubscription,
+        event: ResolvedEvent,
+        retry_count: usize,
+        err: DecodeError,
+    ) {
+        error!(
+            event_id = err.event_id,
+            event_type = err.event_type,
+            field = err.field(),
+            "Error occurred while decoding event {}",
+            err
+        );
+        let res = sub
+            .nack(
+                event,
+                eventstore::NakAction::Park,
+                &NackReason::new(err.to_string(), retry_count).to_nack_reason(),
+            )
+            .await;
+        if let Err(err) = res {
+            error!("Error occurred while parking message {:?}", err);
+        }
+    }
+
+    #[instrument(skip(sub, self, event), level = "error")]
+    async fn handle_error(
+        &self,
+        sub: &mut PersistentSubscription,
+        event: ResolvedEvent,
+        retry_count: usize,
+        err: Box<dyn ProjectorError>,
+    ) {
+        let span = span!(Level::ERROR, "projection_failure");
+        let _span = span.enter();
+        error!("Error occurred while running projection {:?}", err);
+        let action = match err.get_retryable() {
+            true => eventstore::NakAction::Retry,
+            false => eventstore::NakAction::Park,
+        };
+        // Parked events are listed with the reason, so it names the error instead of the action
+        let reason = NackReason::new(format!("Projection failed, {}", err), retry_count);
+        let res = sub.nack(event, action, &reason.to_nack_reason()).await;
+        if let Err(err) = res {
+            error!("Error occurred while nacking message {:?}", err);
+        }
+    }
+}
+
+/// The trace context of the request that appended the event, so projecting it continues the
+/// trace of that request.
+pub(crate) fn parent_context(event: &RecordedEvent) -> Option<Context> {
+    let trace_context = metadata_from_recorded_event(event)?.trace_context?;
+    Some(extract_trace_context(trace_context))
+}
@@ -0,0 +1,7 @@
+use source_control_domain::aggregates::branch::BranchEvent;
+
+use super::aggregate_subscriber::AggregateSubscriber;
+
+pub const BRANCH_STREAM_PREFIX: &str = "Porti.SourceControl/Aggregates/Branch/";
+
+pub type BranchSubscriber = AggregateSubscriber<BranchEvent>;
@@ -0,0 +1,226 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use event_store_util::{from_resolved_event, FromJson};
+use eventstore::{
+    Client, Position, ResolvedEvent, StreamPosition, SubscribeToAllOptions, SubscriptionFilter,
+};
+use opentelemetry::KeyValue;
+use source_control_postgres_persistence_adapter::{
+    projectors::checkpoint::{
+        park_with_checkpoint, project_with_checkpoint, read_projection_checkpoint, CheckpointError,
+        CheckpointedProjection, CheckpointedProjector, ParkedProjectionEvent, ProjectionCheckpoint,
+    },
+    provider::PostgresProvider,
+};
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::aggregate_subscriber::{parent_context, SubscriberMetrics};
+
+/// Same as the default of EventStore persistent subscriptions, after which the event is parked.
+const MAX_RETRY_COUNT: u32 = 10;
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Error, Debug)]
+pub enum CatchUpSubscriberError {
+    #[error("Reading events failed, {0}")]
+    EventStore(#[from] eventstore::Error),
+    #[error("Reading or writing the checkpoint failed, {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+}
+
+/// Feeds the events of a catch-up subscription to `$all` to a projector. The position of the last
+/// applied event is committed together with the writes of the projector, so redelivered events
+/// are skipped instead of applied again. Only one worker per projection makes progress, others
+/// wait for the lock on its checkpoint.
+pub struct CatchUpSubscriber<TEvent> {
+    pub client: Arc<Client>,
+    pub postgres: Arc<dyn PostgresProvider>,
+    pub projector: Box<dyn CheckpointedProjector<TEvent>>,
+    pub projection_name: String,
+    pub stream_prefix: &'static str,
+    pub metrics: SubscriberMetrics,
+}
+
+impl<TEvent> CatchUpSubscriber<TEvent>
+where
+    TEvent: FromJson + Send + 'static,
+{
+    pub fn new(
+        client: Arc<Client>,
+        postgres: Arc<dyn PostgresProvider>,
+        projector: Box<dyn CheckpointedProjector<TEvent>>,
+        projection_name: String,
+        stream_prefix: &'static str,
+        metrics: SubscriberMetrics,
+    ) -> Self {
+        Self {
+            client,
+            postgres,
+            projector,
+            projection_name,
+            stream_prefix,
+            metrics,
+        }
+    }
+
+    /// Returns once `shutdown` is cancelled, after the event being applied is committed.
+    pub async fn subscribe(
+        &self,
+        shutdown: &CancellationToken,
+    ) -> Result<(), CatchUpSubscriberError> {
+        let checkpoint =
+            read_projection_checkpoint(&*self.postgres.get_client().await, &self.projection_name)
+                .await?;
+        let start = match checkpoint {
+            Some(checkpoint) => StreamPosition::Position(Position {
+                commit: checkpoint.commit_position,
+                prepare: checkpoint.prepare_position,
+            }),
+            None => StreamPosition::Start,
+        };
+
+        let mut sub = self
+            .client
+            .subscribe_to_all(
+                &SubscribeToAllOptions::default()
+                    .position(start)
+                    .filter(SubscriptionFilter::on_stream_name().add_prefix(self.stream_prefix)),
+            )
+            .await;
+
+        loop {
+            let event = tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                event = sub.next() => event?,
+            };
+            self.handle_next(event).await?;
+        }
+    }
+
+    #[instrument(
+        skip(self, event),
+        fields(
+            eventstore.event.id = tracing::field::Empty,
+            eventstore.event.type = tracing::field::Empty
+        )
+    )]
+    async fn handle_next(&self, event: ResolvedEvent) -> Result<(), CatchUpSubscriberError> {
+        let original_event = event.get_original_event();
+        if let Some(parent) = parent_context(original_event) {
+            Span::current().set_parent(parent);
+        }
+        let event_type = &original_event.event_type;
+        Span::current().record("eventstore.event.id", original_event.id.to_string());
+        Span::current().record("eventstore.event.type", event_type);
+        let attributes = vec![KeyValue::new("eventstore.event.type", event_type.clone())];
+        self.metrics.event_projection_started.add(1, &attributes);
+        let start = SystemTime::now();
+        info!("Begin processing event");
+
+        let position = ProjectionCheckpoint {
+            commit_position: original_event.position.commit,
+            prepare_position: original_event.position.prepare,
+        };
+
+        let mut retry_count = 0;
+        loop {
+            // Projecting takes the event, so every attempt decodes it again
+            let aggregate_event = match from_resolved_event::<TEvent>(&event) {
+                Ok(aggregate_event) => aggregate_event,
+                Err(err) => {
+                    error!(
+                        event_id = err.event_id,
+                        event_type = err.event_type,
+                        field = err.field(),
+                        "Error occurred while decoding event, parking it {}",
+                        err
+                    );
+                    self.park(&event, position, err.to_string(), retry_count)
+                        .await?;
+                    self.metrics.record_completed(attributes, start, true);
+                    return Ok(());
+                }
+            };
+
+            let res = project_with_checkpoint(
+                &mut *self.postgres.get_client().await,
+                &*self.projector,
+                &self.projection_name,
+                position,
+                aggregate_event,
+            )
+            .await;
+
+            match res {
+                Ok(CheckpointedProjection::Applied) => break,
+                Ok(CheckpointedProjection::AlreadyApplied) => {
+                    info!("Event was already applied");
+                    break;
+                }
+                Err(CheckpointError::Projector(err))
+                    if err.get_retryable() && retry_count < MAX_RETRY_COUNT =>
+                {
+                    error!(
+                        "Error occurred while running projection, retrying {:?}",
+                        err
+                    );
+                    retry_count += 1;
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+                Err(CheckpointError::Projector(err)) => {
+                    error!(
+                        "Error occurred while running projection, parking it {:?}",
+                        err
+                    );
+                    // Parked events are listed with the reason, so it names the error
+                    let reason = format!("Projection failed, {}", err);
+                    self.park(&event, position, reason, retry_count).await?;
+                    self.metrics.record_completed(attributes, start, true);
+                    return Ok(());
+                }
+                Err(CheckpointError::Postgres(err)) => {
+                    self.metrics.record_completed(attributes, start, true);
+                    return Err(err.into());
+                }
+            }
+        }
+
+        self.metrics.record_completed(attributes, start, false);
+        info!("Handled event");
+        Ok(())
+    }
+
+    /// Parks an event the projector can't apply, and moves the checkpoint past it.
+    async fn park(
+        &self,
+        event: &ResolvedEvent,
+        position: ProjectionCheckpoint,
+        reason: String,
+        retry_count: u32,
+    ) -> Result<(), CatchUpSubscriberError> {
+        let original_event = event.get_original_event();
+        let parked = ParkedProjectionEvent {
+            event_id: original_event.id.to_string(),
+            stream_id: original_event.stream_id.clone(),
+            event_type: original_event.event_type.clone(),
+            data: original_event.data.to_vec(),
+            reason,
+            retry_count,
+        };
+        park_with_checkpoint(
+            &mut *self.postgres.get_client().await,
+            &self.projection_name,
+            position,
+            &parked,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+use source_control_domain::aggregates::commit_graph::CommitGraphEvent;
+
+use super::aggregate_subscriber::AggregateSubscriber;
+
+pub const COMMIT_GRAPH_STREAM_PREFIX: &str = "Porti.SourceControl/Aggregates/CommitGraph/";
+
+pub type CommitGraphSubscriber = AggregateSubscriber<CommitGraphEvent>;
@@ -0,0 +1,7 @@
+use source_control_domain::aggregates::developer::DeveloperEvent;
+
+use super::aggregate_subscriber::AggregateSubscriber;
+
+pub const DEVELOPER_STREAM_PREFIX: &str = "Porti.SourceControl/Aggregates/Developer/";
+
+pub type DeveloperSubscriber = AggregateSubscriber<DeveloperEvent>;
@@ -1 +1,9 @@
+pub mod aggregate_subscriber;
+pub mod branch_subscriber;
+pub mod catch_up_subscriber;
+pub mod commit_graph_subscriber;
+pub mod developer_subscriber;
 pub mod organization_subscriber;
+pub mod postgres_subscriber;
+pub mod pull_request_subscriber;
+pub mod repository_subscriber;
@@ -0,0 +1,653 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use event_store_util::{
+    from_stored_json,
+    metadata::{extract_trace_context, metadata_from_json},
+    parked::NackReason,
+    FromJson,
+};
+use opentelemetry::{Context, KeyValue};
+use source_control_postgres_event_store_persistence_adapter::events::{
+    park_event, read_all_after, read_checkpoint, read_replay_requested, remove_replayed_event,
+    try_lock_subscription, unlock_subscription, write_checkpoint, StoredEvent,
+};
+use source_control_postgres_persistence_adapter::{
+    projectors::Projector, provider::PostgresProvider,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::aggregate_subscriber::SubscriberMetrics;
+
+/// Events read from the `events` table at once.
+const BATCH_SIZE: i64 = 100;
+/// Same as the default of EventStore persistent subscriptions, after which the event is parked.
+const MAX_RETRY_COUNT: u32 = 10;
+
+/// Feeds the events stored in postgres to a projector. Polls the `events` table and keeps its
+/// position in `event_subscription_checkpoints`, so only one worker per subscription can run. It
+/// holds an advisory lock on the subscription while it runs, a worker of another process waits
+/// for it.
+pub struct PostgresAggregateSubscriber<TEvent> {
+    pub client: Arc<dyn PostgresProvider>,
+    pub projector: Box<dyn Projector<TEvent>>,
+    pub subscription_name: String,
+    pub stream_prefix: &'static str,
+    pub poll_interval: Duration,
+    pub metrics: SubscriberMetrics,
+}
+
+impl<TEvent> PostgresAggregateSubscriber<TEvent>
+where
+    TEvent: FromJson + 'static,
+{
+    pub fn new(
+        client: Arc<dyn PostgresProvider>,
+        projector: Box<dyn Projector<TEvent>>,
+        subscription_name: String,
+        stream_prefix: &'static str,
+        poll_interval: Duration,
+        metrics: SubscriberMetrics,
+    ) -> Self {
+        Self {
+            client,
+            projector,
+            subscription_name,
+            stream_prefix,
+            poll_interval,
+            metrics,
+        }
+    }
+
+    /// Returns once `shutdown` is cancelled, after the event being applied is checkpointed.
+    pub async fn subscribe(
+        &self,
+        shutdown: &CancellationToken,
+    ) -> Result<(), tokio_postgres::Error> {
+        // The lock belongs to this connection, it is held until the subscription stops
+        let lock = self.client.get_client().await;
+        if !self.wait_for_lock(&lock, shutdown).await? {
+            return Ok(());
+        }
+
+        let result = self.handle_events(shutdown).await;
+        unlock_subscription(&lock, &self.subscription_name).await?;
+        result
+    }
+
+    /// Returns false when `shutdown` is cancelled before the lock was taken.
+    async fn wait_for_lock(
+        &self,
+        client: &tokio_postgres::Client,
+        shutdown: &CancellationToken,
+    ) -> Result<bool, tokio_postgres::Error> {
+        let mut waiting = false;
+        while !try_lock_subscription(client, &self.subscription_name).await? {
+            if !waiting {
+                info!(
+                    subscription_name = self.subscription_name,
+                    "Another process runs the subscription, waiting for it to stop"
+                );
+                waiting = true;
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => return Ok(false),
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn handle_events(
+        &self,
+        shutdown: &CancellationToken,
+    ) -> Result<(), tokio_postgres::Error> {
+        let mut position =
+            read_checkpoint(&*self.client.get_client().await, &self.subscription_name).await?;
+
+        loop {
+            if !self.replay_parked_events(shutdown).await? {
+                return Ok(());
+            }
+
+            let events = read_all_after(
+                &*self.client.get_client().await,
+                self.stream_prefix,
+                position,
+                BATCH_SIZE,
+            )
+            .await?;
+
+            if events.is_empty() {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return Ok(()),
+                    _ = tokio::time::sleep(self.poll_interval) => continue,
+                }
+            }
+
+            for event in events {
+                if shutdown.is_cancelled() {
+                    return Ok(());
+                }
+
+                self.handle_next(&event).await?;
+                position = event.position();
+                write_checkpoint(
+                    &*self.client.get_client().await,
+                    &self.subscription_name,
+                    position,
+                )
+                .await?;
+            }
+        }
+    }
+
+    /// Handles the parked events marked for replay, the checkpoint already moved past them.
+    /// Returns false when `shutdown` was cancelled before they were all handled.
+    async fn replay_parked_events(
+        &self,
+        shutdown: &CancellationToken,
+    ) -> Result<bool, tokio_postgres::Error> {
+        loop {
+            let events = read_replay_requested(
+                &*self.client.get_client().await,
+                &self.subscription_name,
+                BATCH_SIZE,
+            )
+            .await?;
+            if events.is_empty() {
+                return Ok(true);
+            }
+
+            info!(count = events.len(), "Replaying parked events");
+            for event in events {
+                if shutdown.is_cancelled() {
+                    return Ok(false);
+                }
+
+                self.handle_next(&event).await?;
+                remove_replayed_event(
+                    &*self.client.get_client().await,
+                    &self.subscription_name,
+                    event.global_position,
+                )
+                .await?;
+            }
+        }
+    }
+
+    #[instrument(
+        skip(self, event),
+        fields(
+            eventstore.event.id = tracing::field::Empty,
+            eventstore.event.type = tracing::field::Empty
+        )
+    )]
+    async fn handle_next(&self, event: &StoredEvent) -> Result<(), tokio_postgres::Error> {
+        if let Some(parent) = parent_context(event) {
+            Span::current().set_parent(parent);
+        }
+        Span::current().record("eventstore.event.id", &event.event_id);
+        Span::current().record("eventstore.event.type", &event.event_type);
+        let attributes = vec![KeyValue::new(
+            "eventstore.event.type",
+            event.event_type.clone(),
+        )];
+        self.metrics.event_projection_started.add(1, &attributes);
+        let start = SystemTime::now();
+        info!("Begin processing event");
+
+        let mut retry_count = 0;
+        loop {
+            // Projecting takes the event, so every attempt decodes it again
+            let aggregate_event = match from_stored_json::<TEvent>(
+                &event.event_id,
+                &event.event_type,
+                event.data.clone(),
+            ) {
+                Ok(aggregate_event) => aggregate_event,
+                Err(err) => {
+                    error!(
+                        event_id = err.event_id,
+                        event_type = err.event_type,
+                        field = err.field(),
+                        "Error occurred while decoding event {}",
+                        err
+                    );
+                    let reason = NackReason::new(err.to_string(), retry_count as usize);
+                    self.park(event, &reason).await?;
+                    self.metrics.record_completed(attributes, start, true);
+                    return Ok(());
+                }
+            };
+
+            match self.projector.project(aggregate_event).await {
+                Ok(_) => break,
+                Err(err) if err.get_retryable() && retry_count < MAX_RETRY_COUNT => {
+                    error!(
+                        "Error occurred while running projection, retrying {:?}",
+                        err
+                    );
+                    retry_count += 1;
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+                Err(err) => {
+                    error!("Error occurred while running projection {:?}", err);
+                    // Parked events are listed with the reason, so it names the error
+                    let reason = NackReason::new(
+                        format!("Projection failed, {}", err),
+                        retry_count as usize,
+                    );
+                    self.park(event, &reason).await?;
+                    self.metrics.record_completed(attributes, start, true);
+                    return Ok(());
+                }
+            }
+        }
+
+        self.metrics.record_completed(attributes, start, false);
+        info!("Handled event");
+        Ok(())
+    }
+
+    async fn park(
+        &self,
+        event: &StoredEvent,
+        reason: &NackReason,
+    ) -> Result<(), tokio_postgres::Error> {
+        park_event(
+            &*self.client.get_client().await,
+            &self.subscription_name,
+            event.global_position,
+            &reason.to_nack_reason(),
+        )
+        .await
+    }
+}
+
+/// The trace context of the request that appended the event, so projecting it continues the
+/// trace of that request.
+fn parent_context(event: &StoredEvent) -> Option<Context> {
+    let trace_context = metadata_from_json(&event.event_id, &event.metadata)?.trace_context?;
+    Some(extract_trace_context(trace_context))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fmt::{self, Display, Formatter},
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use bb8_postgres::{bb8::PooledConnection, PostgresConnectionManager};
+    use chrono::Utc;
+    use event_codec::{EventCodec, EventField};
+    use event_store_util::parked::NackReason;
+    use futures_util::future::BoxFuture;
+    use opentelemetry::{global, trace::TracerProvider, KeyValue, Value as AttributeValue};
+    use opentelemetry_sdk::{
+        error::OTelSdkResult,
+        propagation::TraceContextPropagator,
+        trace::{SdkTracerProvider, SpanData, SpanExporter},
+    };
+    use source_control_domain::{
+        aggregates::{
+            metadata::{CommandMetadata, EventMetadata, TraceContext},
+            organization::OrganizationEvent,
+        },
+        entities::organization::OrganizationId,
+    };
+    use source_control_postgres_event_store_persistence_adapter::events::{
+        append_to_stream, is_parked, read_checkpoint, read_parked_events, request_replay,
+        try_lock_subscription, unlock_subscription, ExpectedRevision, NewEvent, StoredEvent,
+    };
+    use source_control_postgres_persistence_adapter::{
+        projectors::{Projector, ProjectorError},
+        provider::PostgresProvider,
+        test_support::TestDatabase,
+    };
+    use tokio::sync::Notify;
+    use tokio_postgres::NoTls;
+    use tokio_util::sync::CancellationToken;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::{PostgresAggregateSubscriber, SubscriberMetrics};
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordingExporter {
+        spans: Arc<Mutex<Vec<SpanData>>>,
+    }
+
+    impl SpanExporter for RecordingExporter {
+        fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, OTelSdkResult> {
+            self.spans.lock().unwrap().extend(batch);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    struct AcceptingProjector;
+
+    #[async_trait]
+    impl Projector<OrganizationEvent> for AcceptingProjector {
+        async fn project(&self, _event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
+            Ok(())
+        }
+    }
+
+    /// Projecting succeeds, so nothing is parked and postgres is never used.
+    struct UnusedPostgres;
+
+    #[async_trait]
+    impl PostgresProvider for UnusedPostgres {
+        async fn get_client(&self) -> PooledConnection<'_, PostgresConnectionManager<NoTls>> {
+            unreachable!("Only parking uses postgres")
+        }
+    }
+
+    /// Signals when it starts projecting an event, and only finishes once it is released.
+    #[derive(Default)]
+    struct BlockingProjector {
+        started: Arc<Notify>,
+        released: Arc<Notify>,
+        projected: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Projector<OrganizationEvent> for BlockingProjector {
+        async fn project(&self, _event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
+            self.started.notify_one();
+            self.released.notified().await;
+            self.projected.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingProjector {
+        projected: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Projector<OrganizationEvent> for CountingProjector {
+        async fn project(&self, _event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
+            self.projected.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct ProjectionFailed;
+
+    impl Display for ProjectionFailed {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "Projection failed")
+        }
+    }
+
+    impl ProjectorError for ProjectionFailed {
+        fn get_retryable(&self) -> bool {
+            false
+        }
+    }
+
+    /// Fails the first event it projects, and projects every event after it.
+    #[derive(Default)]
+    struct FailingOnceProjector {
+        attempts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Projector<OrganizationEvent> for FailingOnceProjector {
+        async fn project(&self, _event: OrganizationEvent) -> Result<(), Box<dyn ProjectorError>> {
+            match self.attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(Box::new(ProjectionFailed)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn metrics() -> SubscriberMetrics {
+        let meter = global::meter("test");
+        SubscriberMetrics {
+            event_projection_started: meter.u64_counter("started").build(),
+            event_projection_completed: meter.u64_counter("completed").build(),
+            event_projection_duration_seconds: meter.f64_histogram("duration").build(),
+        }
+    }
+
+    async fn append_organization_events(database: &TestDatabase, count: u64) {
+        let events: Vec<NewEvent> = (1..=count)
+            .map(|id| {
+                let event = OrganizationEvent::ArchiveOrganization {
+                    organization_id: OrganizationId(id),
+                };
+                NewEvent {
+                    event_type: event.get_versioned_event_type(),
+                    data: event.encode(),
+                    metadata: serde_json::Value::Null,
+                }
+            })
+            .collect();
+        append_to_stream(
+            &mut database.connect().await,
+            "Porti.SourceControl/Aggregates/Organization/1",
+            ExpectedRevision::NoStream,
+            &events,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_checkpoint_the_event_being_handled_before_shutting_down() {
+        let database = TestDatabase::migrated("postgres_subscriber_test").await;
+        append_organization_events(&database, 2).await;
+
+        let projector = BlockingProjector::default();
+        let started = projector.started.clone();
+        let released = projector.released.clone();
+        let projected = projector.projected.clone();
+        let postgres = database.postgres().await;
+        let subscriber = PostgresAggregateSubscriber::<OrganizationEvent>::new(
+            postgres.clone(),
+            Box::new(projector),
+            "organizations".to_string(),
+            "Porti.SourceControl/Aggregates/Organization/",
+            Duration::from_millis(1),
+            metrics(),
+        );
+        let shutdown = CancellationToken::new();
+
+        let (result, _) = tokio::join!(subscriber.subscribe(&shutdown), async {
+            started.notified().await;
+            shutdown.cancel();
+            released.notify_one();
+        });
+
+        result.unwrap();
+        let checkpoint = read_checkpoint(&*postgres.get_client().await, "organizations")
+            .await
+            .unwrap();
+        assert_eq!(projected.load(Ordering::SeqCst), 1);
+        assert_eq!(checkpoint.global_position, 1);
+
+        drop(postgres);
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_wait_while_another_process_runs_the_subscription() {
+        let database = TestDatabase::migrated("postgres_subscriber_lock_test").await;
+        append_organization_events(&database, 2).await;
+        let other_process = database.connect().await;
+        assert!(try_lock_subscription(&other_process, "organizations")
+            .await
+            .unwrap());
+
+        let projector = CountingProjector::default();
+        let projected = projector.projected.clone();
+        let postgres = database.postgres().await;
+        let subscriber = PostgresAggregateSubscriber::<OrganizationEvent>::new(
+            postgres.clone(),
+            Box::new(projector),
+            "organizations".to_string(),
+            "Porti.SourceControl/Aggregates/Organization/",
+            Duration::from_millis(1),
+            metrics(),
+        );
+
+        let shutdown = CancellationToken::new();
+        let (result, _) = tokio::join!(subscriber.subscribe(&shutdown), async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            shutdown.cancel();
+        });
+        result.unwrap();
+        assert_eq!(projected.load(Ordering::SeqCst), 0);
+
+        unlock_subscription(&other_process, "organizations")
+            .await
+            .unwrap();
+        let shutdown = CancellationToken::new();
+        let (result, _) = tokio::join!(subscriber.subscribe(&shutdown), async {
+            while projected.load(Ordering::SeqCst) < 2 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            shutdown.cancel();
+        });
+        result.unwrap();
+        // Stopping released the lock
+        assert!(try_lock_subscription(&other_process, "organizations")
+            .await
+            .unwrap());
+
+        drop(other_process);
+        drop(subscriber);
+        drop(postgres);
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server, set SOURCE_CONTROL_TEST_POSTGRES"]
+    async fn should_project_the_parked_events_it_was_asked_to_replay() {
+        let database = TestDatabase::migrated("postgres_subscriber_replay_test").await;
+        append_organization_events(&database, 1).await;
+        let projector = FailingOnceProjector::default();
+        let attempts = projector.attempts.clone();
+        let postgres = database.postgres().await;
+        let subscriber = PostgresAggregateSubscriber::<OrganizationEvent>::new(
+            postgres.clone(),
+            Box::new(projector),
+            "organizations".to_string(),
+            "Porti.SourceControl/Aggregates/Organization/",
+            Duration::from_millis(1),
+            metrics(),
+        );
+        let shutdown = CancellationToken::new();
+        let client = database.connect().await;
+
+        let (result, _) = tokio::join!(subscriber.subscribe(&shutdown), async {
+            while !is_parked(&client, "organizations", 1).await.unwrap() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            let parked = read_parked_events(&client, "organizations", 10)
+                .await
+                .unwrap();
+            assert_eq!(parked.len(), 1);
+            assert_eq!(
+                NackReason::from_nack_reason(&parked[0].reason),
+                NackReason::new("Projection failed, Projection failed", 0)
+            );
+            request_replay(&client, "organizations", None)
+                .await
+                .unwrap();
+            while is_parked(&client, "organizations", 1).await.unwrap() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            shutdown.cancel();
+        });
+
+        result.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        drop(client);
+        drop(subscriber);
+        drop(postgres);
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    async fn should_continue_the_trace_of_the_request_that_appended_the_event() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let exporter = RecordingExporter::default();
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let postgres_subscriber = PostgresAggregateSubscriber::<OrganizationEvent>::new(
+            Arc::new(UnusedPostgres),
+            Box::new(AcceptingProjector),
+            "test".to_string(),
+            "",
+            Duration::from_millis(1),
+            metrics(),
+        );
+        let organization_event = OrganizationEvent::ArchiveOrganization {
+            organization_id: OrganizationId(1),
+        };
+        let event = StoredEvent {
+            global_position: 1,
+            transaction_id: 1,
+            event_id: "5f2b6c3e-8d59-4a64-9d43-3c1f0b9a7e21".to_string(),
+            stream_id: "organization-1".to_string(),
+            revision: 0,
+            event_type: organization_event.get_versioned_event_type(),
+            data: organization_event.encode(),
+            metadata: EventMetadata::new(
+                &CommandMetadata::default(),
+                Utc::now(),
+                Some(TraceContext {
+                    traceparent: "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+                        .to_string(),
+                    tracestate: None,
+                }),
+            )
+            .encode(),
+        };
+
+        postgres_subscriber.handle_next(&event).await.unwrap();
+
+        let spans = exporter.spans.lock().unwrap();
+        let span = spans
+            .iter()
+            .find(|span| span.name == "handle_next")
+            .expect("The projection span is exported");
+        assert_eq!(
+            span.span_context.trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+        assert_eq!(span.parent_span_id.to_string(), "b7ad6b7169203331");
+        assert!(span.attributes.contains(&KeyValue::new(
+            "eventstore.event.id",
+            AttributeValue::from(event.event_id.clone())
+        )));
+        assert!(span.attributes.contains(&KeyValue::new(
+            "eventstore.event.type",
+            AttributeValue::from(event.event_type.clone())
+        )));
+    }
+}
@@ -0,0 +1,7 @@
+use source_control_domain::aggregates::pull_request::PullRequestEvent;
+
+use super::aggregate_subscriber::AggregateSubscriber;
+
+pub const PULL_REQUEST_STREAM_PREFIX: &str = "Porti.SourceControl/Aggregates/PullRequest/";
+
+pub type PullRequestSubscriber = AggregateSubscriber<PullRequestEvent>;
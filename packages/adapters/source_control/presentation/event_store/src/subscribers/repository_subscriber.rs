@@ -0,0 +1,7 @@
+use source_control_domain::aggregates::repository::RepositoryEvent;
+
+use super::aggregate_subscriber::AggregateSubscriber;
+
+pub const REPOSITORY_STREAM_PREFIX: &str = "Porti.SourceControl/Aggregates/Repository/";
+
+pub type RepositorySubscriber = AggregateSubscriber<RepositoryEvent>;
@@ -0,0 +1,27 @@
+use actix_web::{get, web, HttpResponse};
+use shaku::HasComponent;
+use source_control_application::{
+    commands::rebuild_organization_projection::OrganizationProjectionRebuild,
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::models::projection_rebuild::ProjectionRebuildDto;
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Progress of the last rebuild since the server started", body=ProjectionRebuildDto)
+    )
+)]
+#[get(
+    "/admin/projections/organizations/rebuild",
+    name = "organization_projection_rebuild"
+)]
+#[instrument(skip(module))]
+pub async fn get_organization_projection_rebuild(
+    module: web::Data<ApplicationModule>,
+) -> HttpResponse {
+    let rebuild: &dyn OrganizationProjectionRebuild = module.resolve_ref();
+
+    HttpResponse::Ok().json(ProjectionRebuildDto::from(&rebuild.progress()))
+}
@@ -0,0 +1,20 @@
+use actix_web::{get, web, HttpResponse};
+use shaku::HasComponent;
+use source_control_application::{module::ApplicationModule, projection_host::ProjectionHost};
+use tracing::instrument;
+
+use crate::models::projection_worker::ProjectionWorkerDto;
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Projection workers of this instance and their state", body=Vec<ProjectionWorkerDto>)
+    )
+)]
+#[get("/admin/projections/workers", name = "projection_workers")]
+#[instrument(skip(module))]
+pub async fn get_projection_workers(module: web::Data<ApplicationModule>) -> HttpResponse {
+    let host: &dyn ProjectionHost = module.resolve_ref();
+
+    let res: Vec<ProjectionWorkerDto> = host.workers().iter().map(|w| w.into()).collect();
+    HttpResponse::Ok().json(res)
+}
@@ -0,0 +1,3 @@
+pub mod get_rebuild;
+pub mod get_workers;
+pub mod rebuild;
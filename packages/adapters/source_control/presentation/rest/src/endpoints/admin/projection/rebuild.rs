@@ -0,0 +1,88 @@
+use actix_web::{http::header, post, web, HttpRequest, HttpResponse};
+use shaku::{HasComponent, HasProvider};
+use source_control_application::{
+    commands::rebuild_organization_projection::{
+        OrganizationProjectionRebuild, RebuildOrganizationProjectionCommand,
+        RebuildOrganizationProjectionCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::{instrument, warn};
+
+use crate::errors::Conflict;
+
+/// Starts the rebuild and returns right away, its progress is at the `Location`.
+#[utoipa::path(
+    responses(
+        (status = 202, description = "Rebuild of the organization projection started", headers(("Location" = String, description = "Progress of the rebuild"))),
+        (status = 409, description = "A rebuild is already running, or the projection doesn't run as catch-up projection", body=Conflict)
+    )
+)]
+#[post(
+    "/admin/projections/organizations/rebuild",
+    name = "rebuild_organization_projection"
+)]
+#[instrument(skip(module, req))]
+pub async fn rebuild_organization_projection(
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let rebuild: &dyn OrganizationProjectionRebuild = module.resolve_ref();
+    if rebuild.live_projection_name().is_none() {
+        return Conflict::new(
+            "The organization projection can only be rebuilt while it runs as catch-up projection",
+        )
+        .into();
+    }
+    if rebuild.progress().is_running() {
+        return Conflict::new("A rebuild of the organization projection is already running").into();
+    }
+
+    let command_handler: Box<dyn RebuildOrganizationProjectionCommandHandler> =
+        module.provide().unwrap();
+    actix_web::rt::spawn(async move {
+        if let Err(err) = command_handler
+            .handle(RebuildOrganizationProjectionCommand {})
+            .await
+        {
+            warn!("Rebuild of the organization projection stopped {}", err);
+        }
+    });
+
+    HttpResponse::Accepted()
+        .insert_header((
+            header::LOCATION,
+            req.url_for_static("organization_projection_rebuild")
+                .unwrap()
+                .to_string(),
+        ))
+        .finish()
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{http::StatusCode, test, web::Data, App};
+    use source_control_application::module::get_in_memory_module;
+
+    use super::rebuild_organization_projection;
+
+    #[actix_web::test]
+    async fn should_refuse_to_rebuild_without_a_catch_up_projection() {
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(get_in_memory_module()))
+                .service(rebuild_organization_projection),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/admin/projections/organizations/rebuild")
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
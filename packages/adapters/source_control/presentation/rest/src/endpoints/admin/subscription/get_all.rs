@@ -0,0 +1,39 @@
+use actix_web::{get, web, HttpResponse};
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_persistent_subscriptions::{
+        GetPersistentSubscriptionsQuery, GetPersistentSubscriptionsQueryError,
+        GetPersistentSubscriptionsQueryHandler,
+    },
+};
+use tracing::instrument;
+
+use crate::{errors::InternalServerError, models::parked_event::PersistentSubscriptionDto};
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Persistent subscriptions with the number of events they parked", body=Vec<PersistentSubscriptionDto>),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get("/admin/subscriptions", name = "persistent_subscriptions")]
+#[instrument(skip(module))]
+pub async fn get_persistent_subscriptions(module: web::Data<ApplicationModule>) -> HttpResponse {
+    let query_handler: Box<dyn GetPersistentSubscriptionsQueryHandler> = module.provide().unwrap();
+
+    match query_handler
+        .handle(GetPersistentSubscriptionsQuery {})
+        .await
+    {
+        Ok(subscriptions) => {
+            let res: Vec<PersistentSubscriptionDto> =
+                subscriptions.iter().map(|s| s.into()).collect();
+            HttpResponse::Ok().json(res)
+        }
+        Err(GetPersistentSubscriptionsQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the persistent subscriptions",
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,70 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_parked_events::{
+        GetParkedEventsQuery, GetParkedEventsQueryError, GetParkedEventsQueryHandler,
+    },
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::parked_event::ParkedEventDto,
+};
+
+const DEFAULT_LIMIT: usize = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct GetParkedArguments {
+    subscription_name: String,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetParkedQueryArguments {
+    /// At most 100 events are returned by default
+    limit: Option<usize>,
+}
+
+#[utoipa::path(
+    params(
+        GetParkedQueryArguments
+    ),
+    responses(
+        (status = 200, description = "Events the subscription parked, oldest first", body=Vec<ParkedEventDto>),
+        (status = 404, description = "The persistent subscription couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/admin/subscriptions/{subscription_name}/parked",
+    name = "parked_events"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_parked_events(
+    arguments: web::Path<GetParkedArguments>,
+    query_arguments: web::Query<GetParkedQueryArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query_handler: Box<dyn GetParkedEventsQueryHandler> = module.provide().unwrap();
+
+    let query = GetParkedEventsQuery {
+        subscription_name: arguments.subscription_name.clone(),
+        limit: query_arguments.limit.unwrap_or(DEFAULT_LIMIT),
+    };
+
+    match query_handler.handle(query).await {
+        Ok(parked_events) => {
+            let res: Vec<ParkedEventDto> = parked_events.iter().map(|e| e.into()).collect();
+            HttpResponse::Ok().json(res)
+        }
+        Err(GetParkedEventsQueryError::NotFound { .. }) => NotFound::from_request(&req).into(),
+        Err(GetParkedEventsQueryError::Connection) => {
+            InternalServerError::new("Something went wrong while retreiving the parked events")
+                .into()
+        }
+    }
+}
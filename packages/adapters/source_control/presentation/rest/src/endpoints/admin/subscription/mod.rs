@@ -0,0 +1,3 @@
+pub mod get_all;
+pub mod get_parked;
+pub mod replay;
@@ -0,0 +1,67 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::replay_parked_events::{
+        ReplayParkedEventsCommand, ReplayParkedEventsCommandError, ReplayParkedEventsCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::errors::{InternalServerError, NotFound};
+
+#[derive(Deserialize, Debug)]
+pub struct ReplayParkedArguments {
+    subscription_name: String,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct ReplayParkedQueryArguments {
+    /// Replays the parked events up to and including the one with this `parked_revision`, every
+    /// parked event by default. Parked events are replayed from the oldest one, so a single
+    /// event can't be replayed on its own.
+    up_to: Option<u64>,
+}
+
+/// The subscription receives the events again, events that still fail are parked again.
+#[utoipa::path(
+    params(
+        ReplayParkedQueryArguments
+    ),
+    responses(
+        (status = 202, description = "The parked events up to the requested one are being replayed"),
+        (status = 404, description = "The subscription or the parked event to replay up to couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/admin/subscriptions/{subscription_name}/parked/replay",
+    name = "replay_parked_events"
+)]
+#[instrument(skip(module, req))]
+pub async fn replay_parked_events(
+    arguments: web::Path<ReplayParkedArguments>,
+    query_arguments: web::Query<ReplayParkedQueryArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = ReplayParkedEventsCommand {
+        subscription_name: arguments.subscription_name.clone(),
+        up_to: query_arguments.up_to,
+    };
+    let command_handler: Box<dyn ReplayParkedEventsCommandHandler> = module.provide().unwrap();
+
+    match command_handler.handle(command).await {
+        Ok(_) => HttpResponse::Accepted().finish(),
+        Err(
+            ReplayParkedEventsCommandError::NotFound
+            | ReplayParkedEventsCommandError::ParkedEventNotFound,
+        ) => NotFound::from_request(&req).into(),
+        Err(ReplayParkedEventsCommandError::Connection) => {
+            InternalServerError::new("Something went wrong while replaying the parked events")
+                .into()
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use actix_web::{post, web, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::create_developer::{
+        CreateDeveloperCommand, CreateDeveloperCommandError, CreateDeveloperCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError},
+    models::developer::DeveloperDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct CreateArguments {
+    username: String,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Developer created successfully", body=DeveloperDto),
+        (status = 409, description = "A developer with the same username already exists", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/developers", name = "developers")]
+#[instrument(skip(module))]
+pub async fn create_developer(
+    arguments: web::Json<CreateArguments>,
+    module: web::Data<ApplicationModule>,
+) -> HttpResponse {
+    let command = CreateDeveloperCommand {
+        username: arguments.username.clone(),
+    };
+
+    let command_handler: Box<dyn CreateDeveloperCommandHandler> = module.provide().unwrap();
+
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(CreateDeveloperCommandError::AlreadyExists) => {
+            Conflict::new("A developer with this username already exists").into()
+        }
+        Err(CreateDeveloperCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(CreateDeveloperCommandError::Unexpected) => {
+            InternalServerError::new("Something went wrong while creating the developer").into()
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_developer::{GetDeveloperQuery, GetDeveloperQueryError, GetDeveloperQueryHandler},
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::developer::DeveloperDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetPath {
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Developer found successfully", body=DeveloperDto),
+        (status = 404, description = "The developer couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get("/developers/{developer_id}", name = "developer")]
+#[instrument(skip(module, req))]
+pub async fn get_developer(
+    path: web::Path<GetPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query = GetDeveloperQuery {
+        developer_id: path.developer_id,
+    };
+
+    let query_handler: Box<dyn GetDeveloperQueryHandler> = module.provide().unwrap();
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetDeveloperQueryError::NotFound) => NotFound::from_request(&req).into(),
+        Err(GetDeveloperQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the developer".to_string(),
+        )
+        .into(),
+        Err(GetDeveloperQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the developer".to_string(),
+        )
+        .into(),
+    }
+}
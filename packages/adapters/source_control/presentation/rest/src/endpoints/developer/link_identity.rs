@@ -0,0 +1,76 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::link_developer_identity::{
+        LinkDeveloperIdentityCommand, LinkDeveloperIdentityCommandError,
+        LinkDeveloperIdentityCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::developer::{DeveloperDto, DeveloperIdentityDto},
+};
+
+#[derive(Deserialize, Debug)]
+pub struct LinkPath {
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    request_body = DeveloperIdentityDto,
+    responses(
+        (status = 201, description = "Identity linked successfully", body=DeveloperDto),
+        (status = 404, description = "The developer couldn't be found", body=NotFound),
+        (status = 409, description = "The identity is already linked, or the developer is merged into another developer", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/developers/{developer_id}/identities", name = "developer_identities")]
+#[instrument(skip(module, req))]
+pub async fn link_developer_identity(
+    arguments: web::Json<DeveloperIdentityDto>,
+    path: web::Path<LinkPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = LinkDeveloperIdentityCommand {
+        developer_id: path.developer_id,
+        identity: (&arguments.into_inner()).into(),
+    };
+
+    let command_handler: Box<dyn LinkDeveloperIdentityCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(LinkDeveloperIdentityCommandError::AlreadyLinked) => {
+            Conflict::new("The developer already has this identity").into()
+        }
+        Err(LinkDeveloperIdentityCommandError::IdentityTaken { developer_id }) => Conflict::new(
+            format!("The identity belongs to developer {}", developer_id),
+        )
+        .into(),
+        Err(LinkDeveloperIdentityCommandError::Merged { into }) => Conflict::new(format!(
+            "The developer is merged into developer {}, link the identity there instead",
+            into
+        ))
+        .into(),
+        Err(LinkDeveloperIdentityCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while linking the identity").into()
+        }
+        Err(LinkDeveloperIdentityCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(LinkDeveloperIdentityCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(LinkDeveloperIdentityCommandError::NotFound) => NotFound::from_request(&req).into(),
+    }
+}
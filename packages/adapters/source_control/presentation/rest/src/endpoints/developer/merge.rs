@@ -0,0 +1,87 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::merge_developers::{
+        MergeDevelopersCommand, MergeDevelopersCommandError, MergeDevelopersCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    models::developer::DeveloperDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct MergeArguments {
+    /// The developer the duplicate is merged into
+    into: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MergePath {
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Developer merged successfully", body=DeveloperDto),
+        (status = 400, description = "The developer can not be merged into itself", body=BadRequest),
+        (status = 404, description = "One of the developers couldn't be found", body=NotFound),
+        (status = 409, description = "One of the developers is already merged into another developer", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/developers/{developer_id}/merge", name = "developer_merge")]
+#[instrument(skip(module, req))]
+pub async fn merge_developers(
+    arguments: web::Json<MergeArguments>,
+    path: web::Path<MergePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = MergeDevelopersCommand {
+        developer_id: path.developer_id,
+        into: arguments.into,
+    };
+
+    let command_handler: Box<dyn MergeDevelopersCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(MergeDevelopersCommandError::MergeIntoSelf) => {
+            BadRequest::new("A developer can not be merged into itself").into()
+        }
+        Err(MergeDevelopersCommandError::AlreadyMerged { into }) => Conflict::new(format!(
+            "The developer is already merged into developer {}",
+            into
+        ))
+        .into(),
+        Err(MergeDevelopersCommandError::TargetMerged { into }) => Conflict::new(format!(
+            "The target developer is merged into developer {}, merge into that developer instead",
+            into
+        ))
+        .into(),
+        Err(MergeDevelopersCommandError::HasMergedDevelopers) => Conflict::new(
+            "Other developers are merged into the developer, unmerge them before merging it",
+        )
+        .into(),
+        Err(MergeDevelopersCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while merging the developers").into()
+        }
+        Err(MergeDevelopersCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(MergeDevelopersCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(MergeDevelopersCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
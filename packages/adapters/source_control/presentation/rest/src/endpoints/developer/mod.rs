@@ -0,0 +1,7 @@
+pub mod create;
+pub mod get;
+pub mod link_identity;
+pub mod merge;
+pub mod resolve;
+pub mod unlink_identity;
+pub mod unmerge;
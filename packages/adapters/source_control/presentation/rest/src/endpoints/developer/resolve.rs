@@ -0,0 +1,58 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_postgres_persistence_adapter::queries::resolve_developer_identity::{
+    ResolveDeveloperIdentityQuery, ResolveDeveloperIdentityQueryError,
+    ResolveDeveloperIdentityQueryHandler,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{BadRequest, InternalServerError, NotFound},
+    models::developer::{DeveloperDto, DeveloperIdentityParams},
+};
+
+#[utoipa::path(
+    params(
+        DeveloperIdentityParams
+    ),
+    responses(
+        (status = 200, description = "The canonical developer with the identities of all developers merged into it", body=DeveloperDto),
+        (status = 400, description = "Neither a platform login nor an e-mail was given", body=BadRequest),
+        (status = 404, description = "No developer has this identity", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get("/developer-identities/resolve", name = "developer_identity_resolve")]
+#[instrument(skip(module, req))]
+pub async fn resolve_developer_identity(
+    arguments: web::Query<DeveloperIdentityParams>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let Some(identity) = arguments.to_identity() else {
+        return BadRequest::new("Either platform and login, or email should be given").into();
+    };
+
+    let query_handler: Box<dyn ResolveDeveloperIdentityQueryHandler> = module.provide().unwrap();
+
+    let result = query_handler
+        .handle(ResolveDeveloperIdentityQuery { identity })
+        .await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(ResolveDeveloperIdentityQueryError::NotFound) => NotFound::from_request(&req).into(),
+        Err(ResolveDeveloperIdentityQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while resolving the developer identity".to_string(),
+        )
+        .into(),
+        Err(ResolveDeveloperIdentityQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while resolving the developer identity".to_string(),
+        )
+        .into(),
+    }
+}
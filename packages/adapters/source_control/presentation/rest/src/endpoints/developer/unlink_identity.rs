@@ -0,0 +1,77 @@
+use actix_web::{delete, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::unlink_developer_identity::{
+        UnlinkDeveloperIdentityCommand, UnlinkDeveloperIdentityCommandError,
+        UnlinkDeveloperIdentityCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    models::developer::{DeveloperDto, DeveloperIdentityParams},
+};
+
+#[derive(Deserialize, Debug)]
+pub struct UnlinkPath {
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    params(
+        DeveloperIdentityParams
+    ),
+    responses(
+        (status = 200, description = "Identity unlinked successfully", body=DeveloperDto),
+        (status = 400, description = "Neither a platform login nor an e-mail was given", body=BadRequest),
+        (status = 404, description = "The developer or identity couldn't be found", body=NotFound),
+        (status = 409, description = "The developer is merged into another developer", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[delete("/developers/{developer_id}/identities", name = "developer_identities")]
+#[instrument(skip(module, req))]
+pub async fn unlink_developer_identity(
+    arguments: web::Query<DeveloperIdentityParams>,
+    path: web::Path<UnlinkPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let Some(identity) = arguments.to_identity() else {
+        return BadRequest::new("Either platform and login, or email should be given").into();
+    };
+
+    let command = UnlinkDeveloperIdentityCommand {
+        developer_id: path.developer_id,
+        identity,
+    };
+
+    let command_handler: Box<dyn UnlinkDeveloperIdentityCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(UnlinkDeveloperIdentityCommandError::Merged { into }) => {
+            Conflict::new(format!("The developer is merged into developer {}", into)).into()
+        }
+        Err(UnlinkDeveloperIdentityCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while unlinking the identity").into()
+        }
+        Err(UnlinkDeveloperIdentityCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(UnlinkDeveloperIdentityCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(UnlinkDeveloperIdentityCommandError::NotFound)
+        | Err(UnlinkDeveloperIdentityCommandError::IdentityNotFound) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::unmerge_developer::{
+        UnmergeDeveloperCommand, UnmergeDeveloperCommandError, UnmergeDeveloperCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::developer::DeveloperDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct UnmergePath {
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Developer unmerged successfully, its identities resolve to it again", body=DeveloperDto),
+        (status = 404, description = "The developer couldn't be found", body=NotFound),
+        (status = 409, description = "The developer is not merged into another developer", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/developers/{developer_id}/unmerge", name = "developer_unmerge")]
+#[instrument(skip(module, req))]
+pub async fn unmerge_developer(
+    path: web::Path<UnmergePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = UnmergeDeveloperCommand {
+        developer_id: path.developer_id,
+    };
+
+    let command_handler: Box<dyn UnmergeDeveloperCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(developer) => {
+            let dto: DeveloperDto = (&developer).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(UnmergeDeveloperCommandError::NotMerged) => {
+            Conflict::new("The developer is not merged into another developer").into()
+        }
+        Err(UnmergeDeveloperCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while unmerging the developer").into()
+        }
+        Err(UnmergeDeveloperCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(UnmergeDeveloperCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(UnmergeDeveloperCommandError::NotFound) => NotFound::from_request(&req).into(),
+    }
+}
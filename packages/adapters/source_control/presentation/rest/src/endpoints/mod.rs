@@ -1 +1,4 @@
+pub mod admin;
+pub mod developer;
 pub mod organization;
+pub mod repository;
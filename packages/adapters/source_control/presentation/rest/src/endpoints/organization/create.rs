@@ -4,7 +4,7 @@ use actix_web::{
     post,
     web::{self},
 };
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use serde::Deserialize;
 use shaku::HasProvider;
 use source_control_application::{
@@ -18,24 +18,28 @@ use utoipa::ToSchema;
 
 use crate::{
     errors::{Conflict, InternalServerError},
+    etag::revision_etag,
+    metadata::command_metadata,
     models::organization::OrganizationDto,
 };
 
 #[utoipa::path(
     responses(
-        (status = 201, description = "Organization created successfully", body=OrganizationDto),
+        (status = 201, description = "Organization created successfully", body=OrganizationDto, headers(("ETag" = String, description = "Revision of the organization"))),
         (status = 409, description = "An organization with the same name already exists", body=Conflict),
         (status = 500, description = "An unexpected issue happened", body=InternalServerError)
     )
 )]
 #[post("/organizations", name = "organizations")]
-#[instrument(skip(module))]
+#[instrument(skip(module, req))]
 pub async fn create_organization(
     arguments: web::Json<CreateArguments>,
     module: web::Data<ApplicationModule>,
+    req: HttpRequest,
 ) ->  HttpResponse {
     let command = CreateOrganizationCommand {
         name: arguments.name.clone(),
+        metadata: command_metadata(&req),
     };
 
     let command_handler: Box<dyn CreateOrganizationCommandHandler> = module.provide().unwrap();
@@ -44,13 +48,19 @@ pub async fn create_organization(
 
     match result {
         Ok(organization) => {
-            let dto: OrganizationDto = (&organization).into();
-            HttpResponse::Created().json(dto)
+            let dto: OrganizationDto = (&organization.value).into();
+            HttpResponse::Created()
+                .insert_header(revision_etag(organization.revision))
+                .json(dto)
         }
         Err(CreateOrganizationCommandError::Conflict) => {
             Conflict::new("A data conflict happened while creating the organization".to_string()).into()
         }
 
+        Err(CreateOrganizationCommandError::NameTaken) => {
+            Conflict::new("An organization with the same name already exists".to_string()).into()
+        }
+
         Err(CreateOrganizationCommandError::Connection) => {
             InternalServerError::new("Something went wrong while creating organization".to_string()).into()
         }
@@ -65,3 +75,59 @@ pub async fn create_organization(
 pub struct CreateArguments {
     name: String,
 }
+
+#[cfg(test)]
+mod test {
+    use actix_web::{http::StatusCode, test, web::Data, App};
+    use serde_json::{json, Value};
+    use source_control_application::module::get_in_memory_module;
+
+    use super::create_organization;
+    use crate::endpoints::organization::{get::get_organization, get_all::get_organizations};
+
+    #[actix_web::test]
+    async fn should_list_created_organization() {
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(get_in_memory_module()))
+                .service(create_organization)
+                .service(get_organization)
+                .service(get_organizations),
+        )
+        .await;
+
+        let created: Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::post()
+                .uri("/organizations")
+                .set_json(json!({ "name": "Porti" }))
+                .to_request(),
+        )
+        .await;
+        let duplicate = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/organizations")
+                .set_json(json!({ "name": " porti " }))
+                .to_request(),
+        )
+        .await;
+        let found: Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::get()
+                .uri(&format!("/organizations/{}", created["id"]))
+                .to_request(),
+        )
+        .await;
+        let listed: Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::get().uri("/organizations").to_request(),
+        )
+        .await;
+
+        assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+        assert_eq!(found["name"], json!("Porti"));
+        assert_eq!(listed["items"][0]["id"], created["id"]);
+        assert_eq!(listed["items"].as_array().map(Vec::len), Some(1));
+    }
+}
@@ -0,0 +1,72 @@
+use actix_web::{delete, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::delete_organization::{
+        DeleteOrganizationCommand, DeleteOrganizationCommandError, DeleteOrganizationCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound, PreconditionFailed},
+    etag::{expected_revision, revision_etag, revision_mismatch},
+    metadata::command_metadata,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct DeletePath {
+    organization_id: u64,
+}
+
+#[utoipa::path(
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only change the organization when it is still at this ETag")
+    ),
+    responses(
+        (status = 204, description = "Organization deleted successfully", headers(("ETag" = String, description = "Revision of the organization"))),
+        (status = 404, description = "The organization couldn't be found", body=NotFound),
+        (status = 409, description = "A data conflict happened while deleting the organization", body=Conflict),
+        (status = 412, description = "The organization is no longer at the ETag in If-Match", body=PreconditionFailed),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[delete("/organizations/{organization_id}", name = "organization")]
+#[instrument(skip(module, req))]
+pub async fn delete_organization(
+    path: web::Path<DeletePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let expected_revision = match expected_revision(&req) {
+        Ok(expected_revision) => expected_revision,
+        Err(response) => return response,
+    };
+
+    let command = DeleteOrganizationCommand {
+        organization_id: path.organization_id,
+        expected_revision,
+        metadata: command_metadata(&req),
+    };
+
+    let command_handler: Box<dyn DeleteOrganizationCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(revision) => HttpResponse::NoContent()
+            .insert_header(revision_etag(revision))
+            .finish(),
+        Err(DeleteOrganizationCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while deleting the organization").into()
+        }
+        Err(DeleteOrganizationCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(DeleteOrganizationCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(DeleteOrganizationCommandError::NotFound) => NotFound::from_request(&req).into(),
+        Err(DeleteOrganizationCommandError::PreconditionFailed) => revision_mismatch(),
+    }
+}
@@ -12,6 +12,7 @@ use utoipa::ToSchema;
 
 use crate::{
     errors::{InternalServerError, NotFound},
+    etag::{is_not_modified, revision_etag},
     models::organization::OrganizationDto,
 };
 
@@ -21,8 +22,12 @@ pub struct GetArguments {
 }
 
 #[utoipa::path(
+    params(
+        ("If-None-Match" = Option<String>, Header, description = "ETags of the organization the client already has")
+    ),
     responses(
-        (status = 200, description = "Organization found successfully", body=OrganizationDto),
+        (status = 200, description = "Organization found successfully", body=OrganizationDto, headers(("ETag" = String, description = "Revision of the organization"))),
+        (status = 304, description = "The organization is still at an ETag in If-None-Match", headers(("ETag" = String, description = "Revision of the organization"))),
         (status = 404, description = "The organization couldn't be found", body=NotFound),
         (status = 500, description = "An unexpected issue happened", body=InternalServerError)
     )
@@ -43,9 +48,16 @@ pub async fn get_organization(
     let result = query_handler.handle(query).await;
 
     match result {
+        Ok(organization) if is_not_modified(&req, organization.revision) => {
+            HttpResponse::NotModified()
+                .insert_header(revision_etag(organization.revision))
+                .finish()
+        }
         Ok(organization) => {
-            let dto: OrganizationDto = (&organization).into();
-            HttpResponse::Ok().json(dto)
+            let dto: OrganizationDto = (&organization.value).into();
+            HttpResponse::Ok()
+                .insert_header(revision_etag(organization.revision))
+                .json(dto)
         }
         Err(GetOrganizationQueryError::NotFound { .. }) => {
             NotFound::from_request(&req).into()
@@ -11,7 +11,7 @@ use tracing::instrument;
 
 use crate::{
     errors::{InternalServerError, NotFound},
-    models::organization_events::OrganizationEventDto,
+    models::organization_events::OrganizationLogEntryDto,
 };
 
 #[derive(Deserialize, Debug)]
@@ -21,7 +21,7 @@ pub struct GetArguments {
 
 #[utoipa::path(
     responses(
-        (status = 200, description = "Organization found successfully", body=Vec<OrganizationEventDto>),
+        (status = 200, description = "Organization found successfully", body=Vec<OrganizationLogEntryDto>),
         (status = 404, description = "The organization couldn't be found", body=NotFound),
         (status = 500, description = "An unexpected issue happened", body=InternalServerError)
     )
@@ -43,7 +43,7 @@ pub async fn get_organization_log(
 
     match result {
         Ok(organization_log) => {
-            let res: Vec<OrganizationEventDto> =
+            let res: Vec<OrganizationLogEntryDto> =
                 organization_log.iter().map(|e| e.into()).collect();
             HttpResponse::Ok().json(res)
         }
@@ -1,5 +1,7 @@
 pub mod create;
+pub mod delete;
 pub mod get;
 pub mod get_all;
 pub mod get_log;
 pub mod platform_account;
+pub mod update;
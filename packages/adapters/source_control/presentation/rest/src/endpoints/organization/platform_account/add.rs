@@ -12,7 +12,9 @@ use tracing::instrument;
 use utoipa::ToSchema;
 
 use crate::{
-    errors::{Conflict, InternalServerError, NotFound},
+    errors::{Conflict, InternalServerError, NotFound, PreconditionFailed},
+    etag::{expected_revision, revision_etag, revision_mismatch},
+    metadata::command_metadata,
     models::organization::OrganizationDto,
 };
 
@@ -33,10 +35,14 @@ pub struct AddPath {
 }
 
 #[utoipa::path(
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only change the organization when it is still at this ETag")
+    ),
     responses(
-        (status = 201, description = "Platform account successfully added", body=OrganizationDto),
+        (status = 201, description = "Platform account successfully added", body=OrganizationDto, headers(("ETag" = String, description = "Revision of the organization"))),
         (status = 404, description = "Organization couldn't be found", body=NotFound),
-        (status = 409, description = "Platform account already exists on organization", body=Conflict),
+        (status = 409, description = "Platform account already exists on organization, or the organization is archived", body=Conflict),
+        (status = 412, description = "The organization is no longer at the ETag in If-Match", body=PreconditionFailed),
         (status = 500, description = "An unexpected issue happened", body=InternalServerError)
     )
 )]
@@ -57,19 +63,28 @@ pub async fn add_platform_account(
         }));
     }
 
+    let expected_revision = match expected_revision(&req) {
+        Ok(expected_revision) => expected_revision,
+        Err(response) => return response,
+    };
+
     let command_handler: Box<dyn AddPlatformAccountCommandHandler> = module.provide().unwrap();
     let command = AddPlatformAccountCommand {
         organization_id: parse_result.unwrap(),
         name: arguments.name.clone(),
         platform_name: arguments.platform.name.clone(),
+        expected_revision,
+        metadata: command_metadata(&req),
     };
 
     let result = command_handler.handle(command).await;
 
     match result {
         Ok(organization) => {
-            let res: OrganizationDto = (&organization).into();
-            HttpResponse::Created().json(res)
+            let res: OrganizationDto = (&organization.value).into();
+            HttpResponse::Created()
+                .insert_header(revision_etag(organization.revision))
+                .json(res)
         }
         Err(AddPlatformAccountCommandError::Conflict) => {
             Conflict::new("A data conflict happened while adding paltform account".to_string())
@@ -86,6 +101,10 @@ pub async fn add_platform_account(
         Err(AddPlatformAccountCommandError::AccountAlreadyAdded) => {
             Conflict::new("Account was already added to this organization".to_string()).into()
         }
+        Err(AddPlatformAccountCommandError::Archived) => {
+            Conflict::new("Platform accounts can not be added to an archived organization").into()
+        }
         Err(AddPlatformAccountCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+        Err(AddPlatformAccountCommandError::PreconditionFailed) => revision_mismatch(),
     }
 }
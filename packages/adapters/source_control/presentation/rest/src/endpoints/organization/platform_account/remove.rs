@@ -12,7 +12,9 @@ use source_control_application::{
 use tracing::instrument;
 
 use crate::{
-    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    errors::{BadRequest, Conflict, InternalServerError, NotFound, PreconditionFailed},
+    etag::{expected_revision, revision_etag, revision_mismatch},
+    metadata::command_metadata,
     models::organization::OrganizationDto,
 };
 
@@ -23,14 +25,21 @@ pub struct RemovePath {
 }
 
 #[utoipa::path(
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only change the organization when it is still at this ETag")
+    ),
     responses(
-        (status = 201, description = "Platform account successfully removed", body=OrganizationDto),
+        (status = 201, description = "Platform account successfully removed", body=OrganizationDto, headers(("ETag" = String, description = "Revision of the organization"))),
         (status = 404, description = "Organization or platform couldn't be found", body=NotFound),
-        (status = 409, description = "A conflict occurred", body=Conflict),
+        (status = 409, description = "A conflict occurred, or the organization is archived", body=Conflict),
+        (status = 412, description = "The organization is no longer at the ETag in If-Match", body=PreconditionFailed),
         (status = 500, description = "An unexpected issue happened", body=InternalServerError)
     )
 )]
-#[delete("/organizations/{organization_id}/platform-accounts/{platform_account_id}", name = "organization_platform_account")]
+#[delete(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}",
+    name = "organization_platform_account"
+)]
 #[instrument(skip(module))]
 pub async fn remove_platform_account(
     path: web::Path<RemovePath>,
@@ -51,9 +60,16 @@ pub async fn remove_platform_account(
         }));
     }
 
+    let expected_revision = match expected_revision(&req) {
+        Ok(expected_revision) => expected_revision,
+        Err(response) => return response,
+    };
+
     let command = RemovePlatformAccountCommand {
         organization_id: parse_result.clone().unwrap(),
         account_id: platform_account_parse_result.unwrap(),
+        expected_revision,
+        metadata: command_metadata(&req),
     };
 
     let command_handler: Box<dyn RemovePlatformAccountCommandHandler> = module.provide().unwrap();
@@ -61,8 +77,10 @@ pub async fn remove_platform_account(
 
     match result {
         Ok(organization) => {
-            let res: OrganizationDto = (&organization).into();
-            HttpResponse::Ok().json(res)
+            let res: OrganizationDto = (&organization.value).into();
+            HttpResponse::Ok()
+                .insert_header(revision_etag(organization.revision))
+                .json(res)
         }
         Err(RemovePlatformAccountCommandError::Conflict) => {
             Conflict::new("A data conflict happened while adding paltform account").into()
@@ -73,9 +91,14 @@ pub async fn remove_platform_account(
         Err(RemovePlatformAccountCommandError::Unexpected) => {
             InternalServerError::new("Something went unexpectedly wrong").into()
         }
+        Err(RemovePlatformAccountCommandError::Archived) => {
+            Conflict::new("Platform accounts can not be removed from an archived organization")
+                .into()
+        }
         Err(RemovePlatformAccountCommandError::AccountNotFound { .. }) => {
             NotFound::from_request(&req).into()
         }
+        Err(RemovePlatformAccountCommandError::PreconditionFailed) => revision_mismatch(),
         Err(RemovePlatformAccountCommandError::OrganizationNotFound { .. }) => {
             NotFound::from_resource(
                 &req,
@@ -0,0 +1,70 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::archive_repository::{
+        ArchiveRepositoryCommand, ArchiveRepositoryCommandError, ArchiveRepositoryCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::repository::RepositoryDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct ArchivePath {
+    organization_id: u64,
+    platform_account_id: u64,
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Repository archived successfully", body=RepositoryDto),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "The repository is already archived", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories/{repository_id}/archive",
+    name = "organization_platform_account_repository_archive"
+)]
+#[instrument(skip(module, req))]
+pub async fn archive_repository(
+    path: web::Path<ArchivePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = ArchiveRepositoryCommand {
+        organization_id: path.organization_id,
+        platform_account_id: path.platform_account_id,
+        repository_id: path.repository_id,
+    };
+
+    let command_handler: Box<dyn ArchiveRepositoryCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(repository) => {
+            let dto: RepositoryDto = (&repository).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(ArchiveRepositoryCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while archiving the repository").into()
+        }
+        Err(ArchiveRepositoryCommandError::AlreadyArchived) => {
+            Conflict::new("The repository is already archived").into()
+        }
+        Err(ArchiveRepositoryCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(ArchiveRepositoryCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(ArchiveRepositoryCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,66 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_repository::{
+        GetRepositoryQuery, GetRepositoryQueryError, GetRepositoryQueryHandler,
+    },
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::repository::RepositoryDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetPath {
+    organization_id: u64,
+    platform_account_id: u64,
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Repository found successfully", body=RepositoryDto),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories/{repository_id}",
+    name = "organization_platform_account_repository"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_repository(
+    path: web::Path<GetPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query = GetRepositoryQuery {
+        organization_id: path.organization_id,
+        platform_account_id: path.platform_account_id,
+        id: path.repository_id,
+    };
+
+    let query_handler: Box<dyn GetRepositoryQueryHandler> = module.provide().unwrap();
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(repository) => {
+            let dto: RepositoryDto = (&repository).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetRepositoryQueryError::NotFound { .. }) => NotFound::from_request(&req).into(),
+        Err(GetRepositoryQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the repository".to_string(),
+        )
+        .into(),
+        Err(GetRepositoryQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the repository".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,97 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{
+    organization::OrganizationId, platform_account::PlatformAccountId, repository::RepositoryId,
+};
+use source_control_postgres_persistence_adapter::queries::get_repositories::{
+    GetRepositoriesQuery, GetRepositoriesQueryError, GetRepositoriesQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::InternalServerError,
+    models::{
+        paginated_result::{PageMetadata, PaginatedResult},
+        repository::RepositoryDto,
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetAllPath {
+    organization_id: u64,
+    platform_account_id: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetAllArguments {
+    before: Option<u64>,
+    after: Option<u64>,
+}
+
+#[utoipa::path(
+    params(
+        GetAllArguments
+    ),
+    responses(
+        (status = 200, description = "Repositories found successfully", body=PaginatedResult<RepositoryDto>),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories",
+    name = "organization_platform_account_repositories"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_repositories(
+    path: web::Path<GetAllPath>,
+    arguments: web::Query<GetAllArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query_handler: Box<dyn GetRepositoriesQueryHandler> = module.provide().unwrap();
+
+    let query = GetRepositoriesQuery {
+        organization_id: OrganizationId(path.organization_id),
+        platform_account_id: PlatformAccountId(path.platform_account_id),
+        before: arguments.before.map(RepositoryId),
+        after: arguments.after.map(RepositoryId),
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(repositories) => {
+            let first_id = repositories.first().map(|x| x.id.to_primitive());
+            let last_id = repositories.last().map(|x| x.id.to_primitive());
+            let base_url = req.full_url();
+
+            let next = last_id.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("after={}", x)));
+                url.into()
+            });
+            let previous = first_id.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("before={}", x)));
+                url.into()
+            });
+
+            let response: PaginatedResult<RepositoryDto> = PaginatedResult {
+                items: repositories.iter().map(|r| r.into()).collect(),
+                metadata: PageMetadata { next, previous },
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(GetRepositoriesQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the repositories".to_string(),
+        )
+        .into(),
+        Err(GetRepositoriesQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the repositories".to_string(),
+        )
+        .into(),
+    }
+}
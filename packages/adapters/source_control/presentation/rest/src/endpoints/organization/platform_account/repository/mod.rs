@@ -0,0 +1,6 @@
+pub mod archive;
+pub mod get;
+pub mod get_all;
+pub mod move_repository;
+pub mod register;
+pub mod rename;
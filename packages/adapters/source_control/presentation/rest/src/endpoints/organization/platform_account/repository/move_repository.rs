@@ -0,0 +1,85 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::move_repository::{
+        MoveRepositoryCommand, MoveRepositoryCommandError, MoveRepositoryCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    models::repository::RepositoryDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct MoveArguments {
+    platform_account_id: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MovePath {
+    organization_id: u64,
+    platform_account_id: u64,
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Repository moved successfully", body=RepositoryDto),
+        (status = 400, description = "The target platform account is not linked to the organization", body=BadRequest),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "The repository is archived or already on the platform account", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories/{repository_id}/move",
+    name = "organization_platform_account_repository_move"
+)]
+#[instrument(skip(module, req))]
+pub async fn move_repository(
+    arguments: web::Json<MoveArguments>,
+    path: web::Path<MovePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = MoveRepositoryCommand {
+        organization_id: path.organization_id,
+        platform_account_id: path.platform_account_id,
+        repository_id: path.repository_id,
+        target_platform_account_id: arguments.platform_account_id,
+    };
+
+    let command_handler: Box<dyn MoveRepositoryCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(repository) => {
+            let dto: RepositoryDto = (&repository).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(MoveRepositoryCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while moving the repository").into()
+        }
+        Err(MoveRepositoryCommandError::Archived) => {
+            Conflict::new("An archived repository can not be moved").into()
+        }
+        Err(MoveRepositoryCommandError::AlreadyOnPlatformAccount) => {
+            Conflict::new("The repository already belongs to this platform account").into()
+        }
+        Err(MoveRepositoryCommandError::AccountNotFound { .. }) => {
+            BadRequest::new("The target platform account is not linked to the organization").into()
+        }
+        Err(MoveRepositoryCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(MoveRepositoryCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(MoveRepositoryCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,79 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::register_repository::{
+        RegisterRepositoryCommand, RegisterRepositoryCommandError, RegisterRepositoryCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::repository::RepositoryDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct RegisterArguments {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RegisterPath {
+    organization_id: u64,
+    platform_account_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Repository registered successfully", body=RepositoryDto),
+        (status = 404, description = "The organization or platform account couldn't be found", body=NotFound),
+        (status = 409, description = "A repository with the same name already exists on the platform account", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories",
+    name = "organization_platform_account_repositories"
+)]
+#[instrument(skip(module, req))]
+pub async fn register_repository(
+    arguments: web::Json<RegisterArguments>,
+    path: web::Path<RegisterPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = RegisterRepositoryCommand {
+        organization_id: path.organization_id,
+        platform_account_id: path.platform_account_id,
+        name: arguments.name.clone(),
+    };
+
+    let command_handler: Box<dyn RegisterRepositoryCommandHandler> = module.provide().unwrap();
+
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(repository) => {
+            let dto: RepositoryDto = (&repository).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(RegisterRepositoryCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while registering the repository").into()
+        }
+        Err(RegisterRepositoryCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(RegisterRepositoryCommandError::Unexpected) => {
+            InternalServerError::new("Something went wrong while registering the repository").into()
+        }
+        Err(RegisterRepositoryCommandError::OrganizationNotFound { organization_id }) => {
+            NotFound::from_resource(&req, "organization", &[format!("{}", organization_id)]).into()
+        }
+        Err(RegisterRepositoryCommandError::AccountNotFound { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use actix_web::{patch, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::rename_repository::{
+        RenameRepositoryCommand, RenameRepositoryCommandError, RenameRepositoryCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::repository::RepositoryDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct RenameArguments {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RenamePath {
+    organization_id: u64,
+    platform_account_id: u64,
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Repository renamed successfully", body=RepositoryDto),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "The repository is archived or already has this name", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[patch(
+    "/organizations/{organization_id}/platform-accounts/{platform_account_id}/repositories/{repository_id}",
+    name = "organization_platform_account_repository"
+)]
+#[instrument(skip(module, req))]
+pub async fn rename_repository(
+    arguments: web::Json<RenameArguments>,
+    path: web::Path<RenamePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = RenameRepositoryCommand {
+        organization_id: path.organization_id,
+        platform_account_id: path.platform_account_id,
+        repository_id: path.repository_id,
+        name: arguments.name.clone(),
+    };
+
+    let command_handler: Box<dyn RenameRepositoryCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(repository) => {
+            let dto: RepositoryDto = (&repository).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(RenameRepositoryCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while renaming the repository").into()
+        }
+        Err(RenameRepositoryCommandError::Archived) => {
+            Conflict::new("An archived repository can not be renamed").into()
+        }
+        Err(RenameRepositoryCommandError::NameUnchanged) => {
+            Conflict::new("The repository already has this name").into()
+        }
+        Err(RenameRepositoryCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(RenameRepositoryCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(RenameRepositoryCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,197 @@
+use actix_web::{patch, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::update_organization::{
+        UpdateOrganizationCommand, UpdateOrganizationCommandError, UpdateOrganizationCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound, PreconditionFailed},
+    etag::{expected_revision, revision_etag, revision_mismatch},
+    metadata::command_metadata,
+    models::organization::OrganizationDto,
+};
+
+/// Fields that are left out are not changed.
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct UpdateArguments {
+    name: Option<String>,
+    archived: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdatePath {
+    organization_id: u64,
+}
+
+#[utoipa::path(
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only change the organization when it is still at this ETag")
+    ),
+    responses(
+        (status = 200, description = "Organization updated successfully", body=OrganizationDto, headers(("ETag" = String, description = "Revision of the organization"))),
+        (status = 404, description = "The organization couldn't be found", body=NotFound),
+        (status = 409, description = "The organization is archived or the name is already in use", body=Conflict),
+        (status = 412, description = "The organization is no longer at the ETag in If-Match", body=PreconditionFailed),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[patch("/organizations/{organization_id}", name = "organization")]
+#[instrument(skip(module, req))]
+pub async fn update_organization(
+    arguments: web::Json<UpdateArguments>,
+    path: web::Path<UpdatePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let expected_revision = match expected_revision(&req) {
+        Ok(expected_revision) => expected_revision,
+        Err(response) => return response,
+    };
+
+    let command = UpdateOrganizationCommand {
+        organization_id: path.organization_id,
+        name: arguments.name.clone(),
+        archived: arguments.archived,
+        expected_revision,
+        metadata: command_metadata(&req),
+    };
+
+    let command_handler: Box<dyn UpdateOrganizationCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(organization) => {
+            let dto: OrganizationDto = (&organization.value).into();
+            HttpResponse::Ok()
+                .insert_header(revision_etag(organization.revision))
+                .json(dto)
+        }
+        Err(UpdateOrganizationCommandError::Archived) => {
+            Conflict::new("An archived organization can not be changed").into()
+        }
+        Err(UpdateOrganizationCommandError::NameUnchanged) => {
+            Conflict::new("The organization already has this name").into()
+        }
+        Err(UpdateOrganizationCommandError::NameTaken) => {
+            Conflict::new("Another organization already has this name").into()
+        }
+        Err(UpdateOrganizationCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while updating the organization").into()
+        }
+        Err(UpdateOrganizationCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(UpdateOrganizationCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(UpdateOrganizationCommandError::NotFound) => NotFound::from_request(&req).into(),
+        Err(UpdateOrganizationCommandError::PreconditionFailed) => revision_mismatch(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+        web::Data,
+        App,
+    };
+    use serde_json::{json, Value};
+    use source_control_application::module::get_in_memory_module;
+
+    use super::update_organization;
+    use crate::endpoints::organization::{create::create_organization, get::get_organization};
+
+    #[actix_web::test]
+    async fn should_only_update_organization_at_expected_revision() {
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(get_in_memory_module()))
+                .service(create_organization)
+                .service(get_organization)
+                .service(update_organization),
+        )
+        .await;
+
+        let created: Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::post()
+                .uri("/organizations")
+                .set_json(json!({ "name": "Porti" }))
+                .to_request(),
+        )
+        .await;
+        let uri = format!("/organizations/{}", created["id"]);
+        let renamed = test::call_service(
+            &app,
+            test::TestRequest::patch()
+                .uri(&uri)
+                .insert_header((header::IF_MATCH, "\"0\""))
+                .set_json(json!({ "name": "Renamed" }))
+                .to_request(),
+        )
+        .await;
+        let stale = test::call_service(
+            &app,
+            test::TestRequest::patch()
+                .uri(&uri)
+                .insert_header((header::IF_MATCH, "\"0\""))
+                .set_json(json!({ "archived": true }))
+                .to_request(),
+        )
+        .await;
+        let not_modified = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri(&uri)
+                .insert_header((header::IF_NONE_MATCH, "\"1\""))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(renamed.status(), StatusCode::OK);
+        assert_eq!(renamed.headers().get(header::ETAG).unwrap(), "\"1\"");
+        assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(not_modified.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_web::test]
+    async fn should_not_rename_to_a_name_another_organization_has() {
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(get_in_memory_module()))
+                .service(create_organization)
+                .service(update_organization),
+        )
+        .await;
+        let create = |name: &str| {
+            test::TestRequest::post()
+                .uri("/organizations")
+                .set_json(json!({ "name": name }))
+                .to_request()
+        };
+        let rename = |id: &Value, name: &str| {
+            test::TestRequest::patch()
+                .uri(&format!("/organizations/{}", id))
+                .set_json(json!({ "name": name }))
+                .to_request()
+        };
+
+        let porti: Value = test::call_and_read_body_json(&app, create("Porti")).await;
+        let other: Value = test::call_and_read_body_json(&app, create("Other")).await;
+        let taken = test::call_service(&app, rename(&other["id"], "Porti")).await;
+        let renamed = test::call_service(&app, rename(&porti["id"], "Renamed")).await;
+        let released = test::call_service(&app, create("Porti")).await;
+
+        assert_eq!(taken.status(), StatusCode::CONFLICT);
+        assert_eq!(renamed.status(), StatusCode::OK);
+        assert_eq!(released.status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,91 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::create_branch::{
+        CreateBranchCommand, CreateBranchCommandError, CreateBranchCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    models::branch::BranchDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct CreateArguments {
+    name: String,
+    head: String,
+    based_on: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreatePath {
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Branch created successfully", body=BranchDto),
+        (status = 400, description = "The head is invalid, unrecorded, or the base branch doesn't exist", body=BadRequest),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "The branch already exists or the repository is archived", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/repositories/{repository_id}/branches", name = "repository_branches")]
+#[instrument(skip(module, req))]
+pub async fn create_branch(
+    arguments: web::Json<CreateArguments>,
+    path: web::Path<CreatePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = CreateBranchCommand {
+        repository_id: path.repository_id,
+        name: arguments.name.clone(),
+        head: arguments.head.clone(),
+        based_on: arguments.based_on.clone(),
+    };
+
+    let command_handler: Box<dyn CreateBranchCommandHandler> = module.provide().unwrap();
+
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(branch) => {
+            let dto: BranchDto = (&branch).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(CreateBranchCommandError::InvalidSha { sha }) => {
+            BadRequest::new(format!("{} is not a valid commit sha", sha)).into()
+        }
+        Err(CreateBranchCommandError::UnknownCommit { sha }) => {
+            BadRequest::new(format!("Commit {} has not been recorded", sha)).into()
+        }
+        Err(CreateBranchCommandError::BasedOnNotFound { name }) => {
+            BadRequest::new(format!("The branch {} could not be found", name)).into()
+        }
+        Err(CreateBranchCommandError::AlreadyExists) => {
+            Conflict::new("The branch already exists").into()
+        }
+        Err(CreateBranchCommandError::RepositoryArchived { .. }) => {
+            Conflict::new("Branches can not be created on an archived repository").into()
+        }
+        Err(CreateBranchCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while creating the branch").into()
+        }
+        Err(CreateBranchCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(CreateBranchCommandError::Unexpected) => {
+            InternalServerError::new("Something went wrong while creating the branch").into()
+        }
+        Err(CreateBranchCommandError::RepositoryNotFound { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
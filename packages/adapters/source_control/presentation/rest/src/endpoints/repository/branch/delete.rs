@@ -0,0 +1,65 @@
+use actix_web::{delete, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::delete_branch::{
+        DeleteBranchCommand, DeleteBranchCommandError, DeleteBranchCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::branch::BranchDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct DeletePath {
+    repository_id: u64,
+    name: String,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Branch deleted successfully", body=BranchDto),
+        (status = 404, description = "The branch couldn't be found", body=NotFound),
+        (status = 409, description = "A data conflict happened while deleting the branch", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[delete(
+    "/repositories/{repository_id}/branches/{name}",
+    name = "repository_branch"
+)]
+#[instrument(skip(module, req))]
+pub async fn delete_branch(
+    path: web::Path<DeletePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = DeleteBranchCommand {
+        repository_id: path.repository_id,
+        name: path.name.clone(),
+    };
+
+    let command_handler: Box<dyn DeleteBranchCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(branch) => {
+            let dto: BranchDto = (&branch).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(DeleteBranchCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while deleting the branch").into()
+        }
+        Err(DeleteBranchCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(DeleteBranchCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(DeleteBranchCommandError::NotFound) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,62 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_branch::{GetBranchQuery, GetBranchQueryError, GetBranchQueryHandler},
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::branch::BranchDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetPath {
+    repository_id: u64,
+    name: String,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Branch found successfully", body=BranchDto),
+        (status = 404, description = "The branch couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/branches/{name}",
+    name = "repository_branch"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_branch(
+    path: web::Path<GetPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query = GetBranchQuery {
+        repository_id: path.repository_id,
+        name: path.name.clone(),
+    };
+
+    let query_handler: Box<dyn GetBranchQueryHandler> = module.provide().unwrap();
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(branch) => {
+            let dto: BranchDto = (&branch).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetBranchQueryError::NotFound) => NotFound::from_request(&req).into(),
+        Err(GetBranchQueryError::Connection) => {
+            InternalServerError::new("Something went wrong while retreiving the branch".to_string())
+                .into()
+        }
+        Err(GetBranchQueryError::Unexpected) => {
+            InternalServerError::new("Something went wrong while retreiving the branch".to_string())
+                .into()
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{branch::BranchId, repository::RepositoryId};
+use source_control_postgres_persistence_adapter::queries::get_branch_pushes::{
+    GetBranchPushesQuery, GetBranchPushesQueryError, GetBranchPushesQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::{
+        branch::BranchPushDto,
+        paginated_result::{PageMetadata, PaginatedResult},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetPushesPath {
+    repository_id: u64,
+    name: String,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetPushesArguments {
+    /// Only return force pushes
+    forced: Option<bool>,
+    before: Option<u64>,
+    after: Option<u64>,
+}
+
+#[utoipa::path(
+    params(
+        GetPushesArguments
+    ),
+    responses(
+        (status = 200, description = "Pushes found successfully, newest first", body=PaginatedResult<BranchPushDto>),
+        (status = 404, description = "The branch couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/branches/{name}/pushes",
+    name = "repository_branch_pushes"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_branch_pushes(
+    path: web::Path<GetPushesPath>,
+    arguments: web::Query<GetPushesArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query_handler: Box<dyn GetBranchPushesQueryHandler> = module.provide().unwrap();
+
+    let forced_only = arguments.forced.unwrap_or(false);
+    let query = GetBranchPushesQuery {
+        branch_id: BranchId::from_name(RepositoryId(path.repository_id), &path.name),
+        forced_only,
+        before: arguments.before,
+        after: arguments.after,
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(pushes) => {
+            let first_sequence = pushes.first().map(|x| x.sequence);
+            let last_sequence = pushes.last().map(|x| x.sequence);
+            let base_url = req.full_url();
+            let forced_query = if forced_only { "&forced=true" } else { "" };
+
+            let next = last_sequence.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("after={}{}", x, forced_query)));
+                url.into()
+            });
+            let previous = first_sequence.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("before={}{}", x, forced_query)));
+                url.into()
+            });
+
+            let response: PaginatedResult<BranchPushDto> = PaginatedResult {
+                items: pushes.iter().map(|r| (&r.push).into()).collect(),
+                metadata: PageMetadata { next, previous },
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(GetBranchPushesQueryError::NotFound) => NotFound::from_request(&req).into(),
+        Err(GetBranchPushesQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the branch pushes".to_string(),
+        )
+        .into(),
+        Err(GetBranchPushesQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the branch pushes".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod get_pushes;
+pub mod push;
@@ -0,0 +1,97 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::push_branch::{PushBranchCommand, PushBranchCommandError, PushBranchCommandHandler},
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    models::branch::BranchPushDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct PushArguments {
+    pusher_id: u64,
+    after: String,
+    /// RFC 3339 timestamp of the push, defaults to the time the push is received
+    pushed_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PushPath {
+    repository_id: u64,
+    name: String,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Push recorded successfully, forced when the previous head is not an ancestor of the new head", body=BranchPushDto),
+        (status = 400, description = "The commit or timestamp is invalid, or the commit hasn't been recorded", body=BadRequest),
+        (status = 404, description = "The branch couldn't be found", body=NotFound),
+        (status = 409, description = "The branch already points to the commit", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/repositories/{repository_id}/branches/{name}/pushes",
+    name = "repository_branch_pushes"
+)]
+#[instrument(skip(module, req))]
+pub async fn push_branch(
+    arguments: web::Json<PushArguments>,
+    path: web::Path<PushPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let pushed_at = match &arguments.pushed_at {
+        Some(pushed_at) => match DateTime::parse_from_rfc3339(pushed_at) {
+            Ok(pushed_at) => pushed_at.with_timezone(&Utc),
+            Err(_) => {
+                return BadRequest::new("pushed_at should be an RFC 3339 timestamp").into();
+            }
+        },
+        None => Utc::now(),
+    };
+
+    let command = PushBranchCommand {
+        repository_id: path.repository_id,
+        name: path.name.clone(),
+        pusher_id: arguments.pusher_id,
+        after: arguments.after.clone(),
+        pushed_at,
+    };
+
+    let command_handler: Box<dyn PushBranchCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(push) => {
+            let dto: BranchPushDto = (&push).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(PushBranchCommandError::InvalidSha { sha }) => {
+            BadRequest::new(format!("{} is not a valid commit sha", sha)).into()
+        }
+        Err(PushBranchCommandError::UnknownCommit { sha }) => {
+            BadRequest::new(format!("Commit {} has not been recorded", sha)).into()
+        }
+        Err(PushBranchCommandError::HeadUnchanged) => {
+            Conflict::new("The branch already points to this commit").into()
+        }
+        Err(PushBranchCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while pushing the branch").into()
+        }
+        Err(PushBranchCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(PushBranchCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(PushBranchCommandError::NotFound) => NotFound::from_request(&req).into(),
+    }
+}
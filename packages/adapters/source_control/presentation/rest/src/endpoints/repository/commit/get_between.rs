@@ -0,0 +1,84 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{commit::CommitSha, repository::RepositoryId};
+use source_control_postgres_persistence_adapter::queries::get_commits_between::{
+    GetCommitsBetweenQuery, GetCommitsBetweenQueryError, GetCommitsBetweenQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::{BadRequest, InternalServerError, NotFound},
+    models::commit::CommitDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetBetweenPath {
+    repository_id: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetBetweenArguments {
+    from: String,
+    to: String,
+}
+
+#[utoipa::path(
+    params(
+        GetBetweenArguments
+    ),
+    responses(
+        (status = 200, description = "Commits reachable from `to` but not from `from`, newest first", body=Vec<CommitDto>),
+        (status = 400, description = "One of the shas is invalid", body=BadRequest),
+        (status = 404, description = "One of the commits couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/commits/between",
+    name = "repository_commits_between"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_commits_between(
+    path: web::Path<GetBetweenPath>,
+    arguments: web::Query<GetBetweenArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let (Ok(from), Ok(to)) = (
+        CommitSha::from_string(&arguments.from),
+        CommitSha::from_string(&arguments.to),
+    ) else {
+        return BadRequest::new("Commit shas should be 40-character hexadecimal strings").into();
+    };
+
+    let query_handler: Box<dyn GetCommitsBetweenQueryHandler> = module.provide().unwrap();
+
+    let query = GetCommitsBetweenQuery {
+        repository_id: RepositoryId(path.repository_id),
+        from,
+        to,
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(commits) => {
+            let dto: Vec<CommitDto> = commits.iter().map(|x| x.into()).collect();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetCommitsBetweenQueryError::UnknownCommit { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+        Err(GetCommitsBetweenQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the commits".to_string(),
+        )
+        .into(),
+        Err(GetCommitsBetweenQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the commits".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,82 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{commit::CommitSha, repository::RepositoryId};
+use source_control_postgres_persistence_adapter::queries::get_merge_bases::{
+    GetMergeBasesQuery, GetMergeBasesQueryError, GetMergeBasesQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::{BadRequest, InternalServerError, NotFound},
+    models::commit::MergeBasesDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetMergeBasesPath {
+    repository_id: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetMergeBasesArguments {
+    a: String,
+    b: String,
+}
+
+#[utoipa::path(
+    params(
+        GetMergeBasesArguments
+    ),
+    responses(
+        (status = 200, description = "Merge bases found successfully", body=MergeBasesDto),
+        (status = 400, description = "One of the shas is invalid", body=BadRequest),
+        (status = 404, description = "One of the commits couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/commits/merge-base",
+    name = "repository_commits_merge_base"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_merge_bases(
+    path: web::Path<GetMergeBasesPath>,
+    arguments: web::Query<GetMergeBasesArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let (Ok(a), Ok(b)) = (
+        CommitSha::from_string(&arguments.a),
+        CommitSha::from_string(&arguments.b),
+    ) else {
+        return BadRequest::new("Commit shas should be 40-character hexadecimal strings").into();
+    };
+
+    let query_handler: Box<dyn GetMergeBasesQueryHandler> = module.provide().unwrap();
+
+    let query = GetMergeBasesQuery {
+        repository_id: RepositoryId(path.repository_id),
+        a,
+        b,
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(merge_bases) => {
+            let dto: MergeBasesDto = (&merge_bases).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetMergeBasesQueryError::UnknownCommit { .. }) => NotFound::from_request(&req).into(),
+        Err(GetMergeBasesQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the merge bases".to_string(),
+        )
+        .into(),
+        Err(GetMergeBasesQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the merge bases".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{commit::CommitSha, repository::RepositoryId};
+use source_control_postgres_persistence_adapter::queries::is_ancestor::{
+    IsAncestorQuery, IsAncestorQueryError, IsAncestorQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::{BadRequest, InternalServerError, NotFound},
+    models::commit::IsAncestorDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct IsAncestorPath {
+    repository_id: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct IsAncestorArguments {
+    ancestor: String,
+    descendant: String,
+}
+
+#[utoipa::path(
+    params(
+        IsAncestorArguments
+    ),
+    responses(
+        (status = 200, description = "Ancestry determined successfully", body=IsAncestorDto),
+        (status = 400, description = "One of the shas is invalid", body=BadRequest),
+        (status = 404, description = "One of the commits couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/commits/is-ancestor",
+    name = "repository_commits_is_ancestor"
+)]
+#[instrument(skip(module, req))]
+pub async fn is_ancestor(
+    path: web::Path<IsAncestorPath>,
+    arguments: web::Query<IsAncestorArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let (Ok(ancestor), Ok(descendant)) = (
+        CommitSha::from_string(&arguments.ancestor),
+        CommitSha::from_string(&arguments.descendant),
+    ) else {
+        return BadRequest::new("Commit shas should be 40-character hexadecimal strings").into();
+    };
+
+    let query_handler: Box<dyn IsAncestorQueryHandler> = module.provide().unwrap();
+
+    let query = IsAncestorQuery {
+        repository_id: RepositoryId(path.repository_id),
+        ancestor,
+        descendant,
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(is_ancestor) => HttpResponse::Ok().json(IsAncestorDto {
+            ancestor: ancestor.to_string(),
+            descendant: descendant.to_string(),
+            is_ancestor,
+        }),
+        Err(IsAncestorQueryError::UnknownCommit { .. }) => NotFound::from_request(&req).into(),
+        Err(IsAncestorQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the commit ancestry".to_string(),
+        )
+        .into(),
+        Err(IsAncestorQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the commit ancestry".to_string(),
+        )
+        .into(),
+    }
+}
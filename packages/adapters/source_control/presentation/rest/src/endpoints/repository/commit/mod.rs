@@ -0,0 +1,4 @@
+pub mod get_between;
+pub mod get_merge_bases;
+pub mod is_ancestor;
+pub mod record;
@@ -0,0 +1,98 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::record_commits::{
+        RecordCommit, RecordCommitsCommand, RecordCommitsCommandError, RecordCommitsCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{BadRequest, Conflict, InternalServerError, NotFound},
+    metadata::command_metadata,
+    models::commit::CommitDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct RecordArguments {
+    commits: Vec<RecordCommitArguments>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct RecordCommitArguments {
+    sha: String,
+    message: String,
+    parents: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RecordPath {
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Commits recorded successfully, only newly recorded commits are returned", body=Vec<CommitDto>),
+        (status = 400, description = "A sha is invalid or a parent commit has not been recorded", body=BadRequest),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "A data conflict happened while recording the commits", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post("/repositories/{repository_id}/commits", name = "repository_commits")]
+#[instrument(skip(module, req, arguments))]
+pub async fn record_commits(
+    arguments: web::Json<RecordArguments>,
+    path: web::Path<RecordPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = RecordCommitsCommand {
+        repository_id: path.repository_id,
+        commits: arguments
+            .into_inner()
+            .commits
+            .into_iter()
+            .map(|commit| RecordCommit {
+                sha: commit.sha,
+                message: commit.message,
+                parents: commit.parents,
+            })
+            .collect(),
+        metadata: command_metadata(&req),
+    };
+
+    let command_handler: Box<dyn RecordCommitsCommandHandler> = module.provide().unwrap();
+
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(commits) => {
+            let dto: Vec<CommitDto> = commits.iter().map(|x| x.into()).collect();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(RecordCommitsCommandError::InvalidSha { sha }) => {
+            BadRequest::new(format!("{} is not a valid commit sha", sha)).into()
+        }
+        Err(RecordCommitsCommandError::MissingParent { sha, parent }) => BadRequest::new(format!(
+            "Parent {} of commit {} has not been recorded",
+            parent, sha
+        ))
+        .into(),
+        Err(RecordCommitsCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while recording the commits").into()
+        }
+        Err(RecordCommitsCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(RecordCommitsCommandError::Unexpected) => {
+            InternalServerError::new("Something went wrong while recording the commits").into()
+        }
+        Err(RecordCommitsCommandError::RepositoryNotFound { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
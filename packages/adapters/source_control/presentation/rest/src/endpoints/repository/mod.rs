@@ -0,0 +1,3 @@
+pub mod branch;
+pub mod commit;
+pub mod pull_request;
@@ -0,0 +1,71 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::close_pull_request::{
+        ClosePullRequestCommand, ClosePullRequestCommandError, ClosePullRequestCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct ClosePath {
+    repository_id: u64,
+    pull_request_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Pull request closed successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 409, description = "The pull request is no longer open", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}/close",
+    name = "repository_pull_request_close"
+)]
+#[instrument(skip(module, req))]
+pub async fn close_pull_request(
+    path: web::Path<ClosePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = ClosePullRequestCommand {
+        repository_id: path.repository_id,
+        pull_request_id: path.pull_request_id,
+    };
+
+    let command_handler: Box<dyn ClosePullRequestCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(ClosePullRequestCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while closing the pull request").into()
+        }
+        Err(ClosePullRequestCommandError::AlreadyMerged) => {
+            Conflict::new("The pull request is already merged").into()
+        }
+        Err(ClosePullRequestCommandError::AlreadyClosed) => {
+            Conflict::new("The pull request is already closed").into()
+        }
+        Err(ClosePullRequestCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(ClosePullRequestCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(ClosePullRequestCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,77 @@
+use actix_web::{delete, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::dismiss_review::{
+        DismissReviewCommand, DismissReviewCommandError, DismissReviewCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct DismissReviewPath {
+    repository_id: u64,
+    pull_request_id: u64,
+    developer_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Review dismissed successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 409, description = "The pull request is no longer open", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[delete(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}/reviews/{developer_id}",
+    name = "repository_pull_request_review"
+)]
+#[instrument(skip(module, req))]
+pub async fn dismiss_review(
+    path: web::Path<DismissReviewPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = DismissReviewCommand {
+        repository_id: path.repository_id,
+        pull_request_id: path.pull_request_id,
+        developer_id: path.developer_id,
+    };
+
+    let command_handler: Box<dyn DismissReviewCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(DismissReviewCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while dismissing a review on the pull request")
+                .into()
+        }
+        Err(DismissReviewCommandError::AlreadyMerged) => {
+            Conflict::new("The pull request is already merged").into()
+        }
+        Err(DismissReviewCommandError::AlreadyClosed) => {
+            Conflict::new("The pull request is already closed").into()
+        }
+        Err(DismissReviewCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(DismissReviewCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(DismissReviewCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+        Err(DismissReviewCommandError::ReviewNotFound { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
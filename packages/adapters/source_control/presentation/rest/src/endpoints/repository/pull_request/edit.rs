@@ -0,0 +1,81 @@
+use actix_web::{patch, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::edit_pull_request::{
+        EditPullRequestCommand, EditPullRequestCommandError, EditPullRequestCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct EditArguments {
+    title: String,
+    description: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EditPath {
+    repository_id: u64,
+    pull_request_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Pull request edited successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 409, description = "The pull request is no longer open", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[patch(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}",
+    name = "repository_pull_request"
+)]
+#[instrument(skip(module, req))]
+pub async fn edit_pull_request(
+    arguments: web::Json<EditArguments>,
+    path: web::Path<EditPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = EditPullRequestCommand {
+        repository_id: path.repository_id,
+        pull_request_id: path.pull_request_id,
+        title: arguments.title.clone(),
+        description: arguments.description.clone(),
+    };
+
+    let command_handler: Box<dyn EditPullRequestCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(EditPullRequestCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while editing the pull request").into()
+        }
+        Err(EditPullRequestCommandError::AlreadyMerged) => {
+            Conflict::new("The pull request is already merged").into()
+        }
+        Err(EditPullRequestCommandError::AlreadyClosed) => {
+            Conflict::new("The pull request is already closed").into()
+        }
+        Err(EditPullRequestCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(EditPullRequestCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(EditPullRequestCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
@@ -0,0 +1,64 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    module::ApplicationModule,
+    queries::get_pull_request::{
+        GetPullRequestQuery, GetPullRequestQueryError, GetPullRequestQueryHandler,
+    },
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetPath {
+    repository_id: u64,
+    pull_request_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Pull request found successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}",
+    name = "repository_pull_request"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_pull_request(
+    path: web::Path<GetPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query = GetPullRequestQuery {
+        repository_id: path.repository_id,
+        id: path.pull_request_id,
+    };
+
+    let query_handler: Box<dyn GetPullRequestQueryHandler> = module.provide().unwrap();
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(GetPullRequestQueryError::NotFound { .. }) => NotFound::from_request(&req).into(),
+        Err(GetPullRequestQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the pull request".to_string(),
+        )
+        .into(),
+        Err(GetPullRequestQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the pull request".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,99 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_domain::entities::{pull_request::PullRequestId, repository::RepositoryId};
+use source_control_postgres_persistence_adapter::queries::get_pull_requests::{
+    GetPullRequestsQuery, GetPullRequestsQueryError, GetPullRequestsQueryHandler,
+};
+use tracing::instrument;
+use utoipa::IntoParams;
+
+use crate::{
+    errors::InternalServerError,
+    models::{
+        paginated_result::{PageMetadata, PaginatedResult},
+        pull_request::{PullRequestStateDto, PullRequestSummaryDto},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct GetAllPath {
+    repository_id: u64,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct GetAllArguments {
+    state: Option<PullRequestStateDto>,
+    before: Option<u64>,
+    after: Option<u64>,
+}
+
+#[utoipa::path(
+    params(
+        GetAllArguments
+    ),
+    responses(
+        (status = 200, description = "Pull requests found successfully", body=PaginatedResult<PullRequestSummaryDto>),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[get(
+    "/repositories/{repository_id}/pull-requests",
+    name = "repository_pull_requests"
+)]
+#[instrument(skip(module, req))]
+pub async fn get_pull_requests(
+    path: web::Path<GetAllPath>,
+    arguments: web::Query<GetAllArguments>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let query_handler: Box<dyn GetPullRequestsQueryHandler> = module.provide().unwrap();
+
+    let query = GetPullRequestsQuery {
+        repository_id: RepositoryId(path.repository_id),
+        state: arguments.state.map(|x| x.into()),
+        before: arguments.before.map(PullRequestId),
+        after: arguments.after.map(PullRequestId),
+    };
+
+    let result = query_handler.handle(query).await;
+
+    match result {
+        Ok(pull_requests) => {
+            let first_id = pull_requests.first().map(|x| x.id.to_primitive());
+            let last_id = pull_requests.last().map(|x| x.id.to_primitive());
+            let base_url = req.full_url();
+            let state_query = arguments
+                .state
+                .map(|x| format!("&state={}", x.as_str()))
+                .unwrap_or_default();
+
+            let next = last_id.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("after={}{}", x, state_query)));
+                url.into()
+            });
+            let previous = first_id.map(|x| {
+                let mut url = base_url.clone();
+                url.set_query(Some(&format!("before={}{}", x, state_query)));
+                url.into()
+            });
+
+            let response: PaginatedResult<PullRequestSummaryDto> = PaginatedResult {
+                items: pull_requests.iter().map(|r| r.into()).collect(),
+                metadata: PageMetadata { next, previous },
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(GetPullRequestsQueryError::Connection) => InternalServerError::new(
+            "Something went wrong while retreiving the pull requests".to_string(),
+        )
+        .into(),
+        Err(GetPullRequestsQueryError::Unexpected) => InternalServerError::new(
+            "Something went wrong while retreiving the pull requests".to_string(),
+        )
+        .into(),
+    }
+}
@@ -0,0 +1,71 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::merge_pull_request::{
+        MergePullRequestCommand, MergePullRequestCommandError, MergePullRequestCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct MergePath {
+    repository_id: u64,
+    pull_request_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Pull request merged successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 409, description = "The pull request is no longer open", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}/merge",
+    name = "repository_pull_request_merge"
+)]
+#[instrument(skip(module, req))]
+pub async fn merge_pull_request(
+    path: web::Path<MergePath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = MergePullRequestCommand {
+        repository_id: path.repository_id,
+        pull_request_id: path.pull_request_id,
+    };
+
+    let command_handler: Box<dyn MergePullRequestCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(MergePullRequestCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while merging the pull request").into()
+        }
+        Err(MergePullRequestCommandError::AlreadyMerged) => {
+            Conflict::new("The pull request is already merged").into()
+        }
+        Err(MergePullRequestCommandError::AlreadyClosed) => {
+            Conflict::new("The pull request is already closed").into()
+        }
+        Err(MergePullRequestCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(MergePullRequestCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(MergePullRequestCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
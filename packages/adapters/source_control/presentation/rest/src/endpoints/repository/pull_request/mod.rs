@@ -0,0 +1,8 @@
+pub mod close;
+pub mod dismiss_review;
+pub mod edit;
+pub mod get;
+pub mod get_all;
+pub mod merge;
+pub mod open;
+pub mod submit_review;
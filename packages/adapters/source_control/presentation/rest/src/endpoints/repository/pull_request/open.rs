@@ -0,0 +1,85 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::open_pull_request::{
+        OpenPullRequestCommand, OpenPullRequestCommandError, OpenPullRequestCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct OpenArguments {
+    number: u64,
+    author_id: u64,
+    title: String,
+    description: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OpenPath {
+    repository_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Pull request opened successfully", body=PullRequestDto),
+        (status = 404, description = "The repository couldn't be found", body=NotFound),
+        (status = 409, description = "The repository is archived or the pull request already exists", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/repositories/{repository_id}/pull-requests",
+    name = "repository_pull_requests"
+)]
+#[instrument(skip(module, req))]
+pub async fn open_pull_request(
+    arguments: web::Json<OpenArguments>,
+    path: web::Path<OpenPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = OpenPullRequestCommand {
+        repository_id: path.repository_id,
+        number: arguments.number,
+        author_id: arguments.author_id,
+        title: arguments.title.clone(),
+        description: arguments.description.clone(),
+    };
+
+    let command_handler: Box<dyn OpenPullRequestCommandHandler> = module.provide().unwrap();
+
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Created().json(dto)
+        }
+        Err(OpenPullRequestCommandError::AlreadyExists { number }) => Conflict::new(format!(
+            "A pull request with number {} already exists in this repository",
+            number
+        ))
+        .into(),
+        Err(OpenPullRequestCommandError::RepositoryArchived { .. }) => {
+            Conflict::new("Pull requests can not be opened on an archived repository").into()
+        }
+        Err(OpenPullRequestCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(OpenPullRequestCommandError::Unexpected) => {
+            InternalServerError::new("Something went wrong while opening the pull request").into()
+        }
+        Err(OpenPullRequestCommandError::RepositoryNotFound { .. }) => {
+            NotFound::from_request(&req).into()
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shaku::HasProvider;
+use source_control_application::{
+    commands::submit_review::{
+        SubmitReviewCommand, SubmitReviewCommandError, SubmitReviewCommandHandler,
+    },
+    module::ApplicationModule,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{Conflict, InternalServerError, NotFound},
+    models::pull_request::PullRequestDto,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SubmitReviewArguments {
+    developer_id: u64,
+    status: String,
+    accepting: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SubmitReviewPath {
+    repository_id: u64,
+    pull_request_id: u64,
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Review submitted successfully", body=PullRequestDto),
+        (status = 404, description = "The pull request couldn't be found", body=NotFound),
+        (status = 409, description = "The pull request is no longer open", body=Conflict),
+        (status = 500, description = "An unexpected issue happened", body=InternalServerError)
+    )
+)]
+#[post(
+    "/repositories/{repository_id}/pull-requests/{pull_request_id}/reviews",
+    name = "repository_pull_request_reviews"
+)]
+#[instrument(skip(module, req))]
+pub async fn submit_review(
+    arguments: web::Json<SubmitReviewArguments>,
+    path: web::Path<SubmitReviewPath>,
+    module: web::Data<ApplicationModule>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let command = SubmitReviewCommand {
+        repository_id: path.repository_id,
+        pull_request_id: path.pull_request_id,
+        developer_id: arguments.developer_id,
+        status_name: arguments.status.clone(),
+        accepting: arguments.accepting,
+    };
+
+    let command_handler: Box<dyn SubmitReviewCommandHandler> = module.provide().unwrap();
+    let result = command_handler.handle(command).await;
+
+    match result {
+        Ok(pull_request) => {
+            let dto: PullRequestDto = (&pull_request).into();
+            HttpResponse::Ok().json(dto)
+        }
+        Err(SubmitReviewCommandError::Conflict) => {
+            Conflict::new("A data conflict happened while reviewing the pull request").into()
+        }
+        Err(SubmitReviewCommandError::AlreadyMerged) => {
+            Conflict::new("The pull request is already merged").into()
+        }
+        Err(SubmitReviewCommandError::AlreadyClosed) => {
+            Conflict::new("The pull request is already closed").into()
+        }
+        Err(SubmitReviewCommandError::Connection) => {
+            InternalServerError::new("The server failed to connect to the database").into()
+        }
+        Err(SubmitReviewCommandError::Unexpected) => {
+            InternalServerError::new("Something went unexpectedly wrong").into()
+        }
+        Err(SubmitReviewCommandError::NotFound { .. }) => NotFound::from_request(&req).into(),
+    }
+}
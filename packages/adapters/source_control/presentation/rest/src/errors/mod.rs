@@ -47,6 +47,26 @@ impl Conflict {
     }
 }
 
+impl PreconditionFailed {
+    pub fn new<TMessage: Into<String>>(message: TMessage) -> Self {
+        PreconditionFailed {
+            title: "Precondition Failed",
+            status: StatusCodeS(StatusCode::PRECONDITION_FAILED),
+            detail: message.into(),
+        }
+    }
+}
+
+impl UnprocessableEntity {
+    pub fn new<TMessage: Into<String>>(message: TMessage) -> Self {
+        UnprocessableEntity {
+            title: "Unprocessable Entity",
+            status: StatusCodeS(StatusCode::UNPROCESSABLE_ENTITY),
+            detail: message.into(),
+        }
+    }
+}
+
 impl BadRequest {
     pub fn new<TMessage: Into<String>>(message: TMessage) -> Self {
         BadRequest {
@@ -82,6 +102,22 @@ pub struct Conflict {
     detail: String,
 }
 
+#[derive(Serialize, Debug, Display, ToSchema)]
+#[display("PreconditionFailed")]
+pub struct PreconditionFailed {
+    title: &'static str,
+    status: StatusCodeS,
+    detail: String,
+}
+
+#[derive(Serialize, Debug, Display, ToSchema)]
+#[display("UnprocessableEntity")]
+pub struct UnprocessableEntity {
+    title: &'static str,
+    status: StatusCodeS,
+    detail: String,
+}
+
 #[derive(Serialize, Debug, Display, ToSchema)]
 #[display("BadRequest")]
 pub struct BadRequest {
@@ -114,6 +150,22 @@ impl From<Conflict> for HttpResponse {
     }
 }
 
+impl From<PreconditionFailed> for HttpResponse {
+    fn from(value: PreconditionFailed) -> Self {
+        HttpResponse::build(value.status.0)
+            .content_type("application/problem+json")
+            .json(value)
+    }
+}
+
+impl From<UnprocessableEntity> for HttpResponse {
+    fn from(value: UnprocessableEntity) -> Self {
+        HttpResponse::build(value.status.0)
+            .content_type("application/problem+json")
+            .json(value)
+    }
+}
+
 impl From<BadRequest> for HttpResponse {
     fn from(value: BadRequest) -> Self {
         HttpResponse::build(value.status.0)
@@ -0,0 +1,83 @@
+use actix_web::{
+    http::header::{ETag, EntityTag, Header, IfMatch, IfNoneMatch},
+    HttpRequest, HttpResponse,
+};
+
+use crate::errors::{BadRequest, PreconditionFailed};
+
+/// Strong ETag of an aggregate at `revision`.
+pub fn revision_etag(revision: u64) -> ETag {
+    ETag(EntityTag::new_strong(revision.to_string()))
+}
+
+/// The revision `If-Match` expects the aggregate to be at, `None` without the header or for `*`.
+/// Commands compare a single revision, so a list of ETags fails the precondition.
+pub fn expected_revision(req: &HttpRequest) -> Result<Option<u64>, HttpResponse> {
+    if !req.headers().contains_key(IfMatch::name()) {
+        return Ok(None);
+    }
+
+    match IfMatch::parse(req) {
+        Ok(IfMatch::Any) => Ok(None),
+        Ok(IfMatch::Items(tags)) => match tags.as_slice() {
+            [tag] if !tag.weak => tag
+                .tag()
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|_| revision_mismatch()),
+            _ => Err(revision_mismatch()),
+        },
+        Err(_) => Err(BadRequest::new("Incorrectly formatted If-Match header").into()),
+    }
+}
+
+/// Whether `If-None-Match` already has the aggregate at `revision`, so it doesn't have to be sent
+/// again.
+pub fn is_not_modified(req: &HttpRequest, revision: u64) -> bool {
+    match IfNoneMatch::parse(req) {
+        Ok(IfNoneMatch::Any) => true,
+        Ok(IfNoneMatch::Items(tags)) => {
+            tags.iter().any(|tag| tag.weak_eq(&revision_etag(revision)))
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn revision_mismatch() -> HttpResponse {
+    PreconditionFailed::new("The resource changed since the revision in If-Match").into()
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::test::TestRequest;
+
+    use super::{expected_revision, is_not_modified};
+
+    #[test]
+    fn should_read_expected_revision() {
+        let req = TestRequest::default()
+            .insert_header(("If-Match", "\"3\""))
+            .to_http_request();
+        assert_eq!(expected_revision(&req).ok(), Some(Some(3)));
+
+        let req = TestRequest::default()
+            .insert_header(("If-Match", "*"))
+            .to_http_request();
+        assert_eq!(expected_revision(&req).ok(), Some(None));
+
+        let req = TestRequest::default()
+            .insert_header(("If-Match", "W/\"3\""))
+            .to_http_request();
+        assert!(expected_revision(&req).is_err());
+    }
+
+    #[test]
+    fn should_compare_if_none_match_weakly() {
+        let req = TestRequest::default()
+            .insert_header(("If-None-Match", "W/\"3\", \"4\""))
+            .to_http_request();
+
+        assert!(is_not_modified(&req, 3));
+        assert!(!is_not_modified(&req, 5));
+    }
+}
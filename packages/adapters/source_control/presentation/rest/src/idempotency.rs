@@ -0,0 +1,391 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    time::Duration,
+};
+
+use actix_web::{
+    body::{to_bytes, BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{HeaderName, HeaderValue},
+        Method, StatusCode,
+    },
+    web::{Bytes, Data},
+    Error, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+use sha2::{Digest, Sha256};
+use shaku::HasProvider;
+use source_control_application::module::ApplicationModule;
+use source_control_postgres_persistence_adapter::repositories::idempotency_key_repository::{
+    IdempotencyKey, IdempotencyKeyRepository, IdempotencyKeyReservation, StoredResponse,
+};
+use tracing::{error, warn};
+
+use crate::{
+    errors::{Conflict, InternalServerError, UnprocessableEntity},
+    metadata::ACTOR_ID_HEADER,
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Set on responses that were stored for an earlier request with the same key.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Stores the first response of POST, PATCH and DELETE requests with an `Idempotency-Key` header
+/// for `ttl`, retries with the same key and body get that response instead of being handled
+/// again. Keys are scoped to the `X-Actor-Id` and the endpoint of the request.
+pub struct IdempotencyFactory {
+    pub ttl: Duration,
+    /// How long a request holds its key while it is being handled, a retry takes the key over
+    /// after it when the request never finished.
+    pub lease: Duration,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for IdempotencyFactory
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = IdempotencyMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+            ttl: self.ttl,
+            lease: self.lease,
+        }))
+    }
+}
+
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+    ttl: Duration,
+    lease: Duration,
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let ttl = self.ttl;
+        let lease = self.lease;
+
+        Box::pin(async move {
+            let key = idempotency_key(&req);
+            let module = req.app_data::<Data<ApplicationModule>>().cloned();
+            let (Some(key), Some(module)) = (key, module) else {
+                return service
+                    .call(req)
+                    .await
+                    .map(ServiceResponse::map_into_boxed_body);
+            };
+            let repository: Box<dyn IdempotencyKeyRepository> =
+                match module.provide() {
+                    Ok(repository) => repository,
+                    Err(err) => {
+                        error!("Could not provide the idempotency key repository: {}", err);
+                        return Ok(req.into_response(HttpResponse::from(
+                            InternalServerError::new("Could not reserve the Idempotency-Key"),
+                        )));
+                    }
+                };
+
+            // The body is read to hash it, then put back for the handler.
+            let body = req.extract::<Bytes>().await?;
+            let request_hash = request_hash(&body);
+            let (_, mut payload) = actix_http::h1::Payload::create(true);
+            payload.unread_data(body);
+            req.set_payload(payload.into());
+
+            let lease_id =
+                match repository.reserve(&key, &request_hash, lease).await {
+                    Ok(IdempotencyKeyReservation::Reserved { lease_id }) => lease_id,
+                    Ok(IdempotencyKeyReservation::Completed {
+                        request_hash: stored_hash,
+                        response,
+                    }) if stored_hash == request_hash => {
+                        return Ok(req.into_response(replay(response)));
+                    }
+                    Ok(IdempotencyKeyReservation::InProgress {
+                        request_hash: stored_hash,
+                    }) if stored_hash == request_hash => {
+                        return Ok(req.into_response(HttpResponse::from(Conflict::new(
+                            "A request with this Idempotency-Key is still being handled",
+                        ))));
+                    }
+                    Ok(_) => {
+                        return Ok(req.into_response(HttpResponse::from(
+                            UnprocessableEntity::new(
+                                "The Idempotency-Key was already used for a different request",
+                            ),
+                        )));
+                    }
+                    Err(err) => {
+                        error!("Could not reserve idempotency key: {}", err);
+                        return Ok(req.into_response(HttpResponse::from(
+                            InternalServerError::new("Could not reserve the Idempotency-Key"),
+                        )));
+                    }
+                };
+
+            let res = match service.call(req).await {
+                Ok(res) => res,
+                Err(err) => {
+                    release(&*repository, &key, &lease_id).await;
+                    return Err(err);
+                }
+            };
+
+            // Failures on our side are not stored, so the client can retry them with the key.
+            if res.status().is_server_error() {
+                release(&*repository, &key, &lease_id).await;
+                return Ok(res.map_into_boxed_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = match to_bytes(body).await {
+                Ok(body) => body,
+                Err(_) => {
+                    release(&*repository, &key, &lease_id).await;
+                    let res =
+                        HttpResponse::from(InternalServerError::new("Could not read the response"));
+                    return Ok(ServiceResponse::new(req, res));
+                }
+            };
+
+            let stored = StoredResponse {
+                status_code: res.status().as_u16(),
+                headers: res
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+                body: body.to_vec(),
+            };
+            if let Err(err) = repository.complete(&key, &lease_id, &stored, ttl).await {
+                warn!(
+                    "Could not store the response of an idempotent request: {}",
+                    err
+                );
+            }
+
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(body).map_into_boxed_body(),
+            ))
+        })
+    }
+}
+
+fn idempotency_key(req: &ServiceRequest) -> Option<IdempotencyKey> {
+    if !matches!(*req.method(), Method::POST | Method::PATCH | Method::DELETE) {
+        return None;
+    }
+
+    let key = header(req, IDEMPOTENCY_KEY_HEADER)?;
+    Some(IdempotencyKey {
+        actor_id: header(req, ACTOR_ID_HEADER).unwrap_or_default(),
+        method: req.method().to_string(),
+        path: req.path().to_string(),
+        key,
+    })
+}
+
+fn header(req: &ServiceRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn request_hash(body: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(body))
+}
+
+fn replay(response: StoredResponse) -> HttpResponse {
+    let status =
+        StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut builder = HttpResponse::build(status);
+    for (name, value) in response.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            builder.append_header((name, value));
+        }
+    }
+
+    builder
+        .insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"))
+        .body(response.body)
+}
+
+async fn release(repository: &dyn IdempotencyKeyRepository, key: &IdempotencyKey, lease_id: &str) {
+    if let Err(err) = repository.release(key, lease_id).await {
+        warn!("Could not release idempotency key: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use actix_web::{
+        dev::{Service, ServiceResponse},
+        http::StatusCode,
+        test,
+        web::{self, Data},
+        App, HttpResponse,
+    };
+    use serde_json::{json, Value};
+    use source_control_application::module::get_in_memory_module;
+    use tokio::sync::Semaphore;
+
+    use super::{IdempotencyFactory, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER};
+    use crate::{endpoints::organization::create::create_organization, metadata::ACTOR_ID_HEADER};
+
+    /// Counts the requests it handled, after a permit of `Semaphore` was added.
+    async fn count(handled: Data<AtomicU32>, permits: Data<Semaphore>) -> HttpResponse {
+        permits.acquire().await.unwrap().forget();
+        let count = handled.fetch_add(1, Ordering::SeqCst) + 1;
+        HttpResponse::Created().body(count.to_string())
+    }
+
+    async fn counting_app(
+        lease: Duration,
+        permits: Data<Semaphore>,
+    ) -> impl Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        test::init_service(
+            App::new()
+                .wrap(IdempotencyFactory {
+                    ttl: Duration::from_secs(60),
+                    lease,
+                })
+                .app_data(Data::new(get_in_memory_module()))
+                .app_data(Data::new(AtomicU32::new(0)))
+                .app_data(permits)
+                .route("/first", web::post().to(count))
+                .route("/second", web::post().to(count)),
+        )
+        .await
+    }
+
+    fn request(path: &str, actor_id: &str) -> actix_http::Request {
+        test::TestRequest::post()
+            .uri(path)
+            .insert_header((IDEMPOTENCY_KEY_HEADER, "count"))
+            .insert_header((ACTOR_ID_HEADER, actor_id))
+            .to_request()
+    }
+
+    async fn read_count(res: ServiceResponse) -> (String, bool) {
+        let replayed = res.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER);
+        let body = test::read_body(res).await;
+        (String::from_utf8(body.to_vec()).unwrap(), replayed)
+    }
+
+    #[actix_web::test]
+    async fn should_replay_response_of_request_with_the_same_key() {
+        let app = test::init_service(
+            App::new()
+                .wrap(IdempotencyFactory {
+                    ttl: Duration::from_secs(60),
+                    lease: Duration::from_secs(60),
+                })
+                .app_data(Data::new(get_in_memory_module()))
+                .service(create_organization),
+        )
+        .await;
+        let request = |name: &str| {
+            test::TestRequest::post()
+                .uri("/organizations")
+                .insert_header((IDEMPOTENCY_KEY_HEADER, "create-porti"))
+                .set_json(json!({ "name": name }))
+                .to_request()
+        };
+
+        let first = test::call_service(&app, request("Porti")).await;
+        let first_replayed = first.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER);
+        let first: Value = test::read_body_json(first).await;
+        let retry = test::call_service(&app, request("Porti")).await;
+        let retry_replayed = retry.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER);
+        let retry: Value = test::read_body_json(retry).await;
+        let different = test::call_service(&app, request("Other")).await;
+
+        assert!(!first_replayed);
+        assert!(retry_replayed);
+        assert_eq!(first["id"], retry["id"]);
+        assert_eq!(different.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn should_scope_keys_to_the_actor_and_endpoint() {
+        let app = counting_app(Duration::from_secs(60), Data::new(Semaphore::new(10))).await;
+
+        let first = test::call_service(&app, request("/first", "alice")).await;
+        let other_actor = test::call_service(&app, request("/first", "bob")).await;
+        let other_endpoint = test::call_service(&app, request("/second", "alice")).await;
+        let retry = test::call_service(&app, request("/first", "alice")).await;
+
+        assert_eq!(read_count(first).await, ("1".to_string(), false));
+        assert_eq!(read_count(other_actor).await, ("2".to_string(), false));
+        assert_eq!(read_count(other_endpoint).await, ("3".to_string(), false));
+        assert_eq!(read_count(retry).await, ("1".to_string(), true));
+    }
+
+    #[actix_web::test]
+    async fn should_refuse_a_duplicate_request_while_the_first_is_handled() {
+        let permits = Data::new(Semaphore::new(0));
+        let app = counting_app(Duration::from_secs(60), permits.clone()).await;
+
+        let (first, duplicate) = tokio::join!(
+            test::call_service(&app, request("/first", "alice")),
+            async {
+                let duplicate = test::call_service(&app, request("/first", "alice")).await;
+                permits.add_permits(1);
+                duplicate
+            }
+        );
+        let retry = test::call_service(&app, request("/first", "alice")).await;
+
+        assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+        assert_eq!(read_count(first).await, ("1".to_string(), false));
+        assert_eq!(read_count(retry).await, ("1".to_string(), true));
+    }
+
+    #[actix_web::test]
+    async fn should_take_over_the_key_of_a_request_that_outlived_its_lease() {
+        let permits = Data::new(Semaphore::new(0));
+        let app = counting_app(Duration::ZERO, permits.clone()).await;
+
+        let (first, duplicate, _) = tokio::join!(
+            test::call_service(&app, request("/first", "alice")),
+            test::call_service(&app, request("/first", "alice")),
+            async { permits.add_permits(2) }
+        );
+
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert_eq!(duplicate.status(), StatusCode::CREATED);
+        assert!(!duplicate.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+    }
+}
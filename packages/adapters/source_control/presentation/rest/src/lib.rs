@@ -1,3 +1,6 @@
 pub mod endpoints;
 mod models;
 mod errors;
+mod etag;
+pub mod idempotency;
+mod metadata;
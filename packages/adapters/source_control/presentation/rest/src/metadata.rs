@@ -0,0 +1,28 @@
+use actix_web::{HttpMessage, HttpRequest};
+use source_control_domain::aggregates::metadata::CommandMetadata;
+use tracing_actix_web::RequestId;
+
+pub(crate) const ACTOR_ID_HEADER: &str = "X-Actor-Id";
+const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+/// The request causes the command, and starts a new correlation unless the caller passes one.
+pub fn command_metadata(req: &HttpRequest) -> CommandMetadata {
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|request_id| request_id.to_string());
+
+    CommandMetadata {
+        actor_id: header(req, ACTOR_ID_HEADER),
+        correlation_id: header(req, CORRELATION_ID_HEADER).or_else(|| request_id.clone()),
+        causation_id: request_id,
+    }
+}
+
+fn header(req: &HttpRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,46 @@
+use serde::Serialize;
+use source_control_domain::entities::{branch::Branch, branch_push::BranchPush};
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct BranchDto {
+    id: u64,
+    repository_id: u64,
+    name: String,
+    head: String,
+    based_on: Option<u64>,
+}
+
+impl From<&Branch> for BranchDto {
+    fn from(value: &Branch) -> Self {
+        Self {
+            id: value.id.0,
+            repository_id: value.repository_id.0,
+            name: value.name.clone(),
+            head: value.head.to_string(),
+            based_on: value.based_on.map(|x| x.0),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BranchPushDto {
+    pusher_id: u64,
+    before: String,
+    after: String,
+    /// RFC 3339 timestamp of the push
+    pushed_at: String,
+    forced: bool,
+}
+
+impl From<&BranchPush> for BranchPushDto {
+    fn from(value: &BranchPush) -> Self {
+        Self {
+            pusher_id: value.pusher.0,
+            before: value.before.to_string(),
+            after: value.after.to_string(),
+            pushed_at: value.pushed_at.to_rfc3339(),
+            forced: value.forced,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+use source_control_domain::entities::commit::{Commit, CommitSha};
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct CommitDto {
+    sha: String,
+    message: String,
+    parents: Vec<String>,
+}
+
+impl From<&Commit> for CommitDto {
+    fn from(value: &Commit) -> Self {
+        Self {
+            sha: value.sha.to_string(),
+            message: value.message.clone(),
+            parents: value.parents.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IsAncestorDto {
+    pub ancestor: String,
+    pub descendant: String,
+    pub is_ancestor: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MergeBasesDto {
+    merge_bases: Vec<String>,
+}
+
+impl From<&Vec<CommitSha>> for MergeBasesDto {
+    fn from(value: &Vec<CommitSha>) -> Self {
+        Self {
+            merge_bases: value.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
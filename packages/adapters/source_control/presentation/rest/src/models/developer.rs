@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use source_control_domain::entities::developer::{Developer, DeveloperIdentity};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeveloperIdentityDto {
+    PlatformLogin { platform: String, login: String },
+    CommitEmail { email: String },
+}
+
+impl From<&DeveloperIdentity> for DeveloperIdentityDto {
+    fn from(value: &DeveloperIdentity) -> Self {
+        match value {
+            DeveloperIdentity::PlatformLogin { platform, login } => {
+                DeveloperIdentityDto::PlatformLogin {
+                    platform: platform.clone(),
+                    login: login.clone(),
+                }
+            }
+            DeveloperIdentity::CommitEmail { email } => DeveloperIdentityDto::CommitEmail {
+                email: email.clone(),
+            },
+        }
+    }
+}
+
+impl From<&DeveloperIdentityDto> for DeveloperIdentity {
+    fn from(value: &DeveloperIdentityDto) -> Self {
+        match value {
+            DeveloperIdentityDto::PlatformLogin { platform, login } => {
+                DeveloperIdentity::platform_login(platform, login)
+            }
+            DeveloperIdentityDto::CommitEmail { email } => DeveloperIdentity::commit_email(email),
+        }
+    }
+}
+
+/// Identifies a developer identity in a query string, either by `platform` and `login` or by
+/// `email`.
+#[derive(Deserialize, Debug, IntoParams)]
+pub struct DeveloperIdentityParams {
+    platform: Option<String>,
+    login: Option<String>,
+    email: Option<String>,
+}
+
+impl DeveloperIdentityParams {
+    pub fn to_identity(&self) -> Option<DeveloperIdentity> {
+        match (&self.platform, &self.login, &self.email) {
+            (Some(platform), Some(login), None) => {
+                Some(DeveloperIdentity::platform_login(platform, login))
+            }
+            (None, None, Some(email)) => Some(DeveloperIdentity::commit_email(email)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeveloperDto {
+    id: u64,
+    username: String,
+    identities: Vec<DeveloperIdentityDto>,
+    merged_into: Option<u64>,
+}
+
+impl From<&Developer> for DeveloperDto {
+    fn from(value: &Developer) -> Self {
+        Self {
+            id: value.id.0,
+            username: value.username.clone(),
+            identities: value.identities.iter().map(|x| x.into()).collect(),
+            merged_into: value.merged_into.map(|x| x.0),
+        }
+    }
+}
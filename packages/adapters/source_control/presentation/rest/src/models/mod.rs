@@ -3,3 +3,11 @@ pub mod platform_account;
 pub mod platform;
 pub mod paginated_result;
 pub mod organization_events;
+pub mod repository;
+pub mod pull_request;
+pub mod commit;
+pub mod branch;
+pub mod developer;
+pub mod projection_rebuild;
+pub mod parked_event;
+pub mod projection_worker;
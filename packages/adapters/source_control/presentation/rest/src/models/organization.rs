@@ -10,6 +10,7 @@ pub struct OrganizationDto {
     id: u64,
     name: String,
     platform_accounts: Vec<PlatformAccountDto>,
+    archived: bool,
 }
 
 impl From<&Organization> for OrganizationDto {
@@ -18,6 +19,7 @@ impl From<&Organization> for OrganizationDto {
             id: value.id.0,
             name: value.name.clone(),
             platform_accounts: value.platform_accounts.iter().map(|x| x.into()).collect(),
+            archived: value.archived,
         }
     }
 }
@@ -27,6 +29,7 @@ pub struct PartialOrganizationDto {
     id: u64,
     name: String,
     platform_account_count: i64,
+    archived: bool,
 }
 
 impl From<&OrganizationResult> for PartialOrganizationDto {
@@ -35,6 +38,7 @@ impl From<&OrganizationResult> for PartialOrganizationDto {
             id: value.id.0,
             name: value.name.clone(),
             platform_account_count: value.paltform_account_count,
+            archived: value.archived,
         }
     }
 }
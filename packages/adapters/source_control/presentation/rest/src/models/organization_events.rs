@@ -1,5 +1,8 @@
 use serde::Serialize;
-use source_control_domain::aggregates::organization::OrganizationEvent;
+use source_control_domain::aggregates::{
+    metadata::{EventMetadata, LoggedEvent, TraceContext},
+    organization::OrganizationEvent,
+};
 use utoipa::ToSchema;
 
 use super::platform_account::PlatformAccountDto;
@@ -18,6 +21,19 @@ pub enum OrganizationEventDto {
         organization_id: u64,
         name: String,
     },
+    RenameOrganization {
+        organization_id: u64,
+        name: String,
+    },
+    ArchiveOrganization {
+        organization_id: u64,
+    },
+    UnarchiveOrganization {
+        organization_id: u64,
+    },
+    DeleteOrganization {
+        organization_id: u64,
+    },
 }
 
 impl From<&OrganizationEvent> for OrganizationEventDto {
@@ -44,6 +60,81 @@ impl From<&OrganizationEvent> for OrganizationEventDto {
                 organization_id: organization_id.0,
                 name: name.clone(),
             },
+            OrganizationEvent::RenameOrganization {
+                organization_id,
+                name,
+            } => OrganizationEventDto::RenameOrganization {
+                organization_id: organization_id.0,
+                name: name.clone(),
+            },
+            OrganizationEvent::ArchiveOrganization { organization_id } => {
+                OrganizationEventDto::ArchiveOrganization {
+                    organization_id: organization_id.0,
+                }
+            }
+            OrganizationEvent::UnarchiveOrganization { organization_id } => {
+                OrganizationEventDto::UnarchiveOrganization {
+                    organization_id: organization_id.0,
+                }
+            }
+            OrganizationEvent::DeleteOrganization { organization_id } => {
+                OrganizationEventDto::DeleteOrganization {
+                    organization_id: organization_id.0,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationLogEntryDto {
+    event: OrganizationEventDto,
+    /// Missing for events stored before metadata was recorded
+    metadata: Option<EventMetadataDto>,
+}
+
+impl From<&LoggedEvent<OrganizationEvent>> for OrganizationLogEntryDto {
+    fn from(value: &LoggedEvent<OrganizationEvent>) -> Self {
+        Self {
+            event: (&value.event).into(),
+            metadata: value.metadata.as_ref().map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EventMetadataDto {
+    /// RFC 3339 timestamp of when the event was stored
+    occurred_at: String,
+    actor_id: Option<String>,
+    correlation_id: Option<String>,
+    causation_id: Option<String>,
+    trace_context: Option<TraceContextDto>,
+}
+
+impl From<&EventMetadata> for EventMetadataDto {
+    fn from(value: &EventMetadata) -> Self {
+        Self {
+            occurred_at: value.occurred_at.to_rfc3339(),
+            actor_id: value.actor_id.clone(),
+            correlation_id: value.correlation_id.clone(),
+            causation_id: value.causation_id.clone(),
+            trace_context: value.trace_context.as_ref().map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TraceContextDto {
+    traceparent: String,
+    tracestate: Option<String>,
+}
+
+impl From<&TraceContext> for TraceContextDto {
+    fn from(value: &TraceContext) -> Self {
+        Self {
+            traceparent: value.traceparent.clone(),
+            tracestate: value.tracestate.clone(),
         }
     }
 }
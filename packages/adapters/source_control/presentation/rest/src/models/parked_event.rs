@@ -0,0 +1,57 @@
+use serde::Serialize;
+use source_control_application::queries::{
+    get_parked_events::ParkedEvent, get_persistent_subscriptions::PersistentSubscription,
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct PersistentSubscriptionDto {
+    name: String,
+    status: String,
+    parked_event_count: usize,
+}
+
+impl From<&PersistentSubscription> for PersistentSubscriptionDto {
+    fn from(value: &PersistentSubscription) -> Self {
+        Self {
+            name: value.name.clone(),
+            status: value.status.clone(),
+            parked_event_count: value.parked_event_count,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ParkedEventDto {
+    /// `/parked/replay?up_to={parked_revision}` replays the parked events up to and including
+    /// this one
+    parked_revision: u64,
+    event_id: Option<String>,
+    stream_id: Option<String>,
+    event_type: Option<String>,
+    /// The event as it is projected, `null` when it could not be decoded
+    #[schema(value_type = Option<Object>)]
+    event: Option<serde_json::Value>,
+    decode_error: Option<String>,
+    /// Reason the subscription gave when it parked the event
+    reason: Option<String>,
+    /// `null` for events parked before the retry count was recorded
+    retry_count: Option<usize>,
+    parked_at: Option<String>,
+}
+
+impl From<&ParkedEvent> for ParkedEventDto {
+    fn from(value: &ParkedEvent) -> Self {
+        Self {
+            parked_revision: value.parked_revision,
+            event_id: value.event_id.clone(),
+            stream_id: value.stream_id.clone(),
+            event_type: value.event_type.clone(),
+            event: value.event.as_ref().ok().cloned(),
+            decode_error: value.event.as_ref().err().cloned(),
+            reason: value.reason.clone(),
+            retry_count: value.retry_count,
+            parked_at: value.parked_at.clone(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use serde::Serialize;
+use source_control_application::commands::rebuild_organization_projection::{
+    RebuildProgress, RebuildState,
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProjectionRebuildStateDto {
+    NotStarted,
+    Replaying,
+    Swapping,
+    Completed,
+    Failed { reason: String },
+}
+
+impl From<&RebuildState> for ProjectionRebuildStateDto {
+    fn from(value: &RebuildState) -> Self {
+        match value {
+            RebuildState::NotStarted => ProjectionRebuildStateDto::NotStarted,
+            RebuildState::Replaying => ProjectionRebuildStateDto::Replaying,
+            RebuildState::Swapping => ProjectionRebuildStateDto::Swapping,
+            RebuildState::Completed => ProjectionRebuildStateDto::Completed,
+            RebuildState::Failed { reason } => ProjectionRebuildStateDto::Failed {
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProjectionRebuildDto {
+    state: ProjectionRebuildStateDto,
+    events_replayed: u64,
+    /// Commit position in `$all`, or the global position in postgres, of the last replayed event
+    commit_position: Option<u64>,
+    /// RFC 3339 timestamp
+    started_at: Option<String>,
+    /// RFC 3339 timestamp
+    finished_at: Option<String>,
+}
+
+impl From<&RebuildProgress> for ProjectionRebuildDto {
+    fn from(value: &RebuildProgress) -> Self {
+        Self {
+            state: (&value.state).into(),
+            events_replayed: value.events_replayed,
+            commit_position: value.position.map(|position| position.commit_position),
+            started_at: value.started_at.map(|x| x.to_rfc3339()),
+            finished_at: value.finished_at.map(|x| x.to_rfc3339()),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+use source_control_application::projection_host::{WorkerState, WorkerStatus};
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProjectionWorkerStateDto {
+    Running,
+    BackingOff {
+        attempt: u32,
+        /// RFC 3339 timestamp
+        retry_at: String,
+    },
+    Stopped,
+}
+
+impl From<&WorkerState> for ProjectionWorkerStateDto {
+    fn from(value: &WorkerState) -> Self {
+        match value {
+            WorkerState::Running => ProjectionWorkerStateDto::Running,
+            WorkerState::BackingOff { attempt, retry_at } => ProjectionWorkerStateDto::BackingOff {
+                attempt: *attempt,
+                retry_at: retry_at.to_rfc3339(),
+            },
+            WorkerState::Stopped => ProjectionWorkerStateDto::Stopped,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ProjectionWorkerDto {
+    projection: String,
+    worker_id: u32,
+    state: ProjectionWorkerStateDto,
+    restarts: u64,
+    last_error: Option<String>,
+    /// RFC 3339 timestamp of when the worker entered its state
+    since: String,
+}
+
+impl From<&WorkerStatus> for ProjectionWorkerDto {
+    fn from(value: &WorkerStatus) -> Self {
+        Self {
+            projection: value.projection.clone(),
+            worker_id: value.worker_id,
+            state: (&value.state).into(),
+            restarts: value.restarts,
+            last_error: value.last_error.clone(),
+            since: value.since.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use source_control_domain::entities::{
+    pull_request::{PullRequest, PullRequestState},
+    review::Review,
+};
+use source_control_postgres_persistence_adapter::queries::get_pull_requests::PullRequestResult;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PullRequestStateDto {
+    Open,
+    Merged,
+    Closed,
+}
+
+impl PullRequestStateDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullRequestStateDto::Open => "open",
+            PullRequestStateDto::Merged => "merged",
+            PullRequestStateDto::Closed => "closed",
+        }
+    }
+}
+
+impl From<PullRequestState> for PullRequestStateDto {
+    fn from(value: PullRequestState) -> Self {
+        match value {
+            PullRequestState::Open => PullRequestStateDto::Open,
+            PullRequestState::Merged => PullRequestStateDto::Merged,
+            PullRequestState::Closed => PullRequestStateDto::Closed,
+        }
+    }
+}
+
+impl From<PullRequestStateDto> for PullRequestState {
+    fn from(value: PullRequestStateDto) -> Self {
+        match value {
+            PullRequestStateDto::Open => PullRequestState::Open,
+            PullRequestStateDto::Merged => PullRequestState::Merged,
+            PullRequestStateDto::Closed => PullRequestState::Closed,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReviewDto {
+    developer_id: u64,
+    status: String,
+    accepting: bool,
+}
+
+impl From<&Review> for ReviewDto {
+    fn from(value: &Review) -> Self {
+        Self {
+            developer_id: value.developer_id.0,
+            status: value.status.name.clone(),
+            accepting: value.status.accepting,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PullRequestDto {
+    id: u64,
+    repository_id: u64,
+    number: u64,
+    author_id: u64,
+    title: String,
+    description: String,
+    state: PullRequestStateDto,
+    reviews: Vec<ReviewDto>,
+}
+
+impl From<&PullRequest> for PullRequestDto {
+    fn from(value: &PullRequest) -> Self {
+        Self {
+            id: value.id.0,
+            repository_id: value.repository.0,
+            number: value.number,
+            author_id: value.author.0,
+            title: value.title.clone(),
+            description: value.description.clone(),
+            state: value.state.into(),
+            reviews: value.reviews.iter().map(|r| r.into()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PullRequestSummaryDto {
+    id: u64,
+    repository_id: u64,
+    number: u64,
+    author_id: u64,
+    title: String,
+    state: PullRequestStateDto,
+    review_count: u64,
+    approval_count: u64,
+}
+
+impl From<&PullRequestResult> for PullRequestSummaryDto {
+    fn from(value: &PullRequestResult) -> Self {
+        Self {
+            id: value.id.0,
+            repository_id: value.repository_id.0,
+            number: value.number,
+            author_id: value.author_id.0,
+            title: value.title.clone(),
+            state: value.state.into(),
+            review_count: value.review_count,
+            approval_count: value.approval_count,
+        }
+    }
+}
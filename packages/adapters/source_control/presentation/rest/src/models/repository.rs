@@ -0,0 +1,37 @@
+use serde::Serialize;
+use source_control_domain::entities::repository::Repository;
+use source_control_postgres_persistence_adapter::queries::get_repositories::RepositoryResult;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct RepositoryDto {
+    id: u64,
+    organization_id: u64,
+    platform_account_id: u64,
+    name: String,
+    archived: bool,
+}
+
+impl From<&Repository> for RepositoryDto {
+    fn from(value: &Repository) -> Self {
+        Self {
+            id: value.id.0,
+            organization_id: value.organization_id.0,
+            platform_account_id: value.platform_account.0,
+            name: value.name.clone(),
+            archived: value.archived,
+        }
+    }
+}
+
+impl From<&RepositoryResult> for RepositoryDto {
+    fn from(value: &RepositoryResult) -> Self {
+        Self {
+            id: value.id.0,
+            organization_id: value.organization_id.0,
+            platform_account_id: value.platform_account_id.0,
+            name: value.name.clone(),
+            archived: value.archived,
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use shaku::{Interface, Provider};
 use source_control_domain::{
-    aggregates::organization::OrganizationError,
+    aggregates::{metadata::CommandMetadata, organization::OrganizationError},
     entities::organization::{Organization, OrganizationId},
     factories::platform_account::PlatformAccountFactory,
     repositories::organization_repository::{
@@ -11,11 +13,18 @@ use source_control_domain::{
 use thiserror::Error;
 use tracing::instrument;
 
+use crate::versioned::Versioned;
+
+use super::conflict_retry::{retry_on_conflict, ConflictError, ConflictRetry};
+
 #[derive(Debug)]
 pub struct AddPlatformAccountCommand {
     pub organization_id: u64,
     pub name: String,
     pub platform_name: String,
+    /// Revision the client read the organization at, the command fails when it changed since.
+    pub expected_revision: Option<u64>,
+    pub metadata: CommandMetadata,
 }
 
 #[async_trait]
@@ -23,7 +32,7 @@ pub trait AddPlatformAccountCommandHandler: Interface {
     async fn handle(
         &self,
         command: AddPlatformAccountCommand,
-    ) -> Result<Organization, AddPlatformAccountCommandError> ;
+    ) -> Result<Versioned<Organization>, AddPlatformAccountCommandError>;
 }
 
 #[derive(Provider)]
@@ -33,6 +42,8 @@ pub struct AddPlatformAccountCommandHandlerImpl {
     pub repository: Box<dyn OrganizationRepository>,
     #[shaku(provide)]
     pub platform_account_factory: Box<dyn PlatformAccountFactory>,
+    #[shaku(inject)]
+    pub conflict_retry: Arc<dyn ConflictRetry>,
 }
 
 #[async_trait]
@@ -41,7 +52,19 @@ impl AddPlatformAccountCommandHandler for AddPlatformAccountCommandHandlerImpl {
     async fn handle(
         &self,
         command: AddPlatformAccountCommand,
-    ) -> Result<Organization, AddPlatformAccountCommandError> {
+    ) -> Result<Versioned<Organization>, AddPlatformAccountCommandError> {
+        retry_on_conflict(&*self.conflict_retry, "AddPlatformAccount", || {
+            self.attempt(&command)
+        })
+        .await
+    }
+}
+
+impl AddPlatformAccountCommandHandlerImpl {
+    async fn attempt(
+        &self,
+        command: &AddPlatformAccountCommand,
+    ) -> Result<Versioned<Organization>, AddPlatformAccountCommandError> {
         let mut aggregate = match self
             .repository
             .get(OrganizationId(command.organization_id))
@@ -55,13 +78,22 @@ impl AddPlatformAccountCommandHandler for AddPlatformAccountCommandHandlerImpl {
                     })
                 }
                 GetOrganizationError::Connection => Err(AddPlatformAccountCommandError::Connection),
-                GetOrganizationError::Unexpected => Err(AddPlatformAccountCommandError::Unexpected),
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
+                    Err(AddPlatformAccountCommandError::Unexpected)
+                }
             },
         }?;
 
+        if command
+            .expected_revision
+            .is_some_and(|revision| revision != aggregate.latest_revision)
+        {
+            return Err(AddPlatformAccountCommandError::PreconditionFailed);
+        }
+
         let platform_account = self.platform_account_factory.create(
-            command.name,
-            command.platform_name,
+            command.name.clone(),
+            command.platform_name.clone(),
             &aggregate.root,
         );
 
@@ -71,13 +103,25 @@ impl AddPlatformAccountCommandHandler for AddPlatformAccountCommandHandlerImpl {
                 OrganizationError::AccountAlreadyAdded { .. } => {
                     Err(AddPlatformAccountCommandError::AccountAlreadyAdded)
                 }
-                OrganizationError::AccountNotLinked { .. } => panic!(""),
+                OrganizationError::Archived { .. } => Err(AddPlatformAccountCommandError::Archived),
+                OrganizationError::Deleted { organization_id } => {
+                    Err(AddPlatformAccountCommandError::NotFound {
+                        organization_id: organization_id.0,
+                    })
+                }
+                OrganizationError::AccountNotLinked { .. }
+                | OrganizationError::NotArchived { .. }
+                | OrganizationError::NameUnchanged { .. } => panic!(""),
             },
         }?;
         let root = aggregate.root.clone();
+        let revision = aggregate.revision_after_save();
 
-        match self.repository.save(aggregate).await {
-            Ok(_) => Ok(root),
+        match self.repository.save(aggregate, command.metadata.clone()).await {
+            Ok(_) => Ok(Versioned {
+                value: root,
+                revision,
+            }),
             Err(err) => match err {
                 SaveOrganizationError::Connection => {
                     Err(AddPlatformAccountCommandError::Connection)
@@ -103,4 +147,14 @@ pub enum AddPlatformAccountCommandError {
     NotFound { organization_id: u64 },
     #[error("Account already added")]
     AccountAlreadyAdded,
+    #[error("The organization is archived")]
+    Archived,
+    #[error("The organization changed since the expected revision")]
+    PreconditionFailed,
+}
+
+impl ConflictError for AddPlatformAccountCommandError {
+    fn is_conflict(&self) -> bool {
+        matches!(self, AddPlatformAccountCommandError::Conflict)
+    }
 }
@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::repository::RepositoryError,
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    repositories::repository_repository::{
+        GetRepositoryError, RepositoryRepository, SaveRepositoryError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct ArchiveRepositoryCommand {
+    pub organization_id: u64,
+    pub platform_account_id: u64,
+    pub repository_id: u64,
+}
+
+#[async_trait]
+pub trait ArchiveRepositoryCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: ArchiveRepositoryCommand,
+    ) -> Result<Repository, ArchiveRepositoryCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = ArchiveRepositoryCommandHandler)]
+pub struct ArchiveRepositoryCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn RepositoryRepository>,
+}
+
+#[async_trait]
+impl ArchiveRepositoryCommandHandler for ArchiveRepositoryCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: ArchiveRepositoryCommand,
+    ) -> Result<Repository, ArchiveRepositoryCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(RepositoryId(command.repository_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(ArchiveRepositoryCommandError::NotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(ArchiveRepositoryCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(ArchiveRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.organization_id != OrganizationId(command.organization_id)
+            || aggregate.root.platform_account != PlatformAccountId(command.platform_account_id)
+        {
+            return Err(ArchiveRepositoryCommandError::NotFound {
+                repository_id: command.repository_id,
+            });
+        }
+
+        match aggregate.archive() {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                RepositoryError::Archived { .. } => {
+                    Err(ArchiveRepositoryCommandError::AlreadyArchived)
+                }
+                RepositoryError::NameUnchanged { .. }
+                | RepositoryError::AlreadyOnPlatformAccount { .. }
+                | RepositoryError::AccountNotLinked { .. } => {
+                    Err(ArchiveRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveRepositoryError::Connection => Err(ArchiveRepositoryCommandError::Connection),
+                SaveRepositoryError::Unexpected => Err(ArchiveRepositoryCommandError::Unexpected),
+                SaveRepositoryError::Conflict => Err(ArchiveRepositoryCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ArchiveRepositoryCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The repository could not be found")]
+    NotFound { repository_id: u64 },
+    #[error("The repository is already archived")]
+    AlreadyArchived,
+}
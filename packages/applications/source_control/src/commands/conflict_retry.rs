@@ -0,0 +1,198 @@
+use std::{future::Future, time::Duration};
+
+use opentelemetry::{
+    metrics::{Counter, Meter},
+    KeyValue,
+};
+use rand::Rng;
+use shaku::{Component, Interface};
+use tracing::warn;
+
+/// Errors of commands that can fail because another client saved the aggregate first.
+pub trait ConflictError {
+    fn is_conflict(&self) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConflictRetrySettings {
+    /// Attempts including the first one, `1` returns the first conflict.
+    pub max_attempts: u32,
+    /// Upper bound of the delay before the first retry, doubled for every retry after it.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ConflictRetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+pub struct ConflictRetryMetrics {
+    pub retries: Counter<u64>,
+    pub exhausted: Counter<u64>,
+}
+
+impl ConflictRetryMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            retries: meter
+                .u64_counter("command.conflict.retries.total")
+                .with_description("Amount of commands retried after a conflict")
+                .with_unit("retry")
+                .build(),
+            exhausted: meter
+                .u64_counter("command.conflict.exhausted.total")
+                .with_description("Amount of commands that still conflicted after every attempt")
+                .with_unit("command")
+                .build(),
+        }
+    }
+}
+
+pub trait ConflictRetry: Interface {
+    fn settings(&self) -> ConflictRetrySettings;
+    fn metrics(&self) -> &ConflictRetryMetrics;
+}
+
+#[derive(Component)]
+#[shaku(interface = ConflictRetry)]
+pub struct ConflictRetryImpl {
+    #[shaku(default)]
+    settings: ConflictRetrySettings,
+    metrics: ConflictRetryMetrics,
+}
+
+impl ConflictRetry for ConflictRetryImpl {
+    fn settings(&self) -> ConflictRetrySettings {
+        self.settings
+    }
+
+    fn metrics(&self) -> &ConflictRetryMetrics {
+        &self.metrics
+    }
+}
+
+/// Runs `attempt` again while it conflicts, until the attempts run out. Every attempt has to
+/// load the aggregate again, so it decides on what the other client saved.
+pub async fn retry_on_conflict<T, E, F, Fut>(
+    retry: &dyn ConflictRetry,
+    command: &'static str,
+    mut attempt: F,
+) -> Result<T, E>
+where
+    E: ConflictError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let settings = retry.settings();
+    let attributes = [KeyValue::new("command", command)];
+
+    let mut attempts = 1;
+    loop {
+        match attempt().await {
+            Err(err) if err.is_conflict() => {
+                if attempts >= settings.max_attempts {
+                    retry.metrics().exhausted.add(1, &attributes);
+                    return Err(err);
+                }
+
+                warn!(command, attempts, "Command conflicted, retrying");
+                retry.metrics().retries.add(1, &attributes);
+                tokio::time::sleep(backoff(&settings, attempts)).await;
+                attempts += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// A random delay up to the exponential backoff, so clients that conflicted with each other
+/// don't retry at the same time again.
+fn backoff(settings: &ConflictRetrySettings, retry: u32) -> Duration {
+    let ceiling = settings
+        .base_delay
+        .saturating_mul(2u32.saturating_pow(retry - 1))
+        .min(settings.max_delay);
+
+    rand::rng().random_range(Duration::ZERO..=ceiling)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, time::Duration};
+
+    use opentelemetry::global;
+
+    use super::{
+        backoff, retry_on_conflict, ConflictError, ConflictRetryImpl, ConflictRetryMetrics,
+        ConflictRetrySettings,
+    };
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Conflict;
+
+    impl ConflictError for Conflict {
+        fn is_conflict(&self) -> bool {
+            true
+        }
+    }
+
+    fn conflict_retry(max_attempts: u32) -> ConflictRetryImpl {
+        ConflictRetryImpl {
+            settings: ConflictRetrySettings {
+                max_attempts,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            },
+            metrics: ConflictRetryMetrics::new(&global::meter("test")),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_succeed_after_conflicts() {
+        let attempts = Cell::new(0);
+
+        let result = retry_on_conflict(&conflict_retry(3), "Test", || async {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() {
+                3 => Ok(()),
+                _ => Err(Conflict),
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_return_conflict_when_attempts_run_out() {
+        let attempts = Cell::new(0);
+
+        let result = retry_on_conflict(&conflict_retry(3), "Test", || async {
+            attempts.set(attempts.get() + 1);
+            Err::<(), _>(Conflict)
+        })
+        .await;
+
+        assert_eq!(result, Err(Conflict));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn should_keep_backoff_below_max_delay() {
+        let settings = ConflictRetrySettings {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        };
+
+        assert!(backoff(&settings, 1) <= Duration::from_millis(10));
+        assert!(backoff(&settings, 9) <= Duration::from_millis(50));
+    }
+}
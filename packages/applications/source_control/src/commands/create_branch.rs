@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::branch::{BranchAggregate, BranchError},
+    entities::{
+        branch::{Branch, BranchId},
+        commit::CommitSha,
+        repository::RepositoryId,
+    },
+    repositories::{
+        branch_repository::{BranchRepository, CreateBranchError, GetBranchError, SaveBranchError},
+        commit_graph_repository::{CommitGraphRepository, GetCommitGraphError},
+        repository_repository::{GetRepositoryError, RepositoryRepository},
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct CreateBranchCommand {
+    pub repository_id: u64,
+    pub name: String,
+    pub head: String,
+    pub based_on: Option<String>,
+}
+
+#[async_trait]
+pub trait CreateBranchCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: CreateBranchCommand,
+    ) -> Result<Branch, CreateBranchCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = CreateBranchCommandHandler)]
+pub struct CreateBranchCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository_repository: Box<dyn RepositoryRepository>,
+    #[shaku(provide)]
+    pub commit_graph_repository: Box<dyn CommitGraphRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn BranchRepository>,
+}
+
+#[async_trait]
+impl CreateBranchCommandHandler for CreateBranchCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: CreateBranchCommand,
+    ) -> Result<Branch, CreateBranchCommandError> {
+        let head = CommitSha::from_string(&command.head).map_err(|_| {
+            CreateBranchCommandError::InvalidSha {
+                sha: command.head.clone(),
+            }
+        })?;
+
+        let repository = match self
+            .repository_repository
+            .get(RepositoryId(command.repository_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(CreateBranchCommandError::RepositoryNotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(CreateBranchCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(CreateBranchCommandError::Unexpected)
+                }
+            },
+        }?;
+        let repository_id = repository.root.id;
+
+        if repository.root.archived {
+            return Err(CreateBranchCommandError::RepositoryArchived {
+                repository_id: command.repository_id,
+            });
+        }
+
+        let commit_graph = match self.commit_graph_repository.get(repository_id).await {
+            Ok(agg) => Ok(agg),
+            Err(GetCommitGraphError::Connection) => Err(CreateBranchCommandError::Connection),
+            Err(GetCommitGraphError::Unexpected | GetCommitGraphError::Corrupt { .. }) => {
+                Err(CreateBranchCommandError::Unexpected)
+            }
+        }?;
+        if !commit_graph.root.has_commit(head) {
+            return Err(CreateBranchCommandError::UnknownCommit { sha: command.head });
+        }
+
+        let based_on = match &command.based_on {
+            Some(name) => {
+                let based_on_id = BranchId::from_name(repository_id, name);
+                match self.get_branch(based_on_id).await? {
+                    Some(branch) if !branch.root.deleted => Ok(Some(based_on_id)),
+                    _ => Err(CreateBranchCommandError::BasedOnNotFound { name: name.clone() }),
+                }
+            }
+            None => Ok(None),
+        }?;
+
+        let branch_id = BranchId::from_name(repository_id, &command.name);
+        let Some(mut aggregate) = self.get_branch(branch_id).await? else {
+            let res = self
+                .repository
+                .create(repository_id, command.name, head, based_on)
+                .await;
+
+            return res.map_err(|err| match err {
+                CreateBranchError::Connection => CreateBranchCommandError::Connection,
+                CreateBranchError::Unexpected => CreateBranchCommandError::Unexpected,
+                CreateBranchError::Conflict => CreateBranchCommandError::Conflict,
+            });
+        };
+
+        match aggregate.recreate(head, based_on) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                BranchError::NotDeleted { .. } => Err(CreateBranchCommandError::AlreadyExists),
+                BranchError::Deleted { .. }
+                | BranchError::HeadUnchanged { .. }
+                | BranchError::UnknownCommit { .. } => Err(CreateBranchCommandError::Unexpected),
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveBranchError::Connection => Err(CreateBranchCommandError::Connection),
+                SaveBranchError::Unexpected => Err(CreateBranchCommandError::Unexpected),
+                SaveBranchError::Conflict => Err(CreateBranchCommandError::Conflict),
+            },
+        }
+    }
+}
+
+impl CreateBranchCommandHandlerImpl {
+    async fn get_branch(
+        &self,
+        branch_id: BranchId,
+    ) -> Result<Option<BranchAggregate>, CreateBranchCommandError> {
+        match self.repository.get(branch_id).await {
+            Ok(agg) => Ok(Some(agg)),
+            Err(GetBranchError::NotFound { .. }) => Ok(None),
+            Err(GetBranchError::Connection) => Err(CreateBranchCommandError::Connection),
+            Err(GetBranchError::Unexpected | GetBranchError::Corrupt { .. }) => {
+                Err(CreateBranchCommandError::Unexpected)
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateBranchCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The repository could not be found")]
+    RepositoryNotFound { repository_id: u64 },
+    #[error("The repository is archived")]
+    RepositoryArchived { repository_id: u64 },
+    #[error("{sha} is not a valid commit sha")]
+    InvalidSha { sha: String },
+    #[error("Commit {sha} has not been recorded")]
+    UnknownCommit { sha: String },
+    #[error("The branch {name} to base the new branch on could not be found")]
+    BasedOnNotFound { name: String },
+    #[error("The branch already exists")]
+    AlreadyExists,
+}
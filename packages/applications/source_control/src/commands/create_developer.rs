@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::developer::{Developer, DeveloperId},
+    factories::id_generator::IdGenerator,
+    repositories::{
+        developer_repository::{CreateDeveloperError, DeveloperRepository},
+        developer_username_repository::{
+            DeveloperUsernameRepository, ReserveDeveloperUsernameError,
+        },
+    },
+};
+use thiserror::Error;
+use tracing::warn;
+
+pub struct CreateDeveloperCommand {
+    pub username: String,
+}
+
+#[async_trait]
+pub trait CreateDeveloperCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: CreateDeveloperCommand,
+    ) -> Result<Developer, CreateDeveloperCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = CreateDeveloperCommandHandler)]
+pub struct CreateDeveloperCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+    #[shaku(provide)]
+    pub usernames: Box<dyn DeveloperUsernameRepository>,
+    #[shaku(inject)]
+    pub id_generator: Arc<dyn IdGenerator>,
+}
+
+#[async_trait]
+impl CreateDeveloperCommandHandler for CreateDeveloperCommandHandlerImpl {
+    async fn handle(
+        &self,
+        command: CreateDeveloperCommand,
+    ) -> Result<Developer, CreateDeveloperCommandError> {
+        let developer_id = DeveloperId(self.id_generator.next_id());
+
+        self.usernames
+            .reserve(&command.username, developer_id)
+            .await
+            .map_err(|err| match err {
+                ReserveDeveloperUsernameError::Taken { .. } => {
+                    CreateDeveloperCommandError::AlreadyExists
+                }
+                ReserveDeveloperUsernameError::Connection => {
+                    CreateDeveloperCommandError::Connection
+                }
+                ReserveDeveloperUsernameError::Unexpected => {
+                    CreateDeveloperCommandError::Unexpected
+                }
+            })?;
+
+        let res = self
+            .repository
+            .create(developer_id, command.username.clone())
+            .await;
+
+        if res.is_err() {
+            if let Err(err) = self
+                .usernames
+                .release(&command.username, developer_id)
+                .await
+            {
+                warn!(
+                    error = format!("{:?}", err),
+                    "Failed to release the username of a developer that was not created"
+                );
+            }
+        }
+
+        res.map_err(|err| match err {
+            CreateDeveloperError::Connection => CreateDeveloperCommandError::Connection,
+            CreateDeveloperError::Unexpected => CreateDeveloperCommandError::Unexpected,
+            // The id was just generated, so its stream only exists when ids repeat
+            CreateDeveloperError::Conflict => CreateDeveloperCommandError::Unexpected,
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateDeveloperCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("A developer with this username already exists")]
+    AlreadyExists,
+}
@@ -1,13 +1,25 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use shaku::{Interface, Provider};
 use source_control_domain::{
-    entities::organization::Organization,
-    repositories::organization_repository::{CreateOrganizationError, OrganizationRepository},
+    aggregates::metadata::CommandMetadata,
+    entities::organization::{Organization, OrganizationId},
+    factories::id_generator::IdGenerator,
+    repositories::{
+        organization_name_repository::{OrganizationNameRepository, ReserveOrganizationNameError},
+        organization_repository::{CreateOrganizationError, OrganizationRepository},
+    },
 };
 use thiserror::Error;
+use tracing::{instrument, warn};
+
+use crate::versioned::Versioned;
 
+#[derive(Debug)]
 pub struct CreateOrganizationCommand {
     pub name: String,
+    pub metadata: CommandMetadata,
 }
 
 #[async_trait]
@@ -15,7 +27,7 @@ pub trait CreateOrganizationCommandHandler: Interface {
     async fn handle(
         &self,
         command: CreateOrganizationCommand,
-    ) -> Result<Organization, CreateOrganizationCommandError>;
+    ) -> Result<Versioned<Organization>, CreateOrganizationCommandError>;
 }
 
 #[derive(Provider)]
@@ -23,17 +35,56 @@ pub trait CreateOrganizationCommandHandler: Interface {
 pub struct CreateOrganizationCommandHandlerImpl {
     #[shaku(provide)]
     pub repository: Box<dyn OrganizationRepository>,
+    #[shaku(provide)]
+    pub names: Box<dyn OrganizationNameRepository>,
+    #[shaku(inject)]
+    pub id_generator: Arc<dyn IdGenerator>,
 }
 
 #[async_trait]
 impl CreateOrganizationCommandHandler for CreateOrganizationCommandHandlerImpl {
+    #[instrument(skip(self))]
     async fn handle(
         &self,
         command: CreateOrganizationCommand,
-    ) -> Result<Organization, CreateOrganizationCommandError> {
-        let res = self.repository.create(command.name).await;
+    ) -> Result<Versioned<Organization>, CreateOrganizationCommandError> {
+        let organization_id = OrganizationId(self.id_generator.next_id());
+
+        self.names
+            .reserve(&command.name, organization_id)
+            .await
+            .map_err(|err| match err {
+                ReserveOrganizationNameError::Taken { .. } => {
+                    CreateOrganizationCommandError::NameTaken
+                }
+                ReserveOrganizationNameError::Connection => {
+                    CreateOrganizationCommandError::Connection
+                }
+                ReserveOrganizationNameError::Unexpected => {
+                    CreateOrganizationCommandError::Unexpected
+                }
+            })?;
 
-        res.map_err(|err| match err {
+        let res = self
+            .repository
+            .create(organization_id, command.name.clone(), command.metadata)
+            .await;
+
+        if res.is_err() {
+            if let Err(err) = self.names.release(&command.name, organization_id).await {
+                warn!(
+                    error = format!("{:?}", err),
+                    "Failed to release the name of an organization that was not created"
+                );
+            }
+        }
+
+        res.map(|organization| Versioned {
+            value: organization,
+            // The creating event is the first of the stream
+            revision: 0,
+        })
+        .map_err(|err| match err {
             CreateOrganizationError::Connection => CreateOrganizationCommandError::Connection,
             CreateOrganizationError::Unexpected => CreateOrganizationCommandError::Unexpected,
             CreateOrganizationError::Conflict => CreateOrganizationCommandError::Conflict,
@@ -49,4 +100,6 @@ pub enum CreateOrganizationCommandError {
     Unexpected,
     #[error("Another client tried to write to the same aggregate at the same time")]
     Conflict,
+    #[error("Another organization already has this name")]
+    NameTaken,
 }
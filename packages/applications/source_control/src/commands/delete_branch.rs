@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::branch::BranchError,
+    entities::{
+        branch::{Branch, BranchId},
+        repository::RepositoryId,
+    },
+    repositories::branch_repository::{BranchRepository, GetBranchError, SaveBranchError},
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct DeleteBranchCommand {
+    pub repository_id: u64,
+    pub name: String,
+}
+
+#[async_trait]
+pub trait DeleteBranchCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: DeleteBranchCommand,
+    ) -> Result<Branch, DeleteBranchCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = DeleteBranchCommandHandler)]
+pub struct DeleteBranchCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn BranchRepository>,
+}
+
+#[async_trait]
+impl DeleteBranchCommandHandler for DeleteBranchCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: DeleteBranchCommand,
+    ) -> Result<Branch, DeleteBranchCommandError> {
+        let branch_id = BranchId::from_name(RepositoryId(command.repository_id), &command.name);
+        let mut aggregate = match self.repository.get(branch_id).await {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetBranchError::NotFound { .. } => Err(DeleteBranchCommandError::NotFound),
+                GetBranchError::Connection => Err(DeleteBranchCommandError::Connection),
+                GetBranchError::Unexpected | GetBranchError::Corrupt { .. } => {
+                    Err(DeleteBranchCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        match aggregate.delete() {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                BranchError::Deleted { .. } => Err(DeleteBranchCommandError::NotFound),
+                BranchError::NotDeleted { .. }
+                | BranchError::HeadUnchanged { .. }
+                | BranchError::UnknownCommit { .. } => Err(DeleteBranchCommandError::Unexpected),
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveBranchError::Connection => Err(DeleteBranchCommandError::Connection),
+                SaveBranchError::Unexpected => Err(DeleteBranchCommandError::Unexpected),
+                SaveBranchError::Conflict => Err(DeleteBranchCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DeleteBranchCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The branch could not be found")]
+    NotFound,
+}
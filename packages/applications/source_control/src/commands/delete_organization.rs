@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::{metadata::CommandMetadata, organization::OrganizationError},
+    entities::organization::OrganizationId,
+    repositories::{
+        organization_name_repository::OrganizationNameRepository,
+        organization_repository::{
+            GetOrganizationError, OrganizationRepository, SaveOrganizationError,
+        },
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+#[derive(Debug)]
+pub struct DeleteOrganizationCommand {
+    pub organization_id: u64,
+    /// Revision the client read the organization at, the command fails when it changed since.
+    pub expected_revision: Option<u64>,
+    pub metadata: CommandMetadata,
+}
+
+#[async_trait]
+pub trait DeleteOrganizationCommandHandler: Interface {
+    /// Returns the revision of the organization after deleting it.
+    async fn handle(
+        &self,
+        command: DeleteOrganizationCommand,
+    ) -> Result<u64, DeleteOrganizationCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = DeleteOrganizationCommandHandler)]
+pub struct DeleteOrganizationCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn OrganizationRepository>,
+    #[shaku(provide)]
+    pub names: Box<dyn OrganizationNameRepository>,
+}
+
+#[async_trait]
+impl DeleteOrganizationCommandHandler for DeleteOrganizationCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: DeleteOrganizationCommand,
+    ) -> Result<u64, DeleteOrganizationCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(OrganizationId(command.organization_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetOrganizationError::NotFound { .. } => {
+                    Err(DeleteOrganizationCommandError::NotFound)
+                }
+                GetOrganizationError::Connection => Err(DeleteOrganizationCommandError::Connection),
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
+                    Err(DeleteOrganizationCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if command
+            .expected_revision
+            .is_some_and(|revision| revision != aggregate.latest_revision)
+        {
+            return Err(DeleteOrganizationCommandError::PreconditionFailed);
+        }
+
+        match aggregate.delete() {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                OrganizationError::Deleted { .. } => Err(DeleteOrganizationCommandError::NotFound),
+                OrganizationError::Archived { .. }
+                | OrganizationError::NotArchived { .. }
+                | OrganizationError::NameUnchanged { .. }
+                | OrganizationError::AccountAlreadyAdded { .. }
+                | OrganizationError::AccountNotLinked { .. } => {
+                    Err(DeleteOrganizationCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        let id = aggregate.root.id;
+        let name = aggregate.root.name.clone();
+        let revision = aggregate.revision_after_save();
+
+        match self.repository.save(aggregate, command.metadata).await {
+            Ok(_) => {
+                // A leftover reservation only blocks the name, the organization is deleted either way
+                if let Err(err) = self.names.release(&name, id).await {
+                    warn!(
+                        error = format!("{:?}", err),
+                        "Failed to release the name of a deleted organization"
+                    );
+                }
+                Ok(revision)
+            }
+            Err(err) => match err {
+                SaveOrganizationError::Connection => {
+                    Err(DeleteOrganizationCommandError::Connection)
+                }
+                SaveOrganizationError::Unexpected => {
+                    Err(DeleteOrganizationCommandError::Unexpected)
+                }
+                SaveOrganizationError::Conflict => Err(DeleteOrganizationCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DeleteOrganizationCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The organization could not be found")]
+    NotFound,
+    #[error("The organization changed since the expected revision")]
+    PreconditionFailed,
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::pull_request::PullRequestError,
+    entities::{
+        developer::DeveloperId,
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+    repositories::pull_request_repository::{
+        GetPullRequestError, PullRequestRepository, SavePullRequestError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct DismissReviewCommand {
+    pub repository_id: u64,
+    pub pull_request_id: u64,
+    pub developer_id: u64,
+}
+
+#[async_trait]
+pub trait DismissReviewCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: DismissReviewCommand,
+    ) -> Result<PullRequest, DismissReviewCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = DismissReviewCommandHandler)]
+pub struct DismissReviewCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+}
+
+#[async_trait]
+impl DismissReviewCommandHandler for DismissReviewCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: DismissReviewCommand,
+    ) -> Result<PullRequest, DismissReviewCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(PullRequestId(command.pull_request_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetPullRequestError::NotFound { pull_request_id } => {
+                    Err(DismissReviewCommandError::NotFound {
+                        pull_request_id: pull_request_id.0,
+                    })
+                }
+                GetPullRequestError::Connection => Err(DismissReviewCommandError::Connection),
+                GetPullRequestError::Unexpected | GetPullRequestError::Corrupt { .. } => {
+                    Err(DismissReviewCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.repository != RepositoryId(command.repository_id) {
+            return Err(DismissReviewCommandError::NotFound {
+                pull_request_id: command.pull_request_id,
+            });
+        }
+
+        match aggregate.dismiss_review(DeveloperId(command.developer_id)) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                PullRequestError::Merged { .. } => Err(DismissReviewCommandError::AlreadyMerged),
+                PullRequestError::Closed { .. } => Err(DismissReviewCommandError::AlreadyClosed),
+                PullRequestError::ReviewNotFound { developer_id, .. } => {
+                    Err(DismissReviewCommandError::ReviewNotFound {
+                        developer_id: developer_id.0,
+                    })
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SavePullRequestError::Connection => Err(DismissReviewCommandError::Connection),
+                SavePullRequestError::Unexpected => Err(DismissReviewCommandError::Unexpected),
+                SavePullRequestError::Conflict => Err(DismissReviewCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DismissReviewCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The pull request could not be found")]
+    NotFound { pull_request_id: u64 },
+    #[error("The pull request is already merged")]
+    AlreadyMerged,
+    #[error("The pull request is already closed")]
+    AlreadyClosed,
+    #[error("The developer has no review on this pull request")]
+    ReviewNotFound { developer_id: u64 },
+}
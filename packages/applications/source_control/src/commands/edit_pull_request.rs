@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::pull_request::PullRequestError,
+    entities::{
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+    repositories::pull_request_repository::{
+        GetPullRequestError, PullRequestRepository, SavePullRequestError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct EditPullRequestCommand {
+    pub repository_id: u64,
+    pub pull_request_id: u64,
+    pub title: String,
+    pub description: String,
+}
+
+#[async_trait]
+pub trait EditPullRequestCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: EditPullRequestCommand,
+    ) -> Result<PullRequest, EditPullRequestCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = EditPullRequestCommandHandler)]
+pub struct EditPullRequestCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+}
+
+#[async_trait]
+impl EditPullRequestCommandHandler for EditPullRequestCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: EditPullRequestCommand,
+    ) -> Result<PullRequest, EditPullRequestCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(PullRequestId(command.pull_request_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetPullRequestError::NotFound { pull_request_id } => {
+                    Err(EditPullRequestCommandError::NotFound {
+                        pull_request_id: pull_request_id.0,
+                    })
+                }
+                GetPullRequestError::Connection => Err(EditPullRequestCommandError::Connection),
+                GetPullRequestError::Unexpected | GetPullRequestError::Corrupt { .. } => {
+                    Err(EditPullRequestCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.repository != RepositoryId(command.repository_id) {
+            return Err(EditPullRequestCommandError::NotFound {
+                pull_request_id: command.pull_request_id,
+            });
+        }
+
+        match aggregate.edit(command.title, command.description) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                PullRequestError::Merged { .. } => Err(EditPullRequestCommandError::AlreadyMerged),
+                PullRequestError::Closed { .. } => Err(EditPullRequestCommandError::AlreadyClosed),
+                PullRequestError::ReviewNotFound { .. } => {
+                    Err(EditPullRequestCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SavePullRequestError::Connection => Err(EditPullRequestCommandError::Connection),
+                SavePullRequestError::Unexpected => Err(EditPullRequestCommandError::Unexpected),
+                SavePullRequestError::Conflict => Err(EditPullRequestCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EditPullRequestCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The pull request could not be found")]
+    NotFound { pull_request_id: u64 },
+    #[error("The pull request is already merged")]
+    AlreadyMerged,
+    #[error("The pull request is already closed")]
+    AlreadyClosed,
+}
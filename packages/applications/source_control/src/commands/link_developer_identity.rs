@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::developer::DeveloperError,
+    entities::developer::{Developer, DeveloperId, DeveloperIdentity},
+    repositories::{
+        developer_identity_repository::{
+            DeveloperIdentityRepository, ReserveDeveloperIdentityError,
+        },
+        developer_repository::{DeveloperRepository, GetDeveloperError, SaveDeveloperError},
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+#[derive(Debug)]
+pub struct LinkDeveloperIdentityCommand {
+    pub developer_id: u64,
+    pub identity: DeveloperIdentity,
+}
+
+#[async_trait]
+pub trait LinkDeveloperIdentityCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: LinkDeveloperIdentityCommand,
+    ) -> Result<Developer, LinkDeveloperIdentityCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = LinkDeveloperIdentityCommandHandler)]
+pub struct LinkDeveloperIdentityCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+    #[shaku(provide)]
+    pub identities: Box<dyn DeveloperIdentityRepository>,
+}
+
+#[async_trait]
+impl LinkDeveloperIdentityCommandHandler for LinkDeveloperIdentityCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: LinkDeveloperIdentityCommand,
+    ) -> Result<Developer, LinkDeveloperIdentityCommandError> {
+        let mut aggregate = match self.repository.get(DeveloperId(command.developer_id)).await {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetDeveloperError::NotFound { .. } => {
+                    Err(LinkDeveloperIdentityCommandError::NotFound)
+                }
+                GetDeveloperError::Connection => Err(LinkDeveloperIdentityCommandError::Connection),
+                GetDeveloperError::Unexpected | GetDeveloperError::Corrupt { .. } => {
+                    Err(LinkDeveloperIdentityCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        match aggregate.link_identity(command.identity.clone()) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                DeveloperError::Merged { into, .. } => {
+                    Err(LinkDeveloperIdentityCommandError::Merged { into: into.0 })
+                }
+                DeveloperError::IdentityAlreadyLinked { .. } => {
+                    Err(LinkDeveloperIdentityCommandError::AlreadyLinked)
+                }
+                DeveloperError::NotMerged { .. }
+                | DeveloperError::MergeIntoSelf { .. }
+                | DeveloperError::TargetMerged { .. }
+                | DeveloperError::HasMergedDevelopers { .. }
+                | DeveloperError::IdentityNotLinked { .. } => {
+                    Err(LinkDeveloperIdentityCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        // An identity belongs to a single developer, so it is reserved before the developer that
+        // links it is saved
+        let developer_id = aggregate.root.id;
+        self.identities
+            .reserve(&command.identity, developer_id)
+            .await
+            .map_err(|err| match err {
+                ReserveDeveloperIdentityError::Taken { developer_id } => {
+                    LinkDeveloperIdentityCommandError::IdentityTaken {
+                        developer_id: developer_id.0,
+                    }
+                }
+                ReserveDeveloperIdentityError::Connection => {
+                    LinkDeveloperIdentityCommandError::Connection
+                }
+                ReserveDeveloperIdentityError::Unexpected => {
+                    LinkDeveloperIdentityCommandError::Unexpected
+                }
+            })?;
+        let root = aggregate.root.clone();
+
+        let res = self.repository.save(aggregate).await;
+
+        if res.is_err() {
+            if let Err(err) = self
+                .identities
+                .release(&command.identity, developer_id)
+                .await
+            {
+                warn!(
+                    error = format!("{:?}", err),
+                    "Failed to release an identity that was not linked"
+                );
+            }
+        }
+
+        match res {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveDeveloperError::Connection => {
+                    Err(LinkDeveloperIdentityCommandError::Connection)
+                }
+                SaveDeveloperError::Unexpected => {
+                    Err(LinkDeveloperIdentityCommandError::Unexpected)
+                }
+                SaveDeveloperError::Conflict => Err(LinkDeveloperIdentityCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum LinkDeveloperIdentityCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The developer could not be found")]
+    NotFound,
+    #[error("The developer is merged into developer {into}")]
+    Merged { into: u64 },
+    #[error("The developer already has this identity")]
+    AlreadyLinked,
+    #[error("The identity belongs to developer {developer_id}")]
+    IdentityTaken { developer_id: u64 },
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::developer::{DeveloperAggregate, DeveloperError},
+    entities::developer::{Developer, DeveloperId},
+    repositories::developer_repository::{
+        DeveloperRepository, GetDeveloperError, SaveDeveloperError,
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+/// Marks `developer_id` as a duplicate of `into`.
+#[derive(Debug)]
+pub struct MergeDevelopersCommand {
+    pub developer_id: u64,
+    pub into: u64,
+}
+
+#[async_trait]
+pub trait MergeDevelopersCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: MergeDevelopersCommand,
+    ) -> Result<Developer, MergeDevelopersCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = MergeDevelopersCommandHandler)]
+pub struct MergeDevelopersCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+}
+
+#[async_trait]
+impl MergeDevelopersCommandHandler for MergeDevelopersCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: MergeDevelopersCommand,
+    ) -> Result<Developer, MergeDevelopersCommandError> {
+        let mut aggregate = self.get_developer(command.developer_id).await?;
+        let mut target = self.get_developer(command.into).await?;
+
+        match aggregate.merge_into(&target.root) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                DeveloperError::Merged { into, .. } => {
+                    Err(MergeDevelopersCommandError::AlreadyMerged { into: into.0 })
+                }
+                DeveloperError::MergeIntoSelf { .. } => {
+                    Err(MergeDevelopersCommandError::MergeIntoSelf)
+                }
+                DeveloperError::TargetMerged { .. } => {
+                    Err(MergeDevelopersCommandError::TargetMerged {
+                        into: target.root.merged_into.map(|x| x.0).unwrap_or_default(),
+                    })
+                }
+                DeveloperError::HasMergedDevelopers { .. } => {
+                    Err(MergeDevelopersCommandError::HasMergedDevelopers)
+                }
+                DeveloperError::NotMerged { .. }
+                | DeveloperError::IdentityAlreadyLinked { .. }
+                | DeveloperError::IdentityNotLinked { .. } => {
+                    Err(MergeDevelopersCommandError::Unexpected)
+                }
+            },
+        }?;
+        target
+            .add_merged(&aggregate.root)
+            .map_err(|_| MergeDevelopersCommandError::Unexpected)?;
+
+        // The target is saved first, from then on it can't be merged into another developer
+        // until the merge is undone
+        self.save(target).await?;
+
+        let root = aggregate.root.clone();
+        if let Err(err) = self.save(aggregate).await {
+            self.remove_merged(DeveloperId(command.into), root.id).await;
+            return Err(err);
+        }
+
+        Ok(root)
+    }
+}
+
+impl MergeDevelopersCommandHandlerImpl {
+    async fn save(&self, aggregate: DeveloperAggregate) -> Result<(), MergeDevelopersCommandError> {
+        self.repository
+            .save(aggregate)
+            .await
+            .map_err(|err| match err {
+                SaveDeveloperError::Connection => MergeDevelopersCommandError::Connection,
+                SaveDeveloperError::Unexpected => MergeDevelopersCommandError::Unexpected,
+                SaveDeveloperError::Conflict => MergeDevelopersCommandError::Conflict,
+            })
+    }
+
+    /// Undoes recording the merge on the target when the merge itself could not be saved.
+    async fn remove_merged(&self, target_id: DeveloperId, source_id: DeveloperId) {
+        let result = match self.repository.get(target_id).await {
+            Ok(mut target) => {
+                target.remove_merged(source_id);
+                self.repository
+                    .save(target)
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+            Err(err) => Err(err.to_string()),
+        };
+
+        if let Err(err) = result {
+            warn!(
+                error = err,
+                "Failed to remove a developer that was not merged from the merge target"
+            );
+        }
+    }
+
+    async fn get_developer(
+        &self,
+        developer_id: u64,
+    ) -> Result<DeveloperAggregate, MergeDevelopersCommandError> {
+        self.repository
+            .get(DeveloperId(developer_id))
+            .await
+            .map_err(|err| match err {
+                GetDeveloperError::NotFound { developer_id } => {
+                    MergeDevelopersCommandError::NotFound {
+                        developer_id: developer_id.0,
+                    }
+                }
+                GetDeveloperError::Connection => MergeDevelopersCommandError::Connection,
+                GetDeveloperError::Unexpected | GetDeveloperError::Corrupt { .. } => {
+                    MergeDevelopersCommandError::Unexpected
+                }
+            })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MergeDevelopersCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("Developer {developer_id} could not be found")]
+    NotFound { developer_id: u64 },
+    #[error("The developer is already merged into developer {into}")]
+    AlreadyMerged { into: u64 },
+    #[error("A developer can not be merged into itself")]
+    MergeIntoSelf,
+    #[error("The target developer is merged into developer {into}")]
+    TargetMerged { into: u64 },
+    #[error("Other developers are merged into the developer")]
+    HasMergedDevelopers,
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::pull_request::PullRequestError,
+    entities::{
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+    repositories::pull_request_repository::{
+        GetPullRequestError, PullRequestRepository, SavePullRequestError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct MergePullRequestCommand {
+    pub repository_id: u64,
+    pub pull_request_id: u64,
+}
+
+#[async_trait]
+pub trait MergePullRequestCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: MergePullRequestCommand,
+    ) -> Result<PullRequest, MergePullRequestCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = MergePullRequestCommandHandler)]
+pub struct MergePullRequestCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+}
+
+#[async_trait]
+impl MergePullRequestCommandHandler for MergePullRequestCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: MergePullRequestCommand,
+    ) -> Result<PullRequest, MergePullRequestCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(PullRequestId(command.pull_request_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetPullRequestError::NotFound { pull_request_id } => {
+                    Err(MergePullRequestCommandError::NotFound {
+                        pull_request_id: pull_request_id.0,
+                    })
+                }
+                GetPullRequestError::Connection => Err(MergePullRequestCommandError::Connection),
+                GetPullRequestError::Unexpected | GetPullRequestError::Corrupt { .. } => {
+                    Err(MergePullRequestCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.repository != RepositoryId(command.repository_id) {
+            return Err(MergePullRequestCommandError::NotFound {
+                pull_request_id: command.pull_request_id,
+            });
+        }
+
+        match aggregate.merge() {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                PullRequestError::Merged { .. } => Err(MergePullRequestCommandError::AlreadyMerged),
+                PullRequestError::Closed { .. } => Err(MergePullRequestCommandError::AlreadyClosed),
+                PullRequestError::ReviewNotFound { .. } => {
+                    Err(MergePullRequestCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SavePullRequestError::Connection => Err(MergePullRequestCommandError::Connection),
+                SavePullRequestError::Unexpected => Err(MergePullRequestCommandError::Unexpected),
+                SavePullRequestError::Conflict => Err(MergePullRequestCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MergePullRequestCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The pull request could not be found")]
+    NotFound { pull_request_id: u64 },
+    #[error("The pull request is already merged")]
+    AlreadyMerged,
+    #[error("The pull request is already closed")]
+    AlreadyClosed,
+}
@@ -1,3 +1,27 @@
 pub mod add_platform_account;
+pub mod archive_repository;
+pub mod close_pull_request;
+pub mod conflict_retry;
+pub mod create_branch;
+pub mod create_developer;
 pub mod create_organization;
+pub mod delete_branch;
+pub mod delete_organization;
+pub mod dismiss_review;
+pub mod edit_pull_request;
+pub mod link_developer_identity;
+pub mod merge_developers;
+pub mod merge_pull_request;
+pub mod move_repository;
+pub mod open_pull_request;
+pub mod push_branch;
+pub mod rebuild_organization_projection;
+pub mod record_commits;
+pub mod replay_parked_events;
+pub mod register_repository;
 pub mod remove_platform_account;
+pub mod rename_repository;
+pub mod submit_review;
+pub mod unlink_developer_identity;
+pub mod unmerge_developer;
+pub mod update_organization;
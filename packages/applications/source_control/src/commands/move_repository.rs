@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::repository::RepositoryError,
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    repositories::{
+        organization_repository::{GetOrganizationError, OrganizationRepository},
+        repository_repository::{GetRepositoryError, RepositoryRepository, SaveRepositoryError},
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct MoveRepositoryCommand {
+    pub organization_id: u64,
+    pub platform_account_id: u64,
+    pub repository_id: u64,
+    pub target_platform_account_id: u64,
+}
+
+#[async_trait]
+pub trait MoveRepositoryCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: MoveRepositoryCommand,
+    ) -> Result<Repository, MoveRepositoryCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = MoveRepositoryCommandHandler)]
+pub struct MoveRepositoryCommandHandlerImpl {
+    #[shaku(provide)]
+    pub organization_repository: Box<dyn OrganizationRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn RepositoryRepository>,
+}
+
+#[async_trait]
+impl MoveRepositoryCommandHandler for MoveRepositoryCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: MoveRepositoryCommand,
+    ) -> Result<Repository, MoveRepositoryCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(RepositoryId(command.repository_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(MoveRepositoryCommandError::NotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(MoveRepositoryCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(MoveRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.organization_id != OrganizationId(command.organization_id)
+            || aggregate.root.platform_account != PlatformAccountId(command.platform_account_id)
+        {
+            return Err(MoveRepositoryCommandError::NotFound {
+                repository_id: command.repository_id,
+            });
+        }
+
+        let organization = match self
+            .organization_repository
+            .get(aggregate.root.organization_id)
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetOrganizationError::NotFound { .. } => {
+                    Err(MoveRepositoryCommandError::NotFound {
+                        repository_id: command.repository_id,
+                    })
+                }
+                GetOrganizationError::Connection => Err(MoveRepositoryCommandError::Connection),
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
+                    Err(MoveRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        match aggregate.move_to_platform_account(
+            &organization.root,
+            PlatformAccountId(command.target_platform_account_id),
+        ) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                RepositoryError::Archived { .. } => Err(MoveRepositoryCommandError::Archived),
+                RepositoryError::AlreadyOnPlatformAccount { .. } => {
+                    Err(MoveRepositoryCommandError::AlreadyOnPlatformAccount)
+                }
+                RepositoryError::AccountNotLinked { account_id, .. } => {
+                    Err(MoveRepositoryCommandError::AccountNotFound {
+                        account_id: account_id.0,
+                    })
+                }
+                RepositoryError::NameUnchanged { .. } => {
+                    Err(MoveRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveRepositoryError::Connection => Err(MoveRepositoryCommandError::Connection),
+                SaveRepositoryError::Unexpected => Err(MoveRepositoryCommandError::Unexpected),
+                SaveRepositoryError::Conflict => Err(MoveRepositoryCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MoveRepositoryCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The repository could not be found")]
+    NotFound { repository_id: u64 },
+    #[error("The target platform account could not be found")]
+    AccountNotFound { account_id: u64 },
+    #[error("The repository is archived")]
+    Archived,
+    #[error("The repository already belongs to this platform account")]
+    AlreadyOnPlatformAccount,
+}
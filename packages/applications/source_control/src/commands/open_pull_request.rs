@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::{
+        developer::DeveloperId,
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+    factories::id_generator::IdGenerator,
+    repositories::{
+        pull_request_number_repository::{
+            PullRequestNumberRepository, ReservePullRequestNumberError,
+        },
+        pull_request_repository::{CreatePullRequestError, PullRequestRepository},
+        repository_repository::{GetRepositoryError, RepositoryRepository},
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+#[derive(Debug)]
+pub struct OpenPullRequestCommand {
+    pub repository_id: u64,
+    pub number: u64,
+    pub author_id: u64,
+    pub title: String,
+    pub description: String,
+}
+
+#[async_trait]
+pub trait OpenPullRequestCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: OpenPullRequestCommand,
+    ) -> Result<PullRequest, OpenPullRequestCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = OpenPullRequestCommandHandler)]
+pub struct OpenPullRequestCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository_repository: Box<dyn RepositoryRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+    #[shaku(provide)]
+    pub numbers: Box<dyn PullRequestNumberRepository>,
+    #[shaku(inject)]
+    pub id_generator: Arc<dyn IdGenerator>,
+}
+
+#[async_trait]
+impl OpenPullRequestCommandHandler for OpenPullRequestCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: OpenPullRequestCommand,
+    ) -> Result<PullRequest, OpenPullRequestCommandError> {
+        let repository = match self
+            .repository_repository
+            .get(RepositoryId(command.repository_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(OpenPullRequestCommandError::RepositoryNotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(OpenPullRequestCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(OpenPullRequestCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if repository.root.archived {
+            return Err(OpenPullRequestCommandError::RepositoryArchived {
+                repository_id: command.repository_id,
+            });
+        }
+
+        let pull_request_id = PullRequestId(self.id_generator.next_id());
+        self.numbers
+            .reserve(repository.root.id, command.number, pull_request_id)
+            .await
+            .map_err(|err| match err {
+                ReservePullRequestNumberError::Taken { .. } => {
+                    OpenPullRequestCommandError::AlreadyExists {
+                        number: command.number,
+                    }
+                }
+                ReservePullRequestNumberError::Connection => {
+                    OpenPullRequestCommandError::Connection
+                }
+                ReservePullRequestNumberError::Unexpected => {
+                    OpenPullRequestCommandError::Unexpected
+                }
+            })?;
+
+        let res = self
+            .repository
+            .create(
+                pull_request_id,
+                repository.root.id,
+                command.number,
+                DeveloperId(command.author_id),
+                command.title,
+                command.description,
+            )
+            .await;
+
+        if res.is_err() {
+            if let Err(err) = self
+                .numbers
+                .release(repository.root.id, command.number, pull_request_id)
+                .await
+            {
+                warn!(
+                    error = format!("{:?}", err),
+                    "Failed to release the number of a pull request that was not opened"
+                );
+            }
+        }
+
+        res.map_err(|err| match err {
+            CreatePullRequestError::Connection => OpenPullRequestCommandError::Connection,
+            CreatePullRequestError::Unexpected => OpenPullRequestCommandError::Unexpected,
+            CreatePullRequestError::Conflict => OpenPullRequestCommandError::AlreadyExists {
+                number: command.number,
+            },
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OpenPullRequestCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The repository could not be found")]
+    RepositoryNotFound { repository_id: u64 },
+    #[error("The repository is archived")]
+    RepositoryArchived { repository_id: u64 },
+    #[error("A pull request with number {number} already exists in this repository")]
+    AlreadyExists { number: u64 },
+}
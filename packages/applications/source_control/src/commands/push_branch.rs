@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::branch::BranchError,
+    entities::{
+        branch::BranchId, branch_push::BranchPush, commit::CommitSha, developer::DeveloperId,
+        repository::RepositoryId,
+    },
+    repositories::{
+        branch_repository::{BranchRepository, GetBranchError, SaveBranchError},
+        commit_graph_repository::{CommitGraphRepository, GetCommitGraphError},
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct PushBranchCommand {
+    pub repository_id: u64,
+    pub name: String,
+    pub pusher_id: u64,
+    pub after: String,
+    pub pushed_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait PushBranchCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: PushBranchCommand,
+    ) -> Result<BranchPush, PushBranchCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = PushBranchCommandHandler)]
+pub struct PushBranchCommandHandlerImpl {
+    #[shaku(provide)]
+    pub commit_graph_repository: Box<dyn CommitGraphRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn BranchRepository>,
+}
+
+#[async_trait]
+impl PushBranchCommandHandler for PushBranchCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: PushBranchCommand,
+    ) -> Result<BranchPush, PushBranchCommandError> {
+        let after = CommitSha::from_string(&command.after).map_err(|_| {
+            PushBranchCommandError::InvalidSha {
+                sha: command.after.clone(),
+            }
+        })?;
+
+        let repository_id = RepositoryId(command.repository_id);
+        let mut aggregate = match self
+            .repository
+            .get(BranchId::from_name(repository_id, &command.name))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetBranchError::NotFound { .. } => Err(PushBranchCommandError::NotFound),
+                GetBranchError::Connection => Err(PushBranchCommandError::Connection),
+                GetBranchError::Unexpected | GetBranchError::Corrupt { .. } => {
+                    Err(PushBranchCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        let commit_graph = match self.commit_graph_repository.get(repository_id).await {
+            Ok(agg) => Ok(agg),
+            Err(GetCommitGraphError::Connection) => Err(PushBranchCommandError::Connection),
+            Err(GetCommitGraphError::Unexpected | GetCommitGraphError::Corrupt { .. }) => {
+                Err(PushBranchCommandError::Unexpected)
+            }
+        }?;
+
+        let push = match aggregate.push(
+            DeveloperId(command.pusher_id),
+            after,
+            command.pushed_at,
+            &commit_graph,
+        ) {
+            Ok(push) => Ok(push),
+            Err(err) => match err {
+                BranchError::Deleted { .. } => Err(PushBranchCommandError::NotFound),
+                BranchError::HeadUnchanged { .. } => Err(PushBranchCommandError::HeadUnchanged),
+                BranchError::UnknownCommit { sha } => Err(PushBranchCommandError::UnknownCommit {
+                    sha: sha.to_string(),
+                }),
+                BranchError::NotDeleted { .. } => Err(PushBranchCommandError::Unexpected),
+            },
+        }?;
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(push),
+            Err(err) => match err {
+                SaveBranchError::Connection => Err(PushBranchCommandError::Connection),
+                SaveBranchError::Unexpected => Err(PushBranchCommandError::Unexpected),
+                SaveBranchError::Conflict => Err(PushBranchCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PushBranchCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The branch could not be found")]
+    NotFound,
+    #[error("{sha} is not a valid commit sha")]
+    InvalidSha { sha: String },
+    #[error("Commit {sha} has not been recorded")]
+    UnknownCommit { sha: String },
+    #[error("The branch already points to this commit")]
+    HeadUnchanged,
+}
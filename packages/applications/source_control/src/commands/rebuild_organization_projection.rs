@@ -0,0 +1,327 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_store_util::from_resolved_event;
+use eventstore::{Position, ReadAllOptions, StreamPosition};
+use shaku::{Component, Interface, Provider};
+use source_control_domain::aggregates::organization::OrganizationEvent;
+use source_control_event_store_persistence_adapter::{
+    provider::EventStoreProvider, repositories::event_sourced_repository::StreamCategory,
+};
+use source_control_postgres_persistence_adapter::{
+    projectors::{
+        checkpoint::{CheckpointedProjector, ProjectionCheckpoint},
+        rebuild::{
+            create_organization_shadow_tables, drop_retired_organization_tables,
+            lock_organization_tables, swap_organization_shadow_tables,
+            use_organization_shadow_tables,
+        },
+    },
+    provider::PostgresProvider,
+};
+use thiserror::Error;
+use tokio_postgres::Transaction;
+use tracing::{error, info, instrument};
+
+/// Events read and projected in one transaction.
+const BATCH_SIZE: usize = 500;
+
+#[derive(Debug)]
+pub struct RebuildOrganizationProjectionCommand {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RebuildState {
+    #[default]
+    NotStarted,
+    /// Replaying the events into the shadow tables, reads are still served by the live tables.
+    Replaying,
+    /// Applying the events appended during the replay, then swapping the tables.
+    Swapping,
+    Completed,
+    Failed {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RebuildProgress {
+    pub state: RebuildState,
+    pub events_replayed: u64,
+    /// Position in `$all` of the last replayed event.
+    pub position: Option<ProjectionCheckpoint>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl RebuildProgress {
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, RebuildState::Replaying | RebuildState::Swapping)
+    }
+}
+
+/// Progress of the last rebuild of the organization projection, kept for the lifetime of the
+/// process.
+pub trait OrganizationProjectionRebuild: Interface {
+    fn progress(&self) -> RebuildProgress;
+    /// Starts a new rebuild, `false` when one is still running.
+    fn try_start(&self) -> bool;
+    fn update(&self, update: &dyn Fn(&mut RebuildProgress));
+    /// Checkpoint of the live catch-up projection, moved past the events the rebuild applied.
+    /// `None` when organizations are projected by another runtime, which can't skip the events
+    /// the rebuild applied, so rebuilding is refused.
+    fn live_projection_name(&self) -> Option<&str>;
+}
+
+#[derive(Component)]
+#[shaku(interface = OrganizationProjectionRebuild)]
+pub struct OrganizationProjectionRebuildImpl {
+    #[shaku(default)]
+    progress: Mutex<RebuildProgress>,
+    #[shaku(default)]
+    live_projection_name: Option<String>,
+}
+
+impl OrganizationProjectionRebuild for OrganizationProjectionRebuildImpl {
+    fn progress(&self) -> RebuildProgress {
+        self.progress.lock().unwrap().clone()
+    }
+
+    fn try_start(&self) -> bool {
+        let mut progress = self.progress.lock().unwrap();
+        if progress.is_running() {
+            return false;
+        }
+
+        *progress = RebuildProgress {
+            state: RebuildState::Replaying,
+            started_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        true
+    }
+
+    fn update(&self, update: &dyn Fn(&mut RebuildProgress)) {
+        update(&mut self.progress.lock().unwrap());
+    }
+
+    fn live_projection_name(&self) -> Option<&str> {
+        self.live_projection_name.as_deref()
+    }
+}
+
+#[async_trait]
+pub trait RebuildOrganizationProjectionCommandHandler: Interface {
+    /// Replays every organization event into shadow tables and swaps them in place of the
+    /// `Organization` and `PlatformAccount` tables, returns once they are swapped.
+    async fn handle(
+        &self,
+        command: RebuildOrganizationProjectionCommand,
+    ) -> Result<RebuildProgress, RebuildOrganizationProjectionCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = RebuildOrganizationProjectionCommandHandler)]
+pub struct RebuildOrganizationProjectionCommandHandlerImpl {
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+    #[shaku(inject)]
+    pub postgres: Arc<dyn PostgresProvider>,
+    #[shaku(inject)]
+    pub rebuild: Arc<dyn OrganizationProjectionRebuild>,
+    #[shaku(provide)]
+    pub projector: Box<dyn CheckpointedProjector<OrganizationEvent>>,
+}
+
+#[async_trait]
+impl RebuildOrganizationProjectionCommandHandler
+    for RebuildOrganizationProjectionCommandHandlerImpl
+{
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        _command: RebuildOrganizationProjectionCommand,
+    ) -> Result<RebuildProgress, RebuildOrganizationProjectionCommandError> {
+        let Some(live_projection_name) = self.rebuild.live_projection_name() else {
+            return Err(RebuildOrganizationProjectionCommandError::UnsupportedRuntime);
+        };
+        if !self.rebuild.try_start() {
+            return Err(RebuildOrganizationProjectionCommandError::AlreadyRunning);
+        }
+
+        info!("Rebuilding the organization projection");
+        let result = self.rebuild_tables(live_projection_name).await;
+        let state = match &result {
+            Ok(_) => RebuildState::Completed,
+            Err(err) => {
+                error!("Rebuilding the organization projection failed {}", err);
+                RebuildState::Failed {
+                    reason: err.to_string(),
+                }
+            }
+        };
+        self.rebuild.update(&|progress| {
+            progress.state = state.clone();
+            progress.finished_at = Some(Utc::now());
+        });
+
+        result.map(|_| self.rebuild.progress())
+    }
+}
+
+impl RebuildOrganizationProjectionCommandHandlerImpl {
+    async fn rebuild_tables(
+        &self,
+        live_projection_name: &str,
+    ) -> Result<(), RebuildOrganizationProjectionCommandError> {
+        let mut client = self.postgres.get_client().await;
+        create_organization_shadow_tables(&client).await?;
+
+        let mut position = None;
+        loop {
+            let transaction = client.transaction().await?;
+            use_organization_shadow_tables(&transaction).await?;
+            let replayed = self.replay_batch(&transaction, &mut position).await?;
+            transaction.commit().await?;
+
+            if !replayed {
+                break;
+            }
+        }
+
+        // The live tables stop changing, so the events appended since can be replayed before the
+        // tables are swapped
+        self.rebuild
+            .update(&|progress| progress.state = RebuildState::Swapping);
+        let transaction = client.transaction().await?;
+        lock_organization_tables(&transaction, live_projection_name).await?;
+        use_organization_shadow_tables(&transaction).await?;
+        while self.replay_batch(&transaction, &mut position).await? {}
+        swap_organization_shadow_tables(&transaction, live_projection_name, position).await?;
+        transaction.commit().await?;
+
+        drop_retired_organization_tables(&client).await?;
+        info!("Rebuilt the organization projection");
+        Ok(())
+    }
+
+    /// Projects the next batch of events after `position`, `false` when there were none left.
+    async fn replay_batch(
+        &self,
+        transaction: &Transaction<'_>,
+        position: &mut Option<ProjectionCheckpoint>,
+    ) -> Result<bool, RebuildOrganizationProjectionCommandError> {
+        let (events, last_position) = self.read_event_store(*position).await?;
+        let Some(last_position) = last_position else {
+            return Ok(false);
+        };
+
+        let replayed = events.len() as u64;
+        for event in events {
+            self.projector
+                .project_in(transaction, event)
+                .await
+                .map_err(
+                    |err| RebuildOrganizationProjectionCommandError::Projection {
+                        reason: err.to_string(),
+                    },
+                )?;
+        }
+
+        *position = Some(last_position);
+        self.rebuild.update(&|progress| {
+            progress.events_replayed += replayed;
+            progress.position = Some(last_position);
+        });
+        Ok(true)
+    }
+
+    /// Organization events of the next batch of `$all`, and the position of the last event read
+    /// whether it was an organization event or not.
+    async fn read_event_store(
+        &self,
+        after: Option<ProjectionCheckpoint>,
+    ) -> Result<
+        (Vec<OrganizationEvent>, Option<ProjectionCheckpoint>),
+        RebuildOrganizationProjectionCommandError,
+    > {
+        // Reading from a position includes the event at it
+        let start = match after {
+            Some(after) => StreamPosition::Position(Position {
+                commit: after.commit_position,
+                prepare: after.prepare_position,
+            }),
+            None => StreamPosition::Start,
+        };
+        let mut stream = self
+            .eventstore
+            .get_client()
+            .read_all(
+                &ReadAllOptions::default()
+                    .position(start)
+                    .max_count(BATCH_SIZE + 1),
+            )
+            .await?;
+
+        let stream_prefix = OrganizationEvent::get_stream_name("");
+        let mut events = Vec::new();
+        let mut last_position = None;
+        while let Some(event) = stream.next().await? {
+            let original_event = event.get_original_event();
+            let position = ProjectionCheckpoint {
+                commit_position: original_event.position.commit,
+                prepare_position: original_event.position.prepare,
+            };
+            if Some(position) == after {
+                continue;
+            }
+
+            last_position = Some(position);
+            if original_event.stream_id.starts_with(&stream_prefix) {
+                events.push(from_resolved_event::<OrganizationEvent>(&event)?);
+            }
+        }
+
+        Ok((events, last_position))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RebuildOrganizationProjectionCommandError {
+    #[error("A rebuild of the projection is already running")]
+    AlreadyRunning,
+    #[error("Only catch-up projections can be rebuilt")]
+    UnsupportedRuntime,
+    #[error("Reading the events failed, {reason}")]
+    Read { reason: String },
+    #[error("An event could not be decoded, {reason}")]
+    Corrupt { reason: String },
+    #[error("Projecting an event failed, {reason}")]
+    Projection { reason: String },
+    #[error("Writing the projection failed, {reason}")]
+    Database { reason: String },
+}
+
+impl From<eventstore::Error> for RebuildOrganizationProjectionCommandError {
+    fn from(value: eventstore::Error) -> Self {
+        RebuildOrganizationProjectionCommandError::Read {
+            reason: value.to_string(),
+        }
+    }
+}
+
+impl From<event_store_util::DecodeError> for RebuildOrganizationProjectionCommandError {
+    fn from(value: event_store_util::DecodeError) -> Self {
+        RebuildOrganizationProjectionCommandError::Corrupt {
+            reason: value.to_string(),
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for RebuildOrganizationProjectionCommandError {
+    fn from(value: tokio_postgres::Error) -> Self {
+        RebuildOrganizationProjectionCommandError::Database {
+            reason: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::{commit_graph::CommitGraphError, metadata::CommandMetadata},
+    entities::{
+        commit::{Commit, CommitSha},
+        repository::RepositoryId,
+    },
+    repositories::{
+        commit_graph_repository::{
+            CommitGraphRepository, GetCommitGraphError, SaveCommitGraphError,
+        },
+        repository_repository::{GetRepositoryError, RepositoryRepository},
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct RecordCommitsCommand {
+    pub repository_id: u64,
+    pub commits: Vec<RecordCommit>,
+    pub metadata: CommandMetadata,
+}
+
+#[derive(Debug)]
+pub struct RecordCommit {
+    pub sha: String,
+    pub message: String,
+    pub parents: Vec<String>,
+}
+
+#[async_trait]
+pub trait RecordCommitsCommandHandler: Interface {
+    /// Returns the commits that were not recorded before, parents first.
+    async fn handle(
+        &self,
+        command: RecordCommitsCommand,
+    ) -> Result<Vec<Commit>, RecordCommitsCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = RecordCommitsCommandHandler)]
+pub struct RecordCommitsCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository_repository: Box<dyn RepositoryRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn CommitGraphRepository>,
+}
+
+#[async_trait]
+impl RecordCommitsCommandHandler for RecordCommitsCommandHandlerImpl {
+    #[instrument(skip(self, command), fields(repository_id = command.repository_id, count = command.commits.len()))]
+    async fn handle(
+        &self,
+        command: RecordCommitsCommand,
+    ) -> Result<Vec<Commit>, RecordCommitsCommandError> {
+        let commits = command
+            .commits
+            .into_iter()
+            .map(parse_commit)
+            .collect::<Result<Vec<Commit>, RecordCommitsCommandError>>()?;
+
+        let repository_id = RepositoryId(command.repository_id);
+        match self.repository_repository.get(repository_id).await {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(RecordCommitsCommandError::RepositoryNotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(RecordCommitsCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(RecordCommitsCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        let mut aggregate = match self.repository.get(repository_id).await {
+            Ok(agg) => Ok(agg),
+            Err(GetCommitGraphError::Connection) => Err(RecordCommitsCommandError::Connection),
+            Err(GetCommitGraphError::Unexpected | GetCommitGraphError::Corrupt { .. }) => {
+                Err(RecordCommitsCommandError::Unexpected)
+            }
+        }?;
+
+        let recorded = match aggregate.record_commits(commits) {
+            Ok(recorded) => Ok(recorded),
+            Err(err) => match err {
+                CommitGraphError::MissingParent { sha, parent } => {
+                    Err(RecordCommitsCommandError::MissingParent {
+                        sha: sha.to_string(),
+                        parent: parent.to_string(),
+                    })
+                }
+                CommitGraphError::UnknownCommit { .. } => {
+                    Err(RecordCommitsCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        match self.repository.save(aggregate, command.metadata).await {
+            Ok(_) => Ok(recorded),
+            Err(err) => match err {
+                SaveCommitGraphError::Connection => Err(RecordCommitsCommandError::Connection),
+                SaveCommitGraphError::Unexpected => Err(RecordCommitsCommandError::Unexpected),
+                SaveCommitGraphError::Conflict => Err(RecordCommitsCommandError::Conflict),
+            },
+        }
+    }
+}
+
+fn parse_commit(commit: RecordCommit) -> Result<Commit, RecordCommitsCommandError> {
+    Ok(Commit {
+        sha: parse_sha(&commit.sha)?,
+        message: commit.message,
+        parents: commit
+            .parents
+            .iter()
+            .map(|parent| parse_sha(parent))
+            .collect::<Result<Vec<CommitSha>, RecordCommitsCommandError>>()?,
+    })
+}
+
+fn parse_sha(sha: &str) -> Result<CommitSha, RecordCommitsCommandError> {
+    CommitSha::from_string(sha).map_err(|_| RecordCommitsCommandError::InvalidSha {
+        sha: sha.to_string(),
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum RecordCommitsCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The repository could not be found")]
+    RepositoryNotFound { repository_id: u64 },
+    #[error("{sha} is not a valid commit sha")]
+    InvalidSha { sha: String },
+    #[error("Parent {parent} of commit {sha} has not been recorded")]
+    MissingParent { sha: String, parent: String },
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    factories::id_generator::IdGenerator,
+    repositories::{
+        organization_repository::{GetOrganizationError, OrganizationRepository},
+        repository_repository::{CreateRepositoryError, RepositoryRepository},
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct RegisterRepositoryCommand {
+    pub organization_id: u64,
+    pub platform_account_id: u64,
+    pub name: String,
+}
+
+#[async_trait]
+pub trait RegisterRepositoryCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: RegisterRepositoryCommand,
+    ) -> Result<Repository, RegisterRepositoryCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = RegisterRepositoryCommandHandler)]
+pub struct RegisterRepositoryCommandHandlerImpl {
+    #[shaku(provide)]
+    pub organization_repository: Box<dyn OrganizationRepository>,
+    #[shaku(provide)]
+    pub repository: Box<dyn RepositoryRepository>,
+    #[shaku(inject)]
+    pub id_generator: Arc<dyn IdGenerator>,
+}
+
+#[async_trait]
+impl RegisterRepositoryCommandHandler for RegisterRepositoryCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: RegisterRepositoryCommand,
+    ) -> Result<Repository, RegisterRepositoryCommandError> {
+        let organization = match self
+            .organization_repository
+            .get(OrganizationId(command.organization_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetOrganizationError::NotFound { organization_id } => {
+                    Err(RegisterRepositoryCommandError::OrganizationNotFound {
+                        organization_id: organization_id.0,
+                    })
+                }
+                GetOrganizationError::Connection => Err(RegisterRepositoryCommandError::Connection),
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
+                    Err(RegisterRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if organization.root.deleted {
+            return Err(RegisterRepositoryCommandError::OrganizationNotFound {
+                organization_id: command.organization_id,
+            });
+        }
+
+        let platform_account_id = PlatformAccountId(command.platform_account_id);
+        if !organization.root.has_account_with_id(platform_account_id) {
+            return Err(RegisterRepositoryCommandError::AccountNotFound {
+                account_id: command.platform_account_id,
+            });
+        }
+
+        let res = self
+            .repository
+            .create(
+                RepositoryId(self.id_generator.next_id()),
+                organization.root.id,
+                platform_account_id,
+                command.name,
+            )
+            .await;
+
+        res.map_err(|err| match err {
+            CreateRepositoryError::Connection => RegisterRepositoryCommandError::Connection,
+            CreateRepositoryError::Unexpected => RegisterRepositoryCommandError::Unexpected,
+            CreateRepositoryError::Conflict => RegisterRepositoryCommandError::Conflict,
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RegisterRepositoryCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The organization could not be found")]
+    OrganizationNotFound { organization_id: u64 },
+    #[error("The platform account could not be found")]
+    AccountNotFound { account_id: u64 },
+}
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use shaku::{Interface, Provider};
 use source_control_domain::{
-    aggregates::organization::OrganizationError,
+    aggregates::{metadata::CommandMetadata, organization::OrganizationError},
     entities::{
         organization::{Organization, OrganizationId},
         platform_account::PlatformAccountId,
@@ -12,9 +14,16 @@ use source_control_domain::{
 };
 use thiserror::Error;
 
+use crate::versioned::Versioned;
+
+use super::conflict_retry::{retry_on_conflict, ConflictError, ConflictRetry};
+
 pub struct RemovePlatformAccountCommand {
     pub organization_id: u64,
     pub account_id: u64,
+    /// Revision the client read the organization at, the command fails when it changed since.
+    pub expected_revision: Option<u64>,
+    pub metadata: CommandMetadata,
 }
 
 #[async_trait]
@@ -22,7 +31,7 @@ pub trait RemovePlatformAccountCommandHandler: Interface {
     async fn handle(
         &self,
         command: RemovePlatformAccountCommand,
-    ) -> Result<Organization, RemovePlatformAccountCommandError>;
+    ) -> Result<Versioned<Organization>, RemovePlatformAccountCommandError>;
 }
 
 #[derive(Provider)]
@@ -30,6 +39,8 @@ pub trait RemovePlatformAccountCommandHandler: Interface {
 pub struct RemovePlatformAccountCommandHandlerImpl {
     #[shaku(provide)]
     pub repository: Box<dyn OrganizationRepository>,
+    #[shaku(inject)]
+    pub conflict_retry: Arc<dyn ConflictRetry>,
 }
 
 #[async_trait]
@@ -37,7 +48,19 @@ impl RemovePlatformAccountCommandHandler for RemovePlatformAccountCommandHandler
     async fn handle(
         &self,
         command: RemovePlatformAccountCommand,
-    ) -> Result<Organization, RemovePlatformAccountCommandError> {
+    ) -> Result<Versioned<Organization>, RemovePlatformAccountCommandError> {
+        retry_on_conflict(&*self.conflict_retry, "RemovePlatformAccount", || {
+            self.attempt(&command)
+        })
+        .await
+    }
+}
+
+impl RemovePlatformAccountCommandHandlerImpl {
+    async fn attempt(
+        &self,
+        command: &RemovePlatformAccountCommand,
+    ) -> Result<Versioned<Organization>, RemovePlatformAccountCommandError> {
         let mut aggregate = match self
             .repository
             .get(OrganizationId(command.organization_id))
@@ -53,16 +76,33 @@ impl RemovePlatformAccountCommandHandler for RemovePlatformAccountCommandHandler
                 GetOrganizationError::Connection => {
                     Err(RemovePlatformAccountCommandError::Connection)
                 }
-                GetOrganizationError::Unexpected => {
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
                     Err(RemovePlatformAccountCommandError::Unexpected)
                 }
             },
         }?;
 
+        if command
+            .expected_revision
+            .is_some_and(|revision| revision != aggregate.latest_revision)
+        {
+            return Err(RemovePlatformAccountCommandError::PreconditionFailed);
+        }
+
         match aggregate.remove_platform_account(PlatformAccountId(command.account_id)) {
             Ok(_) => Ok(()),
             Err(err) => match err {
-                OrganizationError::AccountAlreadyAdded { .. } => panic!(""),
+                OrganizationError::Archived { .. } => {
+                    Err(RemovePlatformAccountCommandError::Archived)
+                }
+                OrganizationError::Deleted { organization_id } => {
+                    Err(RemovePlatformAccountCommandError::OrganizationNotFound {
+                        organization_id: organization_id.0,
+                    })
+                }
+                OrganizationError::AccountAlreadyAdded { .. }
+                | OrganizationError::NotArchived { .. }
+                | OrganizationError::NameUnchanged { .. } => panic!(""),
                 OrganizationError::AccountNotLinked { .. } => {
                     Err(RemovePlatformAccountCommandError::AccountNotFound {
                         account_id: command.account_id,
@@ -71,9 +111,17 @@ impl RemovePlatformAccountCommandHandler for RemovePlatformAccountCommandHandler
             },
         }?;
         let root = aggregate.root.clone();
+        let revision = aggregate.revision_after_save();
 
-        match self.repository.save(aggregate).await {
-            Ok(_) => Ok(root),
+        match self
+            .repository
+            .save(aggregate, command.metadata.clone())
+            .await
+        {
+            Ok(_) => Ok(Versioned {
+                value: root,
+                revision,
+            }),
             Err(err) => match err {
                 SaveOrganizationError::Connection => {
                     Err(RemovePlatformAccountCommandError::Connection)
@@ -99,4 +147,14 @@ pub enum RemovePlatformAccountCommandError {
     OrganizationNotFound { organization_id: u64 },
     #[error("The platform account could not be found")]
     AccountNotFound { account_id: u64 },
+    #[error("The organization is archived")]
+    Archived,
+    #[error("The organization changed since the expected revision")]
+    PreconditionFailed,
+}
+
+impl ConflictError for RemovePlatformAccountCommandError {
+    fn is_conflict(&self) -> bool {
+        matches!(self, RemovePlatformAccountCommandError::Conflict)
+    }
 }
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::repository::RepositoryError,
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    repositories::repository_repository::{
+        GetRepositoryError, RepositoryRepository, SaveRepositoryError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct RenameRepositoryCommand {
+    pub organization_id: u64,
+    pub platform_account_id: u64,
+    pub repository_id: u64,
+    pub name: String,
+}
+
+#[async_trait]
+pub trait RenameRepositoryCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: RenameRepositoryCommand,
+    ) -> Result<Repository, RenameRepositoryCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = RenameRepositoryCommandHandler)]
+pub struct RenameRepositoryCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn RepositoryRepository>,
+}
+
+#[async_trait]
+impl RenameRepositoryCommandHandler for RenameRepositoryCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: RenameRepositoryCommand,
+    ) -> Result<Repository, RenameRepositoryCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(RepositoryId(command.repository_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetRepositoryError::NotFound { repository_id } => {
+                    Err(RenameRepositoryCommandError::NotFound {
+                        repository_id: repository_id.0,
+                    })
+                }
+                GetRepositoryError::Connection => Err(RenameRepositoryCommandError::Connection),
+                GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. } => {
+                    Err(RenameRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.organization_id != OrganizationId(command.organization_id)
+            || aggregate.root.platform_account != PlatformAccountId(command.platform_account_id)
+        {
+            return Err(RenameRepositoryCommandError::NotFound {
+                repository_id: command.repository_id,
+            });
+        }
+
+        match aggregate.rename(command.name) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                RepositoryError::Archived { .. } => Err(RenameRepositoryCommandError::Archived),
+                RepositoryError::NameUnchanged { .. } => {
+                    Err(RenameRepositoryCommandError::NameUnchanged)
+                }
+                RepositoryError::AlreadyOnPlatformAccount { .. }
+                | RepositoryError::AccountNotLinked { .. } => {
+                    Err(RenameRepositoryCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SaveRepositoryError::Connection => Err(RenameRepositoryCommandError::Connection),
+                SaveRepositoryError::Unexpected => Err(RenameRepositoryCommandError::Unexpected),
+                SaveRepositoryError::Conflict => Err(RenameRepositoryCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RenameRepositoryCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The repository could not be found")]
+    NotFound { repository_id: u64 },
+    #[error("The repository is archived")]
+    Archived,
+    #[error("The repository already has this name")]
+    NameUnchanged,
+}
@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_store_util::parked::parked_stream_name;
+use eventstore::{
+    GetPersistentSubscriptionInfoOptions, ReadStreamOptions, ReplayParkedMessagesOptions,
+    StreamPosition,
+};
+use shaku::{Interface, Provider};
+use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+use source_control_postgres_event_store_persistence_adapter::events::{
+    has_checkpoint, is_parked, request_replay,
+};
+use source_control_postgres_persistence_adapter::provider::PostgresProvider;
+use thiserror::Error;
+use tracing::{info, instrument};
+
+#[derive(Debug)]
+pub struct ReplayParkedEventsCommand {
+    pub subscription_name: String,
+    /// Replays every parked event up to and including the one with this parked revision, every
+    /// parked event when `None`. Parked events are only replayed from the oldest one, a single
+    /// event can't be replayed on its own.
+    pub up_to: Option<u64>,
+}
+
+#[async_trait]
+pub trait ReplayParkedEventsCommandHandler: Interface {
+    /// Hands the parked events back to the subscription, events that fail again are parked again.
+    async fn handle(
+        &self,
+        command: ReplayParkedEventsCommand,
+    ) -> Result<(), ReplayParkedEventsCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = ReplayParkedEventsCommandHandler)]
+pub struct ReplayParkedEventsCommandHandlerImpl {
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl ReplayParkedEventsCommandHandler for ReplayParkedEventsCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: ReplayParkedEventsCommand,
+    ) -> Result<(), ReplayParkedEventsCommandError> {
+        let client = self.eventstore.get_client();
+        match client
+            .get_persistent_subscription_info_to_all(
+                &command.subscription_name,
+                &GetPersistentSubscriptionInfoOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => {}
+            Err(eventstore::Error::ResourceNotFound) => {
+                return Err(ReplayParkedEventsCommandError::NotFound)
+            }
+            Err(_) => return Err(ReplayParkedEventsCommandError::Connection),
+        }
+
+        let options = match command.up_to {
+            Some(parked_revision) => {
+                self.find_parked_event(&command.subscription_name, parked_revision)
+                    .await?;
+                // EventStore replays up to, not including, this revision of the parked stream
+                ReplayParkedMessagesOptions::default().stop_at(parked_revision as usize + 1)
+            }
+            None => ReplayParkedMessagesOptions::default(),
+        };
+
+        client
+            .replay_parked_messages_to_all(&command.subscription_name, &options)
+            .await
+            .map_err(|_| ReplayParkedEventsCommandError::Connection)?;
+        info!("Replaying parked events");
+        Ok(())
+    }
+}
+
+impl ReplayParkedEventsCommandHandlerImpl {
+    /// Fails when nothing is parked at `parked_revision`, or the event was replayed already and
+    /// EventStore truncated it from the parked stream.
+    async fn find_parked_event(
+        &self,
+        subscription_name: &str,
+        parked_revision: u64,
+    ) -> Result<(), ReplayParkedEventsCommandError> {
+        let mut stream = self
+            .eventstore
+            .get_client()
+            .read_stream(
+                parked_stream_name(subscription_name),
+                &ReadStreamOptions::default()
+                    .forwards()
+                    .position(StreamPosition::Position(parked_revision))
+                    .max_count(1),
+            )
+            .await
+            .map_err(|_| ReplayParkedEventsCommandError::Connection)?;
+
+        match stream.next().await {
+            Ok(Some(event)) if event.get_original_event().revision == parked_revision => Ok(()),
+            Ok(_) | Err(eventstore::Error::ResourceNotFound) => {
+                Err(ReplayParkedEventsCommandError::ParkedEventNotFound)
+            }
+            Err(_) => Err(ReplayParkedEventsCommandError::Connection),
+        }
+    }
+}
+
+/// Marks the events parked by the subscriptions to the events stored in postgres for replay, the
+/// subscription hands them to its projector before reading new events. The subscriptions it
+/// doesn't know are replayed by EventStore.
+#[derive(Provider)]
+#[shaku(interface = ReplayParkedEventsCommandHandler)]
+pub struct PostgresReplayParkedEventsCommandHandlerImpl {
+    #[shaku(inject)]
+    pub postgres: Arc<dyn PostgresProvider>,
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl ReplayParkedEventsCommandHandler for PostgresReplayParkedEventsCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: ReplayParkedEventsCommand,
+    ) -> Result<(), ReplayParkedEventsCommandError> {
+        let client = self.postgres.get_client().await;
+        let known = has_checkpoint(&client, &command.subscription_name)
+            .await
+            .map_err(|_| ReplayParkedEventsCommandError::Connection)?;
+        if !known {
+            drop(client);
+            let eventstore = ReplayParkedEventsCommandHandlerImpl {
+                eventstore: self.eventstore.clone(),
+            };
+            return eventstore.handle(command).await;
+        }
+
+        let up_to = command.up_to.map(|revision| revision as i64);
+        if let Some(global_position) = up_to {
+            let parked = is_parked(&client, &command.subscription_name, global_position)
+                .await
+                .map_err(|_| ReplayParkedEventsCommandError::Connection)?;
+            if !parked {
+                return Err(ReplayParkedEventsCommandError::ParkedEventNotFound);
+            }
+        }
+
+        let count = request_replay(&client, &command.subscription_name, up_to)
+            .await
+            .map_err(|_| ReplayParkedEventsCommandError::Connection)?;
+        info!(count, "Replaying parked events");
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ReplayParkedEventsCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Persistent subscription not found")]
+    NotFound,
+    #[error("Parked event not found")]
+    ParkedEventNotFound,
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+
+    use crate::{
+        queries::get_parked_events::{
+            GetParkedEventsQuery, GetParkedEventsQueryHandler, GetParkedEventsQueryHandlerImpl,
+        },
+        test_event_store::{connect, delete_subscription, park_events, wait_for_parked_events},
+    };
+
+    use super::{
+        ReplayParkedEventsCommand, ReplayParkedEventsCommandError,
+        ReplayParkedEventsCommandHandler, ReplayParkedEventsCommandHandlerImpl,
+    };
+
+    #[tokio::test]
+    #[ignore = "needs an EventStore server, set SOURCE_CONTROL_TEST_EVENTSTORE"]
+    async fn should_replay_the_events_parked_up_to_the_chosen_one() {
+        let eventstore = connect();
+        let client = eventstore.get_client();
+        let (subscription_name, mut subscription) = park_events(&client, 3).await;
+        let query_handler = GetParkedEventsQueryHandlerImpl {
+            eventstore: eventstore.clone(),
+        };
+        let parked_events = query_handler
+            .handle(GetParkedEventsQuery {
+                subscription_name: subscription_name.clone(),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        let command_handler = ReplayParkedEventsCommandHandlerImpl { eventstore };
+
+        command_handler
+            .handle(ReplayParkedEventsCommand {
+                subscription_name: subscription_name.clone(),
+                up_to: Some(parked_events[1].parked_revision),
+            })
+            .await
+            .unwrap();
+
+        let mut replayed = Vec::new();
+        for _ in 0..2 {
+            let event = tokio::time::timeout(Duration::from_secs(10), subscription.next())
+                .await
+                .unwrap()
+                .unwrap();
+            replayed.push(event.event.as_ref().map(|event| event.id.to_string()));
+            subscription.ack(event).await.unwrap();
+        }
+        assert_eq!(
+            replayed,
+            vec![
+                parked_events[0].event_id.clone(),
+                parked_events[1].event_id.clone()
+            ]
+        );
+
+        wait_for_parked_events(&client, &subscription_name, 1).await;
+        let still_parked = query_handler
+            .handle(GetParkedEventsQuery {
+                subscription_name: subscription_name.clone(),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(still_parked.len(), 1);
+        assert_eq!(still_parked[0].event_id, parked_events[2].event_id);
+
+        // The replayed events were truncated from the parked stream
+        let result = command_handler
+            .handle(ReplayParkedEventsCommand {
+                subscription_name: subscription_name.clone(),
+                up_to: Some(parked_events[0].parked_revision),
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReplayParkedEventsCommandError::ParkedEventNotFound)
+        ));
+
+        delete_subscription(&client, &subscription_name).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an EventStore server, set SOURCE_CONTROL_TEST_EVENTSTORE"]
+    async fn should_not_replay_an_event_that_was_not_parked() {
+        let eventstore = connect();
+        let client = eventstore.get_client();
+        let (subscription_name, _subscription) = park_events(&client, 1).await;
+        let command_handler = ReplayParkedEventsCommandHandlerImpl { eventstore };
+
+        let result = command_handler
+            .handle(ReplayParkedEventsCommand {
+                subscription_name: subscription_name.clone(),
+                up_to: Some(10),
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ReplayParkedEventsCommandError::ParkedEventNotFound)
+        ));
+
+        delete_subscription(&client, &subscription_name).await;
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::pull_request::PullRequestError,
+    entities::{
+        developer::DeveloperId,
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+        review::{Review, ReviewStatus},
+    },
+    repositories::pull_request_repository::{
+        GetPullRequestError, PullRequestRepository, SavePullRequestError,
+    },
+};
+use thiserror::Error;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct SubmitReviewCommand {
+    pub repository_id: u64,
+    pub pull_request_id: u64,
+    pub developer_id: u64,
+    pub status_name: String,
+    pub accepting: bool,
+}
+
+#[async_trait]
+pub trait SubmitReviewCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: SubmitReviewCommand,
+    ) -> Result<PullRequest, SubmitReviewCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = SubmitReviewCommandHandler)]
+pub struct SubmitReviewCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+}
+
+#[async_trait]
+impl SubmitReviewCommandHandler for SubmitReviewCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: SubmitReviewCommand,
+    ) -> Result<PullRequest, SubmitReviewCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(PullRequestId(command.pull_request_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetPullRequestError::NotFound { pull_request_id } => {
+                    Err(SubmitReviewCommandError::NotFound {
+                        pull_request_id: pull_request_id.0,
+                    })
+                }
+                GetPullRequestError::Connection => Err(SubmitReviewCommandError::Connection),
+                GetPullRequestError::Unexpected | GetPullRequestError::Corrupt { .. } => {
+                    Err(SubmitReviewCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if aggregate.root.repository != RepositoryId(command.repository_id) {
+            return Err(SubmitReviewCommandError::NotFound {
+                pull_request_id: command.pull_request_id,
+            });
+        }
+
+        match aggregate.submit_review(Review {
+            developer_id: DeveloperId(command.developer_id),
+            status: ReviewStatus {
+                name: command.status_name,
+                accepting: command.accepting,
+            },
+        }) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                PullRequestError::Merged { .. } => Err(SubmitReviewCommandError::AlreadyMerged),
+                PullRequestError::Closed { .. } => Err(SubmitReviewCommandError::AlreadyClosed),
+                PullRequestError::ReviewNotFound { .. } => {
+                    Err(SubmitReviewCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => Ok(root),
+            Err(err) => match err {
+                SavePullRequestError::Connection => Err(SubmitReviewCommandError::Connection),
+                SavePullRequestError::Unexpected => Err(SubmitReviewCommandError::Unexpected),
+                SavePullRequestError::Conflict => Err(SubmitReviewCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SubmitReviewCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The pull request could not be found")]
+    NotFound { pull_request_id: u64 },
+    #[error("The pull request is already merged")]
+    AlreadyMerged,
+    #[error("The pull request is already closed")]
+    AlreadyClosed,
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::developer::DeveloperError,
+    entities::developer::{Developer, DeveloperId, DeveloperIdentity},
+    repositories::{
+        developer_identity_repository::DeveloperIdentityRepository,
+        developer_repository::{DeveloperRepository, GetDeveloperError, SaveDeveloperError},
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+#[derive(Debug)]
+pub struct UnlinkDeveloperIdentityCommand {
+    pub developer_id: u64,
+    pub identity: DeveloperIdentity,
+}
+
+#[async_trait]
+pub trait UnlinkDeveloperIdentityCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: UnlinkDeveloperIdentityCommand,
+    ) -> Result<Developer, UnlinkDeveloperIdentityCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = UnlinkDeveloperIdentityCommandHandler)]
+pub struct UnlinkDeveloperIdentityCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+    #[shaku(provide)]
+    pub identities: Box<dyn DeveloperIdentityRepository>,
+}
+
+#[async_trait]
+impl UnlinkDeveloperIdentityCommandHandler for UnlinkDeveloperIdentityCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: UnlinkDeveloperIdentityCommand,
+    ) -> Result<Developer, UnlinkDeveloperIdentityCommandError> {
+        let mut aggregate = match self.repository.get(DeveloperId(command.developer_id)).await {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetDeveloperError::NotFound { .. } => {
+                    Err(UnlinkDeveloperIdentityCommandError::NotFound)
+                }
+                GetDeveloperError::Connection => {
+                    Err(UnlinkDeveloperIdentityCommandError::Connection)
+                }
+                GetDeveloperError::Unexpected | GetDeveloperError::Corrupt { .. } => {
+                    Err(UnlinkDeveloperIdentityCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        match aggregate.unlink_identity(command.identity.clone()) {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                DeveloperError::Merged { into, .. } => {
+                    Err(UnlinkDeveloperIdentityCommandError::Merged { into: into.0 })
+                }
+                DeveloperError::IdentityNotLinked { .. } => {
+                    Err(UnlinkDeveloperIdentityCommandError::IdentityNotFound)
+                }
+                DeveloperError::NotMerged { .. }
+                | DeveloperError::MergeIntoSelf { .. }
+                | DeveloperError::TargetMerged { .. }
+                | DeveloperError::HasMergedDevelopers { .. }
+                | DeveloperError::IdentityAlreadyLinked { .. } => {
+                    Err(UnlinkDeveloperIdentityCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        match self.repository.save(aggregate).await {
+            Ok(_) => {
+                // The identity stays reserved when releasing fails, it is unlinked either way
+                if let Err(err) = self.identities.release(&command.identity, root.id).await {
+                    warn!(
+                        error = format!("{:?}", err),
+                        "Failed to release an unlinked identity"
+                    );
+                }
+                Ok(root)
+            }
+            Err(err) => match err {
+                SaveDeveloperError::Connection => {
+                    Err(UnlinkDeveloperIdentityCommandError::Connection)
+                }
+                SaveDeveloperError::Unexpected => {
+                    Err(UnlinkDeveloperIdentityCommandError::Unexpected)
+                }
+                SaveDeveloperError::Conflict => Err(UnlinkDeveloperIdentityCommandError::Conflict),
+            },
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UnlinkDeveloperIdentityCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The developer could not be found")]
+    NotFound,
+    #[error("The developer is merged into developer {into}")]
+    Merged { into: u64 },
+    #[error("The developer does not have this identity")]
+    IdentityNotFound,
+}
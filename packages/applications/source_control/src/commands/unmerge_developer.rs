@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::developer::DeveloperError,
+    entities::developer::{Developer, DeveloperId},
+    repositories::developer_repository::{
+        DeveloperRepository, GetDeveloperError, SaveDeveloperError,
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+#[derive(Debug)]
+pub struct UnmergeDeveloperCommand {
+    pub developer_id: u64,
+}
+
+#[async_trait]
+pub trait UnmergeDeveloperCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: UnmergeDeveloperCommand,
+    ) -> Result<Developer, UnmergeDeveloperCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = UnmergeDeveloperCommandHandler)]
+pub struct UnmergeDeveloperCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+}
+
+#[async_trait]
+impl UnmergeDeveloperCommandHandler for UnmergeDeveloperCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: UnmergeDeveloperCommand,
+    ) -> Result<Developer, UnmergeDeveloperCommandError> {
+        let mut aggregate = match self.repository.get(DeveloperId(command.developer_id)).await {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetDeveloperError::NotFound { .. } => Err(UnmergeDeveloperCommandError::NotFound),
+                GetDeveloperError::Connection => Err(UnmergeDeveloperCommandError::Connection),
+                GetDeveloperError::Unexpected | GetDeveloperError::Corrupt { .. } => {
+                    Err(UnmergeDeveloperCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        let merged_into = aggregate.root.merged_into;
+        match aggregate.unmerge() {
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                DeveloperError::NotMerged { .. } => Err(UnmergeDeveloperCommandError::NotMerged),
+                DeveloperError::Merged { .. }
+                | DeveloperError::MergeIntoSelf { .. }
+                | DeveloperError::TargetMerged { .. }
+                | DeveloperError::HasMergedDevelopers { .. }
+                | DeveloperError::IdentityAlreadyLinked { .. }
+                | DeveloperError::IdentityNotLinked { .. } => {
+                    Err(UnmergeDeveloperCommandError::Unexpected)
+                }
+            },
+        }?;
+        let root = aggregate.root.clone();
+
+        self.repository
+            .save(aggregate)
+            .await
+            .map_err(|err| match err {
+                SaveDeveloperError::Connection => UnmergeDeveloperCommandError::Connection,
+                SaveDeveloperError::Unexpected => UnmergeDeveloperCommandError::Unexpected,
+                SaveDeveloperError::Conflict => UnmergeDeveloperCommandError::Conflict,
+            })?;
+
+        if let Some(target_id) = merged_into {
+            self.remove_merged(target_id, root.id).await;
+        }
+
+        Ok(root)
+    }
+}
+
+impl UnmergeDeveloperCommandHandlerImpl {
+    /// Until this succeeds the former target can't be merged into another developer, which is
+    /// the safe side to fail on.
+    async fn remove_merged(&self, target_id: DeveloperId, source_id: DeveloperId) {
+        let result = match self.repository.get(target_id).await {
+            Ok(mut target) => {
+                target.remove_merged(source_id);
+                self.repository
+                    .save(target)
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+            Err(err) => Err(err.to_string()),
+        };
+
+        if let Err(err) = result {
+            warn!(
+                error = err,
+                "Failed to remove an unmerged developer from its former merge target"
+            );
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UnmergeDeveloperCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The developer could not be found")]
+    NotFound,
+    #[error("The developer is not merged into another developer")]
+    NotMerged,
+}
@@ -0,0 +1,261 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    aggregates::{metadata::CommandMetadata, organization::OrganizationError},
+    entities::organization::{Organization, OrganizationId},
+    repositories::{
+        organization_name_repository::{
+            organization_name_key, OrganizationNameRepository, ReserveOrganizationNameError,
+        },
+        organization_repository::{
+            GetOrganizationError, OrganizationRepository, SaveOrganizationError,
+        },
+    },
+};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+use crate::versioned::Versioned;
+
+/// Fields that are `None` are left unchanged.
+#[derive(Debug)]
+pub struct UpdateOrganizationCommand {
+    pub organization_id: u64,
+    pub name: Option<String>,
+    pub archived: Option<bool>,
+    /// Revision the client read the organization at, the command fails when it changed since.
+    pub expected_revision: Option<u64>,
+    pub metadata: CommandMetadata,
+}
+
+#[async_trait]
+pub trait UpdateOrganizationCommandHandler: Interface {
+    async fn handle(
+        &self,
+        command: UpdateOrganizationCommand,
+    ) -> Result<Versioned<Organization>, UpdateOrganizationCommandError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = UpdateOrganizationCommandHandler)]
+pub struct UpdateOrganizationCommandHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn OrganizationRepository>,
+    #[shaku(provide)]
+    pub names: Box<dyn OrganizationNameRepository>,
+}
+
+#[async_trait]
+impl UpdateOrganizationCommandHandler for UpdateOrganizationCommandHandlerImpl {
+    #[instrument(skip(self))]
+    async fn handle(
+        &self,
+        command: UpdateOrganizationCommand,
+    ) -> Result<Versioned<Organization>, UpdateOrganizationCommandError> {
+        let mut aggregate = match self
+            .repository
+            .get(OrganizationId(command.organization_id))
+            .await
+        {
+            Ok(agg) => Ok(agg),
+            Err(err) => match err {
+                GetOrganizationError::NotFound { .. } => {
+                    Err(UpdateOrganizationCommandError::NotFound)
+                }
+                GetOrganizationError::Connection => Err(UpdateOrganizationCommandError::Connection),
+                GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. } => {
+                    Err(UpdateOrganizationCommandError::Unexpected)
+                }
+            },
+        }?;
+
+        if command
+            .expected_revision
+            .is_some_and(|revision| revision != aggregate.latest_revision)
+        {
+            return Err(UpdateOrganizationCommandError::PreconditionFailed);
+        }
+
+        let old_name = aggregate.root.name.clone();
+        // Unarchive before renaming and archive after it, so an archived organization can be
+        // renamed in the same request that unarchives it
+        if command.archived == Some(false) && aggregate.root.archived {
+            aggregate.unarchive().map_err(map_organization_error)?;
+        }
+        if let Some(name) = command.name {
+            aggregate.rename(name).map_err(map_organization_error)?;
+        }
+        if command.archived == Some(true) && !aggregate.root.archived {
+            aggregate.archive().map_err(map_organization_error)?;
+        }
+        let root = aggregate.root.clone();
+        let revision = aggregate.revision_after_save();
+
+        if aggregate.draft_events.is_empty() {
+            return Ok(Versioned {
+                value: root,
+                revision,
+            });
+        }
+
+        // Only a change in the name key needs a different reservation, changing the casing of
+        // the name keeps the current one
+        let name_key_changed =
+            organization_name_key(&old_name) != organization_name_key(&root.name);
+        if name_key_changed {
+            self.names
+                .reserve(&root.name, root.id)
+                .await
+                .map_err(|err| match err {
+                    ReserveOrganizationNameError::Taken { .. } => {
+                        UpdateOrganizationCommandError::NameTaken
+                    }
+                    ReserveOrganizationNameError::Connection => {
+                        UpdateOrganizationCommandError::Connection
+                    }
+                    ReserveOrganizationNameError::Unexpected => {
+                        UpdateOrganizationCommandError::Unexpected
+                    }
+                })?;
+        }
+
+        let res = self.repository.save(aggregate, command.metadata).await;
+
+        if name_key_changed {
+            let released = if res.is_ok() { &old_name } else { &root.name };
+            if let Err(err) = self.names.release(released, root.id).await {
+                warn!(
+                    error = format!("{:?}", err),
+                    "Failed to release organization name"
+                );
+            }
+        }
+
+        match res {
+            Ok(_) => Ok(Versioned {
+                value: root,
+                revision,
+            }),
+            Err(err) => match err {
+                SaveOrganizationError::Connection => {
+                    Err(UpdateOrganizationCommandError::Connection)
+                }
+                SaveOrganizationError::Unexpected => {
+                    Err(UpdateOrganizationCommandError::Unexpected)
+                }
+                SaveOrganizationError::Conflict => Err(UpdateOrganizationCommandError::Conflict),
+            },
+        }
+    }
+}
+
+fn map_organization_error(err: OrganizationError) -> UpdateOrganizationCommandError {
+    match err {
+        OrganizationError::Archived { .. } => UpdateOrganizationCommandError::Archived,
+        OrganizationError::Deleted { .. } => UpdateOrganizationCommandError::NotFound,
+        OrganizationError::NameUnchanged { .. } => UpdateOrganizationCommandError::NameUnchanged,
+        OrganizationError::NotArchived { .. }
+        | OrganizationError::AccountAlreadyAdded { .. }
+        | OrganizationError::AccountNotLinked { .. } => UpdateOrganizationCommandError::Unexpected,
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateOrganizationCommandError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+    #[error("The organization could not be found")]
+    NotFound,
+    #[error("The organization is archived")]
+    Archived,
+    #[error("The organization already has this name")]
+    NameUnchanged,
+    #[error("Another organization already has this name")]
+    NameTaken,
+    #[error("The organization changed since the expected revision")]
+    PreconditionFailed,
+}
+
+#[cfg(test)]
+mod test {
+    use shaku::HasProvider;
+    use source_control_domain::aggregates::metadata::CommandMetadata;
+
+    use crate::{
+        commands::create_organization::{
+            CreateOrganizationCommand, CreateOrganizationCommandHandler,
+        },
+        module::{get_in_memory_module, ApplicationModule},
+    };
+
+    use super::{
+        UpdateOrganizationCommand, UpdateOrganizationCommandError, UpdateOrganizationCommandHandler,
+    };
+
+    async fn create(module: &ApplicationModule, name: &str) -> u64 {
+        let handler: Box<dyn CreateOrganizationCommandHandler> = module.provide().unwrap();
+        handler
+            .handle(CreateOrganizationCommand {
+                name: name.to_string(),
+                metadata: CommandMetadata::default(),
+            })
+            .await
+            .unwrap()
+            .value
+            .id
+            .0
+    }
+
+    async fn rename(
+        module: &ApplicationModule,
+        organization_id: u64,
+        name: &str,
+    ) -> Result<(), UpdateOrganizationCommandError> {
+        let handler: Box<dyn UpdateOrganizationCommandHandler> = module.provide().unwrap();
+        handler
+            .handle(UpdateOrganizationCommand {
+                organization_id,
+                name: Some(name.to_string()),
+                archived: None,
+                expected_revision: None,
+                metadata: CommandMetadata::default(),
+            })
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn should_not_rename_to_the_name_of_another_organization() {
+        let module = get_in_memory_module();
+        create(&module, "Porti").await;
+        let other = create(&module, "Other").await;
+
+        let result = rename(&module, other, "porti").await;
+
+        assert!(matches!(
+            result,
+            Err(UpdateOrganizationCommandError::NameTaken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_release_the_old_name_after_renaming() {
+        let module = get_in_memory_module();
+        let porti = create(&module, "Porti").await;
+        rename(&module, porti, "Renamed").await.unwrap();
+
+        let handler: Box<dyn CreateOrganizationCommandHandler> = module.provide().unwrap();
+        let result = handler
+            .handle(CreateOrganizationCommand {
+                name: "Porti".to_string(),
+                metadata: CommandMetadata::default(),
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+}
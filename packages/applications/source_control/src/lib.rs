@@ -1,3 +1,11 @@
 pub mod commands;
 pub mod queries;
 pub mod module;
+pub mod projection_host;
+pub mod versioned;
+
+#[cfg(any(test, feature = "in-memory"))]
+mod unconnected;
+
+#[cfg(test)]
+mod test_event_store;
@@ -1,28 +1,106 @@
 use std::sync::Arc;
 
 use bb8_postgres::{bb8::Pool, PostgresConnectionManager};
-use shaku::module;
-use source_control_domain::factories::platform_account::PlatformAccountFactoryImpl;
+use shaku::{module, Provider};
+use source_control_domain::{
+    factories::{
+        id_generator::{SnowflakeIdGenerator, SnowflakeIdGeneratorParameters},
+        platform_account::PlatformAccountFactoryImpl,
+    },
+    repositories::organization_repository::OrganizationRepository,
+};
 use source_control_event_store_persistence_adapter::{
     provider::{EventStoreProviderImpl, EventStoreProviderImplParameters},
-    repositories::organization_repository::OrganizationRepositoryImpl,
+    repositories::{
+        branch_repository::BranchRepositoryImpl,
+        commit_graph_repository::CommitGraphRepositoryImpl,
+        developer_repository::DeveloperRepositoryImpl,
+        organization_repository::OrganizationRepositoryImpl,
+        pull_request_repository::PullRequestRepositoryImpl,
+        repository_repository::RepositoryRepositoryImpl,
+    },
+    snapshots::{SnapshotSettingsImpl, SnapshotSettingsImplParameters},
 };
+use source_control_postgres_event_store_persistence_adapter::repositories::organization_repository::PostgresOrganizationRepositoryImpl;
 use source_control_postgres_persistence_adapter::{
-    projectors::organization::OrganizationProjector,
+    projectors::{
+        branch::BranchProjector, commit_graph::CommitGraphProjector, developer::DeveloperProjector,
+        organization::{CheckpointedOrganizationProjector, OrganizationProjector},
+        pull_request::PullRequestProjector,
+        repository::RepositoryProjector,
+    },
     provider::{PostgresProviderImpl, PostgresProviderImplParameters},
-    queries::get_organizations::GetOrganizationsQueryHandlerImpl,
+    queries::{
+        get_branch_pushes::GetBranchPushesQueryHandlerImpl,
+        get_commits_between::GetCommitsBetweenQueryHandlerImpl,
+        get_merge_bases::GetMergeBasesQueryHandlerImpl,
+        get_organizations::GetOrganizationsQueryHandlerImpl,
+        get_pull_requests::GetPullRequestsQueryHandlerImpl,
+        get_repositories::GetRepositoriesQueryHandlerImpl, is_ancestor::IsAncestorQueryHandlerImpl,
+        resolve_developer_identity::ResolveDeveloperIdentityQueryHandlerImpl,
+    },
+    repositories::{
+        developer_identity_repository::DeveloperIdentityRepositoryImpl,
+        developer_username_repository::DeveloperUsernameRepositoryImpl,
+        idempotency_key_repository::IdempotencyKeyRepositoryImpl,
+        organization_name_repository::OrganizationNameRepositoryImpl,
+        pull_request_number_repository::PullRequestNumberRepositoryImpl,
+    },
 };
 use tokio_postgres::NoTls;
 
 use crate::{
     commands::{
         add_platform_account::AddPlatformAccountCommandHandlerImpl,
+        archive_repository::ArchiveRepositoryCommandHandlerImpl,
+        close_pull_request::ClosePullRequestCommandHandlerImpl,
+        conflict_retry::{
+            ConflictRetryImpl, ConflictRetryImplParameters, ConflictRetryMetrics,
+            ConflictRetrySettings,
+        },
+        create_branch::CreateBranchCommandHandlerImpl,
+        create_developer::CreateDeveloperCommandHandlerImpl,
         create_organization::CreateOrganizationCommandHandlerImpl,
+        delete_branch::DeleteBranchCommandHandlerImpl,
+        delete_organization::DeleteOrganizationCommandHandlerImpl,
+        dismiss_review::DismissReviewCommandHandlerImpl,
+        edit_pull_request::EditPullRequestCommandHandlerImpl,
+        link_developer_identity::LinkDeveloperIdentityCommandHandlerImpl,
+        merge_developers::MergeDevelopersCommandHandlerImpl,
+        merge_pull_request::MergePullRequestCommandHandlerImpl,
+        move_repository::MoveRepositoryCommandHandlerImpl,
+        open_pull_request::OpenPullRequestCommandHandlerImpl,
+        push_branch::PushBranchCommandHandlerImpl,
+        rebuild_organization_projection::{
+            OrganizationProjectionRebuildImpl, OrganizationProjectionRebuildImplParameters,
+            RebuildOrganizationProjectionCommandHandlerImpl,
+        },
+        record_commits::RecordCommitsCommandHandlerImpl,
+        register_repository::RegisterRepositoryCommandHandlerImpl,
         remove_platform_account::RemovePlatformAccountCommandHandlerImpl,
+        rename_repository::RenameRepositoryCommandHandlerImpl,
+        replay_parked_events::{
+            PostgresReplayParkedEventsCommandHandlerImpl, ReplayParkedEventsCommandHandler,
+            ReplayParkedEventsCommandHandlerImpl,
+        },
+        submit_review::SubmitReviewCommandHandlerImpl,
+        unlink_developer_identity::UnlinkDeveloperIdentityCommandHandlerImpl,
+        unmerge_developer::UnmergeDeveloperCommandHandlerImpl,
+        update_organization::UpdateOrganizationCommandHandlerImpl,
     },
+    projection_host::ProjectionHostImpl,
     queries::{
+        get_branch::GetBranchQueryHandlerImpl,
+        get_developer::GetDeveloperQueryHandlerImpl,
         get_organization::GetOrganizationQueryHandlerImpl,
         get_organization_log::GetOrganizationLogQueryHandlerImpl,
+        get_parked_events::{
+            GetParkedEventsQueryHandler, GetParkedEventsQueryHandlerImpl,
+            PostgresGetParkedEventsQueryHandlerImpl,
+        },
+        get_persistent_subscriptions::GetPersistentSubscriptionsQueryHandlerImpl,
+        get_pull_request::GetPullRequestQueryHandlerImpl,
+        get_repository::GetRepositoryQueryHandlerImpl,
     },
 };
 
@@ -30,32 +108,318 @@ module! {
     pub ApplicationModule {
         components = [
             PostgresProviderImpl,
-            EventStoreProviderImpl
+            EventStoreProviderImpl,
+            SnowflakeIdGenerator,
+            SnapshotSettingsImpl,
+            ConflictRetryImpl,
+            OrganizationProjectionRebuildImpl,
+            ProjectionHostImpl
         ],
         providers = [
             AddPlatformAccountCommandHandlerImpl,
             OrganizationRepositoryImpl,
+            OrganizationNameRepositoryImpl,
             PlatformAccountFactoryImpl,
             CreateOrganizationCommandHandlerImpl,
             GetOrganizationLogQueryHandlerImpl,
             GetOrganizationQueryHandlerImpl,
             RemovePlatformAccountCommandHandlerImpl,
             OrganizationProjector,
+            CheckpointedOrganizationProjector,
             GetOrganizationsQueryHandlerImpl,
+            UpdateOrganizationCommandHandlerImpl,
+            DeleteOrganizationCommandHandlerImpl,
+            RepositoryRepositoryImpl,
+            RegisterRepositoryCommandHandlerImpl,
+            RenameRepositoryCommandHandlerImpl,
+            ArchiveRepositoryCommandHandlerImpl,
+            MoveRepositoryCommandHandlerImpl,
+            GetRepositoryQueryHandlerImpl,
+            RepositoryProjector,
+            GetRepositoriesQueryHandlerImpl,
+            PullRequestRepositoryImpl,
+            PullRequestNumberRepositoryImpl,
+            OpenPullRequestCommandHandlerImpl,
+            EditPullRequestCommandHandlerImpl,
+            SubmitReviewCommandHandlerImpl,
+            DismissReviewCommandHandlerImpl,
+            MergePullRequestCommandHandlerImpl,
+            ClosePullRequestCommandHandlerImpl,
+            GetPullRequestQueryHandlerImpl,
+            PullRequestProjector,
+            GetPullRequestsQueryHandlerImpl,
+            CommitGraphRepositoryImpl,
+            RecordCommitsCommandHandlerImpl,
+            CommitGraphProjector,
+            IsAncestorQueryHandlerImpl,
+            GetMergeBasesQueryHandlerImpl,
+            GetCommitsBetweenQueryHandlerImpl,
+            BranchRepositoryImpl,
+            CreateBranchCommandHandlerImpl,
+            PushBranchCommandHandlerImpl,
+            DeleteBranchCommandHandlerImpl,
+            GetBranchQueryHandlerImpl,
+            BranchProjector,
+            GetBranchPushesQueryHandlerImpl,
+            DeveloperRepositoryImpl,
+            DeveloperUsernameRepositoryImpl,
+            DeveloperIdentityRepositoryImpl,
+            CreateDeveloperCommandHandlerImpl,
+            LinkDeveloperIdentityCommandHandlerImpl,
+            UnlinkDeveloperIdentityCommandHandlerImpl,
+            MergeDevelopersCommandHandlerImpl,
+            UnmergeDeveloperCommandHandlerImpl,
+            GetDeveloperQueryHandlerImpl,
+            DeveloperProjector,
+            ResolveDeveloperIdentityQueryHandlerImpl,
+            IdempotencyKeyRepositoryImpl,
+            RebuildOrganizationProjectionCommandHandlerImpl,
+            GetPersistentSubscriptionsQueryHandlerImpl,
+            GetParkedEventsQueryHandlerImpl,
+            ReplayParkedEventsCommandHandlerImpl,
         ],
     }
 }
 
+/// Where the events of aggregates are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventStorage {
+    #[default]
+    EventStore,
+    /// Only organizations are stored in postgres, the other aggregates and their subscriptions
+    /// stay in EventStore.
+    PostgresOrganizations,
+}
+
+/// `organization_projection_name` names the checkpoint of the catch-up organization projection,
+/// which a rebuild of the projection moves past the events it replayed. `None` when organizations
+/// are projected by another runtime, the projection can't be rebuilt then.
+#[allow(clippy::too_many_arguments)]
 pub fn get_module(
     postgres_client: Arc<Pool<PostgresConnectionManager<NoTls>>>,
     eventstore_client: Arc<eventstore::Client>,
+    worker_id: u16,
+    snapshot_frequency: u64,
+    event_storage: EventStorage,
+    conflict_retry: ConflictRetrySettings,
+    conflict_retry_metrics: ConflictRetryMetrics,
+    organization_projection_name: Option<String>,
 ) -> ApplicationModule {
-    ApplicationModule::builder()
+    let builder = ApplicationModule::builder()
         .with_component_parameters::<PostgresProviderImpl>(PostgresProviderImplParameters {
             client: postgres_client,
         })
         .with_component_parameters::<EventStoreProviderImpl>(EventStoreProviderImplParameters {
             client: eventstore_client,
         })
-        .build()
+        .with_component_parameters::<SnowflakeIdGenerator>(SnowflakeIdGeneratorParameters {
+            worker_id,
+            state: Default::default(),
+        })
+        .with_component_parameters::<SnapshotSettingsImpl>(SnapshotSettingsImplParameters {
+            frequency: snapshot_frequency,
+        })
+        .with_component_parameters::<ConflictRetryImpl>(ConflictRetryImplParameters {
+            settings: conflict_retry,
+            metrics: conflict_retry_metrics,
+        })
+        .with_component_parameters::<OrganizationProjectionRebuildImpl>(
+            OrganizationProjectionRebuildImplParameters {
+                progress: Default::default(),
+                live_projection_name: organization_projection_name,
+            },
+        );
+
+    match event_storage {
+        EventStorage::EventStore => builder.build(),
+        EventStorage::PostgresOrganizations => builder
+            .with_provider_override::<dyn OrganizationRepository>(Box::new(
+                PostgresOrganizationRepositoryImpl::provide,
+            ))
+            .with_provider_override::<dyn GetParkedEventsQueryHandler>(Box::new(
+                PostgresGetParkedEventsQueryHandlerImpl::provide,
+            ))
+            .with_provider_override::<dyn ReplayParkedEventsCommandHandler>(Box::new(
+                PostgresReplayParkedEventsCommandHandlerImpl::provide,
+            ))
+            .build(),
+    }
+}
+
+#[cfg(any(test, feature = "in-memory"))]
+pub use in_memory::get_in_memory_module;
+
+#[cfg(any(test, feature = "in-memory"))]
+mod in_memory {
+    use std::sync::Arc;
+
+    use opentelemetry::global;
+    use source_control_domain::{
+        aggregates::{
+            branch::BranchEvent, commit_graph::CommitGraphEvent, developer::DeveloperEvent,
+            organization::OrganizationEvent, pull_request::PullRequestEvent,
+            repository::RepositoryEvent,
+        },
+        factories::id_generator::{SnowflakeIdGenerator, SnowflakeIdGeneratorParameters},
+        repositories::{
+            branch_repository::BranchRepository, commit_graph_repository::CommitGraphRepository,
+            developer_identity_repository::DeveloperIdentityRepository,
+            developer_repository::DeveloperRepository,
+            developer_username_repository::DeveloperUsernameRepository,
+            organization_name_repository::OrganizationNameRepository,
+            organization_repository::OrganizationRepository,
+            pull_request_number_repository::PullRequestNumberRepository,
+            pull_request_repository::PullRequestRepository,
+            repository_repository::RepositoryRepository,
+        },
+    };
+    use source_control_event_store_persistence_adapter::{
+        provider::EventStoreProvider,
+        snapshots::{SnapshotSettingsImpl, SnapshotSettingsImplParameters},
+    };
+    use source_control_in_memory_persistence_adapter::{
+        idempotency_key_repository::InMemoryIdempotencyKeyRepositoryImpl,
+        organizations::InMemoryOrganizations,
+        unconnected::{Unconnected, UnconnectedEventStoreProvider, UnconnectedPostgresProvider},
+    };
+    use source_control_postgres_persistence_adapter::{
+        projectors::Projector,
+        provider::PostgresProvider,
+        queries::{
+            get_branch_pushes::GetBranchPushesQueryHandler,
+            get_commits_between::GetCommitsBetweenQueryHandler,
+            get_merge_bases::GetMergeBasesQueryHandler,
+            get_organizations::GetOrganizationsQueryHandler,
+            get_pull_requests::GetPullRequestsQueryHandler,
+            get_repositories::GetRepositoriesQueryHandler, is_ancestor::IsAncestorQueryHandler,
+            resolve_developer_identity::ResolveDeveloperIdentityQueryHandler,
+        },
+        repositories::idempotency_key_repository::IdempotencyKeyRepository,
+    };
+
+    use super::ApplicationModule;
+    use crate::{
+        commands::{
+            conflict_retry::{
+                ConflictRetryImpl, ConflictRetryImplParameters, ConflictRetryMetrics,
+            },
+            replay_parked_events::ReplayParkedEventsCommandHandler,
+        },
+        queries::{
+            get_parked_events::GetParkedEventsQueryHandler,
+            get_persistent_subscriptions::GetPersistentSubscriptionsQueryHandler,
+        },
+        unconnected::Unconnected as UnconnectedSubscriptions,
+    };
+
+    /// Keeps organizations and idempotency keys in memory and projects organizations while saving,
+    /// to run handlers and endpoints in tests without EventStore or postgres. The other aggregates
+    /// and the subscriptions fail with connection errors.
+    pub fn get_in_memory_module() -> ApplicationModule {
+        let organizations = Arc::new(InMemoryOrganizations::default());
+        let repository = organizations.clone();
+        let names = organizations.clone();
+        let projector = organizations.clone();
+        let idempotency_keys = InMemoryIdempotencyKeyRepositoryImpl::default();
+
+        ApplicationModule::builder()
+            .with_component_override::<dyn PostgresProvider>(Box::new(UnconnectedPostgresProvider))
+            .with_component_override::<dyn EventStoreProvider>(Box::new(
+                UnconnectedEventStoreProvider,
+            ))
+            .with_component_parameters::<SnowflakeIdGenerator>(SnowflakeIdGeneratorParameters {
+                worker_id: 0,
+                state: Default::default(),
+            })
+            .with_component_parameters::<SnapshotSettingsImpl>(SnapshotSettingsImplParameters {
+                frequency: 0,
+            })
+            .with_component_parameters::<ConflictRetryImpl>(ConflictRetryImplParameters {
+                settings: Default::default(),
+                metrics: ConflictRetryMetrics::new(&global::meter("com.rafaeltab.application")),
+            })
+            .with_provider_override::<dyn OrganizationRepository>(Box::new(move |_| {
+                Ok(repository.repository())
+            }))
+            .with_provider_override::<dyn OrganizationNameRepository>(Box::new(move |_| {
+                Ok(names.names())
+            }))
+            .with_provider_override::<dyn Projector<OrganizationEvent>>(Box::new(move |_| {
+                Ok(projector.projector())
+            }))
+            .with_provider_override::<dyn GetOrganizationsQueryHandler>(Box::new(move |_| {
+                Ok(organizations.get_organizations())
+            }))
+            .with_provider_override::<dyn IdempotencyKeyRepository>(Box::new(move |_| {
+                Ok(Box::new(idempotency_keys.clone()))
+            }))
+            .with_provider_override::<dyn BranchRepository>(Box::new(|_| Ok(Box::new(Unconnected))))
+            .with_provider_override::<dyn CommitGraphRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn DeveloperIdentityRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn DeveloperRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn DeveloperUsernameRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn PullRequestNumberRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn PullRequestRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn RepositoryRepository>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetBranchPushesQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetCommitsBetweenQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetMergeBasesQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetPullRequestsQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetRepositoriesQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn IsAncestorQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn ResolveDeveloperIdentityQueryHandler>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn Projector<BranchEvent>>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn Projector<CommitGraphEvent>>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn Projector<DeveloperEvent>>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn Projector<PullRequestEvent>>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn Projector<RepositoryEvent>>(Box::new(|_| {
+                Ok(Box::new(Unconnected))
+            }))
+            .with_provider_override::<dyn GetParkedEventsQueryHandler>(Box::new(|_| {
+                Ok(Box::new(UnconnectedSubscriptions))
+            }))
+            .with_provider_override::<dyn GetPersistentSubscriptionsQueryHandler>(Box::new(|_| {
+                Ok(Box::new(UnconnectedSubscriptions))
+            }))
+            .with_provider_override::<dyn ReplayParkedEventsCommandHandler>(Box::new(|_| {
+                Ok(Box::new(UnconnectedSubscriptions))
+            }))
+            .build()
+    }
 }
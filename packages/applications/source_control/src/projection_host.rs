@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, fmt::Debug, future::Future, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Utc};
+use opentelemetry::{
+    metrics::{Counter, Meter},
+    KeyValue,
+};
+use shaku::{Component, Interface};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerRestartSettings {
+    /// Delay before the first restart, doubled for every restart after it.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for WorkerRestartSettings {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerState {
+    Running,
+    /// Failed and waits until `retry_at` to start again.
+    BackingOff {
+        attempt: u32,
+        retry_at: DateTime<Utc>,
+    },
+    /// Returned without an error or was shut down, it isn't restarted.
+    Stopped,
+}
+
+impl WorkerState {
+    pub const NAMES: [&'static str; 3] = ["running", "backing_off", "stopped"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorkerState::Running => "running",
+            WorkerState::BackingOff { .. } => "backing_off",
+            WorkerState::Stopped => "stopped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerStatus {
+    pub projection: String,
+    pub worker_id: u32,
+    pub state: WorkerState,
+    pub restarts: u64,
+    pub last_error: Option<String>,
+    /// When the worker entered its current state.
+    pub since: DateTime<Utc>,
+}
+
+pub struct ProjectionHostMetrics {
+    pub restarts: Counter<u64>,
+}
+
+impl ProjectionHostMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            restarts: meter
+                .u64_counter("eventstore.projection.worker.restarts.total")
+                .with_description("Amount of projection workers restarted after they failed")
+                .with_unit("restart")
+                .build(),
+        }
+    }
+}
+
+/// States of the projection workers of this process, updated by [`supervise`].
+pub trait ProjectionHost: Interface {
+    fn workers(&self) -> Vec<WorkerStatus>;
+    fn set_state(
+        &self,
+        projection: &str,
+        worker_id: u32,
+        state: WorkerState,
+        error: Option<String>,
+    );
+}
+
+#[derive(Component)]
+#[shaku(interface = ProjectionHost)]
+pub struct ProjectionHostImpl {
+    #[shaku(default)]
+    workers: Mutex<BTreeMap<(String, u32), WorkerStatus>>,
+}
+
+impl ProjectionHost for ProjectionHostImpl {
+    fn workers(&self) -> Vec<WorkerStatus> {
+        self.workers.lock().unwrap().values().cloned().collect()
+    }
+
+    fn set_state(
+        &self,
+        projection: &str,
+        worker_id: u32,
+        state: WorkerState,
+        error: Option<String>,
+    ) {
+        let mut workers = self.workers.lock().unwrap();
+        let status = workers
+            .entry((projection.to_string(), worker_id))
+            .or_insert_with(|| WorkerStatus {
+                projection: projection.to_string(),
+                worker_id,
+                state: state.clone(),
+                restarts: 0,
+                last_error: None,
+                since: Utc::now(),
+            });
+
+        if matches!(state, WorkerState::BackingOff { .. }) {
+            status.restarts += 1;
+        }
+        if error.is_some() {
+            status.last_error = error;
+        }
+        status.state = state;
+        status.since = Utc::now();
+    }
+}
+
+/// Runs the worker `start` returns, and starts it again with exponential backoff whenever it
+/// fails. A worker that ran for longer than the max delay before failing starts over at the base
+/// delay. Returns once the worker returns without an error, or fails or backs off after
+/// `shutdown` is cancelled.
+pub async fn supervise<E, F, Fut>(
+    host: &dyn ProjectionHost,
+    settings: &WorkerRestartSettings,
+    metrics: &ProjectionHostMetrics,
+    projection: &str,
+    worker_id: u32,
+    shutdown: &CancellationToken,
+    mut start: F,
+) where
+    E: Debug,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    let attributes = [KeyValue::new("projection", projection.to_string())];
+
+    let mut attempt = 0;
+    loop {
+        host.set_state(projection, worker_id, WorkerState::Running, None);
+        let started_at = tokio::time::Instant::now();
+        let err = match start().await {
+            Ok(_) => {
+                info!(projection, worker_id, "Projection worker stopped");
+                host.set_state(projection, worker_id, WorkerState::Stopped, None);
+                return;
+            }
+            Err(err) if shutdown.is_cancelled() => {
+                error!(
+                    projection,
+                    worker_id, "Projection worker failed while shutting down {:?}", err
+                );
+                host.set_state(
+                    projection,
+                    worker_id,
+                    WorkerState::Stopped,
+                    Some(format!("{:?}", err)),
+                );
+                return;
+            }
+            Err(err) => err,
+        };
+
+        if started_at.elapsed() > settings.max_delay {
+            attempt = 0;
+        }
+        attempt += 1;
+        let delay = restart_delay(settings, attempt);
+        error!(
+            projection,
+            worker_id, attempt, "Projection worker failed, restarting {:?}", err
+        );
+        metrics.restarts.add(1, &attributes);
+        host.set_state(
+            projection,
+            worker_id,
+            WorkerState::BackingOff {
+                attempt,
+                retry_at: Utc::now() + delay,
+            },
+            Some(format!("{:?}", err)),
+        );
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                host.set_state(projection, worker_id, WorkerState::Stopped, None);
+                return;
+            }
+            _ = tokio::time::sleep(delay) => {}
+        }
+    }
+}
+
+fn restart_delay(settings: &WorkerRestartSettings, attempt: u32) -> Duration {
+    settings
+        .base_delay
+        .saturating_mul(2u32.saturating_pow(attempt - 1))
+        .min(settings.max_delay)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, time::Duration};
+
+    use opentelemetry::global;
+    use tokio_util::sync::CancellationToken;
+
+    use super::{
+        restart_delay, supervise, ProjectionHost, ProjectionHostImpl, ProjectionHostMetrics,
+        WorkerRestartSettings, WorkerState,
+    };
+
+    #[test]
+    fn should_double_restart_delay_up_to_max_delay() {
+        let settings = WorkerRestartSettings {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+        };
+
+        assert_eq!(restart_delay(&settings, 1), Duration::from_millis(100));
+        assert_eq!(restart_delay(&settings, 3), Duration::from_millis(400));
+        assert_eq!(restart_delay(&settings, 40), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn should_restart_failed_worker_until_it_stops() {
+        let host = ProjectionHostImpl {
+            workers: Default::default(),
+        };
+        let settings = WorkerRestartSettings {
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        };
+        let attempts = Cell::new(0);
+
+        supervise(
+            &host,
+            &settings,
+            &ProjectionHostMetrics::new(&global::meter("test")),
+            "test",
+            0,
+            &CancellationToken::new(),
+            || async {
+                attempts.set(attempts.get() + 1);
+                match attempts.get() {
+                    3 => Ok(()),
+                    _ => Err("Connection lost"),
+                }
+            },
+        )
+        .await;
+
+        let workers = host.workers();
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(workers[0].state, WorkerState::Stopped);
+        assert_eq!(workers[0].restarts, 2);
+        assert_eq!(
+            workers[0].last_error.as_deref(),
+            Some("\"Connection lost\"")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::{
+        branch::{Branch, BranchId},
+        repository::RepositoryId,
+    },
+    repositories::branch_repository::{BranchRepository, GetBranchError},
+};
+use thiserror::Error;
+
+pub struct GetBranchQuery {
+    pub repository_id: u64,
+    pub name: String,
+}
+
+#[async_trait]
+pub trait GetBranchQueryHandler: Interface {
+    async fn handle(&self, query: GetBranchQuery) -> Result<Branch, GetBranchQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetBranchQueryHandler)]
+pub struct GetBranchQueryHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn BranchRepository>,
+}
+
+#[async_trait]
+impl GetBranchQueryHandler for GetBranchQueryHandlerImpl {
+    async fn handle(&self, query: GetBranchQuery) -> Result<Branch, GetBranchQueryError> {
+        let branch_id = BranchId::from_name(RepositoryId(query.repository_id), &query.name);
+        match self.repository.get(branch_id).await {
+            Ok(branch_aggregate) if branch_aggregate.root.deleted => {
+                Err(GetBranchQueryError::NotFound)
+            }
+            Ok(branch_aggregate) => Ok(branch_aggregate.root),
+            Err(GetBranchError::Connection) => Err(GetBranchQueryError::Connection),
+            Err(GetBranchError::Unexpected | GetBranchError::Corrupt { .. }) => {
+                Err(GetBranchQueryError::Unexpected)
+            }
+            Err(GetBranchError::NotFound { .. }) => Err(GetBranchQueryError::NotFound),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetBranchQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Branch not found.")]
+    NotFound,
+}
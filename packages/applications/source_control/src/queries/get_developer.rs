@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::developer::{Developer, DeveloperId},
+    repositories::developer_repository::{DeveloperRepository, GetDeveloperError},
+};
+use thiserror::Error;
+
+pub struct GetDeveloperQuery {
+    pub developer_id: u64,
+}
+
+#[async_trait]
+pub trait GetDeveloperQueryHandler: Interface {
+    async fn handle(&self, query: GetDeveloperQuery) -> Result<Developer, GetDeveloperQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetDeveloperQueryHandler)]
+pub struct GetDeveloperQueryHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn DeveloperRepository>,
+}
+
+#[async_trait]
+impl GetDeveloperQueryHandler for GetDeveloperQueryHandlerImpl {
+    async fn handle(&self, query: GetDeveloperQuery) -> Result<Developer, GetDeveloperQueryError> {
+        match self.repository.get(DeveloperId(query.developer_id)).await {
+            Ok(developer_aggregate) => Ok(developer_aggregate.root),
+            Err(GetDeveloperError::Connection) => Err(GetDeveloperQueryError::Connection),
+            Err(GetDeveloperError::Unexpected | GetDeveloperError::Corrupt { .. }) => {
+                Err(GetDeveloperQueryError::Unexpected)
+            }
+            Err(GetDeveloperError::NotFound { .. }) => Err(GetDeveloperQueryError::NotFound),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetDeveloperQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Developer not found.")]
+    NotFound,
+}
@@ -6,6 +6,8 @@ use source_control_domain::{
 };
 use thiserror::Error;
 
+use crate::versioned::Versioned;
+
 pub struct GetOrganizationQuery {
     pub id: u64,
 }
@@ -15,7 +17,7 @@ pub trait GetOrganizationQueryHandler: Interface {
     async fn handle(
         &self,
         query: GetOrganizationQuery,
-    ) -> Result<Organization, GetOrganizationQueryError>;
+    ) -> Result<Versioned<Organization>, GetOrganizationQueryError>;
 }
 
 
@@ -31,11 +33,21 @@ impl GetOrganizationQueryHandler for GetOrganizationQueryHandlerImpl {
     async fn handle(
         &self,
         query: GetOrganizationQuery,
-    ) -> Result<Organization, GetOrganizationQueryError> {
+    ) -> Result<Versioned<Organization>, GetOrganizationQueryError> {
         match self.repository.get(OrganizationId(query.id)).await {
-            Ok(organization_aggregate) => Ok(organization_aggregate.root),
+            Ok(organization_aggregate) if organization_aggregate.root.deleted => {
+                Err(GetOrganizationQueryError::NotFound {
+                    organization_id: query.id,
+                })
+            }
+            Ok(organization_aggregate) => Ok(Versioned {
+                revision: organization_aggregate.latest_revision,
+                value: organization_aggregate.root,
+            }),
             Err(GetOrganizationError::Connection) => Err(GetOrganizationQueryError::Connection),
-            Err(GetOrganizationError::Unexpected) => Err(GetOrganizationQueryError::Unexpected),
+            Err(GetOrganizationError::Unexpected | GetOrganizationError::Corrupt { .. }) => {
+                Err(GetOrganizationQueryError::Unexpected)
+            }
             Err(GetOrganizationError::NotFound { organization_id }) => {
                 Err(GetOrganizationQueryError::NotFound {
                     organization_id: organization_id.0,
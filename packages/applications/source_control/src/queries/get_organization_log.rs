@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use shaku::{Interface, Provider};
 use source_control_domain::{
-    aggregates::organization::OrganizationEvent,
+    aggregates::{metadata::LoggedEvent, organization::OrganizationEvent},
     entities::organization::OrganizationId,
     repositories::organization_repository::{GetOrganizationLogError, OrganizationRepository},
 };
@@ -16,7 +16,7 @@ pub trait GetOrganizationLogQueryHandler: Interface {
     async fn handle(
         &self,
         query: GetOrganizationLogQuery,
-    ) -> Result<Box<[OrganizationEvent]>, GetOrganizationLogQueryError>;
+    ) -> Result<Box<[LoggedEvent<OrganizationEvent>]>, GetOrganizationLogQueryError>;
 }
 
 #[derive(Provider)]
@@ -31,13 +31,13 @@ impl GetOrganizationLogQueryHandler for GetOrganizationLogQueryHandlerImpl {
     async fn handle(
         &self,
         query: GetOrganizationLogQuery,
-    ) -> Result<Box<[OrganizationEvent]>, GetOrganizationLogQueryError> {
+    ) -> Result<Box<[LoggedEvent<OrganizationEvent>]>, GetOrganizationLogQueryError> {
         match self.repository.get_log(OrganizationId(query.id)).await {
             Ok(organization_log) => Ok(organization_log),
             Err(GetOrganizationLogError::Connection) => {
                 Err(GetOrganizationLogQueryError::Connection)
             }
-            Err(GetOrganizationLogError::Unexpected) => {
+            Err(GetOrganizationLogError::Unexpected | GetOrganizationLogError::Corrupt { .. }) => {
                 Err(GetOrganizationLogQueryError::Unexpected)
             }
             Err(GetOrganizationLogError::NotFound { organization_id }) => {
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_store_util::parked::{
+    decode_parked_event, decode_stored_parked_event, parked_metadata, parked_stream_name,
+    NackReason,
+};
+use eventstore::{GetPersistentSubscriptionInfoOptions, ReadStreamOptions, StreamPosition};
+use shaku::{Interface, Provider};
+use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+use source_control_postgres_event_store_persistence_adapter::events::{
+    has_checkpoint, read_parked_events, ParkedStoredEvent,
+};
+use source_control_postgres_persistence_adapter::provider::PostgresProvider;
+use thiserror::Error;
+
+pub struct GetParkedEventsQuery {
+    pub subscription_name: String,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParkedEvent {
+    /// Revision of the link in the parked stream, or the global position of the event for
+    /// subscriptions to the events stored in postgres. Replaying up to it replays this event.
+    pub parked_revision: u64,
+    /// `None` when the event was deleted since it was parked.
+    pub event_id: Option<String>,
+    pub stream_id: Option<String>,
+    pub event_type: Option<String>,
+    /// The event as it is projected, or why it could not be decoded.
+    pub event: Result<serde_json::Value, String>,
+    pub reason: Option<String>,
+    pub retry_count: Option<usize>,
+    pub parked_at: Option<String>,
+}
+
+#[async_trait]
+pub trait GetParkedEventsQueryHandler: Interface {
+    /// Events the subscription parked that weren't replayed yet, oldest first.
+    async fn handle(
+        &self,
+        query: GetParkedEventsQuery,
+    ) -> Result<Vec<ParkedEvent>, GetParkedEventsQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetParkedEventsQueryHandler)]
+pub struct GetParkedEventsQueryHandlerImpl {
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl GetParkedEventsQueryHandler for GetParkedEventsQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetParkedEventsQuery,
+    ) -> Result<Vec<ParkedEvent>, GetParkedEventsQueryError> {
+        let client = self.eventstore.get_client();
+        match client
+            .get_persistent_subscription_info_to_all(
+                &query.subscription_name,
+                &GetPersistentSubscriptionInfoOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => {}
+            Err(eventstore::Error::ResourceNotFound) => {
+                return Err(GetParkedEventsQueryError::NotFound {
+                    subscription_name: query.subscription_name,
+                })
+            }
+            Err(_) => return Err(GetParkedEventsQueryError::Connection),
+        }
+
+        let mut stream = client
+            .read_stream(
+                parked_stream_name(&query.subscription_name),
+                &ReadStreamOptions::default()
+                    .forwards()
+                    .position(StreamPosition::Start)
+                    .resolve_link_tos()
+                    .max_count(query.limit),
+            )
+            .await
+            .map_err(|_| GetParkedEventsQueryError::Connection)?;
+
+        let mut parked_events = Vec::new();
+        loop {
+            let event = match stream.next().await {
+                Ok(Some(event)) => event,
+                Ok(None) => break,
+                // Nothing was parked yet
+                Err(eventstore::Error::ResourceNotFound) => break,
+                Err(_) => return Err(GetParkedEventsQueryError::Connection),
+            };
+            parked_events.push(to_parked_event(event));
+        }
+
+        Ok(parked_events)
+    }
+}
+
+fn to_parked_event(event: eventstore::ResolvedEvent) -> ParkedEvent {
+    let link = event.get_original_event();
+    let (reason, parked_at) = parked_metadata(link);
+    let recorded_event = event.event.as_ref().filter(|_| event.link.is_some());
+
+    ParkedEvent {
+        parked_revision: link.revision,
+        event_id: recorded_event.map(|event| event.id.to_string()),
+        stream_id: recorded_event.map(|event| event.stream_id.clone()),
+        event_type: recorded_event.map(|event| event.event_type.clone()),
+        event: match recorded_event {
+            Some(recorded_event) => {
+                decode_parked_event(recorded_event).map_err(|err| err.to_string())
+            }
+            None => Err("The event no longer exists".to_string()),
+        },
+        reason: reason.as_ref().map(|reason| reason.reason.clone()),
+        retry_count: reason.and_then(|reason| reason.retry_count),
+        parked_at,
+    }
+}
+
+/// Lists the events parked by the subscriptions to the events stored in postgres, the
+/// subscriptions it doesn't know are looked up in EventStore.
+#[derive(Provider)]
+#[shaku(interface = GetParkedEventsQueryHandler)]
+pub struct PostgresGetParkedEventsQueryHandlerImpl {
+    #[shaku(inject)]
+    pub postgres: Arc<dyn PostgresProvider>,
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl GetParkedEventsQueryHandler for PostgresGetParkedEventsQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetParkedEventsQuery,
+    ) -> Result<Vec<ParkedEvent>, GetParkedEventsQueryError> {
+        let client = self.postgres.get_client().await;
+        let known = has_checkpoint(&client, &query.subscription_name)
+            .await
+            .map_err(|_| GetParkedEventsQueryError::Connection)?;
+        if !known {
+            drop(client);
+            let eventstore = GetParkedEventsQueryHandlerImpl {
+                eventstore: self.eventstore.clone(),
+            };
+            return eventstore.handle(query).await;
+        }
+
+        let parked_events =
+            read_parked_events(&client, &query.subscription_name, query.limit as i64)
+                .await
+                .map_err(|_| GetParkedEventsQueryError::Connection)?;
+        Ok(parked_events
+            .into_iter()
+            .map(to_stored_parked_event)
+            .collect())
+    }
+}
+
+fn to_stored_parked_event(parked: ParkedStoredEvent) -> ParkedEvent {
+    let event = parked.event;
+    let reason = NackReason::from_nack_reason(&parked.reason);
+
+    ParkedEvent {
+        parked_revision: event.global_position as u64,
+        event: decode_stored_parked_event(&event.event_id, &event.event_type, event.data)
+            .map_err(|err| err.to_string()),
+        event_id: Some(event.event_id),
+        stream_id: Some(event.stream_id),
+        event_type: Some(event.event_type),
+        reason: Some(reason.reason),
+        retry_count: reason.retry_count,
+        parked_at: Some(parked.parked_at),
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetParkedEventsQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Persistent subscription {subscription_name} not found.")]
+    NotFound { subscription_name: String },
+}
+
+#[cfg(test)]
+mod test {
+    use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+
+    use crate::test_event_store::{connect, delete_subscription, park_events, NACK_REASON};
+
+    use super::{
+        GetParkedEventsQuery, GetParkedEventsQueryError, GetParkedEventsQueryHandler,
+        GetParkedEventsQueryHandlerImpl,
+    };
+
+    #[tokio::test]
+    #[ignore = "needs an EventStore server, set SOURCE_CONTROL_TEST_EVENTSTORE"]
+    async fn should_list_the_parked_events_oldest_first() {
+        let eventstore = connect();
+        let client = eventstore.get_client();
+        let (subscription_name, _subscription) = park_events(&client, 2).await;
+        let query_handler = GetParkedEventsQueryHandlerImpl { eventstore };
+
+        let parked_events = query_handler
+            .handle(GetParkedEventsQuery {
+                subscription_name: subscription_name.clone(),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        let first_page = query_handler
+            .handle(GetParkedEventsQuery {
+                subscription_name: subscription_name.clone(),
+                limit: 1,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(parked_events.len(), 2);
+        assert!(parked_events[0].parked_revision < parked_events[1].parked_revision);
+        for parked_event in &parked_events {
+            assert!(parked_event.event_id.is_some());
+            assert!(parked_event.event.is_ok());
+            assert_eq!(parked_event.reason.as_deref(), Some(NACK_REASON));
+            assert_eq!(parked_event.retry_count, Some(1));
+            assert!(parked_event.parked_at.is_some());
+        }
+        assert_eq!(first_page.len(), 1);
+        assert_eq!(first_page[0].event_id, parked_events[0].event_id);
+
+        delete_subscription(&client, &subscription_name).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an EventStore server, set SOURCE_CONTROL_TEST_EVENTSTORE"]
+    async fn should_not_list_the_parked_events_of_an_unknown_subscription() {
+        let query_handler = GetParkedEventsQueryHandlerImpl {
+            eventstore: connect(),
+        };
+
+        let result = query_handler
+            .handle(GetParkedEventsQuery {
+                subscription_name: "unknown_subscription".to_string(),
+                limit: 10,
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(GetParkedEventsQueryError::NotFound { .. })
+        ));
+    }
+}
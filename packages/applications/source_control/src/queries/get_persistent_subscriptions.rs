@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use eventstore::ListPersistentSubscriptionsOptions;
+use shaku::{Interface, Provider};
+use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+use thiserror::Error;
+
+pub struct GetPersistentSubscriptionsQuery {}
+
+#[derive(Debug, Clone)]
+pub struct PersistentSubscription {
+    pub name: String,
+    pub status: String,
+    pub parked_event_count: usize,
+}
+
+#[async_trait]
+pub trait GetPersistentSubscriptionsQueryHandler: Interface {
+    /// Persistent subscriptions to `$all`, with how many events each has parked.
+    async fn handle(
+        &self,
+        query: GetPersistentSubscriptionsQuery,
+    ) -> Result<Vec<PersistentSubscription>, GetPersistentSubscriptionsQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetPersistentSubscriptionsQueryHandler)]
+pub struct GetPersistentSubscriptionsQueryHandlerImpl {
+    #[shaku(inject)]
+    pub eventstore: Arc<dyn EventStoreProvider>,
+}
+
+#[async_trait]
+impl GetPersistentSubscriptionsQueryHandler for GetPersistentSubscriptionsQueryHandlerImpl {
+    async fn handle(
+        &self,
+        _query: GetPersistentSubscriptionsQuery,
+    ) -> Result<Vec<PersistentSubscription>, GetPersistentSubscriptionsQueryError> {
+        let subscriptions = self
+            .eventstore
+            .get_client()
+            .list_persistent_subscriptions_to_all(&ListPersistentSubscriptionsOptions::default())
+            .await
+            .map_err(|_| GetPersistentSubscriptionsQueryError::Connection)?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(|subscription| PersistentSubscription {
+                name: subscription.group_name,
+                status: subscription.status,
+                parked_event_count: subscription.stats.parked_message_count,
+            })
+            .collect())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetPersistentSubscriptionsQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+}
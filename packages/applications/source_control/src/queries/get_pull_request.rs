@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::{
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+    repositories::pull_request_repository::{GetPullRequestError, PullRequestRepository},
+};
+use thiserror::Error;
+
+pub struct GetPullRequestQuery {
+    pub repository_id: u64,
+    pub id: u64,
+}
+
+#[async_trait]
+pub trait GetPullRequestQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetPullRequestQuery,
+    ) -> Result<PullRequest, GetPullRequestQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetPullRequestQueryHandler)]
+pub struct GetPullRequestQueryHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn PullRequestRepository>,
+}
+
+#[async_trait]
+impl GetPullRequestQueryHandler for GetPullRequestQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetPullRequestQuery,
+    ) -> Result<PullRequest, GetPullRequestQueryError> {
+        match self.repository.get(PullRequestId(query.id)).await {
+            Ok(pull_request_aggregate) => {
+                let pull_request = pull_request_aggregate.root;
+                if pull_request.repository != RepositoryId(query.repository_id) {
+                    return Err(GetPullRequestQueryError::NotFound {
+                        pull_request_id: query.id,
+                    });
+                }
+
+                Ok(pull_request)
+            }
+            Err(GetPullRequestError::Connection) => Err(GetPullRequestQueryError::Connection),
+            Err(GetPullRequestError::Unexpected | GetPullRequestError::Corrupt { .. }) => {
+                Err(GetPullRequestQueryError::Unexpected)
+            }
+            Err(GetPullRequestError::NotFound { pull_request_id }) => {
+                Err(GetPullRequestQueryError::NotFound {
+                    pull_request_id: pull_request_id.0,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetPullRequestQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Pull request with {pull_request_id} not found.")]
+    NotFound { pull_request_id: u64 },
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use shaku::{Interface, Provider};
+use source_control_domain::{
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+    repositories::repository_repository::{GetRepositoryError, RepositoryRepository},
+};
+use thiserror::Error;
+
+pub struct GetRepositoryQuery {
+    pub organization_id: u64,
+    pub platform_account_id: u64,
+    pub id: u64,
+}
+
+#[async_trait]
+pub trait GetRepositoryQueryHandler: Interface {
+    async fn handle(
+        &self,
+        query: GetRepositoryQuery,
+    ) -> Result<Repository, GetRepositoryQueryError>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = GetRepositoryQueryHandler)]
+pub struct GetRepositoryQueryHandlerImpl {
+    #[shaku(provide)]
+    pub repository: Box<dyn RepositoryRepository>,
+}
+
+#[async_trait]
+impl GetRepositoryQueryHandler for GetRepositoryQueryHandlerImpl {
+    async fn handle(
+        &self,
+        query: GetRepositoryQuery,
+    ) -> Result<Repository, GetRepositoryQueryError> {
+        match self.repository.get(RepositoryId(query.id)).await {
+            Ok(repository_aggregate) => {
+                let repository = repository_aggregate.root;
+                if repository.organization_id != OrganizationId(query.organization_id)
+                    || repository.platform_account != PlatformAccountId(query.platform_account_id)
+                {
+                    return Err(GetRepositoryQueryError::NotFound {
+                        repository_id: query.id,
+                    });
+                }
+
+                Ok(repository)
+            }
+            Err(GetRepositoryError::Connection) => Err(GetRepositoryQueryError::Connection),
+            Err(GetRepositoryError::Unexpected | GetRepositoryError::Corrupt { .. }) => {
+                Err(GetRepositoryQueryError::Unexpected)
+            }
+            Err(GetRepositoryError::NotFound { repository_id }) => {
+                Err(GetRepositoryQueryError::NotFound {
+                    repository_id: repository_id.0,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetRepositoryQueryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Repository with {repository_id} not found.")]
+    NotFound { repository_id: u64 },
+}
+
+#[cfg(test)]
+mod test {
+    use shaku::HasProvider;
+
+    use crate::module::get_in_memory_module;
+
+    use super::{GetRepositoryQuery, GetRepositoryQueryError, GetRepositoryQueryHandler};
+
+    #[tokio::test]
+    async fn should_fail_to_connect_without_a_database() {
+        let module = get_in_memory_module();
+        let handler: Box<dyn GetRepositoryQueryHandler> = module.provide().unwrap();
+
+        let result = handler
+            .handle(GetRepositoryQuery {
+                organization_id: 1,
+                platform_account_id: 1,
+                id: 1,
+            })
+            .await;
+
+        assert!(matches!(result, Err(GetRepositoryQueryError::Connection)));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod get_branch;
+pub mod get_developer;
 pub mod get_organization;
 pub mod get_organization_log;
+pub mod get_parked_events;
+pub mod get_persistent_subscriptions;
+pub mod get_pull_request;
+pub mod get_repository;
@@ -0,0 +1,141 @@
+//! EventStore server for the tests of parked events, set `SOURCE_CONTROL_TEST_EVENTSTORE` to its
+//! connection string, e.g. `esdb://localhost:2113?tls=false`.
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use event_codec::EventCodec;
+use event_store_util::parked::{parked_stream_name, NackReason};
+use eventstore::{
+    AppendToStreamOptions, Client, ClientSettings, DeletePersistentSubscriptionOptions, EventData,
+    NakAction, PersistentSubscription, PersistentSubscriptionToAllOptions, ReadStreamOptions,
+    StreamPosition, SubscribeToPersistentSubscriptionOptions, SubscriptionFilter,
+};
+use source_control_domain::{
+    aggregates::organization::OrganizationEvent, entities::organization::OrganizationId,
+};
+use source_control_event_store_persistence_adapter::provider::EventStoreProvider;
+
+pub struct TestEventStore {
+    client: Arc<Client>,
+}
+
+impl EventStoreProvider for TestEventStore {
+    fn get_client(&self) -> Arc<Client> {
+        self.client.clone()
+    }
+}
+
+pub fn connect() -> Arc<TestEventStore> {
+    let settings = env::var("SOURCE_CONTROL_TEST_EVENTSTORE")
+        .expect("SOURCE_CONTROL_TEST_EVENTSTORE should be set")
+        .parse::<ClientSettings>()
+        .unwrap();
+    Arc::new(TestEventStore {
+        client: Arc::new(Client::new(settings).unwrap()),
+    })
+}
+
+pub const NACK_REASON: &str = "Projection failed";
+
+/// Appends `count` events to a new stream and parks each of them through a new subscription to
+/// it. Returns the name of the subscription and the subscription, to receive replayed events.
+pub async fn park_events(client: &Client, count: usize) -> (String, PersistentSubscription) {
+    let id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+    let stream_name = format!("Porti.SourceControl/Aggregates/Organization/{}", id);
+    let subscription_name = format!("parked_{}", id);
+    let events: Vec<EventData> = (0..count)
+        .map(|_| {
+            let event = OrganizationEvent::ArchiveOrganization {
+                organization_id: OrganizationId(id),
+            };
+            EventData::json(event.get_versioned_event_type(), event.encode()).unwrap()
+        })
+        .collect();
+    client
+        .append_to_stream(
+            stream_name.as_str(),
+            &AppendToStreamOptions::default(),
+            events,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_persistent_subscription_to_all(
+            &subscription_name,
+            &PersistentSubscriptionToAllOptions::default()
+                .start_from(StreamPosition::Start)
+                .filter(SubscriptionFilter::on_stream_name().add_prefix(stream_name)),
+        )
+        .await
+        .unwrap();
+    let mut subscription = client
+        .subscribe_to_persistent_subscription_to_all(
+            &subscription_name,
+            &SubscribeToPersistentSubscriptionOptions::default(),
+        )
+        .await
+        .unwrap();
+    for _ in 0..count {
+        let event = subscription.next().await.unwrap();
+        subscription
+            .nack(
+                event,
+                NakAction::Park,
+                NackReason::new(NACK_REASON, 1).to_nack_reason(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // EventStore writes the parked stream after acknowledging the nack
+    wait_for_parked_events(client, &subscription_name, count).await;
+    (subscription_name, subscription)
+}
+
+/// Waits until the parked stream of the subscription holds `count` events.
+pub async fn wait_for_parked_events(client: &Client, subscription_name: &str, count: usize) {
+    for _ in 0..100 {
+        if count_parked_events(client, subscription_name).await == count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("{} events were never parked", count);
+}
+
+async fn count_parked_events(client: &Client, subscription_name: &str) -> usize {
+    let mut stream = client
+        .read_stream(
+            parked_stream_name(subscription_name),
+            &ReadStreamOptions::default()
+                .forwards()
+                .position(StreamPosition::Start),
+        )
+        .await
+        .unwrap();
+    let mut count = 0;
+    loop {
+        match stream.next().await {
+            Ok(Some(_)) => count += 1,
+            Ok(None) | Err(eventstore::Error::ResourceNotFound) => return count,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+pub async fn delete_subscription(client: &Client, subscription_name: &str) {
+    client
+        .delete_persistent_subscription_to_all(
+            subscription_name,
+            &DeletePersistentSubscriptionOptions::default(),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+
+use crate::{
+    commands::replay_parked_events::{
+        ReplayParkedEventsCommand, ReplayParkedEventsCommandError, ReplayParkedEventsCommandHandler,
+    },
+    queries::{
+        get_parked_events::{
+            GetParkedEventsQuery, GetParkedEventsQueryError, GetParkedEventsQueryHandler,
+            ParkedEvent,
+        },
+        get_persistent_subscriptions::{
+            GetPersistentSubscriptionsQuery, GetPersistentSubscriptionsQueryError,
+            GetPersistentSubscriptionsQueryHandler, PersistentSubscription,
+        },
+    },
+};
+
+/// Stands in for the handlers that read EventStore subscriptions in an in-memory module, they fail
+/// as if EventStore was unreachable.
+#[derive(Clone, Copy, Default)]
+pub struct Unconnected;
+
+#[async_trait]
+impl GetParkedEventsQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetParkedEventsQuery,
+    ) -> Result<Vec<ParkedEvent>, GetParkedEventsQueryError> {
+        Err(GetParkedEventsQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl GetPersistentSubscriptionsQueryHandler for Unconnected {
+    async fn handle(
+        &self,
+        _query: GetPersistentSubscriptionsQuery,
+    ) -> Result<Vec<PersistentSubscription>, GetPersistentSubscriptionsQueryError> {
+        Err(GetPersistentSubscriptionsQueryError::Connection)
+    }
+}
+
+#[async_trait]
+impl ReplayParkedEventsCommandHandler for Unconnected {
+    async fn handle(
+        &self,
+        _command: ReplayParkedEventsCommand,
+    ) -> Result<(), ReplayParkedEventsCommandError> {
+        Err(ReplayParkedEventsCommandError::Connection)
+    }
+}
@@ -0,0 +1,8 @@
+/// A value read from or saved to an aggregate, with the revision of its stream after it. Clients
+/// send the revision back with their next command to make sure nobody changed the aggregate in
+/// between.
+#[derive(Debug, Clone)]
+pub struct Versioned<T> {
+    pub value: T,
+    pub revision: u64,
+}
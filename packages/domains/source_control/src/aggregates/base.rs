@@ -1,13 +1,26 @@
 use std::error::Error;
 
-pub trait DomainEvent<TRoot> {
-    fn get_event_type(&self) -> &'static str;
+use event_codec::EventCodec;
+
+/// The event type, aggregate id and json of an event are derived with `#[derive(EventCodec)]`.
+pub trait DomainEvent<TRoot>: EventCodec {
     fn apply(&self, root: &mut TRoot);
-    fn get_aggregate_id(&self) -> &u64;
 }
 
 pub trait DomainError: Error {}
 
+/// Roots that can be stored as a snapshot, so loading an aggregate only replays the events
+/// written after it.
+pub trait Snapshot: Default + Clone {
+    /// Changes whenever the stored shape of the root changes, snapshots of other versions are
+    /// ignored and the stream is replayed from the start instead.
+    const SNAPSHOT_VERSION: u32;
+}
+
+/// Take a snapshot every `n` events, `0` disables snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotFrequency(pub u64);
+
 pub struct Aggregate<TEvent, TRoot>
 where
     TEvent: DomainEvent<TRoot>,
@@ -44,4 +57,91 @@ where
             latest_revision,
         }
     }
+
+    /// Revision of the last event once the draft events are saved.
+    pub fn revision_after_save(&self) -> u64 {
+        self.latest_revision + self.draft_events.len() as u64
+    }
+}
+
+impl<TEvent, TRoot> Aggregate<TEvent, TRoot>
+where
+    TEvent: DomainEvent<TRoot>,
+    TRoot: Snapshot,
+{
+    /// `events` are the events written after the snapshot was taken.
+    pub fn from_snapshot(snapshot: TRoot, events: Vec<TEvent>, latest_revision: u64) -> Self {
+        let mut root = snapshot;
+
+        for event in &events {
+            event.apply(&mut root);
+        }
+
+        Self {
+            draft_events: vec![],
+            source_events: events,
+            root,
+            latest_revision,
+        }
+    }
+
+    /// Whether saving the draft events passes a multiple of the snapshot frequency.
+    pub fn should_snapshot(&self, frequency: SnapshotFrequency) -> bool {
+        if frequency.0 == 0 || self.draft_events.is_empty() {
+            return false;
+        }
+
+        // Revisions start at 0, so a stream at revision `r` holds `r + 1` events
+        let before = (self.latest_revision + 1) / frequency.0;
+        let after = (self.revision_after_save() + 1) / frequency.0;
+        before != after
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        aggregates::organization::{OrganizationAggregate, OrganizationEvent},
+        entities::organization::OrganizationId,
+    };
+
+    use super::SnapshotFrequency;
+
+    fn organization_at_revision(latest_revision: u64) -> OrganizationAggregate {
+        OrganizationAggregate::from_events(
+            vec![OrganizationEvent::CreateOrganizationEvent {
+                organization_id: OrganizationId(1),
+                name: "rafaeltab".to_string(),
+            }],
+            latest_revision,
+        )
+    }
+
+    #[test]
+    fn should_snapshot_when_passing_frequency() {
+        let mut organization = organization_at_revision(97);
+        organization.archive().unwrap();
+        assert!(!organization.should_snapshot(SnapshotFrequency(100)));
+
+        organization.unarchive().unwrap();
+        assert!(organization.should_snapshot(SnapshotFrequency(100)));
+        assert!(!organization.should_snapshot(SnapshotFrequency(0)));
+    }
+
+    #[test]
+    fn should_apply_events_after_snapshot() {
+        let snapshot = organization_at_revision(0).root;
+
+        let organization = OrganizationAggregate::from_snapshot(
+            snapshot,
+            vec![OrganizationEvent::ArchiveOrganization {
+                organization_id: OrganizationId(1),
+            }],
+            1,
+        );
+
+        assert_eq!(organization.root.name, "rafaeltab");
+        assert!(organization.root.archived);
+        assert_eq!(organization.latest_revision, 1);
+    }
 }
@@ -0,0 +1,302 @@
+use chrono::{DateTime, Utc};
+use event_codec::EventCodec;
+use thiserror::Error;
+
+use crate::entities::{
+    branch::{Branch, BranchId},
+    branch_push::BranchPush,
+    commit::CommitSha,
+    developer::DeveloperId,
+    repository::RepositoryId,
+};
+
+use super::{
+    base::{Aggregate, DomainError, DomainEvent},
+    commit_graph::CommitGraphAggregate,
+};
+
+pub type BranchAggregate = Aggregate<BranchEvent, Branch>;
+
+impl BranchAggregate {
+    /// Moves the head of the branch, the push counts as forced when the previous head is not an
+    /// ancestor of the new head.
+    pub fn push(
+        &mut self,
+        pusher: DeveloperId,
+        after: CommitSha,
+        pushed_at: DateTime<Utc>,
+        commit_graph: &CommitGraphAggregate,
+    ) -> Result<BranchPush, BranchError> {
+        self.ensure_not_deleted()?;
+
+        if self.root.head == after {
+            return Err(BranchError::HeadUnchanged {
+                branch_id: self.root.id,
+            });
+        }
+
+        if !commit_graph.root.has_commit(after) {
+            return Err(BranchError::UnknownCommit { sha: after });
+        }
+
+        let forced = !commit_graph
+            .is_ancestor(self.root.head, after)
+            .unwrap_or(false);
+
+        let push = BranchPush {
+            pusher,
+            before: self.root.head,
+            after,
+            pushed_at,
+            forced,
+        };
+        let event = if forced {
+            BranchEvent::ForcePushBranch {
+                branch_id: self.root.id,
+                push: push.clone(),
+            }
+        } else {
+            BranchEvent::PushBranch {
+                branch_id: self.root.id,
+                push: push.clone(),
+            }
+        };
+        self.add_event(event);
+
+        Ok(push)
+    }
+
+    pub fn delete(&mut self) -> Result<(), BranchError> {
+        self.ensure_not_deleted()?;
+
+        let event = BranchEvent::DeleteBranch {
+            branch_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    /// Creates a deleted branch again, its push history is kept.
+    pub fn recreate(
+        &mut self,
+        head: CommitSha,
+        based_on: Option<BranchId>,
+    ) -> Result<(), BranchError> {
+        if !self.root.deleted {
+            return Err(BranchError::NotDeleted {
+                branch_id: self.root.id,
+            });
+        }
+
+        let event = BranchEvent::CreateBranch {
+            branch_id: self.root.id,
+            repository_id: self.root.repository_id,
+            name: self.root.name.clone(),
+            head,
+            based_on,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    fn ensure_not_deleted(&self) -> Result<(), BranchError> {
+        if self.root.deleted {
+            return Err(BranchError::Deleted {
+                branch_id: self.root.id,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/Branch", aggregate_id = branch_id)]
+pub enum BranchEvent {
+    #[event(name = "Create")]
+    CreateBranch {
+        branch_id: BranchId,
+        repository_id: RepositoryId,
+        name: String,
+        head: CommitSha,
+        based_on: Option<BranchId>,
+    },
+    #[event(name = "Push")]
+    PushBranch {
+        branch_id: BranchId,
+        push: BranchPush,
+    },
+    #[event(name = "ForcePush")]
+    ForcePushBranch {
+        branch_id: BranchId,
+        #[event(with = "forced_push")]
+        push: BranchPush,
+    },
+    #[event(name = "Delete")]
+    DeleteBranch { branch_id: BranchId },
+}
+
+impl DomainEvent<Branch> for BranchEvent {
+    fn apply(&self, aggregate: &mut Branch) {
+        match self {
+            BranchEvent::CreateBranch {
+                branch_id,
+                repository_id,
+                name,
+                head,
+                based_on,
+            } => {
+                aggregate.id = *branch_id;
+                aggregate.repository_id = *repository_id;
+                aggregate.name = name.clone();
+                aggregate.head = *head;
+                aggregate.based_on = *based_on;
+                aggregate.deleted = false;
+            }
+            BranchEvent::PushBranch { push, .. } | BranchEvent::ForcePushBranch { push, .. } => {
+                aggregate.head = push.after;
+                aggregate.pushes.push(push.clone());
+            }
+            BranchEvent::DeleteBranch { .. } => {
+                aggregate.deleted = true;
+            }
+        }
+    }
+}
+
+/// Whether a push was forced is encoded in the event type, not in the push itself.
+mod forced_push {
+    use event_codec::{serde_json::Value, DecodeFailure, EventField};
+
+    use crate::entities::branch_push::BranchPush;
+
+    pub fn encode(push: &BranchPush) -> Value {
+        push.encode()
+    }
+
+    pub fn decode(value: &Value) -> Result<BranchPush, DecodeFailure> {
+        BranchPush::decode(value).map(|push| BranchPush {
+            forced: true,
+            ..push
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BranchError {
+    #[error("Branch {branch_id} is deleted")]
+    Deleted { branch_id: BranchId },
+    #[error("Branch {branch_id} is not deleted")]
+    NotDeleted { branch_id: BranchId },
+    #[error("Branch {branch_id} already points to this commit")]
+    HeadUnchanged { branch_id: BranchId },
+    #[error("Commit {sha} is not part of the commit graph")]
+    UnknownCommit { sha: CommitSha },
+}
+
+impl DomainError for BranchError {}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+
+    use crate::{
+        aggregates::commit_graph::CommitGraphAggregate,
+        entities::{
+            branch::BranchId,
+            commit::{Commit, CommitSha},
+            developer::DeveloperId,
+            repository::RepositoryId,
+        },
+    };
+
+    use super::{BranchAggregate, BranchError, BranchEvent};
+
+    fn sha(n: u8) -> CommitSha {
+        CommitSha([n; 20])
+    }
+
+    // 1 - 2 - 3
+    //      \
+    //       4
+    fn commit_graph() -> CommitGraphAggregate {
+        let mut graph = CommitGraphAggregate::new(RepositoryId(1));
+        graph
+            .record_commits(
+                [(1, vec![]), (2, vec![1]), (3, vec![2]), (4, vec![2])]
+                    .into_iter()
+                    .map(|(n, parents)| Commit {
+                        sha: sha(n),
+                        message: "".to_string(),
+                        parents: parents.into_iter().map(sha).collect(),
+                    })
+                    .collect(),
+            )
+            .unwrap();
+        graph
+    }
+
+    fn main_branch() -> BranchAggregate {
+        BranchAggregate::from_events(
+            vec![BranchEvent::CreateBranch {
+                branch_id: BranchId(1),
+                repository_id: RepositoryId(1),
+                name: "main".to_string(),
+                head: sha(2),
+                based_on: None,
+            }],
+            0,
+        )
+    }
+
+    #[test]
+    fn should_detect_force_push_when_head_is_not_a_descendant() {
+        let graph = commit_graph();
+        let mut branch = main_branch();
+
+        let fast_forward = branch
+            .push(DeveloperId(1), sha(3), Utc::now(), &graph)
+            .unwrap();
+        let rewrite = branch
+            .push(DeveloperId(2), sha(4), Utc::now(), &graph)
+            .unwrap();
+
+        assert!(!fast_forward.forced);
+        assert!(rewrite.forced);
+        assert!(matches!(
+            branch.draft_events[1],
+            BranchEvent::ForcePushBranch { .. }
+        ));
+        assert_eq!(branch.root.head, sha(4));
+        assert_eq!(branch.root.pushes.len(), 2);
+    }
+
+    #[test]
+    fn should_not_push_deleted_branch() {
+        let graph = commit_graph();
+        let mut branch = main_branch();
+        branch.delete().unwrap();
+
+        let result = branch.push(DeveloperId(1), sha(3), Utc::now(), &graph);
+
+        assert!(matches!(result, Err(BranchError::Deleted { .. })));
+    }
+
+    #[test]
+    fn should_keep_push_history_when_recreated() {
+        let graph = commit_graph();
+        let mut branch = main_branch();
+        branch
+            .push(DeveloperId(1), sha(3), Utc::now(), &graph)
+            .unwrap();
+        branch.delete().unwrap();
+
+        branch.recreate(sha(4), None).unwrap();
+
+        assert!(!branch.root.deleted);
+        assert_eq!(branch.root.head, sha(4));
+        assert_eq!(branch.root.pushes.len(), 1);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet};
+
+use event_codec::EventCodec;
+use thiserror::Error;
+
+use crate::entities::{
+    commit::{Commit, CommitSha},
+    commit_graph::CommitGraph,
+    repository::RepositoryId,
+};
+
+use super::base::{Aggregate, DomainError, DomainEvent, Snapshot};
+
+pub type CommitGraphAggregate = Aggregate<CommitGraphEvent, CommitGraph>;
+
+impl Snapshot for CommitGraph {
+    const SNAPSHOT_VERSION: u32 = 1;
+}
+
+impl CommitGraphAggregate {
+    pub fn new(repository_id: RepositoryId) -> Self {
+        Self {
+            source_events: vec![],
+            draft_events: vec![],
+            root: CommitGraph {
+                repository_id,
+                commits: HashMap::new(),
+            },
+            latest_revision: 0,
+        }
+    }
+
+    /// Whether the graph was never saved. Recording commits never stores an empty batch, so a
+    /// saved graph always holds commits besides the drafted ones, even when it was loaded from a
+    /// snapshot without any events after it.
+    pub fn is_new(&self) -> bool {
+        let drafted: usize = self
+            .draft_events
+            .iter()
+            .map(|event| match event {
+                CommitGraphEvent::RecordCommits { commits, .. } => commits.len(),
+            })
+            .sum();
+
+        self.root.commits.len() == drafted
+    }
+
+    /// Records the commits that are not yet part of the graph, ordered so that parents always
+    /// come before their children. Every parent has to be known already or be part of the batch.
+    pub fn record_commits(
+        &mut self,
+        commits: Vec<Commit>,
+    ) -> Result<Vec<Commit>, CommitGraphError> {
+        let mut new_commits: HashMap<CommitSha, Commit> = HashMap::new();
+        for commit in commits {
+            if !self.root.has_commit(commit.sha) {
+                new_commits.entry(commit.sha).or_insert(commit);
+            }
+        }
+
+        for commit in new_commits.values() {
+            for parent in &commit.parents {
+                if !self.root.has_commit(*parent) && !new_commits.contains_key(parent) {
+                    return Err(CommitGraphError::MissingParent {
+                        sha: commit.sha,
+                        parent: *parent,
+                    });
+                }
+            }
+        }
+
+        let ordered = order_parents_first(new_commits)?;
+        if ordered.is_empty() {
+            return Ok(ordered);
+        }
+
+        let event = CommitGraphEvent::RecordCommits {
+            repository_id: self.root.repository_id,
+            commits: ordered.clone(),
+        };
+        self.add_event(event);
+
+        Ok(ordered)
+    }
+
+    /// Returns the commit itself and every commit reachable through its parents.
+    pub fn ancestors(&self, sha: CommitSha) -> Result<HashSet<CommitSha>, CommitGraphError> {
+        self.ensure_known(sha)?;
+
+        let mut visited = HashSet::from([sha]);
+        let mut stack = vec![sha];
+        while let Some(current) = stack.pop() {
+            for parent in self.parents_of(current) {
+                if visited.insert(*parent) {
+                    stack.push(*parent);
+                }
+            }
+        }
+
+        Ok(visited)
+    }
+
+    /// A commit counts as its own ancestor, matching `git merge-base --is-ancestor`.
+    pub fn is_ancestor(
+        &self,
+        ancestor: CommitSha,
+        descendant: CommitSha,
+    ) -> Result<bool, CommitGraphError> {
+        self.ensure_known(ancestor)?;
+
+        Ok(self.ancestors(descendant)?.contains(&ancestor))
+    }
+
+    /// The best common ancestors of both commits: common ancestors that are not an ancestor of
+    /// another common ancestor. Criss-cross merges can yield more than one.
+    pub fn merge_bases(
+        &self,
+        a: CommitSha,
+        b: CommitSha,
+    ) -> Result<Vec<CommitSha>, CommitGraphError> {
+        let ancestors_of_a = self.ancestors(a)?;
+        let ancestors_of_b = self.ancestors(b)?;
+        let common: HashSet<CommitSha> = ancestors_of_a
+            .intersection(&ancestors_of_b)
+            .copied()
+            .collect();
+
+        let superseded: HashSet<CommitSha> = common
+            .iter()
+            .flat_map(|sha| self.parents_of(*sha))
+            .copied()
+            .collect();
+
+        let mut bases: Vec<CommitSha> = common.difference(&superseded).copied().collect();
+        bases.sort_by_key(|sha| sha.0);
+
+        Ok(bases)
+    }
+
+    /// The commits reachable from `to` but not from `from`, newest first, like `git log from..to`.
+    pub fn commits_between(
+        &self,
+        from: CommitSha,
+        to: CommitSha,
+    ) -> Result<Vec<&Commit>, CommitGraphError> {
+        let excluded = self.ancestors(from)?;
+        self.ensure_known(to)?;
+
+        // Iterative post-order walk, so children end up after all of their parents
+        let mut visited: HashSet<CommitSha> = HashSet::new();
+        let mut ordered = vec![];
+        let mut stack = vec![(to, false)];
+        while let Some((sha, expanded)) = stack.pop() {
+            if expanded {
+                ordered.push(sha);
+                continue;
+            }
+            if excluded.contains(&sha) || !visited.insert(sha) {
+                continue;
+            }
+
+            stack.push((sha, true));
+            for parent in self.parents_of(sha) {
+                stack.push((*parent, false));
+            }
+        }
+
+        Ok(ordered
+            .iter()
+            .rev()
+            .filter_map(|sha| self.root.get_commit(*sha))
+            .collect())
+    }
+
+    fn parents_of(&self, sha: CommitSha) -> &[CommitSha] {
+        self.root
+            .get_commit(sha)
+            .map(|commit| commit.parents.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn ensure_known(&self, sha: CommitSha) -> Result<(), CommitGraphError> {
+        if !self.root.has_commit(sha) {
+            return Err(CommitGraphError::UnknownCommit { sha });
+        }
+
+        Ok(())
+    }
+}
+
+fn order_parents_first(
+    mut commits: HashMap<CommitSha, Commit>,
+) -> Result<Vec<Commit>, CommitGraphError> {
+    let mut ordered = Vec::with_capacity(commits.len());
+
+    while !commits.is_empty() {
+        let ready: Vec<CommitSha> = commits
+            .values()
+            .filter(|commit| commit.parents.iter().all(|p| !commits.contains_key(p)))
+            .map(|commit| commit.sha)
+            .collect();
+
+        // Only possible when the batch contains a cycle
+        if ready.is_empty() {
+            let commit = commits.values().next().unwrap();
+            return Err(CommitGraphError::MissingParent {
+                sha: commit.sha,
+                parent: commit.parents[0],
+            });
+        }
+
+        let mut ready: Vec<Commit> = ready.iter().filter_map(|sha| commits.remove(sha)).collect();
+        ready.sort_by_key(|commit| commit.sha.0);
+        ordered.append(&mut ready);
+    }
+
+    Ok(ordered)
+}
+
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/CommitGraph", aggregate_id = repository_id)]
+pub enum CommitGraphEvent {
+    #[event(name = "RecordCommits")]
+    RecordCommits {
+        repository_id: RepositoryId,
+        commits: Vec<Commit>,
+    },
+}
+
+impl DomainEvent<CommitGraph> for CommitGraphEvent {
+    fn apply(&self, aggregate: &mut CommitGraph) {
+        match self {
+            CommitGraphEvent::RecordCommits {
+                repository_id,
+                commits,
+            } => {
+                aggregate.repository_id = *repository_id;
+                for commit in commits {
+                    aggregate.commits.insert(commit.sha, commit.clone());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CommitGraphError {
+    #[error("Commit {sha} is not part of the commit graph")]
+    UnknownCommit { sha: CommitSha },
+    #[error("Parent {parent} of commit {sha} is not part of the commit graph")]
+    MissingParent { sha: CommitSha, parent: CommitSha },
+}
+
+impl DomainError for CommitGraphError {}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::{
+        commit::{Commit, CommitSha},
+        repository::RepositoryId,
+    };
+
+    use super::{CommitGraphAggregate, CommitGraphError, CommitGraphEvent};
+
+    fn sha(n: u8) -> CommitSha {
+        CommitSha([n; 20])
+    }
+
+    fn commit(n: u8, parents: &[u8]) -> Commit {
+        Commit {
+            sha: sha(n),
+            message: format!("commit {}", n),
+            parents: parents.iter().map(|p| sha(*p)).collect(),
+        }
+    }
+
+    // 1 - 2 - 3 ----- 6
+    //      \         /
+    //       4 ---- 5
+    fn merged_graph() -> CommitGraphAggregate {
+        let mut graph = CommitGraphAggregate::new(RepositoryId(1));
+        graph
+            .record_commits(vec![
+                commit(6, &[3, 5]),
+                commit(5, &[4]),
+                commit(4, &[2]),
+                commit(3, &[2]),
+                commit(2, &[1]),
+                commit(1, &[]),
+            ])
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn should_order_recorded_commits_parents_first() {
+        let graph = merged_graph();
+
+        let recorded: Vec<CommitSha> = graph.root.commits.keys().copied().collect();
+        assert_eq!(recorded.len(), 6);
+
+        let CommitGraphEvent::RecordCommits { commits, .. } = &graph.draft_events[0];
+        let order: Vec<CommitSha> = commits.iter().map(|c| c.sha).collect();
+        assert_eq!(order, vec![sha(1), sha(2), sha(3), sha(4), sha(5), sha(6)]);
+    }
+
+    #[test]
+    fn should_reject_commit_with_missing_parent() {
+        let mut graph = merged_graph();
+
+        let result = graph.record_commits(vec![commit(8, &[7])]);
+
+        assert!(matches!(
+            result,
+            Err(CommitGraphError::MissingParent { .. })
+        ));
+        assert_eq!(graph.draft_events.len(), 1);
+    }
+
+    #[test]
+    fn should_only_be_new_until_commits_are_saved() {
+        let graph = merged_graph();
+        assert!(graph.is_new());
+
+        let CommitGraphEvent::RecordCommits { commits, .. } = &graph.draft_events[0];
+        let mut saved = CommitGraphAggregate::from_events(
+            vec![CommitGraphEvent::RecordCommits {
+                repository_id: RepositoryId(1),
+                commits: commits.clone(),
+            }],
+            0,
+        );
+        assert!(!saved.is_new());
+
+        saved.record_commits(vec![commit(7, &[6])]).unwrap();
+        assert!(!saved.is_new());
+    }
+
+    #[test]
+    fn should_answer_ancestry_queries_over_merges() {
+        let graph = merged_graph();
+
+        assert!(graph.is_ancestor(sha(4), sha(6)).unwrap());
+        assert!(!graph.is_ancestor(sha(4), sha(3)).unwrap());
+        assert_eq!(graph.merge_bases(sha(3), sha(5)).unwrap(), vec![sha(2)]);
+
+        let between: Vec<CommitSha> = graph
+            .commits_between(sha(3), sha(6))
+            .unwrap()
+            .iter()
+            .map(|c| c.sha)
+            .collect();
+        assert_eq!(between, vec![sha(6), sha(5), sha(4)]);
+    }
+}
@@ -0,0 +1,346 @@
+use event_codec::EventCodec;
+use thiserror::Error;
+
+use crate::entities::developer::{Developer, DeveloperId, DeveloperIdentity};
+
+use super::base::{Aggregate, DomainError, DomainEvent};
+
+pub type DeveloperAggregate = Aggregate<DeveloperEvent, Developer>;
+
+impl DeveloperAggregate {
+    pub fn link_identity(&mut self, identity: DeveloperIdentity) -> Result<(), DeveloperError> {
+        self.ensure_not_merged()?;
+
+        if self.root.has_identity(&identity) {
+            return Err(DeveloperError::IdentityAlreadyLinked {
+                developer_id: self.root.id,
+                identity,
+            });
+        }
+
+        let event = DeveloperEvent::LinkIdentity {
+            developer_id: self.root.id,
+            identity,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn unlink_identity(&mut self, identity: DeveloperIdentity) -> Result<(), DeveloperError> {
+        self.ensure_not_merged()?;
+
+        if !self.root.has_identity(&identity) {
+            return Err(DeveloperError::IdentityNotLinked {
+                developer_id: self.root.id,
+                identity,
+            });
+        }
+
+        let event = DeveloperEvent::UnlinkIdentity {
+            developer_id: self.root.id,
+            identity,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    /// Marks this developer as a duplicate of `target`. The identities stay linked to this
+    /// developer, so the merge can be undone, but they resolve to the target from now on.
+    pub fn merge_into(&mut self, target: &Developer) -> Result<(), DeveloperError> {
+        self.ensure_not_merged()?;
+
+        if target.id == self.root.id {
+            return Err(DeveloperError::MergeIntoSelf {
+                developer_id: self.root.id,
+            });
+        }
+
+        // The duplicates merged into this developer would otherwise resolve through a chain
+        if !self.root.merged_from.is_empty() {
+            return Err(DeveloperError::HasMergedDevelopers {
+                developer_id: self.root.id,
+            });
+        }
+
+        // Only merging into canonical developers keeps the merge chains free of cycles
+        if target.merged_into.is_some() {
+            return Err(DeveloperError::TargetMerged {
+                developer_id: target.id,
+            });
+        }
+
+        let event = DeveloperEvent::MergeDeveloper {
+            developer_id: self.root.id,
+            into: target.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    /// Records on the target that `source` is merged into it, so the target can't be merged into
+    /// another developer while it has duplicates. Recording a merge twice does nothing.
+    pub fn add_merged(&mut self, source: &Developer) -> Result<(), DeveloperError> {
+        self.ensure_not_merged()?;
+
+        if source.id == self.root.id {
+            return Err(DeveloperError::MergeIntoSelf {
+                developer_id: self.root.id,
+            });
+        }
+
+        if self.root.merged_from.contains(&source.id) {
+            return Ok(());
+        }
+
+        let event = DeveloperEvent::AddMergedDeveloper {
+            developer_id: self.root.id,
+            from: source.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    /// Undoes [`Self::add_merged`] once `source` is unmerged, or when merging it failed.
+    pub fn remove_merged(&mut self, source_id: DeveloperId) {
+        if !self.root.merged_from.contains(&source_id) {
+            return;
+        }
+
+        let event = DeveloperEvent::RemoveMergedDeveloper {
+            developer_id: self.root.id,
+            from: source_id,
+        };
+        self.add_event(event);
+    }
+
+    pub fn unmerge(&mut self) -> Result<(), DeveloperError> {
+        let Some(from) = self.root.merged_into else {
+            return Err(DeveloperError::NotMerged {
+                developer_id: self.root.id,
+            });
+        };
+
+        let event = DeveloperEvent::UnmergeDeveloper {
+            developer_id: self.root.id,
+            from,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    fn ensure_not_merged(&self) -> Result<(), DeveloperError> {
+        if let Some(into) = self.root.merged_into {
+            return Err(DeveloperError::Merged {
+                developer_id: self.root.id,
+                into,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/Developer", aggregate_id = developer_id)]
+pub enum DeveloperEvent {
+    #[event(name = "Create")]
+    CreateDeveloper {
+        developer_id: DeveloperId,
+        username: String,
+    },
+    #[event(name = "LinkIdentity")]
+    LinkIdentity {
+        developer_id: DeveloperId,
+        identity: DeveloperIdentity,
+    },
+    #[event(name = "UnlinkIdentity")]
+    UnlinkIdentity {
+        developer_id: DeveloperId,
+        identity: DeveloperIdentity,
+    },
+    #[event(name = "Merge")]
+    MergeDeveloper {
+        developer_id: DeveloperId,
+        into: DeveloperId,
+    },
+    #[event(name = "Unmerge")]
+    UnmergeDeveloper {
+        developer_id: DeveloperId,
+        from: DeveloperId,
+    },
+    #[event(name = "AddMerged")]
+    AddMergedDeveloper {
+        developer_id: DeveloperId,
+        from: DeveloperId,
+    },
+    #[event(name = "RemoveMerged")]
+    RemoveMergedDeveloper {
+        developer_id: DeveloperId,
+        from: DeveloperId,
+    },
+}
+
+impl DomainEvent<Developer> for DeveloperEvent {
+    fn apply(&self, aggregate: &mut Developer) {
+        match self {
+            DeveloperEvent::CreateDeveloper {
+                developer_id,
+                username,
+            } => {
+                aggregate.id = *developer_id;
+                aggregate.username = username.clone();
+            }
+            DeveloperEvent::LinkIdentity { identity, .. } => {
+                aggregate.identities.push(identity.clone());
+            }
+            DeveloperEvent::UnlinkIdentity { identity, .. } => {
+                aggregate.identities.retain(|x| x != identity);
+            }
+            DeveloperEvent::MergeDeveloper { into, .. } => {
+                aggregate.merged_into = Some(*into);
+            }
+            DeveloperEvent::UnmergeDeveloper { .. } => {
+                aggregate.merged_into = None;
+            }
+            DeveloperEvent::AddMergedDeveloper { from, .. } => {
+                aggregate.merged_from.push(*from);
+            }
+            DeveloperEvent::RemoveMergedDeveloper { from, .. } => {
+                aggregate.merged_from.retain(|x| x != from);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DeveloperError {
+    #[error("Developer {developer_id} is merged into developer {into}")]
+    Merged {
+        developer_id: DeveloperId,
+        into: DeveloperId,
+    },
+    #[error("Developer {developer_id} is not merged into another developer")]
+    NotMerged { developer_id: DeveloperId },
+    #[error("Developer {developer_id} can not be merged into itself")]
+    MergeIntoSelf { developer_id: DeveloperId },
+    #[error(
+        "Developer {developer_id} is merged into another developer and can not be merged into"
+    )]
+    TargetMerged { developer_id: DeveloperId },
+    #[error("Other developers are merged into developer {developer_id}")]
+    HasMergedDevelopers { developer_id: DeveloperId },
+    #[error("Developer {developer_id} already has identity {identity:?}")]
+    IdentityAlreadyLinked {
+        developer_id: DeveloperId,
+        identity: DeveloperIdentity,
+    },
+    #[error("Developer {developer_id} does not have identity {identity:?}")]
+    IdentityNotLinked {
+        developer_id: DeveloperId,
+        identity: DeveloperIdentity,
+    },
+}
+
+impl DomainError for DeveloperError {}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::developer::{Developer, DeveloperId, DeveloperIdentity};
+
+    use super::{DeveloperAggregate, DeveloperError, DeveloperEvent};
+
+    fn created_developer(id: u64) -> DeveloperAggregate {
+        DeveloperAggregate::from_events(
+            vec![DeveloperEvent::CreateDeveloper {
+                developer_id: DeveloperId(id),
+                username: format!("developer-{}", id),
+            }],
+            0,
+        )
+    }
+
+    #[test]
+    fn should_compare_identities_case_insensitively() {
+        let mut developer = created_developer(1);
+        developer
+            .link_identity(DeveloperIdentity::commit_email("Rafael@Example.com"))
+            .unwrap();
+
+        let result = developer.link_identity(DeveloperIdentity::commit_email("rafael@example.com"));
+
+        assert!(matches!(
+            result,
+            Err(DeveloperError::IdentityAlreadyLinked { .. })
+        ));
+    }
+
+    #[test]
+    fn should_keep_identities_when_unmerged() {
+        let mut developer = created_developer(1);
+        let identity = DeveloperIdentity::platform_login("GitHub", "rafaeltab");
+        developer.link_identity(identity.clone()).unwrap();
+        let target = created_developer(2).root;
+
+        developer.merge_into(&target).unwrap();
+        developer.unmerge().unwrap();
+
+        assert!(developer.root.merged_into.is_none());
+        assert!(developer.root.has_identity(&identity));
+        assert_eq!(developer.draft_events.len(), 3);
+    }
+
+    #[test]
+    fn should_not_merge_into_merged_developer() {
+        let mut developer = created_developer(1);
+        let target = Developer {
+            merged_into: Some(DeveloperId(3)),
+            ..created_developer(2).root
+        };
+
+        let result = developer.merge_into(&target);
+
+        assert!(matches!(result, Err(DeveloperError::TargetMerged { .. })));
+        assert!(developer.draft_events.is_empty());
+    }
+
+    #[test]
+    fn should_not_merge_developer_with_merged_developers() {
+        let mut target = created_developer(2);
+        target.add_merged(&created_developer(1).root).unwrap();
+
+        let result = target.merge_into(&created_developer(3).root);
+
+        assert!(matches!(
+            result,
+            Err(DeveloperError::HasMergedDevelopers { .. })
+        ));
+        assert_eq!(target.draft_events.len(), 1);
+    }
+
+    #[test]
+    fn should_merge_developer_again_once_duplicates_are_unmerged() {
+        let mut target = created_developer(2);
+        target.add_merged(&created_developer(1).root).unwrap();
+        target.remove_merged(DeveloperId(1));
+
+        target.merge_into(&created_developer(3).root).unwrap();
+
+        assert!(target.root.merged_from.is_empty());
+        assert_eq!(target.root.merged_into, Some(DeveloperId(3)));
+    }
+
+    #[test]
+    fn should_not_link_identity_when_merged() {
+        let mut developer = created_developer(1);
+        developer.merge_into(&created_developer(2).root).unwrap();
+
+        let result = developer.link_identity(DeveloperIdentity::commit_email("a@example.com"));
+
+        assert!(matches!(result, Err(DeveloperError::Merged { .. })));
+    }
+}
@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use event_codec::EventField;
+
+/// Where a command came from, every event it causes is stored with it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandMetadata {
+    /// Whoever issued the command, when they identified themselves.
+    pub actor_id: Option<String>,
+    /// Shared by everything that happens because of the same original request.
+    pub correlation_id: Option<String>,
+    /// The message that directly caused the command, like the http request.
+    pub causation_id: Option<String>,
+}
+
+/// W3C trace context of the span the event was written in.
+#[derive(Clone, Debug, PartialEq, Eq, EventField)]
+pub struct TraceContext {
+    pub traceparent: String,
+    pub tracestate: Option<String>,
+}
+
+/// Stored next to the json of an event. EventStore reads `$correlationId` and `$causationId`
+/// itself, so those keep its naming.
+#[derive(Clone, Debug, PartialEq, Eq, EventField)]
+pub struct EventMetadata {
+    pub occurred_at: DateTime<Utc>,
+    pub actor_id: Option<String>,
+    #[event(rename = "$correlationId")]
+    pub correlation_id: Option<String>,
+    #[event(rename = "$causationId")]
+    pub causation_id: Option<String>,
+    pub trace_context: Option<TraceContext>,
+}
+
+impl EventMetadata {
+    pub fn new(
+        command: &CommandMetadata,
+        occurred_at: DateTime<Utc>,
+        trace_context: Option<TraceContext>,
+    ) -> Self {
+        Self {
+            occurred_at,
+            actor_id: command.actor_id.clone(),
+            correlation_id: command.correlation_id.clone(),
+            causation_id: command.causation_id.clone(),
+            trace_context,
+        }
+    }
+}
+
+/// An event with the metadata it was stored with, events written before metadata was recorded
+/// have none.
+#[derive(Debug)]
+pub struct LoggedEvent<TEvent> {
+    pub event: TEvent,
+    pub metadata: Option<EventMetadata>,
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use event_codec::{serde_json::json, EventField};
+
+    use super::{CommandMetadata, EventMetadata, TraceContext};
+
+    #[test]
+    fn should_store_correlation_the_way_event_store_reads_it() {
+        let command = CommandMetadata {
+            actor_id: Some("rafaeltab".to_string()),
+            correlation_id: Some("correlation".to_string()),
+            causation_id: Some("request".to_string()),
+        };
+        let metadata = EventMetadata::new(
+            &command,
+            Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(),
+            Some(TraceContext {
+                traceparent: "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_string(),
+                tracestate: None,
+            }),
+        );
+
+        let encoded = metadata.encode();
+
+        assert_eq!(encoded["$correlationId"], json!("correlation"));
+        assert_eq!(encoded["$causationId"], json!("request"));
+        assert_eq!(EventMetadata::decode(&encoded), Ok(metadata));
+    }
+}
@@ -1,2 +1,8 @@
 pub mod base;
+pub mod branch;
+pub mod commit_graph;
+pub mod developer;
+pub mod metadata;
 pub mod organization;
+pub mod pull_request;
+pub mod repository;
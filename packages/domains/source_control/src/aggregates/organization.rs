@@ -1,3 +1,4 @@
+use event_codec::EventCodec;
 use thiserror::Error;
 
 use crate::entities::{
@@ -5,15 +6,21 @@ use crate::entities::{
     platform_account::{PlatformAccount, PlatformAccountId},
 };
 
-use super::base::{Aggregate, DomainError, DomainEvent};
+use super::base::{Aggregate, DomainError, DomainEvent, Snapshot};
 
 pub type OrganizationAggregate = Aggregate<OrganizationEvent, Organization>;
 
+impl Snapshot for Organization {
+    const SNAPSHOT_VERSION: u32 = 1;
+}
+
 impl OrganizationAggregate {
     pub fn add_platform_account(
         &mut self,
         platform_account: PlatformAccount,
     ) -> Result<(), OrganizationError> {
+        self.ensure_writable()?;
+
         if self.root.has_account(&platform_account) {
             return Err(OrganizationError::AccountAlreadyAdded {
                 account_id: platform_account.id,
@@ -34,6 +41,8 @@ impl OrganizationAggregate {
         &mut self,
         platform_account_id: PlatformAccountId,
     ) -> Result<(), OrganizationError> {
+        self.ensure_writable()?;
+
         if !self.root.has_account_with_id(platform_account_id) {
             return Err(OrganizationError::AccountNotLinked {
                 account_id: platform_account_id,
@@ -50,39 +59,122 @@ impl OrganizationAggregate {
 
         Ok(())
     }
+
+    pub fn rename(&mut self, name: String) -> Result<(), OrganizationError> {
+        self.ensure_writable()?;
+
+        if self.root.name == name {
+            return Err(OrganizationError::NameUnchanged {
+                organization_id: self.root.id,
+                name,
+            });
+        }
+
+        let event = OrganizationEvent::RenameOrganization {
+            organization_id: self.root.id,
+            name,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn archive(&mut self) -> Result<(), OrganizationError> {
+        self.ensure_writable()?;
+
+        let event = OrganizationEvent::ArchiveOrganization {
+            organization_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn unarchive(&mut self) -> Result<(), OrganizationError> {
+        self.ensure_not_deleted()?;
+
+        if !self.root.archived {
+            return Err(OrganizationError::NotArchived {
+                organization_id: self.root.id,
+            });
+        }
+
+        let event = OrganizationEvent::UnarchiveOrganization {
+            organization_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    /// Tombstones the organization, a deleted organization can no longer be changed.
+    pub fn delete(&mut self) -> Result<(), OrganizationError> {
+        self.ensure_not_deleted()?;
+
+        let event = OrganizationEvent::DeleteOrganization {
+            organization_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    fn ensure_writable(&self) -> Result<(), OrganizationError> {
+        self.ensure_not_deleted()?;
+
+        if self.root.archived {
+            return Err(OrganizationError::Archived {
+                organization_id: self.root.id,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn ensure_not_deleted(&self) -> Result<(), OrganizationError> {
+        if self.root.deleted {
+            return Err(OrganizationError::Deleted {
+                organization_id: self.root.id,
+            });
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/Organization", aggregate_id = organization_id)]
 pub enum OrganizationEvent {
+    #[event(name = "AddPlatformAccount")]
     AddPlatformAccount {
         organization_id: OrganizationId,
         account: PlatformAccount,
     },
+    #[event(name = "RemovePlatformAccount")]
     RemovePlatformAccount {
         organization_id: OrganizationId,
+        #[event(rename = "account.id")]
         account_id: PlatformAccountId,
     },
+    #[event(name = "Create")]
     CreateOrganizationEvent {
         organization_id: OrganizationId,
         name: String,
     },
+    #[event(name = "Rename")]
+    RenameOrganization {
+        organization_id: OrganizationId,
+        name: String,
+    },
+    #[event(name = "Archive")]
+    ArchiveOrganization { organization_id: OrganizationId },
+    #[event(name = "Unarchive")]
+    UnarchiveOrganization { organization_id: OrganizationId },
+    #[event(name = "Delete")]
+    DeleteOrganization { organization_id: OrganizationId },
 }
 
 impl DomainEvent<Organization> for OrganizationEvent {
-    fn get_event_type(&self) -> &'static str {
-        match self {
-            OrganizationEvent::AddPlatformAccount { .. } => {
-                "Porti.SourceControl/Aggregates/Organization/AddPlatformAccount"
-            }
-            OrganizationEvent::RemovePlatformAccount { .. } => {
-                "Porti.SourceControl/Aggregates/Organization/RemovePlatformAccount"
-            }
-            OrganizationEvent::CreateOrganizationEvent { .. } => {
-                "Porti.SourceControl/Aggregates/Organization/Create"
-            }
-        }
-    }
-
     fn apply(&self, aggregate: &mut Organization) {
         match self {
             OrganizationEvent::AddPlatformAccount { account, .. } => {
@@ -98,20 +190,18 @@ impl DomainEvent<Organization> for OrganizationEvent {
                 aggregate.id = *organization_id;
                 aggregate.name = name.clone();
             }
-        }
-    }
-
-    fn get_aggregate_id(&self) -> &u64 {
-        match self {
-            OrganizationEvent::AddPlatformAccount {
-                organization_id, ..
-            } => &organization_id.0,
-            OrganizationEvent::RemovePlatformAccount {
-                organization_id, ..
-            } => &organization_id.0,
-            OrganizationEvent::CreateOrganizationEvent {
-                organization_id, ..
-            } => &organization_id.0,
+            OrganizationEvent::RenameOrganization { name, .. } => {
+                aggregate.name = name.clone();
+            }
+            OrganizationEvent::ArchiveOrganization { .. } => {
+                aggregate.archived = true;
+            }
+            OrganizationEvent::UnarchiveOrganization { .. } => {
+                aggregate.archived = false;
+            }
+            OrganizationEvent::DeleteOrganization { .. } => {
+                aggregate.deleted = true;
+            }
         }
     }
 }
@@ -128,6 +218,107 @@ pub enum OrganizationError {
         account_id: PlatformAccountId,
         organization_id: OrganizationId,
     },
+    #[error("Organization {organization_id} is archived and can no longer be changed")]
+    Archived { organization_id: OrganizationId },
+    #[error("Organization {organization_id} is not archived")]
+    NotArchived { organization_id: OrganizationId },
+    #[error("Organization {organization_id} is deleted")]
+    Deleted { organization_id: OrganizationId },
+    #[error("Organization {organization_id} is already named {name}")]
+    NameUnchanged {
+        organization_id: OrganizationId,
+        name: String,
+    },
 }
 
 impl DomainError for OrganizationError {}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::{
+        organization::OrganizationId,
+        platform::Platform,
+        platform_account::{PlatformAccount, PlatformAccountId},
+    };
+
+    use super::{OrganizationAggregate, OrganizationError, OrganizationEvent};
+
+    fn created_organization() -> OrganizationAggregate {
+        OrganizationAggregate::from_events(
+            vec![OrganizationEvent::CreateOrganizationEvent {
+                organization_id: OrganizationId(1),
+                name: "rafaeltab".to_string(),
+            }],
+            0,
+        )
+    }
+
+    fn account() -> PlatformAccount {
+        PlatformAccount {
+            id: PlatformAccountId(2),
+            name: "rafaeltab".to_string(),
+            platform: Platform {
+                name: "GitHub".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn should_not_add_account_with_same_name_twice() {
+        let mut organization = created_organization();
+        organization.add_platform_account(account()).unwrap();
+
+        let result = organization.add_platform_account(PlatformAccount {
+            id: PlatformAccountId(3),
+            ..account()
+        });
+
+        assert!(matches!(
+            result,
+            Err(OrganizationError::AccountAlreadyAdded { .. })
+        ));
+        assert_eq!(organization.root.platform_accounts.len(), 1);
+    }
+
+    #[test]
+    fn should_not_add_account_to_archived_organization() {
+        let mut organization = created_organization();
+        organization.archive().unwrap();
+
+        let result = organization.add_platform_account(account());
+
+        assert!(matches!(result, Err(OrganizationError::Archived { .. })));
+        assert!(organization.root.platform_accounts.is_empty());
+    }
+
+    #[test]
+    fn should_add_account_after_unarchiving() {
+        let mut organization = created_organization();
+        organization.archive().unwrap();
+        organization.unarchive().unwrap();
+
+        let result = organization.add_platform_account(account());
+
+        assert!(result.is_ok());
+        assert_eq!(organization.draft_events.len(), 3);
+    }
+
+    #[test]
+    fn should_not_change_deleted_organization() {
+        let mut organization = created_organization();
+        organization.delete().unwrap();
+
+        assert!(matches!(
+            organization.rename("porti".to_string()),
+            Err(OrganizationError::Deleted { .. })
+        ));
+        assert!(matches!(
+            organization.unarchive(),
+            Err(OrganizationError::Deleted { .. })
+        ));
+        assert!(matches!(
+            organization.delete(),
+            Err(OrganizationError::Deleted { .. })
+        ));
+    }
+}
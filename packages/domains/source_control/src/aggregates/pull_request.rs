@@ -0,0 +1,258 @@
+use event_codec::EventCodec;
+use thiserror::Error;
+
+use crate::entities::{
+    developer::DeveloperId,
+    pull_request::{PullRequest, PullRequestId, PullRequestState},
+    repository::RepositoryId,
+    review::Review,
+};
+
+use super::base::{Aggregate, DomainError, DomainEvent};
+
+pub type PullRequestAggregate = Aggregate<PullRequestEvent, PullRequest>;
+
+impl PullRequestAggregate {
+    pub fn edit(&mut self, title: String, description: String) -> Result<(), PullRequestError> {
+        self.ensure_open()?;
+
+        let event = PullRequestEvent::EditPullRequest {
+            pull_request_id: self.root.id,
+            title,
+            description,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn submit_review(&mut self, review: Review) -> Result<(), PullRequestError> {
+        self.ensure_open()?;
+
+        let event = PullRequestEvent::SubmitReview {
+            pull_request_id: self.root.id,
+            review,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn dismiss_review(&mut self, developer_id: DeveloperId) -> Result<(), PullRequestError> {
+        self.ensure_open()?;
+
+        if self.root.get_review(developer_id).is_none() {
+            return Err(PullRequestError::ReviewNotFound {
+                pull_request_id: self.root.id,
+                developer_id,
+            });
+        }
+
+        let event = PullRequestEvent::DismissReview {
+            pull_request_id: self.root.id,
+            developer_id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn merge(&mut self) -> Result<(), PullRequestError> {
+        self.ensure_open()?;
+
+        let event = PullRequestEvent::MergePullRequest {
+            pull_request_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn close(&mut self) -> Result<(), PullRequestError> {
+        self.ensure_open()?;
+
+        let event = PullRequestEvent::ClosePullRequest {
+            pull_request_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    fn ensure_open(&self) -> Result<(), PullRequestError> {
+        match self.root.state {
+            PullRequestState::Open => Ok(()),
+            PullRequestState::Merged => Err(PullRequestError::Merged {
+                pull_request_id: self.root.id,
+            }),
+            PullRequestState::Closed => Err(PullRequestError::Closed {
+                pull_request_id: self.root.id,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/PullRequest", aggregate_id = pull_request_id)]
+pub enum PullRequestEvent {
+    #[event(name = "Open")]
+    OpenPullRequest {
+        pull_request_id: PullRequestId,
+        repository_id: RepositoryId,
+        number: u64,
+        author_id: DeveloperId,
+        title: String,
+        description: String,
+    },
+    #[event(name = "Edit")]
+    EditPullRequest {
+        pull_request_id: PullRequestId,
+        title: String,
+        description: String,
+    },
+    #[event(name = "SubmitReview")]
+    SubmitReview {
+        pull_request_id: PullRequestId,
+        review: Review,
+    },
+    #[event(name = "DismissReview")]
+    DismissReview {
+        pull_request_id: PullRequestId,
+        developer_id: DeveloperId,
+    },
+    #[event(name = "Merge")]
+    MergePullRequest { pull_request_id: PullRequestId },
+    #[event(name = "Close")]
+    ClosePullRequest { pull_request_id: PullRequestId },
+}
+
+impl DomainEvent<PullRequest> for PullRequestEvent {
+    fn apply(&self, aggregate: &mut PullRequest) {
+        match self {
+            PullRequestEvent::OpenPullRequest {
+                pull_request_id,
+                repository_id,
+                number,
+                author_id,
+                title,
+                description,
+            } => {
+                aggregate.id = *pull_request_id;
+                aggregate.repository = *repository_id;
+                aggregate.number = *number;
+                aggregate.author = *author_id;
+                aggregate.title = title.clone();
+                aggregate.description = description.clone();
+                aggregate.state = PullRequestState::Open;
+            }
+            PullRequestEvent::EditPullRequest {
+                title, description, ..
+            } => {
+                aggregate.title = title.clone();
+                aggregate.description = description.clone();
+            }
+            PullRequestEvent::SubmitReview { review, .. } => {
+                // A developer's latest review supersedes the earlier ones
+                aggregate
+                    .reviews
+                    .retain(|r| r.developer_id != review.developer_id);
+                aggregate.reviews.push(review.clone());
+            }
+            PullRequestEvent::DismissReview { developer_id, .. } => {
+                aggregate.reviews.retain(|r| r.developer_id != *developer_id);
+            }
+            PullRequestEvent::MergePullRequest { .. } => {
+                aggregate.state = PullRequestState::Merged;
+            }
+            PullRequestEvent::ClosePullRequest { .. } => {
+                aggregate.state = PullRequestState::Closed;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PullRequestError {
+    #[error("Pull request {pull_request_id} is already merged")]
+    Merged { pull_request_id: PullRequestId },
+    #[error("Pull request {pull_request_id} is already closed")]
+    Closed { pull_request_id: PullRequestId },
+    #[error("Developer {developer_id} has no review on pull request {pull_request_id}")]
+    ReviewNotFound {
+        pull_request_id: PullRequestId,
+        developer_id: DeveloperId,
+    },
+}
+
+impl DomainError for PullRequestError {}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::{
+        developer::DeveloperId,
+        pull_request::PullRequestId,
+        repository::RepositoryId,
+        review::{Review, ReviewStatus},
+    };
+
+    use super::{PullRequestAggregate, PullRequestError, PullRequestEvent};
+
+    fn opened_pull_request() -> PullRequestAggregate {
+        PullRequestAggregate::from_events(
+            vec![PullRequestEvent::OpenPullRequest {
+                pull_request_id: PullRequestId(1),
+                repository_id: RepositoryId(2),
+                number: 1,
+                author_id: DeveloperId(3),
+                title: "Add pull requests".to_string(),
+                description: "".to_string(),
+            }],
+            0,
+        )
+    }
+
+    fn review(developer_id: u64, accepting: bool) -> Review {
+        Review {
+            developer_id: DeveloperId(developer_id),
+            status: ReviewStatus {
+                name: if accepting { "approved" } else { "changes_requested" }.to_string(),
+                accepting,
+            },
+        }
+    }
+
+    #[test]
+    fn should_supersede_earlier_review_of_same_developer() {
+        let mut pull_request = opened_pull_request();
+
+        pull_request.submit_review(review(4, false)).unwrap();
+        pull_request.submit_review(review(5, false)).unwrap();
+        pull_request.submit_review(review(4, true)).unwrap();
+
+        assert_eq!(pull_request.root.reviews.len(), 2);
+        let latest = pull_request.root.get_review(DeveloperId(4)).unwrap();
+        assert!(latest.status.accepting);
+    }
+
+    #[test]
+    fn should_not_review_merged_pull_request() {
+        let mut pull_request = opened_pull_request();
+        pull_request.merge().unwrap();
+
+        let result = pull_request.submit_review(review(4, true));
+
+        assert!(matches!(result, Err(PullRequestError::Merged { .. })));
+    }
+
+    #[test]
+    fn should_not_dismiss_missing_review() {
+        let mut pull_request = opened_pull_request();
+
+        let result = pull_request.dismiss_review(DeveloperId(4));
+
+        assert!(matches!(
+            result,
+            Err(PullRequestError::ReviewNotFound { .. })
+        ));
+    }
+}
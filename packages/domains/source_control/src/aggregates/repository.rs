@@ -0,0 +1,240 @@
+use event_codec::EventCodec;
+use thiserror::Error;
+
+use crate::entities::{
+    organization::{Organization, OrganizationId},
+    platform_account::PlatformAccountId,
+    repository::{Repository, RepositoryId},
+};
+
+use super::base::{Aggregate, DomainError, DomainEvent};
+
+pub type RepositoryAggregate = Aggregate<RepositoryEvent, Repository>;
+
+impl RepositoryAggregate {
+    pub fn rename(&mut self, name: String) -> Result<(), RepositoryError> {
+        self.ensure_not_archived()?;
+
+        if self.root.name == name {
+            return Err(RepositoryError::NameUnchanged {
+                repository_id: self.root.id,
+                name,
+            });
+        }
+
+        let event = RepositoryEvent::RenameRepository {
+            repository_id: self.root.id,
+            name,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn archive(&mut self) -> Result<(), RepositoryError> {
+        self.ensure_not_archived()?;
+
+        let event = RepositoryEvent::ArchiveRepository {
+            repository_id: self.root.id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    pub fn move_to_platform_account(
+        &mut self,
+        organization: &Organization,
+        platform_account_id: PlatformAccountId,
+    ) -> Result<(), RepositoryError> {
+        self.ensure_not_archived()?;
+
+        if self.root.platform_account == platform_account_id {
+            return Err(RepositoryError::AlreadyOnPlatformAccount {
+                repository_id: self.root.id,
+                account_id: platform_account_id,
+            });
+        }
+
+        if organization.id != self.root.organization_id
+            || !organization.has_account_with_id(platform_account_id)
+        {
+            return Err(RepositoryError::AccountNotLinked {
+                account_id: platform_account_id,
+                organization_id: self.root.organization_id,
+            });
+        }
+
+        let event = RepositoryEvent::MoveRepository {
+            repository_id: self.root.id,
+            platform_account_id,
+        };
+        self.add_event(event);
+
+        Ok(())
+    }
+
+    fn ensure_not_archived(&self) -> Result<(), RepositoryError> {
+        if self.root.archived {
+            return Err(RepositoryError::Archived {
+                repository_id: self.root.id,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, EventCodec)]
+#[event(prefix = "Porti.SourceControl/Aggregates/Repository", aggregate_id = repository_id)]
+pub enum RepositoryEvent {
+    #[event(name = "Register")]
+    RegisterRepository {
+        repository_id: RepositoryId,
+        organization_id: OrganizationId,
+        platform_account_id: PlatformAccountId,
+        name: String,
+    },
+    #[event(name = "Rename")]
+    RenameRepository {
+        repository_id: RepositoryId,
+        name: String,
+    },
+    #[event(name = "Archive")]
+    ArchiveRepository { repository_id: RepositoryId },
+    #[event(name = "Move")]
+    MoveRepository {
+        repository_id: RepositoryId,
+        platform_account_id: PlatformAccountId,
+    },
+}
+
+impl DomainEvent<Repository> for RepositoryEvent {
+    fn apply(&self, aggregate: &mut Repository) {
+        match self {
+            RepositoryEvent::RegisterRepository {
+                repository_id,
+                organization_id,
+                platform_account_id,
+                name,
+            } => {
+                aggregate.id = *repository_id;
+                aggregate.organization_id = *organization_id;
+                aggregate.platform_account = *platform_account_id;
+                aggregate.name = name.clone();
+            }
+            RepositoryEvent::RenameRepository { name, .. } => {
+                aggregate.name = name.clone();
+            }
+            RepositoryEvent::ArchiveRepository { .. } => {
+                aggregate.archived = true;
+            }
+            RepositoryEvent::MoveRepository {
+                platform_account_id,
+                ..
+            } => {
+                aggregate.platform_account = *platform_account_id;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    #[error("Repository {repository_id} is archived and can no longer be changed")]
+    Archived { repository_id: RepositoryId },
+    #[error("Repository {repository_id} is already named {name}")]
+    NameUnchanged {
+        repository_id: RepositoryId,
+        name: String,
+    },
+    #[error("Repository {repository_id} already belongs to account {account_id}")]
+    AlreadyOnPlatformAccount {
+        repository_id: RepositoryId,
+        account_id: PlatformAccountId,
+    },
+    #[error("Account {account_id} is not linked to organization {organization_id}")]
+    AccountNotLinked {
+        account_id: PlatformAccountId,
+        organization_id: OrganizationId,
+    },
+}
+
+impl DomainError for RepositoryError {}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::{
+        organization::{Organization, OrganizationId},
+        platform::Platform,
+        platform_account::{PlatformAccount, PlatformAccountId},
+        repository::RepositoryId,
+    };
+
+    use super::{RepositoryAggregate, RepositoryError, RepositoryEvent};
+
+    fn registered_repository() -> RepositoryAggregate {
+        RepositoryAggregate::from_events(
+            vec![RepositoryEvent::RegisterRepository {
+                repository_id: RepositoryId(1),
+                organization_id: OrganizationId(2),
+                platform_account_id: PlatformAccountId(3),
+                name: "porti".to_string(),
+            }],
+            0,
+        )
+    }
+
+    #[test]
+    fn should_not_rename_archived_repository() {
+        let mut repository = registered_repository();
+        repository.archive().unwrap();
+
+        let result = repository.rename("porti-2".to_string());
+
+        assert!(matches!(result, Err(RepositoryError::Archived { .. })));
+        assert_eq!(repository.draft_events.len(), 1);
+    }
+
+    #[test]
+    fn should_move_to_account_of_same_organization() {
+        let mut repository = registered_repository();
+        let organization = Organization {
+            id: OrganizationId(2),
+            name: "rafaeltab".to_string(),
+            platform_accounts: vec![PlatformAccount {
+                id: PlatformAccountId(4),
+                name: "rafaeltab".to_string(),
+                platform: Platform {
+                    name: "GitLab".to_string(),
+                },
+            }],
+            archived: false,
+            deleted: false,
+        };
+
+        let result = repository.move_to_platform_account(&organization, PlatformAccountId(4));
+
+        assert!(result.is_ok());
+        assert_eq!(repository.root.platform_account, PlatformAccountId(4));
+    }
+
+    #[test]
+    fn should_not_move_to_unlinked_account() {
+        let mut repository = registered_repository();
+        let organization = Organization {
+            id: OrganizationId(2),
+            name: "rafaeltab".to_string(),
+            platform_accounts: vec![],
+            archived: false,
+            deleted: false,
+        };
+
+        let result = repository.move_to_platform_account(&organization, PlatformAccountId(4));
+
+        assert!(matches!(
+            result,
+            Err(RepositoryError::AccountNotLinked { .. })
+        ));
+    }
+}
@@ -0,0 +1,50 @@
+use derive_id::DomainIdentity;
+use event_codec::EventField;
+use sha2::{Digest, Sha256};
+
+use super::{branch_push::BranchPush, commit::CommitSha, repository::RepositoryId};
+
+#[derive(DomainIdentity, Default, EventField)]
+pub struct BranchId(pub u64);
+
+impl BranchId {
+    /// Branches are addressed by name, so their id is derived from the repository and the name.
+    /// The id is part of the stream name, so it is the first 8 bytes of a SHA-256 instead of a
+    /// hasher whose output may change between Rust releases.
+    pub fn from_name(repository_id: RepositoryId, name: &str) -> Self {
+        let digest = Sha256::new()
+            .chain_update(repository_id.0.to_be_bytes())
+            .chain_update(name.as_bytes())
+            .finalize();
+
+        let mut id = [0; 8];
+        id.copy_from_slice(&digest[..8]);
+        BranchId(u64::from_be_bytes(id))
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Branch {
+    pub id: BranchId,
+    pub repository_id: RepositoryId,
+    pub name: String,
+    pub head: CommitSha,
+    pub based_on: Option<BranchId>,
+    pub deleted: bool,
+    pub pushes: Vec<BranchPush>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::entities::repository::RepositoryId;
+
+    use super::BranchId;
+
+    #[test]
+    fn should_keep_the_id_of_a_branch_stable() {
+        assert_eq!(
+            BranchId::from_name(RepositoryId(1), "main"),
+            BranchId(9014240177106222076)
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use event_codec::EventField;
+
+use super::{commit::CommitSha, developer::DeveloperId};
+
+#[derive(Clone, Debug, EventField)]
+pub struct BranchPush {
+    #[event(rename = "pusher_id")]
+    pub pusher: DeveloperId,
+    pub before: CommitSha,
+    pub after: CommitSha,
+    pub pushed_at: DateTime<Utc>,
+    /// Follows from the event type, pushes decode as not forced.
+    #[event(skip)]
+    pub forced: bool,
+}
@@ -1,14 +1,24 @@
+use std::fmt::Display;
+
+use event_codec::{serde_json::Value, DecodeFailure, EventField, FieldSchema};
 use thiserror::Error;
 
+#[derive(Clone, Debug, EventField)]
 pub struct Commit {
     pub sha: CommitSha,
     pub message: String,
-    pub previous_commit: Option<Box<Commit>>
+    pub parents: Vec<CommitSha>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct CommitSha(pub [u8; 20]);
 
+impl Display for CommitSha {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
 impl CommitSha {
     pub fn from_string(sha: &str) -> Result<Self, StringToShaError> {
         // Ensure the input is a valid 40-character hexadecimal string
@@ -33,6 +43,24 @@ impl CommitSha {
     }
 }
 
+/// Stored as the hexadecimal string.
+impl EventField for CommitSha {
+    fn encode(&self) -> Value {
+        Value::from(self.to_string())
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        value
+            .as_str()
+            .and_then(|sha| CommitSha::from_string(sha).ok())
+            .ok_or_else(DecodeFailure::invalid)
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "sha"));
+    }
+}
+
 #[derive(Debug, Error, Eq, PartialEq)]
 pub enum StringToShaError {
     #[error("Invalid hexadecimal string")]
@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use super::{
+    commit::{Commit, CommitSha},
+    repository::RepositoryId,
+};
+
+#[derive(Default, Clone)]
+pub struct CommitGraph {
+    pub repository_id: RepositoryId,
+    pub commits: HashMap<CommitSha, Commit>,
+}
+
+impl CommitGraph {
+    pub fn get_commit(&self, sha: CommitSha) -> Option<&Commit> {
+        self.commits.get(&sha)
+    }
+
+    pub fn has_commit(&self, sha: CommitSha) -> bool {
+        self.commits.contains_key(&sha)
+    }
+}
@@ -0,0 +1,47 @@
+use derive_id::DomainIdentity;
+use event_codec::EventField;
+
+#[derive(DomainIdentity, Default, EventField)]
+pub struct DeveloperId(pub u64);
+
+/// Something a developer can be recognized by, identities are compared case-insensitively.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EventField)]
+#[event(tag = "kind")]
+pub enum DeveloperIdentity {
+    #[event(name = "platform_login")]
+    PlatformLogin { platform: String, login: String },
+    #[event(name = "commit_email")]
+    CommitEmail { email: String },
+}
+
+impl DeveloperIdentity {
+    pub fn platform_login(platform: &str, login: &str) -> Self {
+        DeveloperIdentity::PlatformLogin {
+            platform: platform.trim().to_lowercase(),
+            login: login.trim().to_lowercase(),
+        }
+    }
+
+    pub fn commit_email(email: &str) -> Self {
+        DeveloperIdentity::CommitEmail {
+            email: email.trim().to_lowercase(),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Developer {
+    pub id: DeveloperId,
+    pub username: String,
+    pub identities: Vec<DeveloperIdentity>,
+    /// Set when this developer turned out to be a duplicate of another developer.
+    pub merged_into: Option<DeveloperId>,
+    /// The duplicates merged into this developer.
+    pub merged_from: Vec<DeveloperId>,
+}
+
+impl Developer {
+    pub fn has_identity(&self, identity: &DeveloperIdentity) -> bool {
+        self.identities.contains(identity)
+    }
+}
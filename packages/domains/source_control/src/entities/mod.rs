@@ -1,10 +1,11 @@
 pub mod organization;
 pub mod platform;
 pub mod platform_account;
-// pub mod repository;
-// pub mod developer;
-// pub mod pull_request;
-// pub mod review;
-// pub mod branch;
-// pub mod commit;
-// pub mod branch_push;
+pub mod repository;
+pub mod developer;
+pub mod pull_request;
+pub mod review;
+pub mod commit;
+pub mod commit_graph;
+pub mod branch;
+pub mod branch_push;
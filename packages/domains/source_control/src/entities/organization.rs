@@ -1,8 +1,9 @@
 use derive_id::DomainIdentity;
+use event_codec::EventField;
 
 use super::platform_account::{PlatformAccount, PlatformAccountId};
 
-#[derive(DomainIdentity, Default)]
+#[derive(DomainIdentity, Default, EventField)]
 pub struct OrganizationId(pub u64);
 
 #[derive(Default, Clone)]
@@ -10,12 +11,18 @@ pub struct Organization {
     pub id: OrganizationId,
     pub name: String,
     pub platform_accounts: Vec<PlatformAccount>,
+    pub archived: bool,
+    pub deleted: bool,
 }
 
 #[allow(dead_code)]
 impl Organization {
+    /// Accounts are the same when they share a name on the same platform, ids differ every time
+    /// an account is added.
     pub fn has_account(&self, account: &PlatformAccount) -> bool {
-        self.platform_accounts.iter().any(|e| e.id == account.id)
+        self.platform_accounts
+            .iter()
+            .any(|e| e.name == account.name && e.platform.name == account.platform.name)
     }
     pub fn has_account_with_id(&self, account_id: PlatformAccountId) -> bool {
         self.platform_accounts.iter().any(|e| e.id == account_id)
@@ -1,4 +1,6 @@
-#[derive(Clone, Debug)]
+use event_codec::EventField;
+
+#[derive(Clone, Debug, EventField)]
 pub struct Platform {
     pub name: String,
 }
@@ -1,11 +1,12 @@
 use derive_id::DomainIdentity;
+use event_codec::EventField;
 
 use super::platform::Platform;
 
-#[derive(DomainIdentity)]
+#[derive(DomainIdentity, Default, EventField)]
 pub struct PlatformAccountId(pub u64);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, EventField)]
 pub struct PlatformAccount {
     pub id: PlatformAccountId,
     pub name: String,
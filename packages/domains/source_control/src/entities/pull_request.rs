@@ -0,0 +1,33 @@
+use derive_id::DomainIdentity;
+use event_codec::EventField;
+
+use super::{developer::DeveloperId, repository::RepositoryId, review::Review};
+
+#[derive(DomainIdentity, Default, EventField)]
+pub struct PullRequestId(pub u64);
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PullRequestState {
+    #[default]
+    Open,
+    Merged,
+    Closed,
+}
+
+#[derive(Default, Clone)]
+pub struct PullRequest {
+    pub id: PullRequestId,
+    pub repository: RepositoryId,
+    pub number: u64,
+    pub author: DeveloperId,
+    pub title: String,
+    pub description: String,
+    pub state: PullRequestState,
+    pub reviews: Vec<Review>,
+}
+
+impl PullRequest {
+    pub fn get_review(&self, developer_id: DeveloperId) -> Option<&Review> {
+        self.reviews.iter().find(|r| r.developer_id == developer_id)
+    }
+}
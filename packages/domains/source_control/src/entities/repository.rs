@@ -0,0 +1,16 @@
+use derive_id::DomainIdentity;
+use event_codec::EventField;
+
+use super::{organization::OrganizationId, platform_account::PlatformAccountId};
+
+#[derive(DomainIdentity, Default, EventField)]
+pub struct RepositoryId(pub u64);
+
+#[derive(Default, Clone)]
+pub struct Repository {
+    pub id: RepositoryId,
+    pub organization_id: OrganizationId,
+    pub platform_account: PlatformAccountId,
+    pub name: String,
+    pub archived: bool,
+}
@@ -1,10 +1,14 @@
+use event_codec::EventField;
+
 use super::developer::DeveloperId;
 
+#[derive(Clone, Debug, EventField)]
 pub struct Review {
     pub developer_id: DeveloperId,
-    pub stutus: ReviewStatus,
+    pub status: ReviewStatus,
 }
 
+#[derive(Clone, Debug, EventField)]
 pub struct ReviewStatus {
     pub name: String,
     pub accepting: bool,
@@ -1,8 +0,0 @@
-use chrono::{DateTime, Utc};
-
-use super::commit::Commit;
-
-pub struct BranchPush {
-    timestamp: DateTime<Utc>,
-    commit: Commit
-}
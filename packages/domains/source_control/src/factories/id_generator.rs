@@ -0,0 +1,154 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use shaku::{Component, Interface};
+
+pub trait IdGenerator: Interface {
+    fn next_id(&self) -> u64;
+}
+
+/// Milliseconds since the unix epoch at 2025-01-01T00:00:00Z.
+const SNOWFLAKE_EPOCH: u64 = 1_735_689_600_000;
+const WORKER_ID_BITS: u64 = 10;
+const SEQUENCE_BITS: u64 = 12;
+const MAX_WORKER_ID: u16 = (1 << WORKER_ID_BITS) - 1;
+const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+
+/// Generates time-ordered ids made of 41 bits of milliseconds since [`SNOWFLAKE_EPOCH`], 10 bits
+/// of worker id and 12 bits of sequence. Instances that write to the same event store should use
+/// different worker ids.
+#[derive(Component)]
+#[shaku(interface = IdGenerator)]
+pub struct SnowflakeIdGenerator {
+    worker_id: u16,
+    #[shaku(default)]
+    state: Mutex<SnowflakeState>,
+}
+
+#[derive(Default)]
+pub struct SnowflakeState {
+    last_timestamp: u64,
+    sequence: u64,
+}
+
+impl SnowflakeIdGenerator {
+    pub fn new(worker_id: u16) -> Self {
+        Self {
+            worker_id,
+            state: Mutex::default(),
+        }
+    }
+
+    fn current_timestamp() -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System clock is set before the unix epoch")
+            .as_millis() as u64;
+
+        now.saturating_sub(SNOWFLAKE_EPOCH)
+    }
+}
+
+impl IdGenerator for SnowflakeIdGenerator {
+    fn next_id(&self) -> u64 {
+        let mut state = self
+            .state
+            .lock()
+            .expect("Snowflake state lock was poisoned");
+
+        // A clock that moves backwards keeps using the last timestamp, so ids never decrease
+        let mut timestamp = Self::current_timestamp().max(state.last_timestamp);
+        if timestamp == state.last_timestamp {
+            state.sequence = (state.sequence + 1) & MAX_SEQUENCE;
+            if state.sequence == 0 {
+                while timestamp <= state.last_timestamp {
+                    std::hint::spin_loop();
+                    timestamp = Self::current_timestamp();
+                }
+            }
+        } else {
+            state.sequence = 0;
+        }
+        state.last_timestamp = timestamp;
+
+        let worker_id = (self.worker_id & MAX_WORKER_ID) as u64;
+        (timestamp << (WORKER_ID_BITS + SEQUENCE_BITS))
+            | (worker_id << SEQUENCE_BITS)
+            | state.sequence
+    }
+}
+
+/// Generates the same sequence of ids for the same seed, meant for tests. Ids are spread with
+/// splitmix64, which never repeats within 2^64 ids.
+#[derive(Component)]
+#[shaku(interface = IdGenerator)]
+pub struct SeededIdGenerator {
+    seed: u64,
+    #[shaku(default)]
+    counter: AtomicU64,
+}
+
+impl SeededIdGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            counter: AtomicU64::default(),
+        }
+    }
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn next_id(&self) -> u64 {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+
+        let mut z = self
+            .seed
+            .wrapping_add(count.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{IdGenerator, SeededIdGenerator, SnowflakeIdGenerator, SEQUENCE_BITS};
+
+    #[test]
+    fn should_generate_increasing_ids() {
+        let generator = SnowflakeIdGenerator::new(1);
+
+        let ids: Vec<u64> = (0..10_000).map(|_| generator.next_id()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn should_encode_worker_id() {
+        let generator = SnowflakeIdGenerator::new(513);
+
+        let id = generator.next_id();
+
+        assert_eq!((id >> SEQUENCE_BITS) & 0x3ff, 513);
+        assert!(id < i64::MAX as u64);
+    }
+
+    #[test]
+    fn should_generate_same_ids_for_same_seed() {
+        let first = SeededIdGenerator::new(42);
+        let second = SeededIdGenerator::new(42);
+
+        let first_ids: Vec<u64> = (0..100).map(|_| first.next_id()).collect();
+        let second_ids: Vec<u64> = (0..100).map(|_| second.next_id()).collect();
+
+        assert_eq!(first_ids, second_ids);
+        assert_eq!(first_ids.iter().collect::<HashSet<_>>().len(), 100);
+    }
+}
@@ -1,7 +1,8 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
 
 use shaku::{Interface, Provider};
 
+use super::id_generator::IdGenerator;
 use crate::entities::{
     organization::Organization,
     platform::Platform,
@@ -19,20 +20,19 @@ pub trait PlatformAccountFactory: Interface {
 
 #[derive(Provider)]
 #[shaku(interface = PlatformAccountFactory)]
-pub struct PlatformAccountFactoryImpl;
+pub struct PlatformAccountFactoryImpl {
+    #[shaku(inject)]
+    pub id_generator: Arc<dyn IdGenerator>,
+}
 
 impl PlatformAccountFactory for PlatformAccountFactoryImpl {
     fn create(
         &self,
         name: String,
         platform_name: String,
-        organization: &Organization,
+        _organization: &Organization,
     ) -> PlatformAccount {
-        let mut hasher = DefaultHasher::default();
-        name.hash(&mut hasher);
-        organization.id.hash(&mut hasher);
-
-        let id = PlatformAccountId(hasher.finish());
+        let id = PlatformAccountId(self.id_generator.next_id());
 
         PlatformAccount {
             id,
@@ -43,3 +43,25 @@ impl PlatformAccountFactory for PlatformAccountFactoryImpl {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{entities::organization::Organization, factories::id_generator::SeededIdGenerator};
+
+    use super::{PlatformAccountFactory, PlatformAccountFactoryImpl};
+
+    #[test]
+    fn should_create_new_id_when_account_is_created_again() {
+        let factory = PlatformAccountFactoryImpl {
+            id_generator: Arc::new(SeededIdGenerator::new(7)),
+        };
+        let organization = Organization::default();
+
+        let first = factory.create("rafaeltab".to_string(), "GitHub".to_string(), &organization);
+        let second = factory.create("rafaeltab".to_string(), "GitHub".to_string(), &organization);
+
+        assert_ne!(first.id, second.id);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{
+    aggregates::branch::BranchAggregate,
+    entities::{
+        branch::{Branch, BranchId},
+        commit::CommitSha,
+        repository::RepositoryId,
+    },
+};
+
+#[async_trait]
+pub trait BranchRepository: Interface {
+    async fn get(&self, branch_id: BranchId) -> Result<BranchAggregate, GetBranchError>;
+
+    async fn save(&self, branch: BranchAggregate) -> Result<(), SaveBranchError>;
+
+    async fn create(
+        &self,
+        repository_id: RepositoryId,
+        name: String,
+        head: CommitSha,
+        based_on: Option<BranchId>,
+    ) -> Result<Branch, CreateBranchError>;
+}
+
+#[derive(Error, Debug)]
+pub enum GetBranchError {
+    #[error("Branch with {branch_id} not found.")]
+    NotFound { branch_id: BranchId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum SaveBranchError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
+
+#[derive(Error, Debug)]
+pub enum CreateBranchError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
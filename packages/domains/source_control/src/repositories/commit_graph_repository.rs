@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{
+    aggregates::{commit_graph::CommitGraphAggregate, metadata::CommandMetadata},
+    entities::repository::RepositoryId,
+};
+
+/// The commit graph of a repository has no creation event, getting the graph of a repository
+/// without recorded commits returns an empty graph.
+#[async_trait]
+pub trait CommitGraphRepository: Interface {
+    async fn get(
+        &self,
+        repository_id: RepositoryId,
+    ) -> Result<CommitGraphAggregate, GetCommitGraphError>;
+
+    async fn save(
+        &self,
+        commit_graph: CommitGraphAggregate,
+        metadata: CommandMetadata,
+    ) -> Result<(), SaveCommitGraphError>;
+}
+
+#[derive(Error, Debug)]
+pub enum GetCommitGraphError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum SaveCommitGraphError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::entities::developer::{DeveloperId, DeveloperIdentity};
+
+/// Keeps every identity linked to a single developer. The identities of a developer live in its
+/// own stream, so only a reservation can tell that another developer already linked one.
+#[async_trait]
+pub trait DeveloperIdentityRepository: Interface {
+    /// Reserving an identity the developer already holds succeeds.
+    async fn reserve(
+        &self,
+        identity: &DeveloperIdentity,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperIdentityError>;
+
+    /// Only releases the identity when it is held by the developer.
+    async fn release(
+        &self,
+        identity: &DeveloperIdentity,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperIdentityError>;
+}
+
+#[derive(Error, Debug)]
+pub enum ReserveDeveloperIdentityError {
+    #[error("The identity is reserved by developer {developer_id}")]
+    Taken { developer_id: DeveloperId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[derive(Error, Debug)]
+pub enum ReleaseDeveloperIdentityError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{
+    aggregates::developer::DeveloperAggregate,
+    entities::developer::{Developer, DeveloperId},
+};
+
+#[async_trait]
+pub trait DeveloperRepository: Interface {
+    async fn get(&self, developer_id: DeveloperId)
+        -> Result<DeveloperAggregate, GetDeveloperError>;
+
+    async fn save(&self, developer: DeveloperAggregate) -> Result<(), SaveDeveloperError>;
+
+    async fn create(
+        &self,
+        id: DeveloperId,
+        username: String,
+    ) -> Result<Developer, CreateDeveloperError>;
+}
+
+#[derive(Error, Debug)]
+pub enum GetDeveloperError {
+    #[error("Developer with {developer_id} not found.")]
+    NotFound { developer_id: DeveloperId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum SaveDeveloperError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
+
+#[derive(Error, Debug)]
+pub enum CreateDeveloperError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("A developer with this username already exists")]
+    Conflict,
+}
@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::entities::developer::DeveloperId;
+
+/// Keeps the usernames of developers unique, their ids are generated so the streams can't tell
+/// two developers with the same username apart.
+#[async_trait]
+pub trait DeveloperUsernameRepository: Interface {
+    /// Reserving a username the developer already holds succeeds.
+    async fn reserve(
+        &self,
+        username: &str,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReserveDeveloperUsernameError>;
+
+    /// Only releases the username when it is held by the developer.
+    async fn release(
+        &self,
+        username: &str,
+        developer_id: DeveloperId,
+    ) -> Result<(), ReleaseDeveloperUsernameError>;
+}
+
+#[derive(Error, Debug)]
+pub enum ReserveDeveloperUsernameError {
+    #[error("The username is reserved by developer {developer_id}")]
+    Taken { developer_id: DeveloperId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[derive(Error, Debug)]
+pub enum ReleaseDeveloperUsernameError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
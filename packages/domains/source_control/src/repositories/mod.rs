@@ -1 +1,10 @@
+pub mod branch_repository;
+pub mod commit_graph_repository;
+pub mod developer_identity_repository;
+pub mod developer_repository;
+pub mod developer_username_repository;
+pub mod organization_name_repository;
 pub mod organization_repository;
+pub mod pull_request_number_repository;
+pub mod pull_request_repository;
+pub mod repository_repository;
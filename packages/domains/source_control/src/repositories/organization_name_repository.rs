@@ -0,0 +1,49 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::entities::organization::OrganizationId;
+
+/// Keeps organization names unique. A reservation belongs to an organization id, not to a
+/// stream, so it follows the organization through renames.
+#[async_trait]
+pub trait OrganizationNameRepository: Interface {
+    /// Reserving a name the organization already holds succeeds.
+    async fn reserve(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReserveOrganizationNameError>;
+
+    /// Only releases the name when it is held by the organization.
+    async fn release(
+        &self,
+        name: &str,
+        organization_id: OrganizationId,
+    ) -> Result<(), ReleaseOrganizationNameError>;
+}
+
+/// Names are unique regardless of casing and surrounding whitespace.
+pub fn organization_name_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[derive(Error, Debug)]
+pub enum ReserveOrganizationNameError {
+    #[error("The name is reserved by organization {organization_id}")]
+    Taken { organization_id: OrganizationId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[derive(Error, Debug)]
+pub enum ReleaseOrganizationNameError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
@@ -5,7 +5,10 @@ use shaku::Interface;
 use thiserror::Error;
 
 use crate::{
-    aggregates::organization::{OrganizationAggregate, OrganizationEvent},
+    aggregates::{
+        metadata::{CommandMetadata, LoggedEvent},
+        organization::{OrganizationAggregate, OrganizationEvent},
+    },
     entities::organization::{Organization, OrganizationId},
 };
 
@@ -14,16 +17,26 @@ pub trait OrganizationRepository: Interface {
     async fn get_log(
         &self,
         organization_id: OrganizationId,
-    ) -> Result<Box<[OrganizationEvent]>, GetOrganizationLogError>;
+    ) -> Result<Box<[LoggedEvent<OrganizationEvent>]>, GetOrganizationLogError>;
 
     async fn get(
         &self,
         organization_id: OrganizationId,
     ) -> Result<OrganizationAggregate, GetOrganizationError>;
 
-    async fn save(&self, organization: OrganizationAggregate) -> Result<(), SaveOrganizationError>;
+    async fn save(
+        &self,
+        organization: OrganizationAggregate,
+        metadata: CommandMetadata,
+    ) -> Result<(), SaveOrganizationError>;
 
-    async fn create(&self, name: String) -> Result<Organization, CreateOrganizationError>;
+    /// Names aren't checked here, reserve them through the `OrganizationNameRepository` first.
+    async fn create(
+        &self,
+        organization_id: OrganizationId,
+        name: String,
+        metadata: CommandMetadata,
+    ) -> Result<Organization, CreateOrganizationError>;
 }
 
 #[derive(Error, Debug)]
@@ -34,6 +47,8 @@ pub enum GetOrganizationLogError {
     Connection,
     #[error("Unexpected error")]
     Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
 }
 
 #[derive(Error, Debug)]
@@ -44,6 +59,8 @@ pub enum GetOrganizationError {
     Connection,
     #[error("Unexpected error")]
     Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::entities::{pull_request::PullRequestId, repository::RepositoryId};
+
+/// Keeps the numbers of pull requests unique within a repository, their ids are generated so
+/// the streams can't tell two pull requests with the same number apart.
+#[async_trait]
+pub trait PullRequestNumberRepository: Interface {
+    /// Reserving a number the pull request already holds succeeds.
+    async fn reserve(
+        &self,
+        repository_id: RepositoryId,
+        number: u64,
+        pull_request_id: PullRequestId,
+    ) -> Result<(), ReservePullRequestNumberError>;
+
+    /// Only releases the number when it is held by the pull request.
+    async fn release(
+        &self,
+        repository_id: RepositoryId,
+        number: u64,
+        pull_request_id: PullRequestId,
+    ) -> Result<(), ReleasePullRequestNumberError>;
+}
+
+#[derive(Error, Debug)]
+pub enum ReservePullRequestNumberError {
+    #[error("The number is reserved by pull request {pull_request_id}")]
+    Taken { pull_request_id: PullRequestId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
+
+#[derive(Error, Debug)]
+pub enum ReleasePullRequestNumberError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+}
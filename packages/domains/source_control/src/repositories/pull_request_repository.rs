@@ -0,0 +1,66 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{
+    aggregates::pull_request::PullRequestAggregate,
+    entities::{
+        developer::DeveloperId,
+        pull_request::{PullRequest, PullRequestId},
+        repository::RepositoryId,
+    },
+};
+
+#[async_trait]
+pub trait PullRequestRepository: Interface {
+    async fn get(
+        &self,
+        pull_request_id: PullRequestId,
+    ) -> Result<PullRequestAggregate, GetPullRequestError>;
+
+    async fn save(&self, pull_request: PullRequestAggregate) -> Result<(), SavePullRequestError>;
+
+    async fn create(
+        &self,
+        id: PullRequestId,
+        repository_id: RepositoryId,
+        number: u64,
+        author_id: DeveloperId,
+        title: String,
+        description: String,
+    ) -> Result<PullRequest, CreatePullRequestError>;
+}
+
+#[derive(Error, Debug)]
+pub enum GetPullRequestError {
+    #[error("Pull request with {pull_request_id} not found.")]
+    NotFound { pull_request_id: PullRequestId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum SavePullRequestError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
+
+#[derive(Error, Debug)]
+pub enum CreatePullRequestError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
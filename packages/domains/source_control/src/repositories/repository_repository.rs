@@ -0,0 +1,64 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{
+    aggregates::repository::RepositoryAggregate,
+    entities::{
+        organization::OrganizationId,
+        platform_account::PlatformAccountId,
+        repository::{Repository, RepositoryId},
+    },
+};
+
+#[async_trait]
+pub trait RepositoryRepository: Interface {
+    async fn get(
+        &self,
+        repository_id: RepositoryId,
+    ) -> Result<RepositoryAggregate, GetRepositoryError>;
+
+    async fn save(&self, repository: RepositoryAggregate) -> Result<(), SaveRepositoryError>;
+
+    async fn create(
+        &self,
+        id: RepositoryId,
+        organization_id: OrganizationId,
+        platform_account_id: PlatformAccountId,
+        name: String,
+    ) -> Result<Repository, CreateRepositoryError>;
+}
+
+#[derive(Error, Debug)]
+pub enum GetRepositoryError {
+    #[error("Repository with {repository_id} not found.")]
+    NotFound { repository_id: RepositoryId },
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("The stored events could not be decoded: {reason}")]
+    Corrupt { reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum SaveRepositoryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
+
+#[derive(Error, Debug)]
+pub enum CreateRepositoryError {
+    #[error("Connecting to the server failed")]
+    Connection,
+    #[error("Unexpected error")]
+    Unexpected,
+    #[error("Another client tried to write to the same aggregate at the same time")]
+    Conflict,
+}
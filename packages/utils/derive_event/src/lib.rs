@@ -0,0 +1,437 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Fields, FieldsNamed, Ident, LitInt,
+    LitStr, Path,
+};
+
+/// Derives `event_codec::EventCodec` for an event enum, every variant has named fields and is
+/// stored as a json object.
+///
+/// ```ignore
+/// #[derive(EventCodec)]
+/// #[event(prefix = "Porti.SourceControl/Aggregates/Organization", aggregate_id = organization_id)]
+/// pub enum OrganizationEvent {
+///     #[event(name = "RemovePlatformAccount", version = 1)]
+///     RemovePlatformAccount {
+///         organization_id: OrganizationId,
+///         #[event(rename = "account.id")]
+///         account_id: PlatformAccountId,
+///     },
+/// }
+/// ```
+///
+/// The event type of a variant is `{prefix}/{name}`, it is stored with `/{version}` appended and
+/// the version defaults to `1`. Fields accept `rename` with a dotted path, `with` naming a module
+/// that has `encode` and `decode` functions, and `skip` to leave a field out of the json.
+#[proc_macro_derive(EventCodec, attributes(event))]
+pub fn event_codec_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_event_codec(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `event_codec::EventField` for the types used in events.
+///
+/// Structs with named fields become json objects, tuple structs with a single field, like ids,
+/// are stored as that field. Enums need a `tag` naming the field that holds the `name` of the
+/// variant, the fields of the variant are stored next to it.
+#[proc_macro_derive(EventField, attributes(event))]
+pub fn event_field_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_event_field(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct EventAttributes {
+    prefix: Option<LitStr>,
+    aggregate_id: Option<Ident>,
+    name: Option<LitStr>,
+    version: Option<LitInt>,
+    tag: Option<LitStr>,
+    rename: Option<LitStr>,
+    with: Option<Path>,
+    skip: bool,
+}
+
+fn parse_attributes(attrs: &[Attribute]) -> syn::Result<EventAttributes> {
+    let mut result = EventAttributes::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prefix") {
+                result.prefix = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("aggregate_id") {
+                result.aggregate_id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("name") {
+                result.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("version") {
+                result.version = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("tag") {
+                result.tag = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("rename") {
+                result.rename = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("with") {
+                let path: LitStr = meta.value()?.parse()?;
+                result.with = Some(path.parse()?);
+            } else if meta.path.is_ident("skip") {
+                result.skip = true;
+            } else {
+                return Err(meta.error("unsupported event attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
+
+/// The code for the named fields of a struct or variant, the fields are expected to be bound by
+/// reference under their own name when encoding.
+struct NamedFields {
+    bindings: Vec<Ident>,
+    encode: TokenStream2,
+    decode: TokenStream2,
+    describe: TokenStream2,
+}
+
+fn expand_named_fields(fields: &FieldsNamed) -> syn::Result<NamedFields> {
+    let mut bindings = Vec::new();
+    let mut encode = Vec::new();
+    let mut decode = Vec::new();
+    let mut describe = Vec::new();
+
+    for field in &fields.named {
+        let ident = field.ident.clone().expect("named fields have an ident");
+        let ty = &field.ty;
+        let attributes = parse_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            decode.push(quote! { #ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        let path = attributes
+            .rename
+            .map(|rename| rename.value())
+            .unwrap_or_else(|| ident.to_string());
+        let segments = path.split('.');
+
+        let (encode_field, decode_field) = match attributes.with {
+            Some(with) => (quote! { #with::encode(#ident) }, quote! { #with::decode }),
+            None => (
+                quote! { <#ty as ::event_codec::EventField>::encode(#ident) },
+                quote! { <#ty as ::event_codec::EventField>::decode },
+            ),
+        };
+
+        encode.push(quote! {
+            ::event_codec::insert_path(&mut __map, &[#(#segments),*], #encode_field);
+        });
+        let segments = path.split('.');
+        decode.push(quote! {
+            #ident: #decode_field(::event_codec::lookup_path(__value, &[#(#segments),*]))
+                .map_err(|failure| failure.within(#path))?
+        });
+        describe.push(quote! {
+            <#ty as ::event_codec::EventField>::describe(&::event_codec::join_path(__path, #path), __fields);
+        });
+        bindings.push(ident);
+    }
+
+    Ok(NamedFields {
+        bindings,
+        encode: quote! { #(#encode)* },
+        decode: quote! { #(#decode),* },
+        describe: quote! { #(#describe)* },
+    })
+}
+
+fn expand_event_codec(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Data::Enum(DataEnum { variants, .. }) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "EventCodec can only be derived for enums",
+        ));
+    };
+
+    let attributes = parse_attributes(&input.attrs)?;
+    let prefix = attributes.prefix.ok_or_else(|| {
+        syn::Error::new_spanned(name, "EventCodec requires #[event(prefix = \"...\")]")
+    })?;
+    let aggregate_id = attributes.aggregate_id.ok_or_else(|| {
+        syn::Error::new_spanned(name, "EventCodec requires #[event(aggregate_id = field)]")
+    })?;
+
+    let mut event_type_arms = Vec::new();
+    let mut version_arms = Vec::new();
+    let mut aggregate_id_arms = Vec::new();
+    let mut encode_arms = Vec::new();
+    let mut decode_arms = Vec::new();
+    let mut schemas = Vec::new();
+
+    for variant in variants {
+        let ident = &variant.ident;
+        let Fields::Named(fields) = &variant.fields else {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "EventCodec variants need named fields",
+            ));
+        };
+        if !fields
+            .named
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(&aggregate_id))
+        {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("the variant has no `{}` field", aggregate_id),
+            ));
+        }
+
+        let variant_attributes = parse_attributes(&variant.attrs)?;
+        let event_name = variant_attributes.name.ok_or_else(|| {
+            syn::Error::new_spanned(ident, "event variants require #[event(name = \"...\")]")
+        })?;
+        let version = match variant_attributes.version {
+            Some(version) => version.base10_parse::<u32>()?,
+            None => 1,
+        };
+        let event_type = format!("{}/{}", prefix.value(), event_name.value());
+        let versioned_event_type = format!("{}/{}", event_type, version);
+
+        let NamedFields {
+            bindings,
+            encode,
+            decode,
+            describe,
+        } = expand_named_fields(fields)?;
+
+        event_type_arms.push(quote! { Self::#ident { .. } => #event_type });
+        version_arms.push(quote! { Self::#ident { .. } => #version });
+        aggregate_id_arms.push(quote! { Self::#ident { #aggregate_id, .. } => &#aggregate_id.0 });
+        encode_arms.push(quote! {
+            Self::#ident { #(#bindings,)* .. } => {
+                let mut __map = ::event_codec::serde_json::Map::new();
+                #encode
+                ::event_codec::serde_json::Value::Object(__map)
+            }
+        });
+        decode_arms.push(quote! {
+            #versioned_event_type => ::core::result::Result::Ok(Self::#ident { #decode })
+        });
+        schemas.push(quote! {
+            {
+                let __path = "";
+                let mut __fields = ::std::vec::Vec::new();
+                {
+                    let __fields = &mut __fields;
+                    #describe
+                }
+                ::event_codec::EventSchema {
+                    event_type: #event_type,
+                    version: #version,
+                    fields: __fields,
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::event_codec::EventCodec for #name #ty_generics #where_clause {
+            fn get_event_type(&self) -> &'static str {
+                match self {
+                    #(#event_type_arms,)*
+                }
+            }
+
+            fn get_event_version(&self) -> u32 {
+                match self {
+                    #(#version_arms,)*
+                }
+            }
+
+            fn get_aggregate_id(&self) -> &u64 {
+                match self {
+                    #(#aggregate_id_arms,)*
+                }
+            }
+
+            fn encode(&self) -> ::event_codec::serde_json::Value {
+                match self {
+                    #(#encode_arms)*
+                }
+            }
+
+            fn decode(
+                __value: &::event_codec::serde_json::Value,
+                __event_type: &str,
+            ) -> ::core::result::Result<Self, ::event_codec::DecodeFailure> {
+                match __event_type {
+                    #(#decode_arms,)*
+                    _ => ::core::result::Result::Err(::event_codec::DecodeFailure::UnknownEventType),
+                }
+            }
+
+            fn schema() -> ::std::vec::Vec<::event_codec::EventSchema> {
+                ::std::vec![#(#schemas),*]
+            }
+        }
+    })
+}
+
+fn expand_event_field(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (encode, decode, describe) = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let NamedFields {
+                    bindings,
+                    encode,
+                    decode,
+                    describe,
+                } = expand_named_fields(fields)?;
+
+                (
+                    quote! {
+                        let Self { #(#bindings,)* .. } = self;
+                        let mut __map = ::event_codec::serde_json::Map::new();
+                        #encode
+                        ::event_codec::serde_json::Value::Object(__map)
+                    },
+                    quote! { ::core::result::Result::Ok(Self { #decode }) },
+                    describe,
+                )
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed.first().expect("checked the length").ty;
+
+                (
+                    quote! { <#ty as ::event_codec::EventField>::encode(&self.0) },
+                    quote! {
+                        ::core::result::Result::Ok(Self(<#ty as ::event_codec::EventField>::decode(__value)?))
+                    },
+                    quote! { <#ty as ::event_codec::EventField>::describe(__path, __fields); },
+                )
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "EventField can only be derived for structs with named fields or a single unnamed field",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            let tag = parse_attributes(&input.attrs)?.tag.ok_or_else(|| {
+                syn::Error::new_spanned(name, "EventField enums require #[event(tag = \"...\")]")
+            })?;
+
+            let mut encode_arms = Vec::new();
+            let mut decode_arms = Vec::new();
+            let mut describe = Vec::new();
+
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let Fields::Named(fields) = &variant.fields else {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "EventField variants need named fields",
+                    ));
+                };
+                let variant_name = parse_attributes(&variant.attrs)?.name.ok_or_else(|| {
+                    syn::Error::new_spanned(ident, "variants require #[event(name = \"...\")]")
+                })?;
+
+                let NamedFields {
+                    bindings,
+                    encode,
+                    decode,
+                    describe: describe_variant,
+                } = expand_named_fields(fields)?;
+
+                encode_arms.push(quote! {
+                    Self::#ident { #(#bindings,)* .. } => {
+                        let mut __map = ::event_codec::serde_json::Map::new();
+                        __map.insert(
+                            ::std::string::String::from(#tag),
+                            ::event_codec::serde_json::Value::from(#variant_name),
+                        );
+                        #encode
+                        ::event_codec::serde_json::Value::Object(__map)
+                    }
+                });
+                decode_arms.push(quote! {
+                    #variant_name => ::core::result::Result::Ok(Self::#ident { #decode })
+                });
+                describe.push(describe_variant);
+            }
+
+            (
+                quote! {
+                    match self {
+                        #(#encode_arms)*
+                    }
+                },
+                quote! {
+                    let __tag = __value[#tag]
+                        .as_str()
+                        .ok_or(::event_codec::DecodeFailure::field(#tag))?;
+                    match __tag {
+                        #(#decode_arms,)*
+                        _ => ::core::result::Result::Err(::event_codec::DecodeFailure::field(#tag)),
+                    }
+                },
+                // The fields of a variant are only present when the tag names that variant
+                quote! {
+                    __fields.push(::event_codec::FieldSchema::new(
+                        ::event_codec::join_path(__path, #tag),
+                        "tag",
+                    ));
+                    let mut __variant_fields = ::std::vec::Vec::new();
+                    {
+                        let __fields = &mut __variant_fields;
+                        #(#describe)*
+                    }
+                    __fields.extend(__variant_fields.into_iter().map(::event_codec::FieldSchema::optional));
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "EventField can not be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::event_codec::EventField for #name #ty_generics #where_clause {
+            fn encode(&self) -> ::event_codec::serde_json::Value {
+                #encode
+            }
+
+            fn decode(
+                __value: &::event_codec::serde_json::Value,
+            ) -> ::core::result::Result<Self, ::event_codec::DecodeFailure> {
+                #decode
+            }
+
+            fn describe(
+                __path: &str,
+                __fields: &mut ::std::vec::Vec<::event_codec::FieldSchema>,
+            ) {
+                #describe
+            }
+        }
+    })
+}
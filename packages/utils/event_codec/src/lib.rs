@@ -0,0 +1,372 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+// The derives refer to this crate by name, also from its own tests
+extern crate self as event_codec;
+
+pub use derive_event::{EventCodec, EventField};
+pub use serde_json;
+
+/// An event enum stored as json, derived with `#[derive(EventCodec)]`.
+pub trait EventCodec: Sized {
+    /// Event type without the version suffix, `Porti.SourceControl/Aggregates/Organization/Create`.
+    fn get_event_type(&self) -> &'static str;
+    /// Version the json is written in, older versions are upcasted to it before decoding.
+    fn get_event_version(&self) -> u32;
+    fn get_aggregate_id(&self) -> &u64;
+    fn encode(&self) -> Value;
+    /// `event_type` includes the version suffix, `.../Create/1`.
+    fn decode(value: &Value, event_type: &str) -> Result<Self, DecodeFailure>;
+    /// Describes the json of every variant.
+    fn schema() -> Vec<EventSchema>;
+
+    fn get_versioned_event_type(&self) -> String {
+        format!("{}/{}", self.get_event_type(), self.get_event_version())
+    }
+}
+
+/// A value inside the json of an event.
+pub trait EventField: Sized {
+    fn encode(&self) -> Value;
+    /// Failures of nested fields name the field relative to `value`, or nothing when `value`
+    /// itself is invalid.
+    fn decode(value: &Value) -> Result<Self, DecodeFailure>;
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSchema {
+    pub event_type: &'static str,
+    pub version: u32,
+    pub fields: Vec<FieldSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// Dotted path of the field, elements of an array are addressed with `[]`.
+    pub path: String,
+    pub kind: &'static str,
+    pub optional: bool,
+}
+
+impl FieldSchema {
+    pub fn new(path: String, kind: &'static str) -> Self {
+        Self {
+            path,
+            kind,
+            optional: false,
+        }
+    }
+
+    pub fn optional(self) -> Self {
+        Self {
+            optional: true,
+            ..self
+        }
+    }
+}
+
+/// Why the json of an event could not be decoded, without the event it happened for.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DecodeFailure {
+    #[error("the data is not valid json")]
+    InvalidJson,
+    #[error("the event type is unknown")]
+    UnknownEventType,
+    #[error("field `{field}` is missing or invalid")]
+    InvalidField { field: Cow<'static, str> },
+}
+
+impl DecodeFailure {
+    pub fn field(field: impl Into<Cow<'static, str>>) -> Self {
+        DecodeFailure::InvalidField {
+            field: field.into(),
+        }
+    }
+
+    /// The value itself is invalid, the field it is in is added by the caller.
+    pub fn invalid() -> Self {
+        DecodeFailure::field("")
+    }
+
+    /// Prefixes the failing field with the field of the value it was decoded from.
+    pub fn within(self, parent: &'static str) -> Self {
+        match self {
+            DecodeFailure::InvalidField { field } if field.is_empty() => {
+                DecodeFailure::field(parent)
+            }
+            DecodeFailure::InvalidField { field } => {
+                DecodeFailure::field(format!("{}.{}", parent, field))
+            }
+            failure => failure,
+        }
+    }
+}
+
+pub fn join_path(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", parent, field)
+    }
+}
+
+/// Inserts `value` at the dotted path split into `segments`, creating the objects on the way.
+pub fn insert_path(map: &mut Map<String, Value>, segments: &[&str], value: Value) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+
+    let mut current = map;
+    for segment in parents {
+        let entry = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry
+            .as_object_mut()
+            .expect("replaced with an object above");
+    }
+    current.insert(last.to_string(), value);
+}
+
+/// Missing fields are returned as `null`.
+pub fn lookup_path<'a>(value: &'a Value, segments: &[&str]) -> &'a Value {
+    segments
+        .iter()
+        .fold(value, |current, segment| &current[*segment])
+}
+
+impl EventField for u64 {
+    fn encode(&self) -> Value {
+        Value::from(*self)
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        value.as_u64().ok_or_else(DecodeFailure::invalid)
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "u64"));
+    }
+}
+
+impl EventField for bool {
+    fn encode(&self) -> Value {
+        Value::from(*self)
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        value.as_bool().ok_or_else(DecodeFailure::invalid)
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "bool"));
+    }
+}
+
+impl EventField for String {
+    fn encode(&self) -> Value {
+        Value::from(self.as_str())
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(DecodeFailure::invalid)
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "string"));
+    }
+}
+
+/// Stored as an RFC 3339 string.
+impl EventField for DateTime<Utc> {
+    fn encode(&self) -> Value {
+        Value::from(self.to_rfc3339())
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        let raw = value.as_str().ok_or_else(DecodeFailure::invalid)?;
+        DateTime::parse_from_rfc3339(raw)
+            .map(|date| date.with_timezone(&Utc))
+            .map_err(|_| DecodeFailure::invalid())
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "datetime"));
+    }
+}
+
+/// `None` is stored as `null`, a missing field decodes to `None` as well.
+impl<T: EventField> EventField for Option<T> {
+    fn encode(&self) -> Value {
+        match self {
+            Some(value) => value.encode(),
+            None => Value::Null,
+        }
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::decode(value).map(Some),
+        }
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        let mut inner = Vec::new();
+        T::describe(path, &mut inner);
+        fields.extend(inner.into_iter().map(FieldSchema::optional));
+    }
+}
+
+/// Failures inside an element name the field without the index of the element.
+impl<T: EventField> EventField for Vec<T> {
+    fn encode(&self) -> Value {
+        Value::Array(self.iter().map(T::encode).collect())
+    }
+
+    fn decode(value: &Value) -> Result<Self, DecodeFailure> {
+        value
+            .as_array()
+            .ok_or_else(DecodeFailure::invalid)?
+            .iter()
+            .map(T::decode)
+            .collect()
+    }
+
+    fn describe(path: &str, fields: &mut Vec<FieldSchema>) {
+        fields.push(FieldSchema::new(path.to_string(), "array"));
+        T::describe(&format!("{}[]", path), fields);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{DecodeFailure, EventCodec, EventField, FieldSchema};
+
+    #[derive(EventField, Debug, PartialEq)]
+    struct AccountId(u64);
+
+    #[derive(EventField, Debug, PartialEq)]
+    struct Account {
+        id: AccountId,
+        name: String,
+        #[event(skip)]
+        cached: bool,
+    }
+
+    #[derive(EventField, Debug, PartialEq)]
+    #[event(tag = "kind")]
+    enum Identity {
+        #[event(name = "login")]
+        Login { login: String },
+        #[event(name = "email")]
+        Email { email: String },
+    }
+
+    #[derive(EventCodec, Debug, PartialEq)]
+    #[event(prefix = "Test/Aggregates/Owner", aggregate_id = owner_id)]
+    enum OwnerEvent {
+        #[event(name = "AddAccount")]
+        AddAccount {
+            owner_id: AccountId,
+            account: Account,
+        },
+        #[event(name = "RemoveAccount", version = 2)]
+        RemoveAccount {
+            owner_id: AccountId,
+            #[event(rename = "account.id")]
+            account_id: AccountId,
+            identities: Vec<Identity>,
+            reason: Option<String>,
+        },
+    }
+
+    #[test]
+    fn should_encode_renamed_fields_as_nested_objects() {
+        let event = OwnerEvent::RemoveAccount {
+            owner_id: AccountId(1),
+            account_id: AccountId(2),
+            identities: vec![Identity::Email {
+                email: "me@example.com".to_string(),
+            }],
+            reason: None,
+        };
+
+        let encoded = event.encode();
+
+        assert_eq!(
+            encoded,
+            json!({
+                "owner_id": 1,
+                "account": { "id": 2 },
+                "identities": [{ "kind": "email", "email": "me@example.com" }],
+                "reason": null
+            })
+        );
+        assert_eq!(
+            event.get_versioned_event_type(),
+            "Test/Aggregates/Owner/RemoveAccount/2"
+        );
+        assert_eq!(event.get_aggregate_id(), &1);
+        assert_eq!(
+            OwnerEvent::decode(&encoded, "Test/Aggregates/Owner/RemoveAccount/2"),
+            Ok(event)
+        );
+    }
+
+    #[test]
+    fn should_report_nested_failing_field() {
+        let missing_name = OwnerEvent::decode(
+            &json!({ "owner_id": 1, "account": { "id": 2 } }),
+            "Test/Aggregates/Owner/AddAccount/1",
+        );
+        let unknown_kind = Identity::decode(&json!({ "kind": "phone" }));
+        let old_version = OwnerEvent::decode(
+            &json!({ "owner_id": 1, "account": { "id": 2 } }),
+            "Test/Aggregates/Owner/RemoveAccount/1",
+        );
+
+        assert_eq!(missing_name, Err(DecodeFailure::field("account.name")));
+        assert_eq!(unknown_kind, Err(DecodeFailure::field("kind")));
+        assert_eq!(decode_skipped_field(), Ok(false));
+        assert_eq!(old_version, Err(DecodeFailure::UnknownEventType));
+    }
+
+    fn decode_skipped_field() -> Result<bool, DecodeFailure> {
+        Account::decode(&json!({ "id": 2, "name": "porti", "cached": true }))
+            .map(|account| account.cached)
+    }
+
+    #[test]
+    fn should_describe_fields_of_every_variant() {
+        let schema = OwnerEvent::schema();
+
+        assert_eq!(schema.len(), 2);
+        assert_eq!(schema[1].event_type, "Test/Aggregates/Owner/RemoveAccount");
+        assert_eq!(schema[1].version, 2);
+        assert_eq!(
+            schema[1].fields,
+            vec![
+                FieldSchema::new("owner_id".to_string(), "u64"),
+                FieldSchema::new("account.id".to_string(), "u64"),
+                FieldSchema::new("identities".to_string(), "array"),
+                FieldSchema::new("identities[].kind".to_string(), "tag"),
+                FieldSchema::new("identities[].login".to_string(), "string").optional(),
+                FieldSchema::new("identities[].email".to_string(), "string").optional(),
+                FieldSchema::new("reason".to_string(), "string").optional(),
+            ]
+        );
+    }
+}
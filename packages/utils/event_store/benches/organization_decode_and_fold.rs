@@ -0,0 +1,123 @@
+//! Compares decoding and folding the 10k events of an organization against decoding a snapshot
+//! and folding the events after it. The events and snapshot are parsed from bytes held in memory,
+//! reading them from the event store isn't measured, so this is not the time
+//! `EventSourcedRepository::get` takes but the part of it snapshots can save.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use event_store_util::{
+    snapshots::{
+        organization::{EventStoreOrganizationSnapshot, ORGANIZATION_SNAPSHOT_TYPE},
+        snapshot_root, to_snapshot_json,
+    },
+    FromJson, ToJson,
+};
+use serde_json::json;
+use source_control_domain::{
+    aggregates::{
+        base::Snapshot,
+        organization::{OrganizationAggregate, OrganizationEvent},
+    },
+    entities::organization::Organization,
+};
+
+const EVENT_COUNT: u64 = 10_000;
+/// Worst case for the default frequency of 100, the stream is just about to be snapshotted.
+const EVENTS_AFTER_SNAPSHOT: u64 = 99;
+const ORGANIZATION_ID: u64 = 1;
+
+struct StoredEvent {
+    event_type: String,
+    data: Vec<u8>,
+}
+
+fn event(revision: u64) -> StoredEvent {
+    let (event_type, value) = match revision {
+        0 => (
+            "Create",
+            json!({ "organization_id": ORGANIZATION_ID, "name": "rafaeltab" }),
+        ),
+        // Adding and removing accounts keeps the root small, so only the fold is measured
+        r if r % 2 == 1 => (
+            "AddPlatformAccount",
+            json!({
+                "organization_id": ORGANIZATION_ID,
+                "account": { "id": r, "name": format!("account-{}", r), "platform": { "name": "GitHub" } }
+            }),
+        ),
+        r => (
+            "RemovePlatformAccount",
+            json!({ "organization_id": ORGANIZATION_ID, "account": { "id": r - 1 } }),
+        ),
+    };
+
+    StoredEvent {
+        event_type: format!(
+            "Porti.SourceControl/Aggregates/Organization/{}/1",
+            event_type
+        ),
+        data: serde_json::to_vec(&value).unwrap(),
+    }
+}
+
+fn parse_events(events: &[StoredEvent]) -> Vec<OrganizationEvent> {
+    events
+        .iter()
+        .map(|event| {
+            let value = serde_json::from_slice(&event.data).unwrap();
+            OrganizationEvent::from_json(value, &event.event_type).unwrap()
+        })
+        .collect()
+}
+
+fn snapshot_type() -> String {
+    format!(
+        "{}/{}",
+        ORGANIZATION_SNAPSHOT_TYPE,
+        Organization::SNAPSHOT_VERSION
+    )
+}
+
+fn decode_and_fold(c: &mut Criterion) {
+    let events: Vec<StoredEvent> = (0..EVENT_COUNT).map(event).collect();
+    let snapshot_revision = EVENT_COUNT - EVENTS_AFTER_SNAPSHOT - 1;
+
+    let snapshotted = OrganizationAggregate::from_events(
+        parse_events(&events[..=snapshot_revision as usize]),
+        snapshot_revision,
+    );
+    let snapshot = serde_json::to_vec(&to_snapshot_json(
+        EventStoreOrganizationSnapshot::to_json(&snapshotted.root),
+        snapshot_revision,
+    ))
+    .unwrap();
+    let tail = &events[(snapshot_revision + 1) as usize..];
+
+    let mut group = c.benchmark_group("organization_10k_events_decode_and_fold");
+
+    group.bench_function("every_event", |b| {
+        b.iter(|| {
+            OrganizationAggregate::from_events(parse_events(black_box(&events)), EVENT_COUNT - 1)
+        })
+    });
+
+    group.bench_function("snapshot_and_tail", |b| {
+        b.iter(|| {
+            let value: serde_json::Value = serde_json::from_slice(black_box(&snapshot)).unwrap();
+            let root = EventStoreOrganizationSnapshot::from_json(
+                snapshot_root(&value).clone(),
+                &snapshot_type(),
+            )
+            .unwrap();
+            OrganizationAggregate::from_snapshot(
+                root.0,
+                parse_events(black_box(tail)),
+                EVENT_COUNT - 1,
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, decode_and_fold);
+criterion_main!(benches);
@@ -1,25 +1,151 @@
-pub mod aggregates;
+use event_codec::EventCodec;
+use thiserror::Error;
+
+pub use event_codec::DecodeFailure;
+
+pub mod metadata;
+pub mod parked;
+pub mod snapshots;
+pub mod upcasting;
 
 pub trait FromEventStoreEvent: Sized {
     fn from_eventstore_event(event: &eventstore::ResolvedEvent) -> Self;
     fn from_recorded_event(event: &eventstore::RecordedEvent) -> Self;
 }
 
-pub trait FromJson {
-    fn from_json(value: serde_json::Value, event_type: &str) -> Self;
+pub trait FromJson: Sized {
+    fn from_json(value: serde_json::Value, event_type: &str) -> Result<Self, DecodeFailure>;
+}
+
+/// Events decode through the code derived with `#[derive(EventCodec)]`.
+impl<T: EventCodec> FromJson for T {
+    fn from_json(value: serde_json::Value, event_type: &str) -> Result<Self, DecodeFailure> {
+        T::decode(&value, event_type)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Could not decode event {event_id} of type {event_type}, {failure}")]
+pub struct DecodeError {
+    pub event_id: String,
+    pub event_type: String,
+    pub failure: DecodeFailure,
+}
+
+impl DecodeError {
+    pub fn field(&self) -> Option<&str> {
+        match &self.failure {
+            DecodeFailure::InvalidField { field } => Some(field),
+            DecodeFailure::InvalidJson | DecodeFailure::UnknownEventType => None,
+        }
+    }
 }
 
 pub trait ToJson<TResult> {
     fn to_json(value: TResult) -> serde_json::Value;
 }
 
-pub fn from_recorded_event<T: FromJson>(event: &eventstore::RecordedEvent) -> T {
-    let parsed = serde_json::from_slice::<serde_json::Value>(&event.data)
-        .expect("Expected to be able to parse as json");
-    T::from_json(parsed, &event.event_type.clone())
+/// Upcasts the event to its latest version before decoding it.
+pub fn from_recorded_event<T: FromJson>(
+    event: &eventstore::RecordedEvent,
+) -> Result<T, DecodeError> {
+    let event_id = event.id.to_string();
+    let parsed =
+        serde_json::from_slice::<serde_json::Value>(&event.data).map_err(|_| DecodeError {
+            event_id: event_id.clone(),
+            event_type: event.event_type.clone(),
+            failure: DecodeFailure::InvalidJson,
+        })?;
+    from_stored_json(&event_id, &event.event_type, parsed)
 }
 
-pub fn from_resolved_event<T: FromJson>(event: &eventstore::ResolvedEvent) -> T {
+/// Decodes the json of an event stored outside of EventStore, upcasting it the same way.
+pub fn from_stored_json<T: FromJson>(
+    event_id: &str,
+    event_type: &str,
+    value: serde_json::Value,
+) -> Result<T, DecodeError> {
+    let (upcasted, upcasted_type) = upcasting::UPCASTERS.upcast(value, event_type);
+    T::from_json(upcasted, &upcasted_type).map_err(|failure| DecodeError {
+        event_id: event_id.to_string(),
+        event_type: event_type.to_string(),
+        failure,
+    })
+}
+
+pub fn from_resolved_event<T: FromJson>(
+    event: &eventstore::ResolvedEvent,
+) -> Result<T, DecodeError> {
     let original_event = event.get_original_event();
     from_recorded_event::<T>(original_event)
 }
+
+#[cfg(test)]
+mod test {
+    use event_codec::EventCodec;
+    use serde_json::json;
+    use source_control_domain::{
+        aggregates::{
+            branch::BranchEvent, commit_graph::CommitGraphEvent, developer::DeveloperEvent,
+            organization::OrganizationEvent, pull_request::PullRequestEvent,
+            repository::RepositoryEvent,
+        },
+        entities::{organization::OrganizationId, platform_account::PlatformAccountId},
+    };
+
+    use crate::{upcasting::UPCASTERS, DecodeFailure, FromJson};
+
+    #[test]
+    fn should_report_failing_field() {
+        let result = OrganizationEvent::from_json(
+            json!({ "organization_id": 1, "account": { "id": 2, "platform": { "name": "GitHub" } } }),
+            "Porti.SourceControl/Aggregates/Organization/AddPlatformAccount/1",
+        );
+
+        assert_eq!(result.err(), Some(DecodeFailure::field("account.name")));
+    }
+
+    #[test]
+    fn should_report_unknown_event_type() {
+        let result = OrganizationEvent::from_json(
+            json!({ "organization_id": 1 }),
+            "Porti.SourceControl/Aggregates/Organization/Create/9",
+        );
+
+        assert_eq!(result.err(), Some(DecodeFailure::UnknownEventType));
+    }
+
+    #[test]
+    fn should_keep_nested_account_id() {
+        let event = OrganizationEvent::RemovePlatformAccount {
+            organization_id: OrganizationId(1),
+            account_id: PlatformAccountId(2),
+        };
+
+        assert_eq!(
+            event.encode(),
+            json!({ "organization_id": 1, "account": { "id": 2 } })
+        );
+    }
+
+    #[test]
+    fn should_write_the_version_upcasters_end_at() {
+        let schemas = [
+            BranchEvent::schema(),
+            CommitGraphEvent::schema(),
+            DeveloperEvent::schema(),
+            OrganizationEvent::schema(),
+            PullRequestEvent::schema(),
+            RepositoryEvent::schema(),
+        ];
+
+        for schema in schemas.iter().flatten() {
+            assert_eq!(
+                schema.version,
+                UPCASTERS.latest_version(schema.event_type),
+                "{}",
+                schema.event_type
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use event_codec::EventField;
+use log::warn;
+use opentelemetry::{global, Context};
+use source_control_domain::aggregates::metadata::{CommandMetadata, EventMetadata, TraceContext};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Metadata for events written now, from within the current span.
+pub fn event_metadata(command: &CommandMetadata) -> EventMetadata {
+    EventMetadata::new(command, Utc::now(), current_trace_context())
+}
+
+/// `None` when tracing isn't set up, the propagator then writes no `traceparent`.
+fn current_trace_context() -> Option<TraceContext> {
+    let context = Span::current().context();
+    let mut carrier: HashMap<String, String> = HashMap::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut carrier));
+
+    Some(TraceContext {
+        traceparent: carrier.remove("traceparent")?,
+        tracestate: carrier
+            .remove("tracestate")
+            .filter(|tracestate| !tracestate.is_empty()),
+    })
+}
+
+/// The context of the span an event was written in, to continue its trace while handling it.
+pub fn extract_trace_context(trace_context: TraceContext) -> Context {
+    let mut carrier = HashMap::from([("traceparent".to_string(), trace_context.traceparent)]);
+    if let Some(tracestate) = trace_context.tracestate {
+        carrier.insert("tracestate".to_string(), tracestate);
+    }
+
+    global::get_text_map_propagator(|propagator| propagator.extract(&carrier))
+}
+
+/// Events written before metadata was recorded have none, metadata that can't be decoded is
+/// treated the same way so the event itself can still be read.
+pub fn metadata_from_recorded_event(event: &eventstore::RecordedEvent) -> Option<EventMetadata> {
+    if event.custom_metadata.is_empty() {
+        return None;
+    }
+
+    match serde_json::from_slice::<serde_json::Value>(&event.custom_metadata) {
+        Ok(value) => metadata_from_json(&event.id.to_string(), &value),
+        Err(_) => {
+            warn!("Ignoring metadata of event {}, it is not json", event.id);
+            None
+        }
+    }
+}
+
+/// Metadata stored outside of EventStore, `null` when the event has none.
+pub fn metadata_from_json(event_id: &str, value: &serde_json::Value) -> Option<EventMetadata> {
+    if value.is_null() {
+        return None;
+    }
+
+    match EventMetadata::decode(value) {
+        Ok(metadata) => Some(metadata),
+        Err(failure) => {
+            warn!("Ignoring metadata of event {}, {}", event_id, failure);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use opentelemetry::{global, trace::TraceContextExt};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use source_control_domain::aggregates::metadata::TraceContext;
+
+    use super::extract_trace_context;
+
+    #[test]
+    fn should_continue_the_trace_of_the_request() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let context = extract_trace_context(TraceContext {
+            traceparent: "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_string(),
+            tracestate: None,
+        });
+
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+        assert_eq!(span_context.span_id().to_string(), "b7ad6b7169203331");
+    }
+}
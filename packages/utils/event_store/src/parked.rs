@@ -0,0 +1,139 @@
+use event_codec::{DecodeFailure, EventCodec};
+use serde::{Deserialize, Serialize};
+use source_control_domain::aggregates::{
+    branch::BranchEvent, commit_graph::CommitGraphEvent, developer::DeveloperEvent,
+    organization::OrganizationEvent, pull_request::PullRequestEvent, repository::RepositoryEvent,
+};
+
+use crate::{from_recorded_event, from_stored_json, DecodeError};
+
+/// Stream EventStore parks the events of a persistent subscription to `$all` in, as links to the
+/// parked events.
+pub fn parked_stream_name(subscription_name: &str) -> String {
+    format!("$persistentsubscription-$all::{}-parked", subscription_name)
+}
+
+/// Why an event was nacked, written as the nack reason. EventStore doesn't keep the retry count
+/// of parked events, so it is recorded along with the reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NackReason {
+    pub reason: String,
+    pub retry_count: Option<usize>,
+}
+
+impl NackReason {
+    pub fn new(reason: impl Into<String>, retry_count: usize) -> Self {
+        Self {
+            reason: reason.into(),
+            retry_count: Some(retry_count),
+        }
+    }
+
+    pub fn to_nack_reason(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| self.reason.clone())
+    }
+
+    /// Reasons written before the retry count was recorded, or by other clients, are kept as is.
+    pub fn from_nack_reason(reason: &str) -> Self {
+        serde_json::from_str(reason).unwrap_or_else(|_| Self {
+            reason: reason.to_string(),
+            retry_count: None,
+        })
+    }
+}
+
+/// Metadata EventStore writes on the link of a parked event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ParkedMessageMetadata {
+    reason: Option<String>,
+    added: Option<String>,
+}
+
+/// The nack reason and when the event was parked, from the metadata of its link in the parked
+/// stream.
+pub fn parked_metadata(link: &eventstore::RecordedEvent) -> (Option<NackReason>, Option<String>) {
+    match serde_json::from_slice::<ParkedMessageMetadata>(&link.custom_metadata) {
+        Ok(metadata) => (
+            metadata
+                .reason
+                .map(|reason| NackReason::from_nack_reason(&reason)),
+            metadata.added,
+        ),
+        Err(_) => (None, None),
+    }
+}
+
+type Decoder = fn(&eventstore::RecordedEvent) -> Result<serde_json::Value, DecodeError>;
+
+/// Parked events of any subscription, each aggregate rejects the event types of the others.
+const DECODERS: [Decoder; 6] = [
+    decode_as::<OrganizationEvent>,
+    decode_as::<RepositoryEvent>,
+    decode_as::<PullRequestEvent>,
+    decode_as::<CommitGraphEvent>,
+    decode_as::<BranchEvent>,
+    decode_as::<DeveloperEvent>,
+];
+
+fn decode_as<T: EventCodec>(
+    event: &eventstore::RecordedEvent,
+) -> Result<serde_json::Value, DecodeError> {
+    from_recorded_event::<T>(event).map(|event| event.encode())
+}
+
+/// Decodes a parked event of any aggregate, upcasted, and encodes it again to show it.
+pub fn decode_parked_event(
+    event: &eventstore::RecordedEvent,
+) -> Result<serde_json::Value, DecodeError> {
+    let mut result = Err(DecodeError {
+        event_id: event.id.to_string(),
+        event_type: event.event_type.clone(),
+        failure: DecodeFailure::UnknownEventType,
+    });
+    for decode in DECODERS {
+        result = decode(event);
+        if !matches!(
+            result,
+            Err(DecodeError {
+                failure: DecodeFailure::UnknownEventType,
+                ..
+            })
+        ) {
+            break;
+        }
+    }
+    result
+}
+
+/// Decodes a parked event stored in postgres, upcasted, and encodes it again to show it. Only
+/// organizations are stored there.
+pub fn decode_stored_parked_event(
+    event_id: &str,
+    event_type: &str,
+    data: serde_json::Value,
+) -> Result<serde_json::Value, DecodeError> {
+    from_stored_json::<OrganizationEvent>(event_id, event_type, data).map(|event| event.encode())
+}
+
+#[cfg(test)]
+mod test {
+    use super::NackReason;
+
+    #[test]
+    fn should_read_the_retry_count_from_the_nack_reason() {
+        let reason = NackReason::new("The key already existed in the database", 3);
+
+        assert_eq!(
+            NackReason::from_nack_reason(&reason.to_nack_reason()),
+            reason
+        );
+        assert_eq!(
+            NackReason::from_nack_reason("Projection failed, retry"),
+            NackReason {
+                reason: "Projection failed, retry".to_string(),
+                retry_count: None,
+            }
+        );
+    }
+}
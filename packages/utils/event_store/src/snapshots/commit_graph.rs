@@ -0,0 +1,106 @@
+use event_codec::EventField;
+use serde_json::json;
+use source_control_domain::entities::{
+    commit::Commit, commit_graph::CommitGraph, repository::RepositoryId,
+};
+
+use crate::{DecodeFailure, FromJson, ToJson};
+
+pub const COMMIT_GRAPH_SNAPSHOT_TYPE: &str = "Porti.SourceControl/Snapshots/CommitGraph";
+
+/// Commits are stored the same way as in `RecordCommits` events.
+pub struct EventStoreCommitGraphSnapshot(pub CommitGraph);
+
+impl FromJson for EventStoreCommitGraphSnapshot {
+    fn from_json(
+        value: serde_json::Value,
+        event_type: &str,
+    ) -> Result<EventStoreCommitGraphSnapshot, DecodeFailure> {
+        let snapshot = match event_type {
+            "Porti.SourceControl/Snapshots/CommitGraph/1" => {
+                let repository_id = RepositoryId::decode(&value["repository_id"])
+                    .map_err(|failure| failure.within("repository_id"))?;
+                let commits = Vec::<Commit>::decode(&value["commits"])
+                    .map_err(|failure| failure.within("commits"))?;
+
+                EventStoreCommitGraphSnapshot(CommitGraph {
+                    repository_id,
+                    commits: commits
+                        .into_iter()
+                        .map(|commit| (commit.sha, commit))
+                        .collect(),
+                })
+            }
+            _ => return Err(DecodeFailure::UnknownEventType),
+        };
+
+        Ok(snapshot)
+    }
+}
+
+impl ToJson<&CommitGraph> for EventStoreCommitGraphSnapshot {
+    fn to_json(value: &CommitGraph) -> serde_json::Value {
+        let mut commits: Vec<&Commit> = value.commits.values().collect();
+        commits.sort_by_key(|commit| commit.sha.0);
+
+        json!({
+            "repository_id": value.repository_id.encode(),
+            "commits": commits.iter().map(|commit| commit.encode()).collect::<Vec<_>>()
+        })
+    }
+}
+
+impl From<EventStoreCommitGraphSnapshot> for CommitGraph {
+    fn from(value: EventStoreCommitGraphSnapshot) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use source_control_domain::entities::{
+        commit::{Commit, CommitSha},
+        commit_graph::CommitGraph,
+        repository::RepositoryId,
+    };
+
+    use crate::{FromJson, ToJson};
+
+    use super::EventStoreCommitGraphSnapshot;
+
+    #[test]
+    fn should_restore_the_commits_of_a_snapshot() {
+        let commits = [
+            Commit {
+                sha: CommitSha([1; 20]),
+                message: "Initial commit".to_string(),
+                parents: vec![],
+            },
+            Commit {
+                sha: CommitSha([2; 20]),
+                message: "Add readme".to_string(),
+                parents: vec![CommitSha([1; 20])],
+            },
+        ];
+        let graph = CommitGraph {
+            repository_id: RepositoryId(3),
+            commits: commits
+                .iter()
+                .map(|commit| (commit.sha, commit.clone()))
+                .collect(),
+        };
+
+        let json = EventStoreCommitGraphSnapshot::to_json(&graph);
+        let EventStoreCommitGraphSnapshot(restored) = EventStoreCommitGraphSnapshot::from_json(
+            json,
+            "Porti.SourceControl/Snapshots/CommitGraph/1",
+        )
+        .unwrap();
+
+        assert_eq!(restored.repository_id, RepositoryId(3));
+        assert_eq!(restored.commits.len(), 2);
+        let readme = restored.get_commit(CommitSha([2; 20])).unwrap();
+        assert_eq!(readme.message, "Add readme");
+        assert_eq!(readme.parents, vec![CommitSha([1; 20])]);
+    }
+}
@@ -0,0 +1,21 @@
+use serde_json::json;
+
+pub mod commit_graph;
+pub mod organization;
+
+/// Snapshots are stored as `{"revision": <revision>, "root": <root>}`, `revision` being the
+/// revision of the last event applied to the root.
+pub fn to_snapshot_json(root: serde_json::Value, revision: u64) -> serde_json::Value {
+    json!({
+        "revision": revision,
+        "root": root
+    })
+}
+
+pub fn snapshot_revision(value: &serde_json::Value) -> Option<u64> {
+    value["revision"].as_u64()
+}
+
+pub fn snapshot_root(value: &serde_json::Value) -> &serde_json::Value {
+    &value["root"]
+}
@@ -0,0 +1,73 @@
+use serde_json::json;
+use source_control_domain::entities::{
+    organization::{Organization, OrganizationId},
+    platform::Platform,
+    platform_account::{PlatformAccount, PlatformAccountId},
+};
+
+use crate::{DecodeFailure, FromJson, ToJson};
+
+pub const ORGANIZATION_SNAPSHOT_TYPE: &str = "Porti.SourceControl/Snapshots/Organization";
+
+pub struct EventStoreOrganizationSnapshot(pub Organization);
+
+impl FromJson for EventStoreOrganizationSnapshot {
+    fn from_json(value: serde_json::Value, event_type: &str) -> Result<EventStoreOrganizationSnapshot, DecodeFailure> {
+        let snapshot = match event_type {
+            "Porti.SourceControl/Snapshots/Organization/1" => {
+                let organization_id = &value["id"].as_u64().ok_or(DecodeFailure::field("id"))?;
+                let name = &value["name"].as_str().ok_or(DecodeFailure::field("name"))?;
+                let accounts = &value["platform_accounts"].as_array().ok_or(DecodeFailure::field("platform_accounts"))?;
+
+                EventStoreOrganizationSnapshot(Organization {
+                    id: OrganizationId(*organization_id),
+                    name: name.to_string(),
+                    platform_accounts: accounts.iter().map(platform_account_from_json).collect::<Result<_, _>>()?,
+                    archived: value["archived"].as_bool().unwrap_or(false),
+                    deleted: value["deleted"].as_bool().unwrap_or(false),
+                })
+            }
+            _ => return Err(DecodeFailure::UnknownEventType),
+        };
+
+        Ok(snapshot)
+    }
+}
+
+fn platform_account_from_json(value: &serde_json::Value) -> Result<PlatformAccount, DecodeFailure> {
+    let account_id = value["id"].as_u64().ok_or(DecodeFailure::field("platform_accounts.id"))?;
+    let account_name = value["name"].as_str().ok_or(DecodeFailure::field("platform_accounts.name"))?;
+    let platform_name = value["platform"]["name"].as_str().ok_or(DecodeFailure::field("platform_accounts.platform.name"))?;
+
+    Ok(PlatformAccount {
+        id: PlatformAccountId(account_id),
+        name: account_name.to_string(),
+        platform: Platform {
+            name: platform_name.to_string(),
+        },
+    })
+}
+
+impl ToJson<&Organization> for EventStoreOrganizationSnapshot {
+    fn to_json(value: &Organization) -> serde_json::Value {
+        json!({
+            "id": value.id.0,
+            "name": value.name,
+            "platform_accounts": value.platform_accounts.iter().map(|account| json!({
+                "id": account.id.0,
+                "name": account.name,
+                "platform": {
+                    "name": account.platform.name
+                }
+            })).collect::<Vec<_>>(),
+            "archived": value.archived,
+            "deleted": value.deleted
+        })
+    }
+}
+
+impl From<EventStoreOrganizationSnapshot> for Organization {
+    fn from(value: EventStoreOrganizationSnapshot) -> Self {
+        value.0
+    }
+}
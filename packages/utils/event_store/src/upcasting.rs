@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+
+/// Turns the json of one version of an event into the json of the next version.
+pub type UpcastFn = fn(serde_json::Value) -> serde_json::Value;
+
+pub struct Upcaster {
+    /// Event type without the version suffix, `Porti.SourceControl/Aggregates/Organization/Create`.
+    pub event_type: &'static str,
+    /// Version of the json this upcaster accepts, it returns json of `from_version + 1`.
+    pub from_version: u32,
+    pub upcast: UpcastFn,
+}
+
+/// Upcasters for every event type. When the json of an event changes, register an upcaster from
+/// the previous version here and bump the `version` of the event variant, the new version is then
+/// written from then on.
+pub static UPCASTERS: UpcasterPipeline = UpcasterPipeline::new(&[]);
+
+pub struct UpcasterPipeline {
+    upcasters: &'static [Upcaster],
+}
+
+impl UpcasterPipeline {
+    pub const fn new(upcasters: &'static [Upcaster]) -> Self {
+        Self { upcasters }
+    }
+
+    /// Versions start at 1 and every upcaster adds a version.
+    pub fn latest_version(&self, event_type: &str) -> u32 {
+        self.upcasters
+            .iter()
+            .filter(|upcaster| upcaster.event_type == event_type)
+            .map(|upcaster| upcaster.from_version + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Applies upcasters until the json is in the latest version, returns the json together with
+    /// the versioned event type it is in now.
+    pub fn upcast<'a>(
+        &self,
+        mut value: serde_json::Value,
+        versioned_event_type: &'a str,
+    ) -> (serde_json::Value, Cow<'a, str>) {
+        let Some((event_type, mut version)) = split_event_type(versioned_event_type) else {
+            return (value, Cow::Borrowed(versioned_event_type));
+        };
+
+        let original_version = version;
+        while let Some(upcaster) = self
+            .upcasters
+            .iter()
+            .find(|upcaster| upcaster.event_type == event_type && upcaster.from_version == version)
+        {
+            value = (upcaster.upcast)(value);
+            version += 1;
+        }
+
+        if version == original_version {
+            (value, Cow::Borrowed(versioned_event_type))
+        } else {
+            (value, Cow::Owned(format!("{}/{}", event_type, version)))
+        }
+    }
+}
+
+/// Splits `.../Create/1` into `.../Create` and `1`.
+pub fn split_event_type(versioned_event_type: &str) -> Option<(&str, u32)> {
+    let (event_type, version) = versioned_event_type.rsplit_once('/')?;
+    Some((event_type, version.parse().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{Upcaster, UpcasterPipeline};
+
+    const ADD_PLATFORM_ACCOUNT: &str =
+        "Porti.SourceControl/Aggregates/Organization/AddPlatformAccount";
+
+    static PIPELINE: UpcasterPipeline = UpcasterPipeline::new(&[
+        Upcaster {
+            event_type: ADD_PLATFORM_ACCOUNT,
+            from_version: 1,
+            upcast: |mut value| {
+                value["account"]["platform"]["metadata"] = json!({});
+                value
+            },
+        },
+        Upcaster {
+            event_type: ADD_PLATFORM_ACCOUNT,
+            from_version: 2,
+            upcast: |mut value| {
+                value["account"]["display_name"] = value["account"]["name"].clone();
+                value
+            },
+        },
+    ]);
+
+    #[test]
+    fn should_upcast_to_latest_version() {
+        let value = json!({ "account": { "name": "rafaeltab", "platform": { "name": "GitHub" } } });
+
+        let stored_event_type = format!("{}/1", ADD_PLATFORM_ACCOUNT);
+
+        let (value, event_type) = PIPELINE.upcast(value, &stored_event_type);
+
+        assert_eq!(event_type, format!("{}/3", ADD_PLATFORM_ACCOUNT));
+        assert_eq!(value["account"]["platform"]["metadata"], json!({}));
+        assert_eq!(value["account"]["display_name"], "rafaeltab");
+    }
+
+    #[test]
+    fn should_count_versions_from_upcasters() {
+        assert_eq!(PIPELINE.latest_version(ADD_PLATFORM_ACCOUNT), 3);
+        assert_eq!(
+            PIPELINE.latest_version("Porti.SourceControl/Aggregates/Organization/Create"),
+            1
+        );
+    }
+}